@@ -0,0 +1,32 @@
+use std::{env, fs, path::Path};
+
+use activity_vocabulary_derive::{
+    diagnostic::{Source, VocabError},
+    Extension, Options,
+};
+
+fn main() -> miette::Result<()> {
+    let vocab = Source::read("../activity-vocabulary/vocab.yml")?;
+    let mut src = vocab.parse()?;
+    let mut sources = vec![vocab];
+    println!("cargo:rerun-if-changed=../activity-vocabulary/vocab.yml");
+    println!("cargo:rerun-if-changed=../activity-vocabulary/mastodon.yml");
+    // Mastodon adds the leaves which the hand-written modules match on, `Emoji` and `Hashtag`
+    if env::var_os("CARGO_FEATURE_MASTODON").is_some() {
+        sources.push(Source::read("../activity-vocabulary/mastodon.yml")?);
+        let extension: Extension = sources.last().unwrap().parse()?;
+        extension
+            .apply(&mut src)
+            .map_err(|e| VocabError::locate(e, &sources))?;
+    }
+    let options = Options {
+        leaf_subtypes: false,
+        ..Default::default()
+    };
+    let src = activity_vocabulary_derive::gen_with(&src, &options)
+        .map_err(|e| VocabError::locate(e, &sources))?;
+    let out_path = env::var("OUT_DIR").unwrap();
+    let out_path: &Path = out_path.as_ref();
+    fs::write(out_path.join("vocab.rs"), src.as_bytes()).unwrap();
+    Ok(())
+}
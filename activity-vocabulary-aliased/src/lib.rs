@@ -0,0 +1,31 @@
+//! The vocabulary of `activity-vocabulary` generated with [leaf_subtypes] off, so that the subtypes
+//! of the types without subtypes are aliased to the types, together with the modules of
+//! `activity-vocabulary` the generated code refers to. It only checks that this compiles.
+//!
+//! [leaf_subtypes]: activity_vocabulary_derive::Options::leaf_subtypes
+#![allow(clippy::type_complexity, clippy::redundant_field_names)]
+/// Not generated, and the same whether the leaf subtypes are aliased or not.
+pub use activity_vocabulary::Unit;
+use activity_vocabulary_core::*;
+
+include!(concat!(env!("OUT_DIR"), "/vocab.rs"));
+
+#[path = "../../activity-vocabulary/src/audience.rs"]
+pub mod audience;
+#[cfg(feature = "mastodon")]
+#[path = "../../activity-vocabulary/src/content_warning.rs"]
+pub mod content_warning;
+#[path = "../../activity-vocabulary/src/hydrate.rs"]
+pub mod hydrate;
+#[path = "../../activity-vocabulary/src/link.rs"]
+pub mod link;
+#[path = "../../activity-vocabulary/src/normalize.rs"]
+pub mod normalize;
+#[path = "../../activity-vocabulary/src/outbox.rs"]
+pub mod outbox;
+#[path = "../../activity-vocabulary/src/rewrite.rs"]
+pub mod rewrite;
+#[path = "../../activity-vocabulary/src/tag.rs"]
+pub mod tag;
+#[path = "../../activity-vocabulary/src/walk.rs"]
+pub mod walk;
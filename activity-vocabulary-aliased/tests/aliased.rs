@@ -0,0 +1,33 @@
+use activity_vocabulary_aliased::*;
+
+#[test]
+fn aliased() {
+    let note: NoteSubtypes = serde_json::from_value(serde_json::json!({
+        "type": "Note",
+        "content": "hello"
+    }))
+    .unwrap();
+    // the subtypes are the type itself, not an enum of it
+    let note: Note = note;
+    let object = ObjectSubtypes::from(note.clone());
+    assert!(matches!(&object, ObjectSubtypes::Note(_)));
+    assert_eq!(NoteSubtypes::try_from(object).unwrap(), note);
+    assert!(NoteSubtypes::try_from(ObjectSubtypes::from(Article::default())).is_err());
+}
+
+#[test]
+fn round_trip() {
+    let json = serde_json::json!({
+        "type": "Create",
+        "id": "https://example.com/activities/1",
+        "actor": "https://example.com/users/alice",
+        "object": {
+            "type": "Note",
+            "content": "hello",
+            "tag": { "type": "Mention", "href": "https://example.com/users/bob" }
+        }
+    });
+    let object: ObjectSubtypes = serde_json::from_value(json.clone()).unwrap();
+    assert!(matches!(&object, ObjectSubtypes::Create(_)));
+    assert_eq!(serde_json::to_value(&object).unwrap(), json);
+}
@@ -0,0 +1,271 @@
+//! `asvocab`, validating and converting ActivityStreams documents with the vocabulary.
+//!
+//! ```sh
+//! asvocab validate note.json create.json
+//! asvocab pretty --lenient < note.json
+//! asvocab convert --type Article note.json
+//! asvocab fetch https://example.com/users/alice --output alice.json
+//! asvocab manifest > vocab.json
+//! ```
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use activity_vocabulary::{
+    client::{Client, UrlPolicy},
+    lenient::Lenient,
+    link::LinkOrObject,
+};
+use activity_vocabulary_core::{
+    metrics::{self, Metrics, ParseEvent},
+    to_json_string_pretty, Or, Remotable, WithContext,
+};
+use clap::{Args, Parser, Subcommand};
+use rsa::{pkcs8::DecodePrivateKey, RsaPrivateKey};
+use serde_json::Value;
+
+type Error = Box<dyn std::error::Error>;
+
+/// An object or a link of any type, with its context.
+type Document = WithContext<LinkOrObject>;
+
+/// Warns of the unknown types and properties on the standard error, counting them.
+struct Warnings;
+
+static WARNINGS: AtomicUsize = AtomicUsize::new(0);
+
+impl Metrics for Warnings {
+    fn record(&self, event: &ParseEvent) {
+        match event {
+            ParseEvent::UnknownType {
+                subtypes, received, ..
+            } => eprintln!("warning: unknown type '{received}' of {subtypes}"),
+            ParseEvent::UnknownProperty {
+                type_name,
+                property,
+            } => eprintln!("warning: unknown property '{property}' of {type_name}"),
+            ParseEvent::Coerced { .. } => return,
+        }
+        WARNINGS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[derive(Parser)]
+#[command(name = "asvocab", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Check each document is an object or a link of the vocabulary, printing its type.
+    Validate {
+        #[command(flatten)]
+        mode: Mode,
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Print the document parsed and serialized back, indented.
+    Pretty {
+        #[command(flatten)]
+        mode: Mode,
+        /// The document, or the standard input if `-` or omitted.
+        file: Option<PathBuf>,
+    },
+    /// Print the document parsed as another type, without the properties the type does not have.
+    Convert {
+        #[command(flatten)]
+        mode: Mode,
+        /// The type to convert into, such as `Note`.
+        #[arg(long = "type")]
+        type_name: String,
+        /// The document, or the standard input if `-` or omitted.
+        file: Option<PathBuf>,
+    },
+    /// Fetch the document at the url, printing its type on the standard error.
+    Fetch {
+        #[command(flatten)]
+        mode: Mode,
+        #[command(flatten)]
+        remote: Remote,
+        url: url::Url,
+        /// Write the document into the file instead of the standard output.
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        /// Print the document as fetched, not as parsed and serialized back.
+        #[arg(long)]
+        raw: bool,
+    },
+    /// Print the JSON manifest of the types and their properties.
+    Manifest,
+}
+
+#[derive(Args)]
+struct Remote {
+    /// Allow plain http, such as of a development server.
+    #[arg(long)]
+    allow_http: bool,
+    /// Allow loopback, private and other non-global addresses.
+    #[arg(long)]
+    allow_private: bool,
+    /// Key id to sign the request with, for the servers requiring authorized fetch.
+    #[arg(long, requires = "key")]
+    key_id: Option<url::Url>,
+    /// PKCS#8 PEM file of the private key of `--key-id`.
+    #[arg(long, requires = "key_id")]
+    key: Option<PathBuf>,
+}
+
+impl Remote {
+    fn client(&self) -> Result<Client, Error> {
+        let mut policy = UrlPolicy {
+            allow_private: self.allow_private,
+            ..Default::default()
+        };
+        if self.allow_http {
+            policy.schemes.push("http".to_owned());
+        }
+        let client = Client::with_policy(policy);
+        match (&self.key_id, &self.key) {
+            (Some(key_id), Some(key)) => {
+                let key = RsaPrivateKey::from_pkcs8_pem(&fs::read_to_string(key)?)?;
+                Ok(client.signing_key(key_id.clone(), key))
+            }
+            _ => Ok(client),
+        }
+    }
+}
+
+#[derive(Args)]
+struct Mode {
+    /// Coerce the known quirks of documents, such as timestamps for times, before parsing.
+    #[arg(long)]
+    lenient: bool,
+    /// Fail on the unknown types and properties, which are otherwise warned of and ignored.
+    #[arg(long)]
+    strict: bool,
+}
+
+impl Mode {
+    fn parse(&self, document: Value) -> Result<Document, Error> {
+        let warnings = WARNINGS.load(Ordering::Relaxed);
+        let document = if self.lenient {
+            serde_json::from_value::<Lenient<Document>>(document).map(|document| document.0)?
+        } else {
+            serde_json::from_value(document)?
+        };
+        if self.strict && WARNINGS.load(Ordering::Relaxed) > warnings {
+            return Err("unknown types or properties".into());
+        }
+        Ok(document)
+    }
+}
+
+fn read(file: Option<&Path>) -> Result<Value, Error> {
+    let json = match file {
+        Some(path) if path != Path::new("-") => fs::read_to_string(path)?,
+        _ => io::read_to_string(io::stdin())?,
+    };
+    Ok(serde_json::from_str(&json)?)
+}
+
+fn type_name(document: &Document) -> &'static str {
+    match &document.body {
+        Or::Prim(link) => link.type_name(),
+        Or::Snd(Remotable::Inline(object)) => object.type_name(),
+        Or::Snd(Remotable::Remote(_)) => "reference",
+    }
+}
+
+fn validate(mode: &Mode, files: &[PathBuf]) -> bool {
+    let mut valid = true;
+    for path in files {
+        match read(Some(path)).and_then(|document| mode.parse(document)) {
+            Ok(document) => println!("{}: {}", path.display(), type_name(&document)),
+            Err(e) => {
+                eprintln!("{}: {e}", path.display());
+                valid = false;
+            }
+        }
+    }
+    valid
+}
+
+fn convert(mode: &Mode, type_name: &str, file: Option<&Path>) -> Result<Document, Error> {
+    let mut document = read(file)?;
+    let Value::Object(object) = &mut document else {
+        return Err("the document is not an object".into());
+    };
+    object.insert("type".to_owned(), Value::String(type_name.to_owned()));
+    let document = mode.parse(document)?;
+    if self::type_name(&document) != type_name {
+        return Err(format!("'{type_name}' is not a type of the vocabulary").into());
+    }
+    Ok(document)
+}
+
+fn run(command: Command) -> Result<bool, Error> {
+    match command {
+        Command::Manifest => {
+            println!("{}", activity_vocabulary::embedded_manifest());
+            Ok(true)
+        }
+        Command::Validate { mode, files } => Ok(validate(&mode, &files)),
+        Command::Pretty { mode, file } => {
+            let document = mode.parse(read(file.as_deref())?)?;
+            println!("{}", to_json_string_pretty(&document));
+            Ok(true)
+        }
+        Command::Convert {
+            mode,
+            type_name,
+            file,
+        } => {
+            let document = convert(&mode, &type_name, file.as_deref())?;
+            println!("{}", to_json_string_pretty(&document));
+            Ok(true)
+        }
+        Command::Fetch {
+            mode,
+            remote,
+            url,
+            output,
+            raw,
+        } => {
+            let client = remote.client()?;
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?;
+            let fetched = runtime.block_on(client.fetch_object::<Value>(&url))?;
+            let document = mode.parse(fetched.clone())?;
+            eprintln!("{url}: {}", type_name(&document));
+            let json = if raw {
+                to_json_string_pretty(&fetched)
+            } else {
+                to_json_string_pretty(&document)
+            };
+            match output {
+                Some(output) => fs::write(output, json + "\n")?,
+                None => println!("{json}"),
+            }
+            Ok(true)
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    metrics::set_metrics(Warnings).expect("metrics are set only here");
+    match run(Cli::parse().command) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("asvocab: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
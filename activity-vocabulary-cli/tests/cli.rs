@@ -0,0 +1,152 @@
+use std::{
+    fs,
+    io::{Read, Write},
+    net::TcpListener,
+    path::PathBuf,
+    process::{Command, Output, Stdio},
+};
+
+fn asvocab(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_asvocab"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn documents() -> (PathBuf, PathBuf) {
+    let dir = std::env::temp_dir().join("activity-vocabulary-cli");
+    fs::create_dir_all(&dir).unwrap();
+    let note = dir.join("note.json");
+    fs::write(&note, r#"{"type": "Note", "content": "hello"}"#).unwrap();
+    let quirky = dir.join("quirky.json");
+    fs::write(&quirky, r#"{"type": "Note", "published": 1700000000}"#).unwrap();
+    (note, quirky)
+}
+
+#[test]
+fn validate() {
+    let (note, quirky) = documents();
+    let output = asvocab(
+        &["validate", note.to_str().unwrap(), quirky.to_str().unwrap()],
+        "",
+    );
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!("{}: Note\n", note.display())
+    );
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with(&format!("{}: ", quirky.display())));
+
+    let output = asvocab(&["validate", "--lenient", quirky.to_str().unwrap()], "");
+    assert!(output.status.success());
+}
+
+#[test]
+fn strict() {
+    let document = r#"{"type": "Note", "sensitive": true}"#;
+    let output = asvocab(&["pretty"], document);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "warning: unknown property 'sensitive' of Note\n"
+    );
+
+    let output = asvocab(&["pretty", "--strict"], document);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn pretty() {
+    let output = asvocab(&["pretty"], r#"{"content":"hello","type":"Note"}"#);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "{\n  \"content\": \"hello\",\n  \"type\": \"Note\"\n}\n"
+    );
+}
+
+#[test]
+fn convert() {
+    let output = asvocab(
+        &["convert", "--type", "Article", "-"],
+        r#"{"type": "Note", "content": "hello"}"#,
+    );
+    assert!(output.status.success());
+    let converted: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        converted,
+        serde_json::json!({ "type": "Article", "content": "hello" })
+    );
+
+    let output = asvocab(&["convert", "--type", "Nte"], r#"{"type": "Note"}"#);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("'Nte' is not a type of the vocabulary"));
+}
+
+/// Serve `body` to a single request on a local port.
+fn serve(body: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/note", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0; 1024];
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+            let len = stream.read(&mut buf).unwrap();
+            request.extend_from_slice(&buf[..len]);
+        }
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+    });
+    url
+}
+
+#[test]
+fn fetch() {
+    let url = serve(r#"{"type": "Note", "content": "hello"}"#);
+    let output = asvocab(&["fetch", &url], "");
+    assert!(!output.status.success());
+
+    let url = serve(r#"{"type": "Note", "content": "hello"}"#);
+    let output = asvocab(&["fetch", "--allow-http", "--allow-private", &url], "");
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        format!("{url}: Note\n")
+    );
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "{\n  \"content\": \"hello\",\n  \"type\": \"Note\"\n}\n"
+    );
+}
+
+#[test]
+fn manifest() {
+    let output = asvocab(&["manifest"], "");
+    assert!(output.status.success());
+    let manifest: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let note = &manifest["types"]["Note"];
+    assert_eq!(note["uri"], "https://www.w3.org/ns/activitystreams#Note");
+    assert_eq!(note["extends"], serde_json::json!(["Document"]));
+    let content = &note["properties"]["content"];
+    assert_eq!(content["tag"], "content");
+    assert_eq!(content["container_tag"], "contentMap");
+}
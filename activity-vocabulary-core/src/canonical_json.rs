@@ -0,0 +1,119 @@
+use std::io::Write;
+
+use serde::Serialize;
+
+/// Serialize `value` into the [JSON Canonicalization Scheme](https://www.rfc-editor.org/rfc/rfc8785)
+/// form, which is byte-for-byte stable and thus suitable for hashing and deduplication.
+///
+/// Object keys are sorted by their UTF-16 code units, numbers are printed as ECMAScript does
+/// and no whitespace is emitted. Arrays keep their order, since the order of values of a
+/// [crate::Property] is preserved on deserialization.
+pub fn to_canonical_string<T: Serialize>(value: &T) -> Result<String, serde_json::Error> {
+    let value = serde_json::to_value(value)?;
+    let mut out = String::new();
+    write_value(&mut out, &value);
+    Ok(out)
+}
+
+/// [to_canonical_string] into a writer.
+pub fn to_canonical_writer<W: Write, T: Serialize>(
+    mut writer: W,
+    value: &T,
+) -> Result<(), serde_json::Error> {
+    let canonical = to_canonical_string(value)?;
+    writer
+        .write_all(canonical.as_bytes())
+        .map_err(serde_json::Error::io)
+}
+
+fn write_value(out: &mut String, value: &serde_json::Value) {
+    match value {
+        serde_json::Value::Null => out.push_str("null"),
+        serde_json::Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        serde_json::Value::Number(number) => write_number(out, number),
+        serde_json::Value::String(s) => write_string(out, s),
+        serde_json::Value::Array(values) => {
+            out.push('[');
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, value);
+            }
+            out.push(']');
+        }
+        serde_json::Value::Object(map) => {
+            let mut entries = map.iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(out, key);
+                out.push(':');
+                write_value(out, value);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_string(out: &mut String, s: &str) {
+    // serde_json escapes exactly the characters RFC 8785 requires, with lowercase hex digits
+    out.push_str(&serde_json::Value::from(s).to_string());
+}
+
+/// Largest integer which an IEEE 754 double represents exactly, with its neighbors.
+const MAX_SAFE_INTEGER: u64 = 1 << 53;
+
+fn write_number(out: &mut String, number: &serde_json::Number) {
+    if let Some(n) = number.as_u64().filter(|n| *n <= MAX_SAFE_INTEGER) {
+        out.push_str(&n.to_string());
+    } else if let Some(n) = number
+        .as_i64()
+        .filter(|n| n.unsigned_abs() <= MAX_SAFE_INTEGER)
+    {
+        out.push_str(&n.to_string());
+    } else {
+        write_double(out, number.as_f64().unwrap_or_default());
+    }
+}
+
+/// `Number.prototype.toString` of ECMAScript.
+fn write_double(out: &mut String, value: f64) {
+    if value == 0.0 {
+        out.push('0');
+        return;
+    }
+    if value < 0.0 {
+        out.push('-');
+    }
+    // Rust prints the shortest digits which round-trip, as ECMAScript requires
+    let formatted = format!("{:e}", value.abs());
+    let (mantissa, exponent) = formatted.split_once('e').unwrap_or((&formatted, "0"));
+    let digits = mantissa.replace('.', "");
+    let k = digits.len() as i32;
+    let n = exponent.parse::<i32>().unwrap_or_default() + 1;
+    if k <= n && n <= 21 {
+        out.push_str(&digits);
+        out.extend(std::iter::repeat_n('0', (n - k) as usize));
+    } else if 0 < n && n <= 21 {
+        out.push_str(&digits[..n as usize]);
+        out.push('.');
+        out.push_str(&digits[n as usize..]);
+    } else if -6 < n && n <= 0 {
+        out.push_str("0.");
+        out.extend(std::iter::repeat_n('0', (-n) as usize));
+        out.push_str(&digits);
+    } else {
+        out.push_str(&digits[..1]);
+        if k > 1 {
+            out.push('.');
+            out.push_str(&digits[1..]);
+        }
+        out.push('e');
+        out.push(if n > 0 { '+' } else { '-' });
+        out.push_str(&(n - 1).abs().to_string());
+    }
+}
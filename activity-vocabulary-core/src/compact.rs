@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{Context, WithContext};
+
+/// Namespace IRI of the Activity Vocabulary. Bound to the `as` prefix by the ActivityStreams context.
+pub const AS_NAMESPACE: &str = "https://www.w3.org/ns/activitystreams#";
+
+/// Terms of the ActivityStreams context which are declared with `"@container": "@language"`,
+/// paired with the term of their plain (non-map) form.
+const LANGUAGE_MAP_TERMS: &[(&str, &str)] = &[
+    ("contentMap", "content"),
+    ("nameMap", "name"),
+    ("summaryMap", "summary"),
+];
+
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+struct TermDefinition {
+    iri: String,
+    language_container: bool,
+}
+
+impl Context {
+    /// Default language declared by `@language` in the inline context.
+    pub fn language(&self) -> Option<&str> {
+        self.inline.get("@language").and_then(|lang| lang.as_str())
+    }
+
+    fn expand_iri(&self, value: &str) -> String {
+        if value.starts_with('@') {
+            return value.to_owned();
+        }
+        match value.split_once(':') {
+            Some(("as", suffix)) => format!("{AS_NAMESPACE}{suffix}"),
+            Some((prefix, suffix)) if !suffix.starts_with("//") => {
+                match self.inline.get(prefix).and_then(|iri| iri.as_str()) {
+                    Some(namespace) => format!("{namespace}{suffix}"),
+                    None => value.to_owned(),
+                }
+            }
+            _ => value.to_owned(),
+        }
+    }
+
+    fn term_definition(&self, term: &str) -> Option<TermDefinition> {
+        if term.starts_with('@') {
+            return None;
+        }
+        match self.inline.get(term)? {
+            serde_json::Value::String(iri) => Some(TermDefinition {
+                iri: self.expand_iri(iri),
+                language_container: false,
+            }),
+            serde_json::Value::Object(definition) => Some(TermDefinition {
+                iri: self.expand_iri(definition.get("@id")?.as_str()?),
+                language_container: definition.get("@container").and_then(|c| c.as_str())
+                    == Some("@language"),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Definition of a term the generated serializers emit, as the ActivityStreams context declares it.
+    fn builtin_definition(term: &str) -> TermDefinition {
+        match term {
+            "id" => TermDefinition {
+                iri: "@id".to_owned(),
+                language_container: false,
+            },
+            "type" => TermDefinition {
+                iri: "@type".to_owned(),
+                language_container: false,
+            },
+            term => match LANGUAGE_MAP_TERMS.iter().find(|(map, _)| *map == term) {
+                Some((_, plain)) => TermDefinition {
+                    iri: format!("{AS_NAMESPACE}{plain}"),
+                    language_container: true,
+                },
+                None => TermDefinition {
+                    iri: format!("{AS_NAMESPACE}{term}"),
+                    language_container: false,
+                },
+            },
+        }
+    }
+
+    /// Reverse of [Context::builtin_definition].
+    fn builtin_term(definition: &TermDefinition) -> Option<String> {
+        match definition.iri.as_str() {
+            "@id" => Some("id".to_owned()),
+            "@type" => Some("type".to_owned()),
+            iri => {
+                let term = iri.strip_prefix(AS_NAMESPACE)?;
+                if definition.language_container {
+                    LANGUAGE_MAP_TERMS
+                        .iter()
+                        .find(|(_, plain)| *plain == term)
+                        .map(|(map, _)| map.to_string())
+                } else {
+                    Some(term.to_owned())
+                }
+            }
+        }
+    }
+
+    /// IRI of `key` in a document read with `context`, and whether it is a language map.
+    /// Keys the context does not define are the terms the generated serializers emit.
+    pub(crate) fn resolve_key(context: Option<&Self>, key: &str) -> (String, bool) {
+        if key.starts_with('@') {
+            return (key.to_owned(), false);
+        }
+        let empty = Self::new(Vec::new(), HashMap::new());
+        let context = context.unwrap_or(&empty);
+        let definition = context.term_definition(key).unwrap_or_else(|| {
+            if key.contains(':') {
+                TermDefinition {
+                    iri: context.expand_iri(key),
+                    language_container: false,
+                }
+            } else {
+                Self::builtin_definition(key)
+            }
+        });
+        (definition.iri, definition.language_container)
+    }
+
+    /// Term the generated serializers emit for `iri`, if it is of the ActivityStreams vocabulary.
+    pub(crate) fn builtin_key(iri: &str, language_container: bool) -> Option<String> {
+        Self::builtin_term(&TermDefinition {
+            iri: iri.to_owned(),
+            language_container,
+        })
+    }
+
+    fn aliases(&self) -> HashMap<TermDefinition, String> {
+        let mut aliases = HashMap::new();
+        let mut terms = self.inline.keys().collect::<Vec<_>>();
+        // prefer the shortest, then lexicographically least term like the JSON-LD term selection does
+        terms.sort_by(|a, b| a.len().cmp(&b.len()).then(a.cmp(b)));
+        for term in terms {
+            if let Some(definition) = self.term_definition(term) {
+                aliases.entry(definition).or_insert_with(|| term.to_owned());
+            }
+        }
+        aliases
+    }
+
+    /// Rewrite a document produced by the generated serializers so that its keys use the terms
+    /// declared in this context instead of the hardcoded ActivityStreams terms.
+    ///
+    /// Keys which the inline context defines itself are kept as is.
+    /// If `@language` is declared, language maps holding only a value of that language are
+    /// compacted into the plain property.
+    pub fn compact(&self, value: &mut serde_json::Value) {
+        self.compact_with(&self.aliases(), value)
+    }
+
+    fn compact_with(
+        &self,
+        aliases: &HashMap<TermDefinition, String>,
+        value: &mut serde_json::Value,
+    ) {
+        match value {
+            serde_json::Value::Array(values) => {
+                for value in values {
+                    self.compact_with(aliases, value);
+                }
+            }
+            serde_json::Value::Object(map) => {
+                if let Some(language) = self.language() {
+                    for (map_term, plain_term) in LANGUAGE_MAP_TERMS {
+                        if map.contains_key(*plain_term) {
+                            continue;
+                        }
+                        let Some(serde_json::Value::Object(per_lang)) = map.get_mut(*map_term)
+                        else {
+                            continue;
+                        };
+                        if let Some(default) = per_lang.remove(language) {
+                            if per_lang.is_empty() {
+                                map.remove(*map_term);
+                            }
+                            map.insert(plain_term.to_string(), default);
+                        }
+                    }
+                }
+                let entries = std::mem::take(map);
+                for (key, mut value) in entries {
+                    if key == "@context" {
+                        map.insert(key, value);
+                        continue;
+                    }
+                    self.compact_with(aliases, &mut value);
+                    let definition = Self::builtin_definition(&key);
+                    if definition.iri == "@type" {
+                        self.compact_type_names(aliases, &mut value);
+                    }
+                    let key = if key.starts_with('@') || self.inline.contains_key(&key) {
+                        key
+                    } else {
+                        aliases.get(&definition).cloned().unwrap_or(key)
+                    };
+                    map.insert(key, value);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn compact_type_names(
+        &self,
+        aliases: &HashMap<TermDefinition, String>,
+        value: &mut serde_json::Value,
+    ) {
+        match value {
+            serde_json::Value::String(name) if !self.inline.contains_key(name.as_str()) => {
+                let definition = TermDefinition {
+                    iri: format!("{AS_NAMESPACE}{name}"),
+                    language_container: false,
+                };
+                if let Some(alias) = aliases.get(&definition) {
+                    *name = alias.clone();
+                }
+            }
+            serde_json::Value::Array(names) => {
+                for name in names {
+                    self.compact_type_names(aliases, name);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Inverse of [Context::compact].
+    /// Rewrite keys which are aliases declared in this context into the ActivityStreams terms
+    /// the generated deserializers understand.
+    pub fn expand(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Array(values) => {
+                for value in values {
+                    self.expand(value);
+                }
+            }
+            serde_json::Value::Object(map) => {
+                let entries = std::mem::take(map);
+                for (key, mut value) in entries {
+                    if key == "@context" {
+                        map.insert(key, value);
+                        continue;
+                    }
+                    self.expand(&mut value);
+                    let key = self
+                        .term_definition(&key)
+                        .and_then(|definition| Self::builtin_term(&definition))
+                        .unwrap_or(key);
+                    if key == "type" {
+                        self.expand_type_names(&mut value);
+                    }
+                    map.insert(key, value);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    pub(crate) fn expand_type_names(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::String(name) => {
+                if let Some(term) = self
+                    .term_definition(name)
+                    .and_then(|definition| Self::builtin_term(&definition))
+                {
+                    *name = term;
+                }
+            }
+            serde_json::Value::Array(names) => {
+                for name in names {
+                    self.expand_type_names(name);
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+/// [WithContext] whose body is (de)serialized with the terms of its own `@context`.
+///
+/// On deserialization aliases declared in the context are expanded before the body is parsed,
+/// and on serialization the body is compacted against the same context with [Context::compact].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Compacted<T>(pub WithContext<T>);
+
+impl<T: Serialize> Serialize for Compacted<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut value = serde_json::to_value(&self.0).map_err(serde::ser::Error::custom)?;
+        if let Some(context) = &self.0.context {
+            context.compact(&mut value);
+        }
+        value.serialize(serializer)
+    }
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Compacted<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut value = serde_json::Value::deserialize(deserializer)?;
+        let context = value
+            .get("@context")
+            .cloned()
+            .map(Context::deserialize)
+            .transpose()
+            .map_err(serde::de::Error::custom)?;
+        if let Some(context) = &context {
+            context.expand(&mut value);
+        }
+        WithContext::deserialize(value)
+            .map(Self)
+            .map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,300 @@
+use std::collections::BTreeMap;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+pub use serde_json::{Error, Value};
+
+use crate::Context;
+
+/// Value of a property of a [DynObject].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DynValue {
+    /// An embedded node, such as an object or a link.
+    Node(DynObject),
+    /// A literal, or the IRI of a node.
+    Literal(Value),
+}
+
+impl DynValue {
+    /// The string literal, such as the IRI of a node.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            DynValue::Literal(Value::String(s)) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// The embedded node.
+    pub fn as_node(&self) -> Option<&DynObject> {
+        match self {
+            DynValue::Node(node) => Some(node),
+            DynValue::Literal(_) => None,
+        }
+    }
+
+    /// Id of the embedded node, or the IRI of the node it refers to.
+    pub fn id(&self) -> Option<&str> {
+        match self {
+            DynValue::Node(node) => node.id(),
+            literal => literal.as_str(),
+        }
+    }
+}
+
+impl From<DynObject> for DynValue {
+    fn from(node: DynObject) -> Self {
+        Self::Node(node)
+    }
+}
+
+impl From<Value> for DynValue {
+    fn from(literal: Value) -> Self {
+        Self::Literal(literal)
+    }
+}
+
+impl From<&str> for DynValue {
+    fn from(literal: &str) -> Self {
+        Self::Literal(literal.into())
+    }
+}
+
+/// Values of a property of a [DynObject].
+///
+/// As in AS2 a single value is the array of only it, and the values of the language map such as
+/// `contentMap` are kept apart from the others.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DynProperty {
+    pub values: Vec<DynValue>,
+    pub per_lang: BTreeMap<String, Vec<DynValue>>,
+}
+
+impl DynProperty {
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty() && self.per_lang.is_empty()
+    }
+}
+
+/// Object of any type, with its properties by their IRIs instead of fields.
+///
+/// It keeps the properties no generated type knows, such as those of unknown extensions, and
+/// converts from and into the generated types.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DynObject {
+    /// Properties by their IRIs, or by the JSON-LD keywords `@id` and `@type`.
+    pub properties: BTreeMap<String, DynProperty>,
+}
+
+impl DynObject {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Values of the property of `iri`, empty if it has none.
+    pub fn values(&self, iri: &str) -> &[DynValue] {
+        self.properties
+            .get(iri)
+            .map(|property| property.values.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, iri: &str) -> Option<&DynProperty> {
+        self.properties.get(iri)
+    }
+
+    pub fn get_mut(&mut self, iri: &str) -> Option<&mut DynProperty> {
+        self.properties.get_mut(iri)
+    }
+
+    pub fn insert(&mut self, iri: impl Into<String>, property: DynProperty) -> Option<DynProperty> {
+        self.properties.insert(iri.into(), property)
+    }
+
+    pub fn remove(&mut self, iri: &str) -> Option<DynProperty> {
+        self.properties.remove(iri)
+    }
+
+    /// Add `value` to the property of `iri`.
+    pub fn push(&mut self, iri: impl Into<String>, value: impl Into<DynValue>) {
+        self.properties
+            .entry(iri.into())
+            .or_default()
+            .values
+            .push(value.into());
+    }
+
+    pub fn id(&self) -> Option<&str> {
+        self.values("@id").first().and_then(DynValue::as_str)
+    }
+
+    /// Names of the types, such as `Note`.
+    pub fn types(&self) -> impl Iterator<Item = &str> {
+        self.values("@type").iter().filter_map(DynValue::as_str)
+    }
+
+    pub fn has_type(&self, type_name: &str) -> bool {
+        self.types().any(|t| t == type_name)
+    }
+
+    /// Lead the types with `type_name` if the object does not have it.
+    pub fn insert_type(&mut self, type_name: &str) {
+        if !self.has_type(type_name) {
+            self.properties
+                .entry("@type".to_owned())
+                .or_default()
+                .values
+                .insert(0, type_name.into());
+        }
+    }
+
+    /// Read a JSON object whose terms are declared in `context`, or are those of the generated
+    /// serializers without it. `@context` of the object itself is skipped.
+    pub fn from_json(value: Value, context: Option<&Context>) -> Result<Self, Error> {
+        let Value::Object(map) = value else {
+            return Err(serde::de::Error::custom(format!(
+                "expected a JSON object, found {value}"
+            )));
+        };
+        let mut object = Self::new();
+        for (key, value) in map {
+            if key == "@context" {
+                continue;
+            }
+            let (iri, language_container) = Context::resolve_key(context, &key);
+            let property = object.properties.entry(iri.clone()).or_default();
+            match value {
+                Value::Object(per_lang) if language_container => {
+                    for (lang, value) in per_lang {
+                        let values = property.per_lang.entry(lang).or_default();
+                        values.extend(read_values(value, context, &iri)?);
+                    }
+                }
+                mut value => {
+                    if iri == "@type" {
+                        if let Some(context) = context {
+                            context.expand_type_names(&mut value);
+                        }
+                    }
+                    property.values.extend(read_values(value, context, &iri)?);
+                }
+            }
+        }
+        object.properties.retain(|_, property| !property.is_empty());
+        Ok(object)
+    }
+
+    /// Write as JSON with the terms of the generated serializers, compacted against `context`
+    /// if any. The properties out of the ActivityStreams vocabulary are keyed by their IRIs.
+    pub fn to_json(&self, context: Option<&Context>) -> Value {
+        let mut map = serde_json::Map::new();
+        for (iri, property) in &self.properties {
+            let mut values = property.values.iter().map(write_value).collect::<Vec<_>>();
+            if !property.per_lang.is_empty() {
+                let per_lang = property.per_lang.iter().map(|(lang, values)| {
+                    (lang, values.iter().map(write_value).collect::<Vec<_>>())
+                });
+                match Context::builtin_key(iri, true) {
+                    Some(map_key) => {
+                        let per_lang = per_lang
+                            .map(|(lang, values)| (lang.clone(), single_or_array(values)))
+                            .collect();
+                        map.insert(map_key, Value::Object(per_lang));
+                    }
+                    // without its language map, the values are tagged with their languages
+                    None => {
+                        for (lang, per_lang) in per_lang {
+                            values.extend(per_lang.into_iter().map(
+                                |value| serde_json::json!({ "@value": value, "@language": lang }),
+                            ));
+                        }
+                    }
+                }
+            }
+            if !values.is_empty() {
+                let key = Context::builtin_key(iri, false).unwrap_or_else(|| iri.clone());
+                map.insert(key, single_or_array(values));
+            }
+        }
+        let mut value = Value::Object(map);
+        if let Some(context) = context {
+            context.compact(&mut value);
+        }
+        value
+    }
+
+    /// Convert an object of a generated type, or anything else serialized as a JSON object.
+    pub fn from_typed<T: Serialize>(object: &T) -> Self {
+        // the generated types are always serialized as JSON objects
+        serde_json::to_value(object)
+            .and_then(|value| Self::from_json(value, None))
+            .unwrap_or_default()
+    }
+
+    /// Convert into a generated type.
+    pub fn into_typed<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        serde_json::from_value(self.to_json(None))
+    }
+}
+
+/// Values of the property of `iri`, a single value as the array of it.
+fn read_values(value: Value, context: Option<&Context>, iri: &str) -> Result<Vec<DynValue>, Error> {
+    match value {
+        Value::Null => Ok(Vec::new()),
+        Value::Array(values) => values
+            .into_iter()
+            .filter(|value| !value.is_null())
+            .map(|value| read_value(value, context, iri))
+            .collect(),
+        value => read_value(value, context, iri).map(|value| vec![value]),
+    }
+}
+
+fn read_value(value: Value, context: Option<&Context>, iri: &str) -> Result<DynValue, Error> {
+    match value {
+        // the keywords and the value objects hold literals
+        Value::Object(map) if !iri.starts_with('@') && !map.contains_key("@value") => {
+            DynObject::from_json(Value::Object(map), context).map(DynValue::Node)
+        }
+        literal => Ok(DynValue::Literal(literal)),
+    }
+}
+
+fn write_value(value: &DynValue) -> Value {
+    match value {
+        DynValue::Node(node) => node.to_json(None),
+        DynValue::Literal(literal) => literal.clone(),
+    }
+}
+
+fn single_or_array(mut values: Vec<Value>) -> Value {
+    if values.len() == 1 {
+        values.remove(0)
+    } else {
+        Value::Array(values)
+    }
+}
+
+impl Serialize for DynObject {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.to_json(None).serialize(serializer)
+    }
+}
+
+/// Read with the `@context` of the object if it has one.
+impl<'de> Deserialize<'de> for DynObject {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        let context = value
+            .get("@context")
+            .cloned()
+            .map(Context::deserialize)
+            .transpose()
+            .map_err(serde::de::Error::custom)?;
+        Self::from_json(value, context.as_ref()).map_err(serde::de::Error::custom)
+    }
+}
@@ -1,14 +1,85 @@
 use std::{
+    cell::Cell,
     collections::{BTreeMap, HashMap},
     fmt::Debug,
     hash::Hash,
-    marker::PhantomData,
 };
 
 use serde::{de::Visitor, ser::SerializeSeq, Deserialize, Serialize};
+use serde_value::ValueDeserializer;
 
+pub mod canonical_json;
+pub mod compact;
+pub mod dynamic;
+pub mod media_type;
+pub mod metrics;
+pub mod object_uri;
+#[cfg(feature = "canonicalization")]
+pub mod rdf;
+pub mod reflect;
+mod tagged;
 pub mod xsd;
 
+pub use media_type::MediaType;
+pub use object_uri::ObjectUri;
+pub use tagged::TaggedContentVisitor;
+
+/// Id of the special collection of all the actors, which addresses an object publicly.
+pub const PUBLIC: &str = "https://www.w3.org/ns/activitystreams#Public";
+
+/// [PUBLIC] as a url.
+pub fn public() -> url::Url {
+    PUBLIC.parse().unwrap()
+}
+
+/// Whether `iri` is [PUBLIC], also in its compact forms `Public` and `as:Public`.
+pub fn is_public_iri(iri: &str) -> bool {
+    matches!(iri, PUBLIC | "Public" | "as:Public")
+}
+
+/// Buffer a value to deserialize it as more than one type.
+fn buffer<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<serde_value::Value, D::Error> {
+    serde_value::Value::deserialize(deserializer).map(integers)
+}
+
+/// Key of the map serde_json reads a number into with its `arbitrary_precision` feature.
+#[cfg(feature = "arbitrary-precision")]
+const NUMBER_TOKEN: &str = "$serde_json::private::Number";
+
+/// The numbers buffered as maps, which only [xsd::Float] and `serde_json::Value` read back,
+/// turned into integers where they are, such as for `width`.
+#[cfg(feature = "arbitrary-precision")]
+fn integers(value: serde_value::Value) -> serde_value::Value {
+    use serde_value::Value;
+    match value {
+        Value::Map(map) => {
+            if let (1, Some(Value::String(number))) =
+                (map.len(), map.get(&Value::String(NUMBER_TOKEN.into())))
+            {
+                if let Ok(number) = number.parse::<u64>() {
+                    return Value::U64(number);
+                }
+                if let Ok(number) = number.parse::<i64>() {
+                    return Value::I64(number);
+                }
+                return Value::Map(map);
+            }
+            Value::Map(map.into_iter().map(|(k, v)| (k, integers(v))).collect())
+        }
+        Value::Seq(seq) => Value::Seq(seq.into_iter().map(integers).collect()),
+        Value::Option(Some(value)) => Value::Option(Some(Box::new(integers(*value)))),
+        Value::Newtype(value) => Value::Newtype(Box::new(integers(*value))),
+        value => value,
+    }
+}
+
+#[cfg(not(feature = "arbitrary-precision"))]
+fn integers(value: serde_value::Value) -> serde_value::Value {
+    value
+}
+
 #[derive(PartialEq, Eq, Clone, Debug, Hash)]
 pub enum Remotable<T> {
     Remote(url::Url),
@@ -17,6 +88,11 @@ pub enum Remotable<T> {
 
 pub trait ObjectId {
     fn object_id(&self) -> Option<&url::Url>;
+
+    /// [ObjectId::object_id] to compare as normalized.
+    fn object_uri(&self) -> Option<ObjectUri> {
+        self.object_id().cloned().map(ObjectUri::from)
+    }
 }
 
 impl<T: ObjectId> ObjectId for Remotable<T> {
@@ -28,6 +104,141 @@ impl<T: ObjectId> ObjectId for Remotable<T> {
     }
 }
 
+/// Whether `a` and `b` are the same object, that is they have the same id as compared by
+/// [ObjectUri].
+///
+/// Objects without id are anonymous, and never the same as another.
+pub fn eq_by_id<A: ObjectId + ?Sized, B: ObjectId + ?Sized>(a: &A, b: &B) -> bool {
+    eq_by_id_with(a, b, &object_uri::Normalization::default())
+}
+
+/// [eq_by_id] with the ids normalized by `normalization`.
+pub fn eq_by_id_with<A: ObjectId + ?Sized, B: ObjectId + ?Sized>(
+    a: &A,
+    b: &B,
+    normalization: &object_uri::Normalization,
+) -> bool {
+    match (a.object_uri(), b.object_id()) {
+        (Some(a), Some(b)) => a.matches(b, normalization),
+        _ => false,
+    }
+}
+
+/// An object compared and hashed by its id as an [ObjectUri], to deduplicate objects in sets and
+/// maps.
+///
+/// It borrows as the [ObjectUri] of the id, so that a map keyed by [IdKey] can be looked up by an
+/// id written differently, such as with a trailing slash.
+#[derive(Clone, Debug)]
+pub struct IdKey<T> {
+    uri: ObjectUri,
+    object: T,
+}
+
+impl<T: ObjectId> IdKey<T> {
+    /// `None` if `object` is anonymous.
+    pub fn new(object: T) -> Option<Self> {
+        let uri = object.object_uri()?;
+        Some(Self { uri, object })
+    }
+
+    pub fn id(&self) -> &url::Url {
+        &self.uri
+    }
+
+    pub fn get(&self) -> &T {
+        &self.object
+    }
+
+    pub fn into_inner(self) -> T {
+        self.object
+    }
+}
+
+impl<T> PartialEq for IdKey<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.uri == other.uri
+    }
+}
+
+impl<T> Eq for IdKey<T> {}
+
+impl<T> Hash for IdKey<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.uri.hash(state)
+    }
+}
+
+impl<T> std::borrow::Borrow<ObjectUri> for IdKey<T> {
+    fn borrow(&self) -> &ObjectUri {
+        &self.uri
+    }
+}
+
+/// HTTP caching headers of a dereferenced document.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct CacheHeaders {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub cache_control: Option<String>,
+}
+
+/// Result of [Dereferencer::dereference_conditional].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Dereferenced {
+    Modified {
+        document: serde_json::Value,
+        headers: CacheHeaders,
+    },
+    /// The cached document is still valid.
+    NotModified { headers: CacheHeaders },
+}
+
+/// Fetches the document a [Remotable::Remote] refers to.
+pub trait Dereferencer {
+    type Error;
+
+    fn dereference(
+        &self,
+        url: &url::Url,
+    ) -> impl std::future::Future<Output = Result<serde_json::Value, Self::Error>> + Send;
+
+    /// Fetch the document unless it is still the one `cached` describes, reporting its caching headers.
+    ///
+    /// By default the document is always fetched by [Dereferencer::dereference], without headers.
+    fn dereference_conditional(
+        &self,
+        url: &url::Url,
+        _cached: Option<&CacheHeaders>,
+    ) -> impl std::future::Future<Output = Result<Dereferenced, Self::Error>> + Send {
+        let document = self.dereference(url);
+        async move {
+            document.await.map(|document| Dereferenced::Modified {
+                document,
+                headers: CacheHeaders::default(),
+            })
+        }
+    }
+
+    /// Whether `error` tells that the document does not exist, such as by `404 Not Found` or
+    /// `410 Gone`, rather than that it could not be fetched.
+    ///
+    /// By default no error does.
+    fn is_gone(_error: &Self::Error) -> bool {
+        false
+    }
+}
+
+/// The id of a remote object, or the summary of an inline one.
+impl<T: std::fmt::Display> std::fmt::Display for Remotable<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Remotable::Remote(id) => f.write_str(id.as_str()),
+            Remotable::Inline(inline) => inline.fmt(f),
+        }
+    }
+}
+
 impl<T: Serialize> Serialize for Remotable<T> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -45,10 +256,14 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for Remotable<T> {
     where
         D: serde::Deserializer<'de>,
     {
-        let value = serde_value::Value::deserialize(deserializer)?;
+        let value = buffer(deserializer)?;
         let deserializer = serde_value::ValueDeserializer::<D::Error>::new(value.clone());
-        match T::deserialize(deserializer) {
+        match metrics::speculate(|| T::deserialize(deserializer)) {
             Ok(inline) => Ok(Self::Inline(inline)),
+            // the compact forms of the public collection are common, and `Public` is not a url
+            Err(_) if matches!(&value, serde_value::Value::String(iri) if is_public_iri(iri)) => {
+                Ok(Self::Remote(public()))
+            }
             Err(inline_err) => url::Url::deserialize(serde_value::ValueDeserializer::new(value))
                 .map_err(|e: D::Error| serde::de::Error::custom(format!("{inline_err} & {e}")))
                 .map(Self::Remote),
@@ -56,6 +271,54 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for Remotable<T> {
     }
 }
 
+/// Removes `bto` and `bcc`, which must not be delivered nor shown publicly,
+/// from an object and the objects inlined in it.
+pub trait BlindAddressing {
+    fn strip_blind_addressing(&mut self);
+}
+
+macro_rules! blind_addressing_leaf {
+    ($($ty:ty),*) => {
+        $(
+            impl BlindAddressing for $ty {
+                fn strip_blind_addressing(&mut self) {}
+            }
+        )*
+    };
+}
+
+blind_addressing_leaf!(url::Url, bool, xsd::DateTime);
+
+impl<T: BlindAddressing> BlindAddressing for Box<T> {
+    fn strip_blind_addressing(&mut self) {
+        self.as_mut().strip_blind_addressing()
+    }
+}
+
+impl<T: BlindAddressing> BlindAddressing for Option<T> {
+    fn strip_blind_addressing(&mut self) {
+        if let Some(inner) = self {
+            inner.strip_blind_addressing()
+        }
+    }
+}
+
+impl<T: BlindAddressing> BlindAddressing for Remotable<T> {
+    fn strip_blind_addressing(&mut self) {
+        if let Remotable::Inline(inline) = self {
+            inline.strip_blind_addressing()
+        }
+    }
+}
+
+impl<T: BlindAddressing> BlindAddressing for Property<T> {
+    fn strip_blind_addressing(&mut self) {
+        self.0
+            .iter_mut()
+            .for_each(BlindAddressing::strip_blind_addressing)
+    }
+}
+
 #[derive(PartialEq, Eq, Clone, Debug, Hash)]
 pub struct Property<T>(pub Vec<T>);
 
@@ -66,7 +329,7 @@ impl<T: Serialize> Serialize for Property<T> {
     {
         if let [inner] = &self.0[..] {
             inner.serialize(serializer)
-        } else if self.0.len() > 1 {
+        } else if self.0.len() > 1 || empty_behavior() == EmptyBehavior::Array {
             self.0.serialize(serializer)
         } else {
             serializer.serialize_none()
@@ -79,15 +342,31 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for Property<T> {
     where
         D: serde::Deserializer<'de>,
     {
-        let content = serde::__private::de::Content::deserialize(deserializer)?;
-        let deserializer = serde::__private::de::ContentRefDeserializer::<D::Error>::new(&content);
-        match Vec::<T>::deserialize(deserializer) {
-            Ok(inner) => Ok(Self(inner)),
-            Err(seq_err) => match Option::<T>::deserialize(deserializer) {
-                Ok(inner) => Ok(Self(inner.into_iter().collect())),
-                Err(opt_err) => Err(serde::de::Error::custom(format!("{seq_err} & {opt_err}"))),
-            },
+        // an array is the values, and anything else is a single value or null
+        match buffer(deserializer)? {
+            value @ serde_value::Value::Seq(_) => {
+                Vec::<T>::deserialize(ValueDeserializer::<D::Error>::new(value)).map(Self)
+            }
+            value => Option::<T>::deserialize(ValueDeserializer::<D::Error>::new(value))
+                .map(|inner| Self(inner.into_iter().collect())),
+        }
+    }
+}
+
+/// The value itself if there is just one, as on serialization, otherwise the values in brackets.
+impl<T: std::fmt::Display> std::fmt::Display for Property<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let [value] = &self.0[..] {
+            return value.fmt(f);
+        }
+        f.write_str("[")?;
+        for (i, value) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            value.fmt(f)?;
         }
+        f.write_str("]")
     }
 }
 
@@ -103,16 +382,26 @@ pub enum Or<T, U> {
     Snd(U),
 }
 
+impl<T: BlindAddressing, U: BlindAddressing> BlindAddressing for Or<T, U> {
+    fn strip_blind_addressing(&mut self) {
+        match self {
+            Or::Prim(prim) => prim.strip_blind_addressing(),
+            Or::Snd(snd) => snd.strip_blind_addressing(),
+        }
+    }
+}
+
 impl<'de, L: Deserialize<'de>, R: Deserialize<'de>> Deserialize<'de> for Or<L, R> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        let content = serde::__private::de::Content::deserialize(deserializer)?;
-        let deserializer = serde::__private::de::ContentRefDeserializer::<D::Error>::new(&content);
-        match L::deserialize(deserializer) {
+        let value = buffer(deserializer)?;
+        match metrics::speculate(|| {
+            L::deserialize(ValueDeserializer::<D::Error>::new(value.clone()))
+        }) {
             Ok(left) => Ok(Self::Prim(left)),
-            Err(left_err) => R::deserialize(deserializer)
+            Err(left_err) => R::deserialize(ValueDeserializer::<D::Error>::new(value))
                 .map_err(|right_err| {
                     serde::de::Error::custom(format!("{left_err} and {right_err}"))
                 })
@@ -151,6 +440,112 @@ impl<L, R> Or<L, R> {
     }
 }
 
+/// A link, which refers to a resource by its url.
+pub trait Href {
+    fn href(&self) -> &url::Url;
+}
+
+/// The "Object | Link" range of the most properties, a link or an object inlined or referred by id.
+pub type LinkOrObject<L, T> = Or<L, Remotable<T>>;
+
+#[derive(Debug)]
+pub enum ResolveError<E> {
+    Dereference(E),
+    Malformed(serde_json::Error),
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for ResolveError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Dereference(e) => f.write_fmt(format_args!("dereference: {e}")),
+            Self::Malformed(e) => f.write_fmt(format_args!("malformed object: {e}")),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for ResolveError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Dereference(e) => Some(e),
+            Self::Malformed(e) => Some(e),
+        }
+    }
+}
+
+impl<L: Href, T> LinkOrObject<L, T> {
+    /// Url of the link, unless this is an object.
+    pub fn href(&self) -> Option<&url::Url> {
+        self.prim().map(Href::href)
+    }
+
+    /// The object if it is inlined.
+    pub fn as_object(&self) -> Option<&T> {
+        match self {
+            Or::Snd(Remotable::Inline(object)) => Some(object),
+            _ => None,
+        }
+    }
+
+    /// Id of the object, or url of the link.
+    pub fn id_or_href(&self) -> Option<&url::Url>
+    where
+        T: ObjectId,
+    {
+        match self {
+            Or::Prim(link) => Some(link.href()),
+            Or::Snd(object) => object.object_id(),
+        }
+    }
+
+    /// The object, fetched through `dereferencer` unless inlined.
+    ///
+    /// A link is followed to the object at its url.
+    pub async fn resolve<D: Dereferencer>(
+        &self,
+        dereferencer: &D,
+    ) -> Result<T, ResolveError<D::Error>>
+    where
+        T: Clone + serde::de::DeserializeOwned,
+    {
+        let url = match self {
+            Or::Snd(Remotable::Inline(object)) => return Ok(object.clone()),
+            Or::Snd(Remotable::Remote(id)) => id,
+            Or::Prim(link) => link.href(),
+        };
+        #[cfg(feature = "tracing")]
+        tracing::debug!(%url, "dereference");
+        let document = dereferencer
+            .dereference(url)
+            .await
+            .map_err(ResolveError::Dereference)?;
+        serde_json::from_value(document).map_err(|e| {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(%url, error = %e, "malformed object");
+            ResolveError::Malformed(e)
+        })
+    }
+}
+
+impl<L: std::fmt::Display, R: std::fmt::Display> std::fmt::Display for Or<L, R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Or::Prim(value) => value.fmt(f),
+            Or::Snd(value) => value.fmt(f),
+        }
+    }
+}
+
+/// Pretty-printed JSON of `value` with the keys sorted, for the types whose serialization does not
+/// fail such as the generated ones.
+///
+/// Going through [serde_json::Value] also merges the `type` the subtypes enums tag with into the
+/// one of the object.
+pub fn to_json_string_pretty<T: Serialize + ?Sized>(value: &T) -> String {
+    serde_json::to_value(value)
+        .and_then(|value| serde_json::to_string_pretty(&value))
+        .expect("serialize into JSON")
+}
+
 impl<P, S> From<P> for Or<P, S> {
     fn from(value: P) -> Self {
         Or::Prim(value)
@@ -163,10 +558,35 @@ impl<L: Default, R> Default for Or<L, R> {
     }
 }
 
+/// Whether a property is omitted from the object on serialization, such as an empty one.
+///
+/// [impl_skip_serialization] implements it for the types of custom properties.
 pub trait SkipSerialization {
     fn should_skip(&self) -> bool;
 }
 
+/// Implement [SkipSerialization] for the types, skipped if `is_empty`, or if the expression on
+/// `this` is true.
+///
+/// `impl_skip_serialization!(Emojis, Tags)` for the types with `is_empty`, and
+/// `impl_skip_serialization!(Rating => |this| this.0 == 0)` for the others.
+#[macro_export]
+macro_rules! impl_skip_serialization {
+    ($ty:ty => |$this:ident| $skip:expr) => {
+        impl $crate::SkipSerialization for $ty {
+            fn should_skip(&self) -> bool {
+                let $this = self;
+                $skip
+            }
+        }
+    };
+    ($($ty:ty),* $(,)?) => {
+        $(
+            $crate::impl_skip_serialization!($ty => |this| this.is_empty());
+        )*
+    };
+}
+
 impl<T> SkipSerialization for Option<T> {
     fn should_skip(&self) -> bool {
         self.is_none()
@@ -175,16 +595,75 @@ impl<T> SkipSerialization for Option<T> {
 
 impl<T> SkipSerialization for Property<T> {
     fn should_skip(&self) -> bool {
-        self.0.is_empty()
+        self.0.is_empty() && empty_behavior() == EmptyBehavior::Omit
     }
 }
 
+/// How an empty [Property] is serialized.
+///
+/// A required property is written even if it is empty, as `[]` by [EmptyBehavior::Array] and
+/// otherwise as `null`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EmptyBehavior {
+    /// Omitted from the object, and `null` on its own.
+    #[default]
+    Omit,
+    /// `null`, also in the object.
+    Null,
+    /// `[]`, also on its own.
+    Array,
+}
+
+thread_local! {
+    static EMPTY_BEHAVIOR: Cell<EmptyBehavior> = const { Cell::new(EmptyBehavior::Omit) };
+}
+
+/// The [EmptyBehavior] of the serializations on this thread, [EmptyBehavior::Omit] unless in
+/// [with_empty_behavior].
+pub fn empty_behavior() -> EmptyBehavior {
+    EMPTY_BEHAVIOR.get()
+}
+
+/// Run `f`, such as `serde_json::to_string`, serializing the empty [Property]s by `behavior`.
+pub fn with_empty_behavior<R>(behavior: EmptyBehavior, f: impl FnOnce() -> R) -> R {
+    /// Restores the behavior outside, also on a panic.
+    struct Restore(EmptyBehavior);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            EMPTY_BEHAVIOR.set(self.0);
+        }
+    }
+
+    let _restore = Restore(EMPTY_BEHAVIOR.replace(behavior));
+    f()
+}
+
 impl<K, V> SkipSerialization for HashMap<K, V> {
     fn should_skip(&self) -> bool {
         self.is_empty()
     }
 }
 
+impl<K, V> SkipSerialization for BTreeMap<K, V> {
+    fn should_skip(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+impl<T> SkipSerialization for Vec<T> {
+    fn should_skip(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+/// Skipped without the default and any language, as both of its entries are.
+impl<T> SkipSerialization for LangContainer<T> {
+    fn should_skip(&self) -> bool {
+        self.default.is_none() && self.per_lang.is_empty()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct LangContainer<T> {
     pub default: Option<T>,
@@ -196,9 +675,9 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for LangContainer<T> {
     where
         D: serde::Deserializer<'de>,
     {
-        let value = serde_value::Value::deserialize(deserializer)?;
+        let value = buffer(deserializer)?;
         let deserializer = serde_value::ValueDeserializer::<D::Error>::new(value.clone());
-        match T::deserialize(deserializer) {
+        match metrics::speculate(|| T::deserialize(deserializer)) {
             Ok(inline) => Ok(Self {
                 default: Some(inline),
                 per_lang: Default::default(),
@@ -279,6 +758,45 @@ pub struct Context {
     inline: HashMap<String, serde_json::Value>,
 }
 
+/// URL of the ActivityStreams context document.
+pub const AS_CONTEXT: &str = "https://www.w3.org/ns/activitystreams";
+
+/// Terms of the `as:` namespace in use which the ActivityStreams context document does not define.
+/// Mastodon declares these inline, and so does [Context::activitystreams].
+const AS_EXTENSION_TERMS: &[(&str, &str)] = &[
+    ("manuallyApprovesFollowers", "as:manuallyApprovesFollowers"),
+    ("movedTo", "as:movedTo"),
+    ("sensitive", "as:sensitive"),
+];
+
+impl Context {
+    pub fn new(urls: Vec<url::Url>, inline: HashMap<String, serde_json::Value>) -> Self {
+        Self { urls, inline }
+    }
+
+    /// The ActivityStreams context with the `as:` extension terms such as `manuallyApprovesFollowers`.
+    pub fn activitystreams() -> Self {
+        let inline = AS_EXTENSION_TERMS
+            .iter()
+            .map(|(term, iri)| {
+                let definition = if *term == "movedTo" {
+                    serde_json::json!({ "@id": iri, "@type": "@id" })
+                } else {
+                    serde_json::Value::from(*iri)
+                };
+                (term.to_string(), definition)
+            })
+            .collect();
+        Self::new(vec![AS_CONTEXT.parse().unwrap()], inline)
+    }
+
+    /// Declare `term` in the inline context.
+    pub fn define(&mut self, term: impl Into<String>, definition: serde_json::Value) -> &mut Self {
+        self.inline.insert(term.into(), definition);
+        self
+    }
+}
+
 impl Serialize for Context {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -410,7 +928,12 @@ impl<'de> Deserialize<'de> for Context {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Jsonb)
+)]
 pub struct WithContext<T> {
     #[serde(rename = "@context", skip_serializing_if = "Option::is_none")]
     pub context: Option<Context>,
@@ -418,45 +941,212 @@ pub struct WithContext<T> {
     pub body: T,
 }
 
-pub struct TaggedContentVisitor<T> {
-    name: &'static str,
-    tag: &'static str,
-    _tag: PhantomData<T>,
+/// Stored as `jsonb` through [sqlx::types::Json].
+#[cfg(feature = "sqlx")]
+impl<T> sqlx::Type<sqlx::Postgres> for WithContext<T> {
+    fn type_info() -> sqlx::postgres::PgTypeInfo {
+        <sqlx::types::Json<Self> as sqlx::Type<sqlx::Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
+        <sqlx::types::Json<Self> as sqlx::Type<sqlx::Postgres>>::compatible(ty)
+    }
+}
+
+#[cfg(feature = "sqlx")]
+impl<T: Serialize> sqlx::Encode<'_, sqlx::Postgres> for WithContext<T> {
+    fn encode_by_ref(
+        &self,
+        buf: &mut sqlx::postgres::PgArgumentBuffer,
+    ) -> Result<sqlx::encode::IsNull, sqlx::error::BoxDynError> {
+        sqlx::types::Json(self).encode_by_ref(buf)
+    }
 }
 
-impl<T> TaggedContentVisitor<T> {
-    pub fn new(name: &'static str, tag: &'static str) -> Self {
-        Self {
-            name,
-            tag,
-            _tag: Default::default(),
+#[cfg(feature = "sqlx")]
+impl<'r, T: serde::de::DeserializeOwned> sqlx::Decode<'r, sqlx::Postgres> for WithContext<T> {
+    fn decode(value: sqlx::postgres::PgValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+        <sqlx::types::Json<Self> as sqlx::Decode<sqlx::Postgres>>::decode(value).map(|json| json.0)
+    }
+}
+
+/// Written as `jsonb` in its binary format, a version byte followed by the text.
+#[cfg(feature = "diesel")]
+impl<T: Serialize + Debug> diesel::serialize::ToSql<diesel::sql_types::Jsonb, diesel::pg::Pg>
+    for WithContext<T>
+{
+    fn to_sql<'b>(
+        &'b self,
+        out: &mut diesel::serialize::Output<'b, '_, diesel::pg::Pg>,
+    ) -> diesel::serialize::Result {
+        use std::io::Write;
+        out.write_all(&[1])?;
+        serde_json::to_writer(out, self)?;
+        Ok(diesel::serialize::IsNull::No)
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl<T: serde::de::DeserializeOwned>
+    diesel::deserialize::FromSql<diesel::sql_types::Jsonb, diesel::pg::Pg> for WithContext<T>
+{
+    fn from_sql(value: diesel::pg::PgValue<'_>) -> diesel::deserialize::Result<Self> {
+        let value = <serde_json::Value as diesel::deserialize::FromSql<
+            diesel::sql_types::Jsonb,
+            diesel::pg::Pg,
+        >>::from_sql(value)?;
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for WithContext<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.body.fmt(f)
+    }
+}
+
+/// The JSON-LD keywords, such as `@id` for `id`, the properties of an object are read with, to be
+/// written back with them.
+///
+/// Objects are equal however their properties are written.
+#[derive(Debug, Clone, Default)]
+pub struct Keywords(Vec<&'static str>);
+
+impl Keywords {
+    pub fn insert(&mut self, keyword: &'static str) {
+        if !self.contains(keyword) {
+            self.0.push(keyword);
         }
     }
+
+    pub fn contains(&self, keyword: &str) -> bool {
+        self.0.contains(&keyword)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
 }
 
-impl<'de, T: Deserialize<'de> + Debug + Default> Visitor<'de> for TaggedContentVisitor<T> {
-    type Value = (T, serde_value::Value);
+impl PartialEq for Keywords {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
 
-    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        formatter.write_str(self.name)
+/// The string of `key` in the map of the tagged `content`, such as its `id`.
+pub fn content_str<'a>(content: &'a serde_value::Value, key: &str) -> Option<&'a str> {
+    match content {
+        serde_value::Value::Map(map) => match map.get(&serde_value::Value::String(key.into())) {
+            Some(serde_value::Value::String(value)) => Some(value),
+            _ => None,
+        },
+        _ => None,
     }
+}
 
-    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
-    where
-        A: serde::de::MapAccess<'de>,
-    {
-        let mut content = BTreeMap::new();
-        let mut tag = None;
-        while let Some((k, v)) = map.next_entry::<serde_value::Value, serde_value::Value>()? {
-            if let serde_value::Value::String(label) = &k {
-                if label == self.tag {
-                    tag = Some(T::deserialize(serde_value::ValueDeserializer::new(
-                        v.clone(),
-                    ))?)
-                }
-            }
-            content.insert(k, v);
+/// Span of the deserialization of the tagged `content` as one of `subtypes`, with its type and id.
+#[cfg(feature = "tracing")]
+pub fn deserialize_span(
+    subtypes: &'static str,
+    content: &serde_value::Value,
+) -> tracing::span::EnteredSpan {
+    tracing::trace_span!(
+        "deserialize",
+        subtypes,
+        r#type = content_str(content, "type"),
+        id = content_str(content, "id"),
+    )
+    .entered()
+}
+
+/// Trace `error` of the value of `property` of `type_name`.
+#[cfg(feature = "tracing")]
+pub fn trace_malformed_property(
+    type_name: &'static str,
+    property: &'static str,
+    error: &dyn std::fmt::Display,
+) {
+    tracing::debug!(r#type = type_name, property, error = %error, "malformed property");
+}
+
+/// Number of the edits of characters from `a` to `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let substituted = diagonal + usize::from(a != *b);
+            diagonal = row[j + 1];
+            row[j + 1] = substituted.min(diagonal + 1).min(row[j] + 1);
         }
-        Ok((tag.unwrap_or_default(), serde_value::Value::Map(content)))
     }
+    row[b.len()]
 }
+
+/// Error of a document whose type is none of the expected ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownType {
+    /// The type of the document, empty without one.
+    pub received: String,
+    /// Id of the document.
+    pub id: Option<String>,
+    pub expected: &'static [&'static str],
+}
+
+impl UnknownType {
+    /// Error of the tagged `content`, to be deserialized into one of `expected`.
+    pub fn new(
+        received: String,
+        content: &serde_value::Value,
+        expected: &'static [&'static str],
+    ) -> Self {
+        Self {
+            received,
+            id: content_str(content, "id").map(ToOwned::to_owned),
+            expected,
+        }
+    }
+
+    /// The expected type nearest to the received one, by the edit distance ignoring the case and
+    /// the surrounding spaces, if it is near enough to be a typo.
+    pub fn suggestion(&self) -> Option<&'static str> {
+        let received = self.received.trim().to_lowercase();
+        if received.is_empty() {
+            return None;
+        }
+        let max_distance = (received.chars().count() / 3).max(1);
+        self.expected
+            .iter()
+            .map(|expected| {
+                (
+                    edit_distance(&received, &expected.to_lowercase()),
+                    *expected,
+                )
+            })
+            .filter(|(distance, _)| *distance <= max_distance)
+            .min()
+            .map(|(_, expected)| expected)
+    }
+}
+
+impl std::fmt::Display for UnknownType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.received.is_empty() {
+            f.write_str("no known type")?;
+        } else {
+            f.write_fmt(format_args!("unknown type '{}'", self.received))?;
+        }
+        if let Some(id) = &self.id {
+            f.write_fmt(format_args!(" of {id}"))?;
+        }
+        match self.suggestion() {
+            Some(suggestion) => f.write_fmt(format_args!(", did you mean '{suggestion}'?")),
+            None => f.write_fmt(format_args!(", expected {}", self.expected.join(", "))),
+        }
+    }
+}
+
+impl std::error::Error for UnknownType {}
@@ -0,0 +1,177 @@
+use std::{fmt::Display, ops::Deref, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// A MIME media type such as `image/png` or `text/html; charset=utf-8`.
+///
+/// The essence is kept in lower case and the parameters are separated by `; `.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct MediaType(String);
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MediaTypeError(pub String);
+
+impl Display for MediaTypeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("invalid media type {}", self.0))
+    }
+}
+
+impl std::error::Error for MediaTypeError {}
+
+/// Trim spaces, lower the essence and the parameter names and drop empty parameters.
+fn normalize(src: &str) -> String {
+    let mut parts = src.split(';');
+    let essence = parts
+        .next()
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect::<String>()
+        .to_ascii_lowercase();
+    parts
+        .filter_map(|param| {
+            let (name, value) = param.split_once('=')?;
+            Some(format!(
+                "{}={}",
+                name.trim().to_ascii_lowercase(),
+                value.trim()
+            ))
+        })
+        .fold(essence, |media_type, param| media_type + "; " + &param)
+}
+
+#[cfg(not(feature = "mime"))]
+fn is_token(src: &str) -> bool {
+    !src.is_empty()
+        && src
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$&^_.+-".contains(&b))
+}
+
+#[cfg(not(feature = "mime"))]
+fn is_valid(src: &str) -> bool {
+    let mut parts = src.trim().split(';');
+    let essence = parts.next().unwrap_or_default();
+    essence
+        .split_once('/')
+        .is_some_and(|(top, sub)| is_token(top) && is_token(sub))
+        && parts.all(|param| {
+            param
+                .split_once('=')
+                .is_some_and(|(name, value)| is_token(name.trim()) && !value.trim().is_empty())
+        })
+}
+
+#[cfg(feature = "mime")]
+fn is_valid(src: &str) -> bool {
+    // `mime` takes `image/` for a media type without a subtype
+    src.trim()
+        .parse::<mime::Mime>()
+        .is_ok_and(|mime| !mime.type_().as_str().is_empty() && !mime.subtype().as_str().is_empty())
+}
+
+impl MediaType {
+    /// `type/subtype` without the parameters.
+    pub fn essence(&self) -> &str {
+        self.0
+            .split_once(';')
+            .map_or(&self.0, |(essence, _)| essence)
+    }
+
+    /// The value of the parameter `name`, unquoted.
+    pub fn parameter(&self, name: &str) -> Option<&str> {
+        self.0.split("; ").skip(1).find_map(|param| {
+            let (key, value) = param.split_once('=')?;
+            key.eq_ignore_ascii_case(name)
+                .then(|| value.trim_matches('"'))
+        })
+    }
+
+    pub fn is_image(&self) -> bool {
+        self.essence().starts_with("image/")
+    }
+
+    pub fn is_video(&self) -> bool {
+        self.essence().starts_with("video/")
+    }
+
+    pub fn is_audio(&self) -> bool {
+        self.essence().starts_with("audio/")
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The media type parsed by the `mime` crate.
+    #[cfg(feature = "mime")]
+    pub fn mime(&self) -> Option<mime::Mime> {
+        self.0.parse().ok()
+    }
+}
+
+impl Deref for MediaType {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<str> for MediaType {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for MediaType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(feature = "mime")]
+impl From<mime::Mime> for MediaType {
+    fn from(mime: mime::Mime) -> Self {
+        Self(normalize(mime.as_ref()))
+    }
+}
+
+/// Parse strictly, rejecting what is not `type/subtype` with optional parameters.
+impl FromStr for MediaType {
+    type Err = MediaTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if is_valid(s) {
+            Ok(Self(normalize(s)))
+        } else {
+            Err(MediaTypeError(s.to_owned()))
+        }
+    }
+}
+
+/// Deserialize leniently, normalizing sloppy values such as `IMAGE/PNG;` instead of rejecting them.
+///
+/// Values without a type or a subtype, such as `""` and `image/`, are still rejected.
+impl<'de> Deserialize<'de> for MediaType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let src: String = <String as Deserialize>::deserialize(deserializer)?;
+        let media_type = Self(normalize(&src));
+        match media_type.essence().split_once('/') {
+            Some((top, sub)) if !top.is_empty() && !sub.is_empty() => Ok(media_type),
+            _ => Err(serde::de::Error::custom(MediaTypeError(src))),
+        }
+    }
+}
+
+impl Serialize for MediaType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
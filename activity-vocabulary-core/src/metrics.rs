@@ -0,0 +1,237 @@
+use std::{cell::RefCell, sync::OnceLock};
+
+/// Event of the deserialization to be counted, such as to find the servers sending unusual
+/// documents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseEvent {
+    /// A type none of `subtypes`, deserialized as the base type if `fallback`.
+    UnknownType {
+        subtypes: &'static str,
+        received: String,
+        fallback: bool,
+    },
+    /// A property not of the type, ignored. Properties which are null or `[]` are not told.
+    UnknownProperty {
+        type_name: &'static str,
+        property: String,
+    },
+    /// A known quirk of `property` coerced by a lenient deserialization.
+    Coerced {
+        quirk: &'static str,
+        property: String,
+    },
+}
+
+/// Receiver of the [ParseEvent]s, such as counters of prometheus.
+pub trait Metrics: Send + Sync {
+    fn record(&self, event: &ParseEvent);
+}
+
+/// Error of [set_metrics] called twice.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetricsAlreadySet;
+
+impl std::fmt::Display for MetricsAlreadySet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("metrics are already set")
+    }
+}
+
+impl std::error::Error for MetricsAlreadySet {}
+
+static METRICS: OnceLock<Box<dyn Metrics>> = OnceLock::new();
+
+thread_local! {
+    /// Events of the speculative deserializations in progress, innermost last.
+    static PENDING: RefCell<Vec<Vec<ParseEvent>>> = const { RefCell::new(Vec::new()) };
+    /// Events of the [capture]s in progress, innermost last.
+    static CAPTURED: RefCell<Vec<Vec<ParseEvent>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Whether the events are received, by the metrics set or by a [capture] on this thread.
+fn is_recording() -> bool {
+    METRICS.get().is_some() || CAPTURED.with_borrow(|captured| !captured.is_empty())
+}
+
+/// Set `metrics` to receive the events of all the deserializations of the process.
+pub fn set_metrics(metrics: impl Metrics + 'static) -> Result<(), MetricsAlreadySet> {
+    METRICS
+        .set(Box::new(metrics))
+        .map_err(|_| MetricsAlreadySet)
+}
+
+/// Record the event, made only if metrics are set or it is in a [capture].
+///
+/// Inside [speculate], the event is kept until the speculative deserialization succeeds.
+pub fn record(event: impl FnOnce() -> ParseEvent) {
+    if !is_recording() {
+        return;
+    }
+    let event = event();
+    let event = PENDING.with_borrow_mut(|pending| match pending.last_mut() {
+        Some(pending) => {
+            pending.push(event);
+            None
+        }
+        None => Some(event),
+    });
+    let Some(event) = event else {
+        return;
+    };
+    CAPTURED.with_borrow_mut(|captured| {
+        if let Some(captured) = captured.last_mut() {
+            captured.push(event.clone());
+        }
+    });
+    if let Some(metrics) = METRICS.get() {
+        metrics.record(&event);
+    }
+}
+
+/// Pops the pending events of a speculation, also on a panic.
+struct Speculation;
+
+impl Drop for Speculation {
+    fn drop(&mut self) {
+        PENDING.with_borrow_mut(|pending| pending.pop());
+    }
+}
+
+/// Run a deserialization which may be tried in another way on failure, such as the left of
+/// [Or](crate::Or), recording its events only if it succeeds.
+pub fn speculate<T, E>(f: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+    if !is_recording() {
+        return f();
+    }
+    PENDING.with_borrow_mut(|pending| pending.push(Vec::new()));
+    let speculation = Speculation;
+    let result = f();
+    let events = PENDING.with_borrow_mut(|pending| pending.last_mut().map(std::mem::take));
+    drop(speculation);
+    if result.is_ok() {
+        for event in events.into_iter().flatten() {
+            record(|| event);
+        }
+    }
+    result
+}
+
+/// Pops the events of a capture, also on a panic.
+struct Capture;
+
+impl Drop for Capture {
+    fn drop(&mut self) {
+        CAPTURED.with_borrow_mut(|captured| captured.pop());
+    }
+}
+
+/// Run `f` and return the events of the deserializations in it on this thread, also without
+/// metrics set, such as to lint a document.
+///
+/// The events are still received by the metrics set.
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, Vec<ParseEvent>) {
+    CAPTURED.with_borrow_mut(|captured| captured.push(Vec::new()));
+    let capture = Capture;
+    let output = f();
+    let events = CAPTURED
+        .with_borrow_mut(|captured| captured.last_mut().map(std::mem::take).unwrap_or_default());
+    drop(capture);
+    (output, events)
+}
+
+/// A value skipped by deserialization, telling only whether it is null or `[]`, which are
+/// written for absent properties.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ignored {
+    pub empty: bool,
+}
+
+impl<'de> serde::Deserialize<'de> for Ignored {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(IgnoredVisitor)
+    }
+}
+
+struct IgnoredVisitor;
+
+impl<'de> serde::de::Visitor<'de> for IgnoredVisitor {
+    type Value = Ignored;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("anything")
+    }
+
+    fn visit_bool<E>(self, _: bool) -> Result<Self::Value, E> {
+        Ok(Ignored { empty: false })
+    }
+
+    fn visit_i64<E>(self, _: i64) -> Result<Self::Value, E> {
+        Ok(Ignored { empty: false })
+    }
+
+    fn visit_u64<E>(self, _: u64) -> Result<Self::Value, E> {
+        Ok(Ignored { empty: false })
+    }
+
+    fn visit_f64<E>(self, _: f64) -> Result<Self::Value, E> {
+        Ok(Ignored { empty: false })
+    }
+
+    fn visit_str<E>(self, _: &str) -> Result<Self::Value, E> {
+        Ok(Ignored { empty: false })
+    }
+
+    fn visit_bytes<E>(self, _: &[u8]) -> Result<Self::Value, E> {
+        Ok(Ignored { empty: false })
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(Ignored { empty: true })
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(Ignored { empty: true })
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        <Ignored as serde::Deserialize>::deserialize(deserializer)
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        <Ignored as serde::Deserialize>::deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let empty = seq.next_element::<serde::de::IgnoredAny>()?.is_none();
+        while seq.next_element::<serde::de::IgnoredAny>()?.is_some() {}
+        Ok(Ignored { empty })
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        serde::de::IgnoredAny.visit_map(map)?;
+        Ok(Ignored { empty: false })
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::EnumAccess<'de>,
+    {
+        serde::de::IgnoredAny.visit_enum(data)?;
+        Ok(Ignored { empty: false })
+    }
+}
@@ -0,0 +1,198 @@
+use std::{
+    fmt::Display,
+    hash::Hash,
+    ops::{Deref, DerefMut},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// How an [ObjectUri] is normalized to be compared with another.
+///
+/// The default ports, such as `:443` of `https`, are always dropped by [url::Url] itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Normalization {
+    /// Drop the fragment, so that `https://example.com/users/alice#main-key` is the actor.
+    pub strip_fragment: bool,
+    /// Drop the trailing slash of the path, except of the root.
+    pub strip_trailing_slash: bool,
+    /// Decode the percent-encoded unreserved characters such as `%7E`, and upper the hex digits of
+    /// the others.
+    pub percent_encoding: bool,
+}
+
+impl Default for Normalization {
+    /// Keep the fragment, which often tells another object such as a key.
+    fn default() -> Self {
+        Self {
+            strip_fragment: false,
+            strip_trailing_slash: true,
+            percent_encoding: true,
+        }
+    }
+}
+
+fn is_unreserved(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~')
+}
+
+/// `src` with the unreserved characters decoded and the hex digits of the others in upper case.
+fn normalize_percent_encoding(src: &str) -> String {
+    let mut normalized = String::with_capacity(src.len());
+    let mut rest = src;
+    while let Some(i) = rest.find('%') {
+        normalized.push_str(&rest[..i]);
+        rest = &rest[i..];
+        match rest
+            .get(1..3)
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            Some(byte) if is_unreserved(byte) => normalized.push(byte as char),
+            Some(_) => normalized.push_str(&rest[..3].to_ascii_uppercase()),
+            None => {
+                normalized.push('%');
+                rest = &rest[1..];
+                continue;
+            }
+        }
+        rest = &rest[3..];
+    }
+    normalized.push_str(rest);
+    normalized
+}
+
+/// An `id` of an object, compared and hashed as normalized by [Normalization::default] and
+/// serialized as it is written.
+#[derive(Debug, Clone)]
+pub struct ObjectUri(url::Url);
+
+impl ObjectUri {
+    pub fn new(url: url::Url) -> Self {
+        Self(url)
+    }
+
+    pub fn into_url(self) -> url::Url {
+        self.0
+    }
+
+    /// The url normalized by `normalization`.
+    pub fn normalize(&self, normalization: &Normalization) -> url::Url {
+        let mut url = self.0.clone();
+        if normalization.strip_fragment {
+            url.set_fragment(None);
+        }
+        if normalization.percent_encoding {
+            let path = normalize_percent_encoding(url.path());
+            url.set_path(&path);
+            if let Some(query) = url.query().map(normalize_percent_encoding) {
+                url.set_query(Some(&query));
+            }
+            if let Some(fragment) = url.fragment().map(normalize_percent_encoding) {
+                url.set_fragment(Some(&fragment));
+            }
+        }
+        if normalization.strip_trailing_slash && !url.cannot_be_a_base() {
+            let path = url.path();
+            if path.len() > 1 && path.ends_with('/') {
+                let path = path.trim_end_matches('/').to_owned();
+                url.set_path(&path);
+            }
+        }
+        url
+    }
+
+    /// Whether `self` and `other` are the same when normalized by `normalization`.
+    pub fn matches(&self, other: &url::Url, normalization: &Normalization) -> bool {
+        self.normalize(normalization) == ObjectUri(other.clone()).normalize(normalization)
+    }
+}
+
+impl PartialEq for ObjectUri {
+    fn eq(&self, other: &Self) -> bool {
+        self.matches(&other.0, &Normalization::default())
+    }
+}
+
+impl Eq for ObjectUri {}
+
+impl Hash for ObjectUri {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.normalize(&Normalization::default()).hash(state)
+    }
+}
+
+impl PartialEq<url::Url> for ObjectUri {
+    fn eq(&self, other: &url::Url) -> bool {
+        self.matches(other, &Normalization::default())
+    }
+}
+
+impl Deref for ObjectUri {
+    type Target = url::Url;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for ObjectUri {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl AsRef<url::Url> for ObjectUri {
+    fn as_ref(&self) -> &url::Url {
+        &self.0
+    }
+}
+
+impl From<url::Url> for ObjectUri {
+    fn from(url: url::Url) -> Self {
+        Self(url)
+    }
+}
+
+impl From<ObjectUri> for url::Url {
+    fn from(uri: ObjectUri) -> Self {
+        uri.0
+    }
+}
+
+impl From<ObjectUri> for String {
+    fn from(uri: ObjectUri) -> Self {
+        uri.0.into()
+    }
+}
+
+impl FromStr for ObjectUri {
+    type Err = url::ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self)
+    }
+}
+
+impl Display for ObjectUri {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Serialize for ObjectUri {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ObjectUri {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        url::Url::deserialize(deserializer).map(Self)
+    }
+}
@@ -0,0 +1,935 @@
+use std::{
+    cell::Cell,
+    collections::{BTreeMap, HashMap},
+    fmt::{Display, Write},
+};
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const RDF_FIRST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#first";
+const RDF_REST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#rest";
+const RDF_NIL: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#nil";
+const RDF_LANG_STRING: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString";
+const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+const XSD_BOOLEAN: &str = "http://www.w3.org/2001/XMLSchema#boolean";
+const XSD_INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+const XSD_DOUBLE: &str = "http://www.w3.org/2001/XMLSchema#double";
+
+/// Permutations of related blank nodes and n-degree hashes tried before the canonicalization gives
+/// up, since they grow factorially with blank nodes that cannot be told apart.
+pub const MAX_CANONICALIZATION_WORK: usize = 100_000;
+
+/// Remote contexts are never fetched. Documents referring these contexts by URL are expanded with
+/// the snapshots bundled in this crate.
+const BUNDLED_CONTEXTS: &[(&str, &str)] = &[
+    (
+        "www.w3.org/ns/activitystreams",
+        include_str!("../contexts/activitystreams.jsonld"),
+    ),
+    (
+        "w3id.org/security/v1",
+        include_str!("../contexts/security-v1.jsonld"),
+    ),
+    (
+        "w3id.org/identity/v1",
+        include_str!("../contexts/identity-v1.jsonld"),
+    ),
+];
+
+fn bundled_context(url: &str) -> Option<&'static str> {
+    let url = url
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_end_matches('#')
+        .trim_end_matches(".jsonld");
+    BUNDLED_CONTEXTS
+        .iter()
+        .find(|(bundled, _)| *bundled == url)
+        .map(|(_, document)| *document)
+}
+
+#[derive(Debug)]
+pub enum CanonicalizationError {
+    Serialize(serde_json::Error),
+    UnknownContext(String),
+    InvalidContext(String),
+    InvalidDocument(String),
+    TooComplex,
+}
+
+impl Display for CanonicalizationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Serialize(e) => f.write_fmt(format_args!("serialize document: {e}")),
+            Self::UnknownContext(url) => f.write_fmt(format_args!("unknown context {url}")),
+            Self::InvalidContext(msg) => f.write_fmt(format_args!("invalid context: {msg}")),
+            Self::InvalidDocument(msg) => f.write_fmt(format_args!("invalid document: {msg}")),
+            Self::TooComplex => f.write_fmt(format_args!(
+                "blank nodes need more than {MAX_CANONICALIZATION_WORK} steps to canonicalize"
+            )),
+        }
+    }
+}
+
+impl std::error::Error for CanonicalizationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Serialize(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct TermDefinition {
+    iri: String,
+    type_mapping: Option<String>,
+    container: Option<String>,
+    language: Option<Option<String>>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct ActiveContext {
+    terms: HashMap<String, TermDefinition>,
+    vocab: Option<String>,
+    language: Option<String>,
+}
+
+type LocalContext = serde_json::Map<String, serde_json::Value>;
+
+impl ActiveContext {
+    fn process(&self, local: &serde_json::Value) -> Result<Self, CanonicalizationError> {
+        let mut result = self.clone();
+        let locals = match local {
+            serde_json::Value::Array(locals) => locals.iter().collect(),
+            local => vec![local],
+        };
+        for local in locals {
+            match local {
+                serde_json::Value::Null => result = Self::default(),
+                serde_json::Value::String(url) => {
+                    let document = bundled_context(url)
+                        .ok_or_else(|| CanonicalizationError::UnknownContext(url.clone()))?;
+                    let document: serde_json::Value =
+                        serde_json::from_str(document).expect("bundled contexts are valid JSON");
+                    let context = document.get("@context").ok_or_else(|| {
+                        CanonicalizationError::InvalidContext(format!("{url} has no @context"))
+                    })?;
+                    result = result.process(context)?;
+                }
+                serde_json::Value::Object(local) => result.define_all(local)?,
+                local => return Err(CanonicalizationError::InvalidContext(local.to_string())),
+            }
+        }
+        Ok(result)
+    }
+
+    fn define_all(&mut self, local: &LocalContext) -> Result<(), CanonicalizationError> {
+        match local.get("@vocab") {
+            Some(serde_json::Value::String(vocab)) => self.vocab = Some(vocab.clone()),
+            Some(serde_json::Value::Null) => self.vocab = None,
+            Some(vocab) => return Err(CanonicalizationError::InvalidContext(vocab.to_string())),
+            None => (),
+        }
+        match local.get("@language") {
+            Some(serde_json::Value::String(language)) => {
+                self.language = Some(language.to_lowercase())
+            }
+            Some(serde_json::Value::Null) => self.language = None,
+            Some(language) => {
+                return Err(CanonicalizationError::InvalidContext(language.to_string()))
+            }
+            None => (),
+        }
+        let mut defined = HashMap::new();
+        for term in local.keys() {
+            if !term.starts_with('@') {
+                self.create_term(local, term, &mut defined)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn create_term(
+        &mut self,
+        local: &LocalContext,
+        term: &str,
+        defined: &mut HashMap<String, bool>,
+    ) -> Result<(), CanonicalizationError> {
+        match defined.get(term) {
+            Some(true) => return Ok(()),
+            Some(false) => {
+                return Err(CanonicalizationError::InvalidContext(format!(
+                    "cyclic IRI mapping of {term}"
+                )))
+            }
+            None => (),
+        }
+        defined.insert(term.to_owned(), false);
+        self.terms.remove(term);
+        let (id, type_mapping, container, language) = match &local[term] {
+            serde_json::Value::Null => {
+                defined.insert(term.to_owned(), true);
+                return Ok(());
+            }
+            serde_json::Value::String(id) => (Some(id.as_str()), None, None, None),
+            serde_json::Value::Object(definition) => (
+                definition.get("@id").and_then(|id| id.as_str()),
+                definition.get("@type").and_then(|ty| ty.as_str()),
+                definition.get("@container").and_then(|c| c.as_str()),
+                definition
+                    .get("@language")
+                    .map(|language| language.as_str().map(str::to_lowercase)),
+            ),
+            definition => {
+                return Err(CanonicalizationError::InvalidContext(format!(
+                    "{term}: {definition}"
+                )))
+            }
+        };
+        let type_mapping = type_mapping
+            .map(|ty| self.expand_iri_in(local, ty, defined))
+            .transpose()?;
+        let iri = match id {
+            Some(id) => self.expand_iri_in(local, id, defined)?,
+            None if term.contains(':') => self.expand_iri_in(local, term, defined)?,
+            None => match &self.vocab {
+                Some(vocab) => format!("{vocab}{term}"),
+                None => {
+                    return Err(CanonicalizationError::InvalidContext(format!(
+                        "{term} has no IRI mapping"
+                    )))
+                }
+            },
+        };
+        self.terms.insert(
+            term.to_owned(),
+            TermDefinition {
+                iri,
+                type_mapping,
+                container: container.map(ToOwned::to_owned),
+                language,
+            },
+        );
+        defined.insert(term.to_owned(), true);
+        Ok(())
+    }
+
+    /// IRI expansion during context processing, which defines the terms `value` depends on first.
+    fn expand_iri_in(
+        &mut self,
+        local: &LocalContext,
+        value: &str,
+        defined: &mut HashMap<String, bool>,
+    ) -> Result<String, CanonicalizationError> {
+        if value.starts_with('@') {
+            return Ok(value.to_owned());
+        }
+        let dependency = value
+            .split_once(':')
+            .map(|(prefix, _)| prefix)
+            .unwrap_or(value);
+        if local.contains_key(dependency) && defined.get(dependency) != Some(&true) {
+            self.create_term(local, dependency, defined)?;
+        }
+        Ok(self.expand_iri(value, true))
+    }
+
+    fn expand_iri(&self, value: &str, vocab: bool) -> String {
+        if value.starts_with('@') {
+            return value.to_owned();
+        }
+        if vocab {
+            if let Some(definition) = self.terms.get(value) {
+                return definition.iri.clone();
+            }
+        }
+        if let Some((prefix, suffix)) = value.split_once(':') {
+            if prefix == "_" || suffix.starts_with("//") {
+                return value.to_owned();
+            }
+            return match self.terms.get(prefix) {
+                Some(definition) => format!("{}{suffix}", definition.iri),
+                None => value.to_owned(),
+            };
+        }
+        match (&self.vocab, vocab) {
+            (Some(prefix), true) => format!("{prefix}{value}"),
+            _ => value.to_owned(),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+enum RdfTerm {
+    Iri(String),
+    Blank(String),
+    Literal {
+        value: String,
+        datatype: String,
+        language: Option<String>,
+    },
+}
+
+impl RdfTerm {
+    fn literal(value: impl Into<String>, datatype: &str) -> Self {
+        Self::Literal {
+            value: value.into(),
+            datatype: datatype.to_owned(),
+            language: None,
+        }
+    }
+
+    fn lang_string(value: impl Into<String>, language: String) -> Self {
+        Self::Literal {
+            value: value.into(),
+            datatype: RDF_LANG_STRING.to_owned(),
+            language: Some(language),
+        }
+    }
+
+    fn write_nquads(&self, out: &mut String, rename: &impl Fn(&str) -> String) {
+        match self {
+            Self::Iri(iri) => {
+                out.push('<');
+                out.push_str(iri);
+                out.push('>');
+            }
+            Self::Blank(label) => out.push_str(&rename(label)),
+            Self::Literal {
+                value,
+                datatype,
+                language,
+            } => {
+                out.push('"');
+                for c in value.chars() {
+                    match c {
+                        '\u{8}' => out.push_str("\\b"),
+                        '\t' => out.push_str("\\t"),
+                        '\n' => out.push_str("\\n"),
+                        '\u{c}' => out.push_str("\\f"),
+                        '\r' => out.push_str("\\r"),
+                        '"' => out.push_str("\\\""),
+                        '\\' => out.push_str("\\\\"),
+                        c if c <= '\u{1f}' || c == '\u{7f}' => {
+                            let _ = write!(out, "\\u{:04X}", c as u32);
+                        }
+                        c => out.push(c),
+                    }
+                }
+                out.push('"');
+                if let Some(language) = language {
+                    out.push('@');
+                    out.push_str(language);
+                } else if datatype != XSD_STRING {
+                    out.push_str("^^<");
+                    out.push_str(datatype);
+                    out.push('>');
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Quad {
+    subject: RdfTerm,
+    predicate: String,
+    object: RdfTerm,
+}
+
+impl Quad {
+    fn to_nquads(&self, rename: impl Fn(&str) -> String) -> String {
+        let mut out = String::new();
+        self.subject.write_nquads(&mut out, &rename);
+        out.push_str(" <");
+        out.push_str(&self.predicate);
+        out.push_str("> ");
+        self.object.write_nquads(&mut out, &rename);
+        out.push_str(" .\n");
+        out
+    }
+}
+
+#[derive(Clone, Debug)]
+struct IdentifierIssuer {
+    prefix: &'static str,
+    issued: Vec<String>,
+    identifiers: HashMap<String, String>,
+}
+
+impl IdentifierIssuer {
+    fn new(prefix: &'static str) -> Self {
+        Self {
+            prefix,
+            issued: Vec::new(),
+            identifiers: HashMap::new(),
+        }
+    }
+
+    fn get(&self, existing: &str) -> Option<&String> {
+        self.identifiers.get(existing)
+    }
+
+    fn issue(&mut self, existing: &str) -> String {
+        if let Some(issued) = self.identifiers.get(existing) {
+            return issued.clone();
+        }
+        let issued = format!("{}{}", self.prefix, self.issued.len());
+        self.issued.push(existing.to_owned());
+        self.identifiers.insert(existing.to_owned(), issued.clone());
+        issued
+    }
+
+    fn fresh(&mut self) -> String {
+        let label = format!("{}{}", self.prefix, self.issued.len());
+        self.issued.push(label.clone());
+        self.identifiers.insert(label.clone(), label.clone());
+        label
+    }
+}
+
+struct ToRdf {
+    quads: Vec<Quad>,
+    blank_nodes: IdentifierIssuer,
+}
+
+fn as_items(value: &serde_json::Value) -> Vec<&serde_json::Value> {
+    match value {
+        serde_json::Value::Array(items) => items.iter().collect(),
+        value => vec![value],
+    }
+}
+
+fn canonical_double(value: f64) -> String {
+    let formatted = format!("{value:.15E}");
+    let (mantissa, exponent) = formatted.split_once('E').unwrap_or((&formatted, "0"));
+    let mantissa = mantissa.trim_end_matches('0');
+    if mantissa.ends_with('.') {
+        format!("{mantissa}0E{exponent}")
+    } else {
+        format!("{mantissa}E{exponent}")
+    }
+}
+
+fn number_literal(number: &serde_json::Number, datatype: Option<&str>) -> RdfTerm {
+    let integral = match number.as_f64() {
+        _ if number.is_i64() || number.is_u64() => Some(number.to_string()),
+        Some(float) if float.fract() == 0.0 && float.abs() < 1e21 => Some(format!("{float:.0}")),
+        _ => None,
+    };
+    match integral {
+        Some(integral) if datatype != Some(XSD_DOUBLE) => {
+            RdfTerm::literal(integral, datatype.unwrap_or(XSD_INTEGER))
+        }
+        _ => RdfTerm::literal(
+            canonical_double(number.as_f64().unwrap_or_default()),
+            datatype.unwrap_or(XSD_DOUBLE),
+        ),
+    }
+}
+
+impl ToRdf {
+    fn node_reference(&mut self, iri: String) -> RdfTerm {
+        if iri.starts_with("_:") {
+            RdfTerm::Blank(self.blank_nodes.issue(&iri))
+        } else {
+            RdfTerm::Iri(iri)
+        }
+    }
+
+    fn document(
+        &mut self,
+        context: &ActiveContext,
+        document: &serde_json::Value,
+    ) -> Result<(), CanonicalizationError> {
+        for item in as_items(document) {
+            let serde_json::Value::Object(node) = item else {
+                return Err(CanonicalizationError::InvalidDocument(item.to_string()));
+            };
+            let context = match node.get("@context") {
+                Some(local) => context.process(local)?,
+                None => context.clone(),
+            };
+            let graph = node
+                .iter()
+                .find(|(key, _)| context.expand_iri(key, true) == "@graph");
+            let only_graph = node
+                .keys()
+                .all(|key| key == "@context" || context.expand_iri(key, true) == "@graph");
+            match graph {
+                Some((_, graph)) if only_graph => self.document(&context, graph)?,
+                _ => {
+                    self.node(&context, node)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn node(
+        &mut self,
+        context: &ActiveContext,
+        node: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<RdfTerm, CanonicalizationError> {
+        let context = match node.get("@context") {
+            Some(local) => context.process(local)?,
+            None => context.clone(),
+        };
+        let mut subject = None;
+        let mut types = Vec::new();
+        for (key, value) in node {
+            match context.expand_iri(key, true).as_str() {
+                "@id" => {
+                    let id = value
+                        .as_str()
+                        .ok_or_else(|| CanonicalizationError::InvalidDocument(value.to_string()))?;
+                    subject = Some(self.node_reference(context.expand_iri(id, false)));
+                }
+                "@type" => {
+                    for ty in as_items(value) {
+                        let ty = ty.as_str().ok_or_else(|| {
+                            CanonicalizationError::InvalidDocument(ty.to_string())
+                        })?;
+                        types.push(context.expand_iri(ty, true));
+                    }
+                }
+                _ => (),
+            }
+        }
+        let subject = match subject {
+            Some(subject) => subject,
+            None => RdfTerm::Blank(self.blank_nodes.fresh()),
+        };
+        for ty in types {
+            let object = self.node_reference(ty);
+            self.quads.push(Quad {
+                subject: subject.clone(),
+                predicate: RDF_TYPE.to_owned(),
+                object,
+            });
+        }
+        for (key, value) in node {
+            let predicate = context.expand_iri(key, true);
+            // keywords, and terms dropped on expansion or only expressible in generalized RDF
+            if predicate.starts_with('@') || predicate.starts_with("_:") || !predicate.contains(':')
+            {
+                continue;
+            }
+            let definition = context.terms.get(key);
+            self.property(&context, &subject, &predicate, definition, value)?;
+        }
+        Ok(subject)
+    }
+
+    fn property(
+        &mut self,
+        context: &ActiveContext,
+        subject: &RdfTerm,
+        predicate: &str,
+        definition: Option<&TermDefinition>,
+        value: &serde_json::Value,
+    ) -> Result<(), CanonicalizationError> {
+        let container = definition.and_then(|definition| definition.container.as_deref());
+        let objects = match (container, value) {
+            (Some("@language"), serde_json::Value::Object(per_lang)) => {
+                let mut objects = Vec::new();
+                for (language, values) in per_lang {
+                    for value in as_items(values) {
+                        if let serde_json::Value::String(value) = value {
+                            objects.push(RdfTerm::lang_string(value, language.to_lowercase()));
+                        }
+                    }
+                }
+                objects
+            }
+            (Some("@list"), value) => {
+                let items = match value {
+                    serde_json::Value::Object(list) if list.contains_key("@list") => {
+                        as_items(&list["@list"])
+                    }
+                    value => as_items(value),
+                };
+                vec![self.list(context, definition, items)?]
+            }
+            (_, value) => {
+                let mut objects = Vec::new();
+                for item in as_items(value) {
+                    if let Some(object) = self.object(context, definition, item)? {
+                        objects.push(object);
+                    }
+                }
+                objects
+            }
+        };
+        for object in objects {
+            self.quads.push(Quad {
+                subject: subject.clone(),
+                predicate: predicate.to_owned(),
+                object,
+            });
+        }
+        Ok(())
+    }
+
+    fn list(
+        &mut self,
+        context: &ActiveContext,
+        definition: Option<&TermDefinition>,
+        items: Vec<&serde_json::Value>,
+    ) -> Result<RdfTerm, CanonicalizationError> {
+        let mut objects = Vec::new();
+        for item in items {
+            if let Some(object) = self.object(context, definition, item)? {
+                objects.push(object);
+            }
+        }
+        let nodes = objects
+            .iter()
+            .map(|_| RdfTerm::Blank(self.blank_nodes.fresh()))
+            .collect::<Vec<_>>();
+        for (i, object) in objects.into_iter().enumerate() {
+            self.quads.push(Quad {
+                subject: nodes[i].clone(),
+                predicate: RDF_FIRST.to_owned(),
+                object,
+            });
+            self.quads.push(Quad {
+                subject: nodes[i].clone(),
+                predicate: RDF_REST.to_owned(),
+                object: nodes
+                    .get(i + 1)
+                    .cloned()
+                    .unwrap_or_else(|| RdfTerm::Iri(RDF_NIL.to_owned())),
+            });
+        }
+        Ok(nodes
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| RdfTerm::Iri(RDF_NIL.to_owned())))
+    }
+
+    fn object(
+        &mut self,
+        context: &ActiveContext,
+        definition: Option<&TermDefinition>,
+        item: &serde_json::Value,
+    ) -> Result<Option<RdfTerm>, CanonicalizationError> {
+        let type_mapping = definition.and_then(|definition| definition.type_mapping.as_deref());
+        let datatype = type_mapping.filter(|ty| *ty != "@id" && *ty != "@vocab");
+        match item {
+            serde_json::Value::Null => Ok(None),
+            serde_json::Value::Array(_) => Err(CanonicalizationError::InvalidDocument(format!(
+                "list of lists {item}"
+            ))),
+            serde_json::Value::Bool(value) => Ok(Some(RdfTerm::literal(
+                value.to_string(),
+                datatype.unwrap_or(XSD_BOOLEAN),
+            ))),
+            serde_json::Value::Number(number) => Ok(Some(number_literal(number, datatype))),
+            serde_json::Value::String(value) => Ok(Some(match type_mapping {
+                Some("@id") => self.node_reference(context.expand_iri(value, false)),
+                Some("@vocab") => self.node_reference(context.expand_iri(value, true)),
+                Some(datatype) => RdfTerm::literal(value, datatype),
+                None => match definition
+                    .and_then(|definition| definition.language.clone())
+                    .unwrap_or_else(|| context.language.clone())
+                {
+                    Some(language) => RdfTerm::lang_string(value, language),
+                    None => RdfTerm::literal(value, XSD_STRING),
+                },
+            })),
+            serde_json::Value::Object(object) => {
+                let keyword = |keyword: &str| {
+                    object
+                        .iter()
+                        .find(|(key, _)| context.expand_iri(key, true) == keyword)
+                        .map(|(_, value)| value)
+                };
+                if let Some(value) = keyword("@value") {
+                    let datatype = keyword("@type")
+                        .and_then(|ty| ty.as_str())
+                        .map(|ty| context.expand_iri(ty, true));
+                    let language = keyword("@language").and_then(|language| language.as_str());
+                    Ok(match (value, datatype, language) {
+                        (serde_json::Value::Null, _, _) => None,
+                        (serde_json::Value::String(value), None, Some(language)) => {
+                            Some(RdfTerm::lang_string(value, language.to_lowercase()))
+                        }
+                        (serde_json::Value::String(value), datatype, _) => Some(RdfTerm::literal(
+                            value,
+                            datatype.as_deref().unwrap_or(XSD_STRING),
+                        )),
+                        (serde_json::Value::Number(number), datatype, _) => {
+                            Some(number_literal(number, datatype.as_deref()))
+                        }
+                        (serde_json::Value::Bool(value), datatype, _) => Some(RdfTerm::literal(
+                            value.to_string(),
+                            datatype.as_deref().unwrap_or(XSD_BOOLEAN),
+                        )),
+                        (value, _, _) => {
+                            return Err(CanonicalizationError::InvalidDocument(value.to_string()))
+                        }
+                    })
+                } else if let Some(list) = keyword("@list") {
+                    Ok(Some(self.list(context, definition, as_items(list))?))
+                } else {
+                    Ok(Some(self.node(context, object)?))
+                }
+            }
+        }
+    }
+}
+
+/// Lowercase hex of the SHA-256 of `data`.
+pub fn sha256_hex(data: &str) -> String {
+    Sha256::digest(data.as_bytes())
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+fn permutations(items: &[String]) -> Vec<Vec<String>> {
+    if items.len() <= 1 {
+        return vec![items.to_vec()];
+    }
+    let mut result = Vec::new();
+    for i in 0..items.len() {
+        let mut rest = items.to_vec();
+        let head = rest.remove(i);
+        for mut permutation in permutations(&rest) {
+            permutation.insert(0, head.clone());
+            result.push(permutation);
+        }
+    }
+    result
+}
+
+/// State of the URDNA2015 algorithm.
+struct Urdna2015<'a> {
+    quads: &'a [Quad],
+    blank_node_to_quads: HashMap<&'a str, Vec<&'a Quad>>,
+    canonical_issuer: IdentifierIssuer,
+    work: Cell<usize>,
+}
+
+impl<'a> Urdna2015<'a> {
+    fn new(quads: &'a [Quad]) -> Self {
+        let mut blank_node_to_quads = HashMap::<&str, Vec<&Quad>>::new();
+        for quad in quads {
+            for term in [&quad.subject, &quad.object] {
+                if let RdfTerm::Blank(label) = term {
+                    let mentioned = blank_node_to_quads.entry(label).or_default();
+                    if mentioned.last() != Some(&quad) {
+                        mentioned.push(quad);
+                    }
+                }
+            }
+        }
+        Self {
+            quads,
+            blank_node_to_quads,
+            canonical_issuer: IdentifierIssuer::new("_:c14n"),
+            work: Cell::new(0),
+        }
+    }
+
+    fn spend(&self, steps: usize) -> Result<(), CanonicalizationError> {
+        let work = self.work.get().saturating_add(steps);
+        self.work.set(work);
+        if work > MAX_CANONICALIZATION_WORK {
+            Err(CanonicalizationError::TooComplex)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn hash_first_degree(&self, reference: &str) -> String {
+        let mut nquads = self.blank_node_to_quads[reference]
+            .iter()
+            .map(|quad| {
+                quad.to_nquads(|label| {
+                    if label == reference {
+                        "_:a".to_owned()
+                    } else {
+                        "_:z".to_owned()
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        nquads.sort();
+        sha256_hex(&nquads.concat())
+    }
+
+    fn hash_related_blank_node(
+        &self,
+        related: &str,
+        quad: &Quad,
+        issuer: &IdentifierIssuer,
+        position: char,
+    ) -> String {
+        let identifier = match self
+            .canonical_issuer
+            .get(related)
+            .or_else(|| issuer.get(related))
+        {
+            Some(identifier) => identifier.clone(),
+            None => self.hash_first_degree(related),
+        };
+        sha256_hex(&format!("{position}<{}>{identifier}", quad.predicate))
+    }
+
+    fn hash_n_degree(
+        &self,
+        identifier: &str,
+        mut issuer: IdentifierIssuer,
+    ) -> Result<(String, IdentifierIssuer), CanonicalizationError> {
+        self.spend(1)?;
+        let mut hash_to_related = BTreeMap::<String, Vec<String>>::new();
+        for quad in &self.blank_node_to_quads[identifier] {
+            for (term, position) in [(&quad.subject, 's'), (&quad.object, 'o')] {
+                if let RdfTerm::Blank(related) = term {
+                    if related != identifier {
+                        let hash = self.hash_related_blank_node(related, quad, &issuer, position);
+                        hash_to_related
+                            .entry(hash)
+                            .or_default()
+                            .push(related.clone());
+                    }
+                }
+            }
+        }
+        let mut data_to_hash = String::new();
+        for (related_hash, blank_nodes) in hash_to_related {
+            data_to_hash.push_str(&related_hash);
+            let mut chosen_path = String::new();
+            let mut chosen_issuer = None;
+            // checked before the permutations are listed, as listing them is already factorial
+            self.spend((1..=blank_nodes.len()).fold(1usize, |count, n| count.saturating_mul(n)))?;
+            'permutation: for permutation in permutations(&blank_nodes) {
+                let mut issuer_copy = issuer.clone();
+                let mut path = String::new();
+                let mut recursion_list = Vec::new();
+                for related in &permutation {
+                    match self.canonical_issuer.get(related) {
+                        Some(canonical) => path.push_str(canonical),
+                        None => {
+                            if issuer_copy.get(related).is_none() {
+                                recursion_list.push(related.clone());
+                            }
+                            path.push_str(&issuer_copy.issue(related));
+                        }
+                    }
+                    if !chosen_path.is_empty()
+                        && path.len() >= chosen_path.len()
+                        && path > chosen_path
+                    {
+                        continue 'permutation;
+                    }
+                }
+                for related in recursion_list {
+                    let (hash, result_issuer) =
+                        self.hash_n_degree(&related, issuer_copy.clone())?;
+                    path.push_str(&issuer_copy.issue(&related));
+                    path.push('<');
+                    path.push_str(&hash);
+                    path.push('>');
+                    issuer_copy = result_issuer;
+                    if !chosen_path.is_empty()
+                        && path.len() >= chosen_path.len()
+                        && path > chosen_path
+                    {
+                        continue 'permutation;
+                    }
+                }
+                if chosen_path.is_empty() || path < chosen_path {
+                    chosen_path = path;
+                    chosen_issuer = Some(issuer_copy);
+                }
+            }
+            data_to_hash.push_str(&chosen_path);
+            if let Some(chosen_issuer) = chosen_issuer {
+                issuer = chosen_issuer;
+            }
+        }
+        Ok((sha256_hex(&data_to_hash), issuer))
+    }
+
+    fn canonicalize(mut self) -> Result<String, CanonicalizationError> {
+        let mut hash_to_blank_nodes = BTreeMap::<String, Vec<&str>>::new();
+        let mut blank_nodes = self.blank_node_to_quads.keys().copied().collect::<Vec<_>>();
+        blank_nodes.sort();
+        for blank_node in blank_nodes {
+            hash_to_blank_nodes
+                .entry(self.hash_first_degree(blank_node))
+                .or_default()
+                .push(blank_node);
+        }
+        for blank_nodes in hash_to_blank_nodes.values() {
+            if let [unique] = blank_nodes.as_slice() {
+                self.canonical_issuer.issue(unique);
+            }
+        }
+        for blank_nodes in hash_to_blank_nodes.values() {
+            if blank_nodes.len() < 2 {
+                continue;
+            }
+            let mut results = Vec::new();
+            for blank_node in blank_nodes {
+                if self.canonical_issuer.get(blank_node).is_some() {
+                    continue;
+                }
+                let mut issuer = IdentifierIssuer::new("_:b");
+                issuer.issue(blank_node);
+                results.push(self.hash_n_degree(blank_node, issuer)?);
+            }
+            results.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (_, issuer) in results {
+                for existing in issuer.issued {
+                    self.canonical_issuer.issue(&existing);
+                }
+            }
+        }
+        let mut nquads = self
+            .quads
+            .iter()
+            .map(|quad| {
+                quad.to_nquads(|label| {
+                    self.canonical_issuer
+                        .get(label)
+                        .cloned()
+                        .unwrap_or_else(|| label.to_owned())
+                })
+            })
+            .collect::<Vec<_>>();
+        nquads.sort();
+        nquads.dedup();
+        Ok(nquads.concat())
+    }
+}
+
+/// Canonicalize a JSON-LD document into N-Quads by [URDNA2015](https://www.w3.org/TR/rdf-canon/).
+///
+/// Contexts referred by URL are resolved with the snapshots bundled in this crate
+/// (ActivityStreams, `https://w3id.org/security/v1` and `https://w3id.org/identity/v1`),
+/// other remote contexts are rejected by [CanonicalizationError::UnknownContext].
+/// Documents whose blank nodes take more than [MAX_CANONICALIZATION_WORK] steps to tell apart are
+/// rejected by [CanonicalizationError::TooComplex].
+pub fn canonicalize<T: Serialize>(document: &T) -> Result<String, CanonicalizationError> {
+    let document = serde_json::to_value(document).map_err(CanonicalizationError::Serialize)?;
+    canonicalize_value(&document)
+}
+
+/// [canonicalize] for an already serialized document.
+pub fn canonicalize_value(document: &serde_json::Value) -> Result<String, CanonicalizationError> {
+    let mut to_rdf = ToRdf {
+        quads: Vec::new(),
+        blank_nodes: IdentifierIssuer::new("_:b"),
+    };
+    to_rdf.document(&ActiveContext::default(), document)?;
+    Urdna2015::new(&to_rdf.quads).canonicalize()
+}
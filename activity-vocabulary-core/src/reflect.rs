@@ -0,0 +1,47 @@
+use std::fmt::Display;
+
+use serde::{de::DeserializeOwned, Serialize};
+pub use serde_json::Value;
+
+/// Error of setting a property to a value which is not of its type.
+#[derive(Debug)]
+pub struct InvalidValue {
+    pub type_name: &'static str,
+    /// Name of the property in JSON.
+    pub property: &'static str,
+    pub error: serde_json::Error,
+}
+
+impl Display for InvalidValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "invalid {} of {}: {}",
+            self.property, self.type_name, self.error
+        ))
+    }
+}
+
+impl std::error::Error for InvalidValue {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// The property as JSON, `null` if it is empty.
+pub fn to_value<T: Serialize>(property: &T) -> Value {
+    // the values of the properties are always representable in JSON
+    serde_json::to_value(property).unwrap_or(Value::Null)
+}
+
+/// The property read from `value` as JSON, for `property` of `type_name`.
+pub fn from_value<T: DeserializeOwned>(
+    type_name: &'static str,
+    property: &'static str,
+    value: Value,
+) -> Result<T, InvalidValue> {
+    serde_json::from_value(value).map_err(|error| InvalidValue {
+        type_name,
+        property,
+        error,
+    })
+}
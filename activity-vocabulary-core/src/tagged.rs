@@ -0,0 +1,176 @@
+use std::{collections::BTreeMap, fmt::Debug, marker::PhantomData};
+
+use serde::{
+    de::{value::MapDeserializer, value::SeqDeserializer, IgnoredAny, IntoDeserializer, Visitor},
+    forward_to_deserialize_any, Deserialize, Deserializer,
+};
+use serde_value::Value;
+
+/// Visitor of a map which is internally tagged by `tag`, such as `type` of the objects.
+///
+/// The map is read into its content as a [serde_value::Value::Map] keyed by the names of the
+/// properties, and the value of `tag` or of its JSON-LD keyword, such as `@type` for `type`, is
+/// read as `T` on the way, which is `T::default()` without one. The content is left to be
+/// deserialized as the type the tag tells.
+///
+/// A tag other than the keyword, such as `kind`, reads the keyword by [Self::with_keyword].
+///
+/// The keys of the other than strings are read as names if they can be, such as the integers and
+/// the UTF-8 bytes, and the entries of the others, such as the maps, are skipped without reading
+/// their values.
+pub struct TaggedContentVisitor<T> {
+    name: &'static str,
+    tag: &'static str,
+    keyword: Option<&'static str>,
+    _tag: PhantomData<T>,
+}
+
+impl<T> TaggedContentVisitor<T> {
+    /// Visitor of the map of `tag`, which is expected to be `name` on errors.
+    pub fn new(name: &'static str, tag: &'static str) -> Self {
+        Self {
+            name,
+            tag,
+            keyword: None,
+            _tag: Default::default(),
+        }
+    }
+
+    /// Also read the tag from `keyword`, such as `@type`.
+    pub fn with_keyword(self, keyword: &'static str) -> Self {
+        Self {
+            keyword: Some(keyword),
+            ..self
+        }
+    }
+
+    fn is_tag(&self, key: &str) -> bool {
+        key == self.tag || key.strip_prefix('@') == Some(self.tag) || Some(key) == self.keyword
+    }
+}
+
+/// The key as the name of a property, if it can be one.
+fn property_name(key: Value) -> Option<String> {
+    match key {
+        Value::String(key) => Some(key),
+        Value::Char(key) => Some(key.to_string()),
+        Value::Bytes(key) => String::from_utf8(key).ok(),
+        Value::U8(key) => Some(key.to_string()),
+        Value::U16(key) => Some(key.to_string()),
+        Value::U32(key) => Some(key.to_string()),
+        Value::U64(key) => Some(key.to_string()),
+        Value::I8(key) => Some(key.to_string()),
+        Value::I16(key) => Some(key.to_string()),
+        Value::I32(key) => Some(key.to_string()),
+        Value::I64(key) => Some(key.to_string()),
+        Value::Newtype(key) => property_name(*key),
+        _ => None,
+    }
+}
+
+impl<'de, T: Deserialize<'de> + Debug + Default> Visitor<'de> for TaggedContentVisitor<T> {
+    type Value = (T, Value);
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str(self.name)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        let mut content = BTreeMap::new();
+        let mut tag = None;
+        while let Some(key) = map.next_key::<Value>()? {
+            let Some(key) = property_name(key) else {
+                map.next_value::<IgnoredAny>()?;
+                continue;
+            };
+            let value = crate::integers(map.next_value::<Value>()?);
+            // also the JSON-LD keyword, such as `@type` for `type`
+            if self.is_tag(&key) {
+                tag = Some(T::deserialize(ValueRef::<A::Error>::new(&value))?);
+            }
+            content.insert(Value::String(key), value);
+        }
+        Ok((tag.unwrap_or_default(), Value::Map(content)))
+    }
+}
+
+/// Deserializer of a borrowed [Value], to read the tag without cloning it.
+struct ValueRef<'a, E> {
+    value: &'a Value,
+    _error: PhantomData<E>,
+}
+
+impl<'a, E> ValueRef<'a, E> {
+    fn new(value: &'a Value) -> Self {
+        Self {
+            value,
+            _error: PhantomData,
+        }
+    }
+}
+
+impl<'de, E: serde::de::Error> IntoDeserializer<'de, E> for ValueRef<'_, E> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de, E: serde::de::Error> Deserializer<'de> for ValueRef<'_, E> {
+    type Error = E;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        match self.value {
+            Value::Bool(v) => visitor.visit_bool(*v),
+            Value::U8(v) => visitor.visit_u8(*v),
+            Value::U16(v) => visitor.visit_u16(*v),
+            Value::U32(v) => visitor.visit_u32(*v),
+            Value::U64(v) => visitor.visit_u64(*v),
+            Value::I8(v) => visitor.visit_i8(*v),
+            Value::I16(v) => visitor.visit_i16(*v),
+            Value::I32(v) => visitor.visit_i32(*v),
+            Value::I64(v) => visitor.visit_i64(*v),
+            Value::F32(v) => visitor.visit_f32(*v),
+            Value::F64(v) => visitor.visit_f64(*v),
+            Value::Char(v) => visitor.visit_char(*v),
+            Value::String(v) => visitor.visit_str(v),
+            Value::Unit => visitor.visit_unit(),
+            Value::Option(None) => visitor.visit_none(),
+            Value::Option(Some(v)) => visitor.visit_some(ValueRef::new(v)),
+            Value::Newtype(v) => visitor.visit_newtype_struct(ValueRef::new(v)),
+            Value::Seq(v) => {
+                let mut seq = SeqDeserializer::new(v.iter().map(ValueRef::new));
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            Value::Map(v) => {
+                let mut map = MapDeserializer::new(
+                    v.iter().map(|(k, v)| (ValueRef::new(k), ValueRef::new(v))),
+                );
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+            Value::Bytes(v) => visitor.visit_bytes(v),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        match self.value {
+            Value::Option(None) | Value::Unit => visitor.visit_none(),
+            Value::Option(Some(v)) => visitor.visit_some(ValueRef::new(v)),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
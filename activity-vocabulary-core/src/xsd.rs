@@ -6,9 +6,10 @@ use std::{
 use chrono::{Datelike, FixedOffset, Timelike};
 use nom::{
     bytes::complete::tag,
-    character::complete::{i64, u64},
+    character::complete::{digit1, u64},
     combinator::{eof, opt},
-    sequence::tuple,
+    error::{Error, ErrorKind},
+    sequence::{pair, preceded, terminated, tuple},
     IResult,
 };
 use serde::{de::Visitor, Deserialize, Serialize};
@@ -19,6 +20,112 @@ pub enum DateTime {
     WithOffset(chrono::DateTime<FixedOffset>),
 }
 
+impl DateTime {
+    /// The current time in UTC.
+    pub fn now() -> Self {
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        let now = chrono::DateTime::<chrono::Utc>::from(std::time::SystemTime::now());
+        // the system clock is not available without the host
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        let now =
+            chrono::DateTime::from_timestamp_millis(js_sys::Date::now() as i64).unwrap_or_default();
+        Self::WithOffset(now.fixed_offset())
+    }
+
+    /// The time of `secs` seconds since the Unix epoch, in UTC.
+    pub fn from_unix(secs: i64) -> Option<Self> {
+        Self::from_timestamp_millis(secs.checked_mul(1000)?)
+    }
+
+    /// The time of `millis` milliseconds since the Unix epoch, in UTC.
+    pub fn from_timestamp_millis(millis: i64) -> Option<Self> {
+        let datetime = chrono::DateTime::from_timestamp_millis(millis)?;
+        Some(Self::WithOffset(datetime.fixed_offset()))
+    }
+
+    /// The instant in UTC, taking a [DateTime::Naive] as UTC, to compare times.
+    pub fn to_utc(&self) -> chrono::DateTime<chrono::Utc> {
+        match self {
+            Self::Naive(naive) => naive.and_utc(),
+            Self::WithOffset(datetime) => datetime.with_timezone(&chrono::Utc),
+        }
+    }
+
+    /// The same instant in `offset`, taking a [DateTime::Naive] as UTC.
+    pub fn with_offset(&self, offset: FixedOffset) -> Self {
+        Self::WithOffset(self.to_utc().with_timezone(&offset))
+    }
+
+    /// Seconds since the Unix epoch, taking a [DateTime::Naive] as UTC.
+    pub fn timestamp(&self) -> i64 {
+        self.to_utc().timestamp()
+    }
+
+    /// Milliseconds since the Unix epoch, taking a [DateTime::Naive] as UTC.
+    pub fn timestamp_millis(&self) -> i64 {
+        self.to_utc().timestamp_millis()
+    }
+}
+
+/// Times are ordered by their instants of [DateTime::to_utc], taking a [DateTime::Naive] as UTC.
+///
+/// A naive time is ordered before the same instant with an offset, which it is not equal to, and
+/// times with offsets are equal at the same instant whatever their offsets, as [PartialEq] tells.
+impl Ord for DateTime {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.to_utc().cmp(&other.to_utc()).then_with(|| {
+            matches!(self, Self::WithOffset(_)).cmp(&matches!(other, Self::WithOffset(_)))
+        })
+    }
+}
+
+impl PartialOrd for DateTime {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<chrono::DateTime<chrono::Utc>> for DateTime {
+    fn from(datetime: chrono::DateTime<chrono::Utc>) -> Self {
+        Self::WithOffset(datetime.fixed_offset())
+    }
+}
+
+impl From<chrono::DateTime<FixedOffset>> for DateTime {
+    fn from(datetime: chrono::DateTime<FixedOffset>) -> Self {
+        Self::WithOffset(datetime)
+    }
+}
+
+impl From<chrono::NaiveDateTime> for DateTime {
+    fn from(naive: chrono::NaiveDateTime) -> Self {
+        Self::Naive(naive)
+    }
+}
+
+/// Error of a [DateTime::Naive] converted into a time with an offset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoOffset(pub chrono::NaiveDateTime);
+
+impl Display for NoOffset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{} has no offset", self.0))
+    }
+}
+
+impl std::error::Error for NoOffset {}
+
+impl TryFrom<DateTime> for chrono::DateTime<FixedOffset> {
+    type Error = NoOffset;
+
+    fn try_from(datetime: DateTime) -> Result<Self, Self::Error> {
+        match datetime {
+            DateTime::Naive(naive) => Err(NoOffset(naive)),
+            DateTime::WithOffset(datetime) => Ok(datetime),
+        }
+    }
+}
+
 impl FromStr for DateTime {
     type Err = chrono::ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -82,86 +189,246 @@ impl Serialize for DateTime {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+/// `xsd:float`, such as a latitude, kept as the JSON number it is read from.
+///
+/// With the `arbitrary-precision` feature the number is written back as it is written, such as
+/// `35.6580000`, and otherwise as the nearest [f64] is.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Float(pub serde_json::Number);
+
+impl Float {
+    /// [None] for NaN and the infinities, which JSON does not have.
+    pub fn from_f64(value: f64) -> Option<Self> {
+        serde_json::Number::from_f64(value).map(Self)
+    }
+
+    /// The nearest [f64], or NaN if the number is out of its range.
+    pub fn to_f64(&self) -> f64 {
+        self.0.as_f64().unwrap_or(f64::NAN)
+    }
+}
+
+impl From<i64> for Float {
+    fn from(value: i64) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<u64> for Float {
+    fn from(value: u64) -> Self {
+        Self(value.into())
+    }
+}
+
+impl Display for Float {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// `xsd:duration`, such as `P1Y2M3DT4H5M6.5S`, also read with the week designator of ISO 8601
+/// such as `P2W`.
+///
+/// The string parsed is written back as is while the components are unchanged, and otherwise the
+/// components are written in the canonical form.
+///
+/// Build one with [Duration::new]. The string parsed is kept in a private field, so a struct
+/// literal such as `Duration { negative, years, .. }` no longer compiles.
+#[derive(Debug, Clone)]
 pub struct Duration {
     pub negative: bool,
     pub years: u64,
     pub months: u64,
     pub days: u64,
+    /// Hours, minutes and seconds, whose sign is of [Duration::negative].
     pub duration: chrono::Duration,
+    /// The string parsed and the components it parsed into.
+    lexical: Option<Box<(String, Components)>>,
+}
+
+type Components = (bool, u64, u64, u64, chrono::Duration);
+
+impl Duration {
+    /// The duration of the components, written in the canonical form.
+    pub fn new(
+        negative: bool,
+        years: u64,
+        months: u64,
+        days: u64,
+        duration: chrono::Duration,
+    ) -> Self {
+        Self {
+            negative,
+            years,
+            months,
+            days,
+            duration,
+            lexical: None,
+        }
+    }
+
+    fn components(&self) -> Components {
+        (
+            self.negative,
+            self.years,
+            self.months,
+            self.days,
+            self.duration,
+        )
+    }
+}
+
+/// Durations are equal by their components, however they are written.
+impl PartialEq for Duration {
+    fn eq(&self, other: &Self) -> bool {
+        self.components() == other.components()
+    }
+}
+
+impl Eq for Duration {}
+
+impl std::hash::Hash for Duration {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.components().hash(state)
+    }
 }
 
 impl Display for Duration {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_char('P')?;
+        if let Some((lexical, components)) = self.lexical.as_deref() {
+            if *components == self.components() {
+                return f.write_str(lexical);
+            }
+        }
         if self.negative {
             f.write_char('-')?;
         }
+        f.write_char('P')?;
         if self.years != 0 {
             f.write_fmt(format_args!("{}Y", self.years))?;
         }
         if self.months != 0 {
-            f.write_fmt(format_args!("{}M", self.years))?;
+            f.write_fmt(format_args!("{}M", self.months))?;
         }
         if self.days != 0 {
             f.write_fmt(format_args!("{}D", self.days))?;
         }
-        if !self.duration.num_seconds() != 0 {
-            f.write_char('T')?;
-            if self.duration.num_hours() != 0 {
-                f.write_fmt(format_args!("{}H", self.duration.num_hours()))?;
-            }
-            if self.duration.num_minutes() % 60 != 0 {
-                f.write_fmt(format_args!("{}M", self.duration.num_minutes() % 60))?;
-            }
-            if self.duration.num_seconds() % 60 != 0 {
-                f.write_fmt(format_args!("{}S", self.duration.num_seconds() % 60))?;
+        let duration = self.duration.abs();
+        let seconds = duration.num_seconds();
+        let nanos = (duration - chrono::Duration::seconds(seconds))
+            .num_nanoseconds()
+            .unwrap_or(0);
+        if seconds == 0 && nanos == 0 {
+            if self.years == 0 && self.months == 0 && self.days == 0 {
+                f.write_str("T0S")?;
             }
+            return Ok(());
+        }
+        f.write_char('T')?;
+        if seconds / 3600 != 0 {
+            f.write_fmt(format_args!("{}H", seconds / 3600))?;
+        }
+        if seconds / 60 % 60 != 0 {
+            f.write_fmt(format_args!("{}M", seconds / 60 % 60))?;
+        }
+        if nanos != 0 {
+            let fraction = format!("{nanos:09}");
+            f.write_fmt(format_args!(
+                "{}.{}S",
+                seconds % 60,
+                fraction.trim_end_matches('0')
+            ))?;
+        } else if seconds % 60 != 0 {
+            f.write_fmt(format_args!("{}S", seconds % 60))?;
         }
         Ok(())
     }
 }
 
-fn parse_duration_time_section(src: &str) -> IResult<&str, (i64, i64, i64)> {
-    let (src, _) = tag("T")(src)?;
-    let (src, hours) = opt(tuple((i64, tag("H"))))(src)?;
-    let hours = hours.map(|(n, _)| n).unwrap_or(0);
-    let (src, minutes) = opt(tuple((i64, tag("M"))))(src)?;
-    let minutes = minutes.map(|(n, _)| n).unwrap_or(0);
-    let (src, seconds) = opt(tuple((i64, tag("S"))))(src)?;
-    let seconds = seconds.map(|(n, _)| n).unwrap_or(0);
-    let (src, _) = eof(src)?;
-    Ok((src, (hours, minutes, seconds)))
+fn designated<'a>(
+    designator: &'static str,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Option<u64>> {
+    opt(terminated(u64, tag(designator)))
 }
 
-fn parse_duration(src: &str) -> IResult<&str, Duration> {
-    let (src, _) = tag("P")(src)?;
-    let (src, negative) = opt(tag("-"))(src)?;
-    let (src, years) = opt(tuple((u64, tag("Y"))))(src)?;
-    let years = years.map(|(n, _)| n).unwrap_or(0);
-    let (src, months) = opt(tuple((u64, tag("M"))))(src)?;
-    let months = months.map(|(n, _)| n).unwrap_or(0);
-    let (src, days) = opt(tuple((u64, tag("D"))))(src)?;
-    let days = days.map(|(n, _)| n).unwrap_or(0);
-    let (src, (hours, minutes, seconds)) = parse_duration_time_section(src)?;
-    let (_, _) = eof(src)?;
+fn too_large(src: &str) -> nom::Err<Error<&str>> {
+    nom::Err::Failure(Error::new(src, ErrorKind::TooLarge))
+}
 
+/// Hours, minutes and seconds with the fraction in nanoseconds, at least one of them.
+fn parse_duration_time_section(src: &str) -> IResult<&str, Option<(u64, u64, u64, u32)>> {
+    let Ok((src, _)) = tag::<_, _, Error<&str>>("T")(src) else {
+        return Ok((src, None));
+    };
+    let (src, hours) = designated("H")(src)?;
+    let (src, minutes) = designated("M")(src)?;
+    let (src, seconds) = opt(terminated(
+        pair(u64, opt(preceded(tag("."), digit1))),
+        tag("S"),
+    ))(src)?;
+    if hours.is_none() && minutes.is_none() && seconds.is_none() {
+        return Err(nom::Err::Failure(Error::new(src, ErrorKind::Verify)));
+    }
+    let (seconds, fraction) = seconds.unwrap_or_default();
+    // digits beyond nanoseconds are truncated
+    let nanos = fraction.map_or(0, |fraction| {
+        fraction
+            .bytes()
+            .chain(std::iter::repeat(b'0'))
+            .take(9)
+            .fold(0, |nanos, digit| nanos * 10 + u32::from(digit - b'0'))
+    });
     Ok((
         src,
-        Duration {
-            negative: negative.is_some(),
-            years,
-            months,
-            days,
-            // wait chrono v0.5
-            #[allow(deprecated)]
-            duration: chrono::Duration::hours(hours)
-                + chrono::Duration::minutes(minutes)
-                + chrono::Duration::seconds(seconds),
-        },
+        Some((hours.unwrap_or(0), minutes.unwrap_or(0), seconds, nanos)),
     ))
 }
 
+fn parse_duration(src: &str) -> IResult<&str, Duration> {
+    let (src, negative) = opt(tag("-"))(src)?;
+    let (src, _) = tag("P")(src)?;
+    // written after `P` by the earlier versions
+    let (src, negative_after) = opt(tag("-"))(src)?;
+    if let Ok((_, (Some(weeks), _))) = tuple((designated("W"), eof::<_, Error<&str>>))(src) {
+        let days = weeks.checked_mul(7).ok_or_else(|| too_large(src))?;
+        return Ok((
+            "",
+            Duration::new(
+                negative.is_some() || negative_after.is_some(),
+                0,
+                0,
+                days,
+                chrono::Duration::zero(),
+            ),
+        ));
+    }
+    let (src, years) = designated("Y")(src)?;
+    let (src, months) = designated("M")(src)?;
+    let (src, days) = designated("D")(src)?;
+    let (src, time) = parse_duration_time_section(src)?;
+    let (src, _) = eof(src)?;
+    if years.is_none() && months.is_none() && days.is_none() && time.is_none() {
+        return Err(nom::Err::Failure(Error::new(src, ErrorKind::Verify)));
+    }
+    let (years, months, days) = (years.unwrap_or(0), months.unwrap_or(0), days.unwrap_or(0));
+    let (hours, minutes, seconds, nanos) = time.unwrap_or_default();
+    // chrono panics on the durations beyond `i64::MAX` milliseconds
+    let millis = hours
+        .checked_mul(3600)
+        .zip(minutes.checked_mul(60))
+        .and_then(|(hours, minutes)| hours.checked_add(minutes)?.checked_add(seconds))
+        .and_then(|seconds| i64::try_from(seconds).ok()?.checked_mul(1000))
+        .ok_or_else(|| too_large(src))?;
+    let duration = chrono::Duration::milliseconds(millis)
+        .checked_add(&chrono::Duration::nanoseconds(nanos.into()))
+        .ok_or_else(|| too_large(src))?;
+    // `-PT0S` is the same as `PT0S`
+    let zero = years == 0 && months == 0 && days == 0 && duration.is_zero();
+    let negative = (negative.is_some() || negative_after.is_some()) && !zero;
+    Ok((src, Duration::new(negative, years, months, days, duration)))
+}
+
 #[derive(Debug)]
 pub struct DurationParseError(String);
 impl Display for DurationParseError {
@@ -187,7 +454,8 @@ impl std::error::Error for DurationParseError {
 impl FromStr for Duration {
     type Err = DurationParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, duration) = parse_duration(s).map_err(|e| DurationParseError(e.to_string()))?;
+        let (_, mut duration) = parse_duration(s).map_err(|e| DurationParseError(e.to_string()))?;
+        duration.lexical = Some(Box::new((s.to_owned(), duration.components())));
         Ok(duration)
     }
 }
@@ -0,0 +1,259 @@
+use std::{collections::HashMap, fmt::Display, ops::Range, path::Path};
+
+use miette::{LabeledSpan, NamedSource, SourceSpan};
+use serde::de::DeserializeOwned;
+use yaml_rust2::{
+    parser::{Event, MarkedEventReceiver, Parser},
+    scanner::Marker,
+};
+
+/// Definition an error of the generation is about, attached to it as the context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Definition {
+    pub type_name: String,
+    /// Keys in the definition of the type, such as `["properties", "content"]`.
+    pub path: Vec<String>,
+}
+
+impl Definition {
+    pub fn of_type(type_name: &str) -> Self {
+        Self {
+            type_name: type_name.to_owned(),
+            path: Vec::new(),
+        }
+    }
+
+    pub fn of_property(type_name: &str, property: &str) -> Self {
+        Self::of_type(type_name).entry(["properties", property])
+    }
+
+    pub fn entry<'a>(mut self, path: impl IntoIterator<Item = &'a str>) -> Self {
+        self.path.extend(path.into_iter().map(str::to_owned));
+        self
+    }
+
+    /// Paths in a vocabulary and an extension it may be defined at, the most specific first.
+    fn candidates(&self) -> Vec<Vec<String>> {
+        let mut candidates = Vec::new();
+        for len in (0..=self.path.len()).rev() {
+            let path = &self.path[..len];
+            let type_name = self.type_name.clone();
+            candidates.push(
+                [type_name.clone()]
+                    .into_iter()
+                    .chain(path.to_vec())
+                    .collect(),
+            );
+            candidates.push(
+                ["types".to_owned(), type_name.clone()]
+                    .into_iter()
+                    .chain(path.to_vec())
+                    .collect(),
+            );
+            // the properties an extension adds to the types already defined
+            if let Some((head, rest)) = path.split_first() {
+                if head == "properties" {
+                    candidates.push(
+                        ["properties".to_owned(), type_name]
+                            .into_iter()
+                            .chain(rest.to_vec())
+                            .collect(),
+                    );
+                }
+            }
+        }
+        candidates
+    }
+}
+
+impl Display for Definition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("in the definition of {}", self.type_name))?;
+        for key in &self.path {
+            f.write_fmt(format_args!(".{key}"))?;
+        }
+        Ok(())
+    }
+}
+
+/// YAML file of the definitions, with the locations of its keys to point at them in errors.
+pub struct Source {
+    name: String,
+    text: String,
+    spans: HashMap<Vec<String>, Range<usize>>,
+}
+
+impl Source {
+    pub fn new(name: impl Into<String>, text: impl Into<String>) -> Self {
+        let name = name.into();
+        let text = text.into();
+        let mut index = Index::default();
+        // a syntax error is reported by `parse` with its location
+        let _ = Parser::new_from_str(&text).load(&mut index, false);
+        let spans = index
+            .spans
+            .into_iter()
+            .map(|(path, chars)| (path, byte_range(&text, chars)))
+            .collect();
+        Self { name, text, spans }
+    }
+
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, VocabError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| VocabError {
+            message: format!("read {}: {e}", path.display()),
+            source_code: None,
+        })?;
+        Ok(Self::new(path.display().to_string(), text))
+    }
+
+    /// Deserialize the definitions, pointing at where they are wrong.
+    pub fn parse<T: DeserializeOwned>(&self) -> Result<T, VocabError> {
+        serde_yaml::from_str(&self.text).map_err(|e| {
+            let span = e
+                .location()
+                .map(|location| SourceSpan::from((location.index(), 0)));
+            VocabError {
+                message: e.to_string(),
+                source_code: span.map(|span| (self.named(), span)),
+            }
+        })
+    }
+
+    fn named(&self) -> NamedSource<String> {
+        NamedSource::new(&self.name, self.text.clone()).with_language("yaml")
+    }
+
+    fn span(&self, path: &[String]) -> Option<SourceSpan> {
+        self.spans
+            .get(path)
+            .map(|range| SourceSpan::from(range.clone()))
+    }
+}
+
+/// Error in the definitions, shown by [miette] with the YAML source around it.
+#[derive(Debug)]
+pub struct VocabError {
+    message: String,
+    source_code: Option<(NamedSource<String>, SourceSpan)>,
+}
+
+impl VocabError {
+    /// Point at the [Definition] the error of the generation is about, in the last of `sources`
+    /// which defines it as extensions override the vocabulary.
+    pub fn locate(error: anyhow::Error, sources: &[Source]) -> Self {
+        let source_code = error.downcast_ref::<Definition>().and_then(|definition| {
+            definition.candidates().into_iter().find_map(|path| {
+                sources
+                    .iter()
+                    .rev()
+                    .find_map(|source| Some((source.named(), source.span(&path)?)))
+            })
+        });
+        Self {
+            message: format!("{error:#}"),
+            source_code,
+        }
+    }
+}
+
+impl Display for VocabError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for VocabError {}
+
+impl miette::Diagnostic for VocabError {
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        self.source_code
+            .as_ref()
+            .map(|(source, _)| source as &dyn miette::SourceCode)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let (_, span) = self.source_code.as_ref()?;
+        Some(Box::new(std::iter::once(LabeledSpan::at(*span, "here"))))
+    }
+}
+
+/// Spans of the keys of the mappings, and of the scalars in the sequences, by their paths.
+#[derive(Default)]
+struct Index {
+    frames: Vec<Frame>,
+    spans: HashMap<Vec<String>, Range<usize>>,
+}
+
+struct Frame {
+    /// Key of the collection in its parent.
+    name: String,
+    mapping: bool,
+    /// Key of the mapping whose value is not read yet.
+    key: Option<String>,
+}
+
+impl Index {
+    fn record(&mut self, value: &str, mark: Marker) {
+        let path = self
+            .frames
+            .iter()
+            .skip(1)
+            .map(|frame| frame.name.clone())
+            .chain([value.to_owned()])
+            .collect();
+        let start = mark.index();
+        self.spans
+            .entry(path)
+            .or_insert(start..start + value.chars().count());
+    }
+}
+
+impl MarkedEventReceiver for Index {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        match event {
+            Event::Scalar(value, ..) => {
+                let Some(frame) = self.frames.last_mut() else {
+                    return;
+                };
+                if !frame.mapping {
+                    self.record(&value, mark);
+                } else if frame.key.take().is_none() {
+                    frame.key = Some(value.clone());
+                    self.record(&value, mark);
+                }
+            }
+            Event::MappingStart(..) | Event::SequenceStart(..) => {
+                let name = self
+                    .frames
+                    .last_mut()
+                    .and_then(|frame| frame.key.take())
+                    .unwrap_or_default();
+                self.frames.push(Frame {
+                    name,
+                    mapping: matches!(event, Event::MappingStart(..)),
+                    key: None,
+                });
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                self.frames.pop();
+            }
+            Event::Alias(_) => {
+                if let Some(frame) = self.frames.last_mut() {
+                    frame.key = None;
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Range of bytes of a range of chars, as the parser counts chars.
+fn byte_range(text: &str, chars: Range<usize>) -> Range<usize> {
+    let offset = |index| {
+        text.char_indices()
+            .nth(index)
+            .map_or(text.len(), |(offset, _)| offset)
+    };
+    offset(chars.start)..offset(chars.end)
+}
@@ -3,10 +3,14 @@ use std::collections::{HashMap, HashSet};
 use anyhow::{anyhow, Context};
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use syn::{LitByteStr, LitStr, Type};
 
-#[derive(Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub mod diagnostic;
+
+use diagnostic::Definition;
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
 pub enum PropertyKind {
     Required,
     Functional,
@@ -59,6 +63,64 @@ impl PropertyDef {
             PropertyDef::LangContainer { doc, .. } => doc,
         }
     }
+
+    fn kind(&self) -> &PropertyKind {
+        match self {
+            PropertyDef::Simple { kind, .. } => kind,
+            PropertyDef::LangContainer { kind, .. } => kind,
+        }
+    }
+
+    /// The JSON-LD keyword the property also is, such as `@id`.
+    fn keyword(&self) -> Option<&str> {
+        match self {
+            PropertyDef::Simple { uri, .. } if uri.starts_with('@') => Some(uri),
+            _ => None,
+        }
+    }
+
+    fn apply_override(&mut self, property_override: &PropertyOverride) {
+        let (PropertyDef::Simple {
+            tag,
+            property_type,
+            kind,
+            ..
+        }
+        | PropertyDef::LangContainer {
+            tag,
+            property_type,
+            kind,
+            ..
+        }) = self;
+        if let Some(new_type) = &property_override.property_type {
+            *property_type = new_type.clone();
+        }
+        if let Some(new_kind) = &property_override.kind {
+            *kind = new_kind.clone();
+        }
+        if let Some(new_tag) = &property_override.tag {
+            *tag = Some(new_tag.clone());
+        }
+    }
+}
+
+/// Whether some of the properties are JSON-LD keywords, kept in `keywords` of the type.
+fn has_keywords(properties: &HashMap<String, PropertyDef>) -> bool {
+    properties.values().any(|def| def.keyword().is_some())
+}
+
+/// Replacement of a property a subtype inherits, keeping what it leaves out as inherited.
+///
+/// The subtype is converted into its supertypes with [Into] from the replaced type to the
+/// inherited one, such as from `T` to `Option<T>` if the property is made required.
+#[derive(Deserialize, Clone, Default)]
+pub struct PropertyOverride {
+    #[serde(default, rename = "type")]
+    pub property_type: Option<String>,
+    #[serde(default)]
+    pub kind: Option<PropertyKind>,
+    #[serde(default)]
+    pub tag: Option<String>,
 }
 
 #[derive(Deserialize, Clone)]
@@ -70,17 +132,58 @@ pub enum PreferredPropertyName {
 #[derive(Deserialize, Clone)]
 pub struct TypeDef {
     pub uri: String,
+    /// Supertypes, the earlier of which wins if they have the same property differently.
     #[serde(default)]
-    pub extends: HashSet<String>,
+    pub extends: Vec<String>,
     #[serde(default)]
     pub properties: HashMap<String, PropertyDef>,
     #[serde(default)]
     pub preferred_property_name: HashMap<String, PreferredPropertyName>,
     #[serde(default)]
     pub except_properties: HashSet<String>,
+    /// Inherited properties replaced in this type.
+    #[serde(default)]
+    pub override_properties: HashMap<String, PropertyOverride>,
     pub doc: String,
 }
 
+/// Extension vocabulary which adds types, and properties to the types already defined.
+#[derive(Deserialize, Clone, Default)]
+pub struct Extension {
+    #[serde(default)]
+    pub types: HashMap<String, TypeDef>,
+    #[serde(default)]
+    pub properties: HashMap<String, HashMap<String, PropertyDef>>,
+}
+
+impl Extension {
+    pub fn apply(self, defs: &mut HashMap<String, TypeDef>) -> anyhow::Result<()> {
+        for (name, def) in self.types {
+            if defs.insert(name.clone(), def).is_some() {
+                return Err(anyhow!("{name} is already defined"))
+                    .context(Definition::of_type(&name));
+            }
+        }
+        for (name, properties) in self.properties {
+            let def = defs
+                .get_mut(&name)
+                .ok_or_else(|| anyhow!("{name} is not defined"))
+                .context(Definition::of_type(&name))?;
+            for (property, property_def) in properties {
+                if def
+                    .properties
+                    .insert(property.clone(), property_def)
+                    .is_some()
+                {
+                    return Err(anyhow!("{name}::{property} is already defined"))
+                        .context(Definition::of_property(&name, &property));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 impl PropertyKind {
     fn wrap_type(&self, ty: syn::Type) -> Type {
         match self {
@@ -93,7 +196,7 @@ impl PropertyKind {
     fn serializing_stmt(
         &self,
         serializer: TokenStream,
-        tag: &str,
+        tag: TokenStream,
         property: TokenStream,
     ) -> TokenStream {
         if self == &Self::Required {
@@ -213,9 +316,11 @@ fn collect_properties(
     type_def: &TypeDef,
     full_defs: &HashMap<String, TypeDef>,
 ) -> anyhow::Result<HashMap<String, PropertyDef>> {
+    // the earlier supertype is collected later to override the others
     let properties = type_def
         .extends
         .iter()
+        .rev()
         .map(|super_name| {
             let super_def = full_defs
                 .get(super_name)
@@ -225,10 +330,16 @@ fn collect_properties(
         .collect::<anyhow::Result<Vec<_>>>()?
         .into_iter()
         .flatten()
+        .map(|(name, mut def)| {
+            if let Some(property_override) = type_def.override_properties.get(&name) {
+                def.apply_override(property_override);
+            }
+            (name, def)
+        })
         .collect::<Vec<_>>();
     let properties = properties
         .into_iter()
-        .chain(type_def.properties.clone().into_iter())
+        .chain(type_def.properties.clone())
         .filter(|(name, _)| !type_def.except_properties.contains(name))
         .map(|(name, def)| rename_default_name(type_def, &name, def).map(|def| (name, def)))
         .collect::<anyhow::Result<HashMap<String, PropertyDef>>>()?;
@@ -244,6 +355,7 @@ fn gen_type(
     type_def: &TypeDef,
     full_defs: &HashMap<String, TypeDef>,
 ) -> anyhow::Result<TokenStream> {
+    let keywords = has_keywords(&collect_properties(type_def, full_defs)?);
     let properties = collect_properties(type_def, full_defs)?
         .iter()
         .map(|(name, def)| {
@@ -260,7 +372,86 @@ fn gen_type(
             ))
         })
         .collect::<anyhow::Result<TokenStream>>()?;
+    let mut all_properties = collect_properties(type_def, full_defs)?
+        .into_iter()
+        .collect::<Vec<_>>();
+    all_properties.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let (required, optional): (Vec<_>, Vec<_>) = all_properties
+        .iter()
+        .partition(|(_, def)| def.kind() == &PropertyKind::Required);
     let type_name = ident(type_name);
+    // a required property has no sensible default, so take it in `new` instead
+    let (derive_default, constructor) = if required.is_empty() {
+        let emptiness = optional
+            .iter()
+            .map(|(name, def)| {
+                let name = ident(name);
+                match (def, def.kind()) {
+                    (PropertyDef::LangContainer { .. }, _) => {
+                        quote!(self.#name.default.is_none() && self.#name.per_lang.is_empty())
+                    }
+                    (_, PropertyKind::Normal) => quote!(self.#name.0.is_empty()),
+                    _ => quote!(self.#name.is_none()),
+                }
+            })
+            .collect::<Vec<_>>();
+        let is_empty = if emptiness.is_empty() {
+            quote!(true)
+        } else {
+            quote!(#(#emptiness)&&*)
+        };
+        let doc =
+            format!("Whether no property of [{type_name}] is set, as in `{type_name}::default()`.");
+        (
+            quote!(#[derive(Default)]),
+            quote!(
+                impl #type_name {
+                    #[doc = #doc]
+                    pub fn is_empty(&self) -> bool {
+                        #is_empty
+                    }
+                }
+            ),
+        )
+    } else {
+        let params = required
+            .iter()
+            .map(|(name, def)| {
+                let name = ident(name);
+                let ty = def.gen_type()?;
+                Ok(quote!(#name: #ty))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let required = required.iter().map(|(name, _)| ident(name));
+        let optional = optional
+            .iter()
+            .map(|(name, _)| ident(name))
+            .chain(keywords.then(|| ident("keywords")));
+        let doc = format!("[{type_name}] of the required properties, leaving the others empty.");
+        (
+            quote!(),
+            quote!(
+                impl #type_name {
+                    #[doc = #doc]
+                    pub fn new(#(#params),*) -> Self {
+                        Self {
+                            #(#required,)*
+                            #(#optional: ::std::default::Default::default(),)*
+                        }
+                    }
+                }
+            ),
+        )
+    };
+    let keywords_field = if keywords {
+        quote! {
+            /// The JSON-LD keywords the properties are read with, such as `@id` for `id`.
+            #[builder(default)]
+            pub keywords: ::activity_vocabulary_core::Keywords,
+        }
+    } else {
+        quote!()
+    };
     let doc_uri = format!("`{}`", &type_def.uri);
     let doc_body = &type_def.doc;
     let doc = quote!(
@@ -271,20 +462,53 @@ fn gen_type(
     Ok(quote! {
         #[derive(Debug, Clone, PartialEq)]
         #[derive(::typed_builder::TypedBuilder)]
+        #derive_default
         #doc
         #[allow(clippy::type_complexity)]
         pub struct #type_name {
             #properties
+            #keywords_field
         }
+
+        #constructor
     })
 }
 
 fn gen_serialize_stmt(serializer: TokenStream, name: String, def: PropertyDef) -> TokenStream {
     let name_ident = ident(&name);
+    let keyword = def.keyword().map(str::to_owned);
     match def {
         PropertyDef::Simple { tag, kind, .. } => {
             let tag = tag.unwrap_or(name);
-            kind.serializing_stmt(serializer, &tag, quote!(&self.#name_ident))
+            let Some(keyword) = keyword else {
+                return kind.serializing_stmt(serializer, quote!(#tag), quote!(&self.#name_ident));
+            };
+            let stmt =
+                kind.serializing_stmt(serializer.clone(), quote!(key), quote!(&self.#name_ident));
+            // the variant of the subtypes leads the types if the object does not tell it
+            let stmt = if keyword == "@type" {
+                quote! {
+                    match type_name {
+                        Some(type_name) if !self.#name_ident.0.iter().any(|t| t == type_name) => {
+                            let types = ::activity_vocabulary_core::Property(
+                                std::iter::once(type_name.to_owned())
+                                    .chain(self.#name_ident.0.iter().cloned())
+                                    .collect(),
+                            );
+                            #serializer.serialize_entry(key, &types)?;
+                        }
+                        _ => #stmt
+                    }
+                }
+            } else {
+                stmt
+            };
+            quote! {
+                {
+                    let key = if self.keywords.contains(#keyword) { #keyword } else { #tag };
+                    #stmt
+                }
+            }
         }
         PropertyDef::LangContainer {
             tag,
@@ -293,11 +517,14 @@ fn gen_serialize_stmt(serializer: TokenStream, name: String, def: PropertyDef) -
             ..
         } => {
             let tag = tag.unwrap_or(name);
-            let default =
-                kind.serializing_stmt(serializer.clone(), &tag, quote!(&self.#name_ident.default));
+            let default = kind.serializing_stmt(
+                serializer.clone(),
+                quote!(#tag),
+                quote!(&self.#name_ident.default),
+            );
             let per_lang = kind.serializing_stmt(
                 serializer,
-                &container_tag,
+                quote!(#container_tag),
                 quote!(&self.#name_ident.per_lang),
             );
 
@@ -313,27 +540,57 @@ fn gen_serialize_impl(
     type_name: &str,
     type_def: &TypeDef,
     full_defs: &HashMap<String, TypeDef>,
+    options: &Options,
 ) -> anyhow::Result<TokenStream> {
     let type_ident = ident(type_name);
     let properties = collect_properties(type_def, full_defs)?;
+    let has_type = properties
+        .values()
+        .any(|def| def.keyword() == Some("@type"));
+    let type_entry = if has_type {
+        quote!()
+    } else {
+        let type_tag = &options.type_tag;
+        quote! {
+            if let Some(type_name) = type_name {
+                serializer.serialize_entry(#type_tag, type_name)?;
+            }
+        }
+    };
     let serializings = properties
         .into_iter()
         .map(|(name, def)| gen_serialize_stmt(quote!(serializer), name, def))
         .collect::<TokenStream>();
     Ok(quote! {
         const _: () = {
-            #[allow(unused_mut)]
-            impl serde::Serialize for #type_ident {
-                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            impl #type_ident {
+                /// Serialize with `type_name` leading the types if the object does not have it, as
+                /// the variant of the subtypes.
+                #[allow(unused_mut, clippy::possible_missing_else)]
+                pub(crate) fn serialize_as<S>(
+                    &self,
+                    serializer: S,
+                    type_name: Option<&'static str>,
+                ) -> Result<S::Ok, S::Error>
                 where
                     S: serde::Serializer,
                 {
                     use serde::ser::SerializeMap;
                     let mut serializer = serializer.serialize_map(None)?;
+                    #type_entry
                     #serializings
                     serializer.end()
                 }
             }
+
+            impl serde::Serialize for #type_ident {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: serde::Serializer,
+                {
+                    self.serialize_as(serializer, None)
+                }
+            }
         };
     })
 }
@@ -414,15 +671,19 @@ fn gen_label_deserialize_helper(map: HashMap<String, String>) -> TokenStream {
                 self,
                 mut value: A
             ) -> Result<Self::Value, A::Error> {
+                // the first known one, or the first one to be reported as unknown
+                let mut unknown = None;
                 loop {
                     match value.next_element::<__Label>() {
-                        Ok(Some(__Label::__Ignore(_))) => continue,
+                        Ok(Some(__Label::__Ignore(label))) => {
+                            unknown.get_or_insert(label);
+                        }
                         Ok(Some(x)) => return Ok(x),
                         Ok(None) => break,
                         Err(_) => continue,
                     }
                 }
-                Ok(__Label::__Ignore(Default::default()))
+                Ok(__Label::__Ignore(unknown.unwrap_or_default()))
             }
         }
 
@@ -449,6 +710,10 @@ fn gen_label_deserialize_helper_for_struct(
                     aka.iter()
                         .chain(std::iter::once(tag))
                         .map(|tag| (tag.to_owned(), name.to_owned()))
+                        .chain(
+                            def.keyword()
+                                .map(|keyword| (keyword.to_owned(), keyword_label(name))),
+                        )
                         .collect::<Vec<_>>()
                 }
                 PropertyDef::LangContainer {
@@ -472,6 +737,11 @@ fn gen_label_deserialize_helper_for_struct(
     )
 }
 
+/// Label of the property read with its JSON-LD keyword.
+fn keyword_label(name: &str) -> String {
+    format!("{name}__keyword")
+}
+
 fn gen_field_placeholder_for_struct(name: &str, def: &PropertyDef) -> anyhow::Result<TokenStream> {
     let name_ident = ident(name);
     match def {
@@ -487,16 +757,31 @@ fn gen_field_placeholder_for_struct(name: &str, def: &PropertyDef) -> anyhow::Re
     }
 }
 
+/// Expression of the next value of `__map` as `ty`, traced with the property `key` of `type_name`
+/// if it fails.
+fn gen_next_value(ty: &syn::Type, type_name: &str, key: &str) -> TokenStream {
+    quote! {
+        {
+            let value = __map.next_value::<#ty>();
+            #[cfg(feature = "tracing")]
+            if let Err(e) = &value {
+                ::activity_vocabulary_core::trace_malformed_property(#type_name, #key, e);
+            }
+            value?
+        }
+    }
+}
+
 fn gen_insert_deserialized_field(
     name: Ident,
-    ty: syn::Type,
+    next_value: TokenStream,
     err_label: &str,
     kind: &PropertyKind,
 ) -> TokenStream {
     if kind == &PropertyKind::Normal {
         quote! {
             __Label::#name => {
-                let value = __map.next_value::<#ty>()?;
+                let value = #next_value;
                 if let Some(occupied) = #name.as_mut() {
                     ::activity_vocabulary_core::MergeableProperty::merge(occupied, value);
                 }
@@ -508,7 +793,7 @@ fn gen_insert_deserialized_field(
     } else {
         quote! {
             __Label::#name => {
-                let value = __map.next_value::<#ty>()?;
+                let value = #next_value;
                 if #name.is_some() {
                     return Err(::serde::de::Error::duplicate_field(#err_label))
                 }
@@ -521,27 +806,35 @@ fn gen_insert_deserialized_field(
 }
 
 fn gen_deserialize_match_arm_for_struct(
+    type_name: &str,
     name: &str,
     def: &PropertyDef,
 ) -> anyhow::Result<TokenStream> {
     let ty = def.gen_type()?;
     match def {
-        PropertyDef::Simple { kind, .. } => {
-            Ok(gen_insert_deserialized_field(ident(name), ty, name, kind))
+        PropertyDef::Simple { kind, tag, .. } => {
+            let next_value = gen_next_value(&ty, type_name, tag.as_deref().unwrap_or(name));
+            Ok(gen_insert_deserialized_field(
+                ident(name),
+                next_value,
+                name,
+                kind,
+            ))
         }
-        PropertyDef::LangContainer { kind, .. } => {
+        PropertyDef::LangContainer { kind, tag, .. } => {
+            let next_value = gen_next_value(&ty, type_name, tag.as_deref().unwrap_or(name));
             let name = ident(name);
             if kind == &PropertyKind::Required {
                 Ok(quote!(
                     __Label::#name => {
-                        let value = __map.next_value::<#ty>()?;
+                        let value = #next_value;
                         #name.deep_merge(value);
                     }
                 ))
             } else {
                 Ok(quote!(
                     __Label::#name => {
-                        let value = __map.next_value::<#ty>()?;
+                        let value = #next_value;
                         #name.merge(value);
                     }
                 ))
@@ -582,17 +875,56 @@ fn gen_impl_visitor_for_struct(
     let deserialize_match_arms = properties
         .iter()
         .map(|(name, def)| {
-            let arm = gen_deserialize_match_arm_for_struct(name, def)?;
+            let arm = gen_deserialize_match_arm_for_struct(type_name, name, def)?;
             Ok(quote!(#arm,))
         })
         .collect::<anyhow::Result<TokenStream>>()?;
-    let build_struct = properties
+    let mut build_struct = properties
         .iter()
         .map(|(name, def)| {
             let build = gen_build_field(name, def)?;
             Ok(quote!(#build,))
         })
         .collect::<anyhow::Result<TokenStream>>()?;
+    let keyword_arms = properties
+        .iter()
+        .filter_map(|(name, def)| {
+            let keyword = def.keyword()?;
+            let label = ident(&keyword_label(name));
+            let name = ident(name);
+            Some(quote! {
+                __Label::#label => {
+                    __keywords.insert(#keyword);
+                    __Label::#name
+                }
+            })
+        })
+        .collect::<TokenStream>();
+    let keyword_arms = if keyword_arms.is_empty() {
+        quote!()
+    } else {
+        quote! {
+            let __key = match __key {
+                #keyword_arms
+                __key => __key,
+            };
+        }
+    };
+    // taken by the keyword arms above
+    let keyword_labels = properties
+        .iter()
+        .filter(|(_, def)| def.keyword().is_some())
+        .map(|(name, _)| {
+            let label = ident(&keyword_label(name));
+            quote!(__Label::#label => unreachable!(),)
+        })
+        .collect::<TokenStream>();
+    let keywords_placeholder = if has_keywords(properties) {
+        build_struct.extend(quote!(keywords: __keywords,));
+        quote!(let mut __keywords = ::activity_vocabulary_core::Keywords::default();)
+    } else {
+        quote!()
+    };
     Ok(quote! {
         struct __Visitor;
         impl<'de> ::serde::de::Visitor<'de> for __Visitor {
@@ -611,11 +943,24 @@ fn gen_impl_visitor_for_struct(
                     A: serde::de::MapAccess<'de>,
             {
                 #field_placeholders
+                #keywords_placeholder
                 while let Some(__key) = __map.next_key::<__Label>()? {
+                    #keyword_arms
                     match __key {
                         #deserialize_match_arms
-                        __Label::__Ignore(_) => {
-                            let _ = __map.next_value::<serde::de::IgnoredAny>();
+                        #keyword_labels
+                        __Label::__Ignore(__property) => {
+                            let __empty = __map
+                                .next_value::<::activity_vocabulary_core::metrics::Ignored>()
+                                .is_ok_and(|__value| __value.empty);
+                            if !__property.is_empty() && !__property.starts_with('@') && !__empty {
+                                ::activity_vocabulary_core::metrics::record(|| {
+                                    ::activity_vocabulary_core::metrics::ParseEvent::UnknownProperty {
+                                        type_name: #type_name,
+                                        property: __property,
+                                    }
+                                });
+                            }
                         }
                     }
                 }
@@ -628,11 +973,12 @@ fn gen_impl_visitor_for_struct(
 fn gen_tags(properties: &HashMap<String, PropertyDef>) -> Vec<String> {
     properties
         .iter()
-        .flat_map(|(name, tag)| match tag {
+        .flat_map(|(name, def)| match def {
             PropertyDef::Simple { tag, aka, .. } => aka
                 .clone()
                 .into_iter()
                 .chain(std::iter::once(tag.clone().unwrap_or_else(|| name.clone())))
+                .chain(def.keyword().map(str::to_owned))
                 .collect::<Vec<_>>(),
             PropertyDef::LangContainer {
                 tag,
@@ -695,7 +1041,9 @@ fn collect_subtypes<'a>(
     while let Some((name, def)) = names.pop() {
         subtypes.insert(name, def);
         for (sub_name, sub_def) in full_defs {
-            if sub_def.extends.contains(name) && !subtypes.contains_key(sub_name.as_str()) {
+            if sub_def.extends.iter().any(|super_name| super_name == name)
+                && !subtypes.contains_key(sub_name.as_str())
+            {
                 names.push((sub_name, sub_def));
             }
         }
@@ -716,14 +1064,17 @@ fn gen_upcast_from_sub(
     let sub_properties = collect_properties(sub_def, full_defs)?;
 
     let straights = self_properties
-        .keys()
-        .filter_map(|field| {
+        .iter()
+        .filter_map(|(field, def)| {
             let field_ident = ident(field);
-            if sub_properties.contains_key(field) {
-                Some(quote! { #field_ident: value.#field_ident, })
-            } else {
-                None
-            }
+            let sub_def = sub_properties.get(field)?;
+            // the type overridden in the subtype converts into the inherited one
+            Some(match (def.gen_type(), sub_def.gen_type()) {
+                (Ok(ty), Ok(sub_ty)) if quote!(#ty).to_string() != quote!(#sub_ty).to_string() => {
+                    quote! { #field_ident: ::std::convert::Into::into(value.#field_ident), }
+                }
+                _ => quote! { #field_ident: value.#field_ident, },
+            })
         })
         .collect::<TokenStream>();
 
@@ -739,12 +1090,19 @@ fn gen_upcast_from_sub(
         })
         .collect::<TokenStream>();
 
+    let keywords = if has_keywords(&self_properties) {
+        quote!(keywords: value.keywords,)
+    } else {
+        quote!()
+    };
+
     Ok(quote! {
         impl From<#sub_ident> for #type_ident {
             fn from(value: #sub_ident) -> Self {
                 Self {
                     #straights
                     #defaults
+                    #keywords
                 }
             }
         }
@@ -769,6 +1127,17 @@ fn gen_upcasts_from_subs(
         .collect::<anyhow::Result<TokenStream>>()
 }
 
+/// Name of the property of `type_def` which is its `@type`.
+fn type_property(
+    type_def: &TypeDef,
+    full_defs: &HashMap<String, TypeDef>,
+) -> anyhow::Result<Option<String>> {
+    Ok(collect_properties(type_def, full_defs)?
+        .into_iter()
+        .find(|(_, def)| def.keyword() == Some("@type"))
+        .map(|(name, _)| name))
+}
+
 fn gen_subtypes(
     type_name: &str,
     type_def: &TypeDef,
@@ -782,16 +1151,215 @@ fn gen_subtypes(
             quote!(#ident(#ident),)
         })
         .collect::<TokenStream>();
-    let ident = ident(&format!("{type_name}Subtypes"));
+    let subtype_ident = ident(&format!("{type_name}Subtypes"));
+    let froms = subtypes
+        .keys()
+        .map(|name| {
+            let sub_ident = ident(name);
+            quote! {
+                impl From<#sub_ident> for #subtype_ident {
+                    fn from(value: #sub_ident) -> Self {
+                        Self::#sub_ident(value)
+                    }
+                }
+            }
+        })
+        .collect::<TokenStream>();
+    let type_name_arms = subtypes
+        .keys()
+        .map(|name| {
+            let sub_ident = ident(name);
+            quote!(Self::#sub_ident(_) => #name,)
+        })
+        .collect::<TokenStream>();
+    let type_properties = subtypes
+        .iter()
+        .map(|(name, def)| Ok((name, type_property(def, full_defs)?)))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let serialize_arms = type_properties
+        .iter()
+        .map(|(name, property)| {
+            let sub_ident = ident(name);
+            match property {
+                // the unknown types which fell back to the base are kept as they are
+                Some(property) if **name == type_name => {
+                    let property = ident(property);
+                    quote! {
+                        Self::#sub_ident(inner) => inner.serialize_as(
+                            serializer,
+                            inner.#property.0.is_empty().then_some(#name),
+                        ),
+                    }
+                }
+                _ => {
+                    quote!(Self::#sub_ident(inner) => inner.serialize_as(serializer, Some(#name)),)
+                }
+            }
+        })
+        .collect::<TokenStream>();
+    let secondary_types_arms = type_properties
+        .iter()
+        .map(|(name, property)| {
+            let sub_ident = ident(name);
+            match property {
+                Some(property) => {
+                    let property = ident(property);
+                    quote! {
+                        Self::#sub_ident(inner) => inner
+                            .#property
+                            .0
+                            .iter()
+                            .map(String::as_str)
+                            .filter(|t| *t != #name)
+                            .collect(),
+                    }
+                }
+                None => quote!(Self::#sub_ident(_) => Vec::new(),),
+            }
+        })
+        .collect::<TokenStream>();
     Ok(quote! {
-        #[derive(Debug, PartialEq, Clone, ::serde::Serialize)]
-        #[serde(tag = "type")]
-        pub enum #ident {
+        #[derive(Debug, PartialEq, Clone)]
+        #[cfg_attr(
+            feature = "diesel",
+            derive(::diesel::expression::AsExpression, ::diesel::deserialize::FromSqlRow),
+            diesel(sql_type = ::diesel::sql_types::Jsonb)
+        )]
+        pub enum #subtype_ident {
             #contents
         }
+        #froms
+        impl ::serde::Serialize for #subtype_ident {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+            {
+                match self {
+                    #serialize_arms
+                }
+            }
+        }
+        impl #subtype_ident {
+            /// Name of the variant, which is its `type` on serialization.
+            pub fn type_name(&self) -> &'static str {
+                match self {
+                    #type_name_arms
+                }
+            }
+
+            /// Types of the object other than the variant, such as `toot:Featured` of
+            /// `"type": ["Person", "toot:Featured"]`.
+            pub fn secondary_types(&self) -> Vec<&str> {
+                match self {
+                    #secondary_types_arms
+                }
+            }
+        }
+        #[cfg(feature = "sqlx")]
+        crate::sqlx::json_type!(#subtype_ident);
+        #[cfg(feature = "diesel")]
+        impl ::diesel::serialize::ToSql<::diesel::sql_types::Jsonb, ::diesel::pg::Pg> for #subtype_ident {
+            fn to_sql<'b>(
+                &'b self,
+                out: &mut ::diesel::serialize::Output<'b, '_, ::diesel::pg::Pg>,
+            ) -> ::diesel::serialize::Result {
+                ::std::io::Write::write_all(out, &[1])?;
+                ::serde_json::to_writer(out, self)?;
+                Ok(::diesel::serialize::IsNull::No)
+            }
+        }
+        #[cfg(feature = "diesel")]
+        impl ::diesel::deserialize::FromSql<::diesel::sql_types::Jsonb, ::diesel::pg::Pg> for #subtype_ident {
+            fn from_sql(value: ::diesel::pg::PgValue<'_>) -> ::diesel::deserialize::Result<Self> {
+                let value = <::serde_json::Value as ::diesel::deserialize::FromSql<
+                    ::diesel::sql_types::Jsonb,
+                    ::diesel::pg::Pg,
+                >>::from_sql(value)?;
+                Ok(::serde_json::from_value(value)?)
+            }
+        }
     })
 }
 
+/// Accessors of the properties which every variant has with the same type.
+fn gen_subtypes_accessors(
+    type_name: &str,
+    type_def: &TypeDef,
+    full_defs: &HashMap<String, TypeDef>,
+) -> anyhow::Result<TokenStream> {
+    let subtype_ident = ident(&format!("{type_name}Subtypes"));
+    let subtypes = collect_subtypes(type_name, type_def, full_defs)?;
+    let sub_idents = subtypes.keys().map(|name| ident(name)).collect::<Vec<_>>();
+    let sub_properties = subtypes
+        .values()
+        .map(|sub_def| collect_properties(sub_def, full_defs))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let mut properties = collect_properties(type_def, full_defs)?
+        .into_iter()
+        .collect::<Vec<_>>();
+    properties.sort_by(|(a, _), (b, _)| a.cmp(b));
+    properties
+        .into_iter()
+        .map(|(name, def)| {
+            let ty = def.gen_type()?;
+            let ty_str = quote!(#ty).to_string();
+            for sub_properties in &sub_properties {
+                match sub_properties.get(&name) {
+                    Some(sub_def) => {
+                        let sub_ty = sub_def.gen_type()?;
+                        if quote!(#sub_ty).to_string() != ty_str {
+                            return Ok(quote! {});
+                        }
+                    }
+                    None => return Ok(quote! {}),
+                }
+            }
+            let name_ident = ident(&name);
+            let doc = format!("[{type_name}::{name}] of the variant.");
+            let (ret, access) = match (&def, def.kind()) {
+                (PropertyDef::Simple { property_type, .. }, PropertyKind::Normal) => {
+                    let inner: Type = syn::parse_str(property_type)
+                        .with_context(|| format!("parse {property_type}"))?;
+                    (quote!(&[#inner]), quote!(&inner.#name_ident.0))
+                }
+                (PropertyDef::Simple { property_type, .. }, PropertyKind::Functional) => {
+                    // recursive properties are boxed, which is not a concern of the callers
+                    match property_type
+                        .strip_prefix("Box<")
+                        .and_then(|boxed| boxed.strip_suffix('>'))
+                    {
+                        Some(boxed) => {
+                            let inner: Type = syn::parse_str(boxed)
+                                .with_context(|| format!("parse {property_type}"))?;
+                            (
+                                quote!(Option<&#inner>),
+                                quote!(inner.#name_ident.as_deref()),
+                            )
+                        }
+                        None => {
+                            let inner: Type = syn::parse_str(property_type)
+                                .with_context(|| format!("parse {property_type}"))?;
+                            (quote!(Option<&#inner>), quote!(inner.#name_ident.as_ref()))
+                        }
+                    }
+                }
+                _ => (quote!(&#ty), quote!(&inner.#name_ident)),
+            };
+            Ok(quote! {
+                impl #subtype_ident {
+                    #[doc = #doc]
+                    #[allow(clippy::type_complexity)]
+                    pub fn #name_ident(&self) -> #ret {
+                        match self {
+                            #(#subtype_ident::#sub_idents(inner) => #access,)*
+                        }
+                    }
+                }
+            })
+        })
+        .collect()
+}
+
 fn gen_subtypes_upcast_to_self(
     type_name: &str,
     type_def: &TypeDef,
@@ -830,7 +1398,9 @@ fn gen_subtypes_deserialize(
     type_name: &str,
     type_def: &TypeDef,
     full_defs: &HashMap<String, TypeDef>,
+    options: &Options,
 ) -> anyhow::Result<TokenStream> {
+    let type_tag = &options.type_tag;
     let base_ident = ident(type_name);
     let subtype_ident = ident(&format!("{type_name}Subtypes"));
     let subtypes = collect_subtypes(type_name, type_def, full_defs)?;
@@ -844,15 +1414,12 @@ fn gen_subtypes_deserialize(
         .keys()
         .map(|name| {
             let ident = ident(name);
-            quote! { __Label::#ident => Ok(#subtype_ident::#ident(#ident::deserialize(deserializer)?)), }
+            quote! { __Label::#ident => Ok(#subtype_ident::#ident(#ident::deserialize(::serde_value::ValueDeserializer::<D::Error>::new(content))?)), }
         })
         .collect::<TokenStream>();
 
-    let expected = subtypes
-        .keys()
-        .map(ToString::to_string)
-        .collect::<Vec<_>>()
-        .join(", ");
+    let mut expected = subtypes.keys().collect::<Vec<_>>();
+    expected.sort();
 
     Ok(quote! {
         const _:() = {
@@ -864,17 +1431,36 @@ fn gen_subtypes_deserialize(
                     #label_helper
 
                     let (tag, content) = deserializer.deserialize_any(
-                        ::activity_vocabulary_core::TaggedContentVisitor::<__Label>::new(#type_name, "type")
+                        ::activity_vocabulary_core::TaggedContentVisitor::<__Label>::new(#type_name, #type_tag)
+                            .with_keyword("@type")
                     )?;
-                    let deserializer = ::serde_value::ValueDeserializer::new(content);
+                    #[cfg(feature = "tracing")]
+                    let _span = ::activity_vocabulary_core::deserialize_span(#type_name, &content);
                     match tag {
                         #arms
                         __Label::__Ignore(name) => {
-                            if let Ok(object) = #base_ident::deserialize(deserializer) {
+                            let unknown = ::activity_vocabulary_core::UnknownType::new(
+                                name,
+                                &content,
+                                &[#(#expected),*],
+                            );
+                            let fallback = ::activity_vocabulary_core::metrics::speculate(|| {
+                                #base_ident::deserialize(::serde_value::ValueDeserializer::<D::Error>::new(content))
+                            });
+                            ::activity_vocabulary_core::metrics::record(|| {
+                                ::activity_vocabulary_core::metrics::ParseEvent::UnknownType {
+                                    subtypes: #type_name,
+                                    received: unknown.received.clone(),
+                                    fallback: fallback.is_ok(),
+                                }
+                            });
+                            if let Ok(object) = fallback {
                                 Ok(#subtype_ident::#base_ident(object))
                             }
                             else {
-                                Err(::serde::de::Error::invalid_type(::serde::de::Unexpected::Str(&name), &#expected))
+                                #[cfg(feature = "tracing")]
+                                ::tracing::debug!(error = %unknown, "unknown type");
+                                Err(::serde::de::Error::custom(unknown))
                             }
                         }
                     }
@@ -884,33 +1470,1419 @@ fn gen_subtypes_deserialize(
     })
 }
 
-fn gen_set(
-    name: &str,
-    def: &TypeDef,
-    defs: &HashMap<String, TypeDef>,
-) -> anyhow::Result<TokenStream> {
-    let type_def = gen_type(name, def, defs)?;
-    let serialize_impl = gen_serialize_impl(name, def, defs)?;
-    let deserialize_impl = gen_deserialize_impl(name, def, defs)?;
-    let subtypes_def = gen_subtypes(name, def, defs)?;
-    let subtypes_deserialize_impl = gen_subtypes_deserialize(name, def, defs)?;
-    let upcasts = gen_upcasts_from_subs(name, def, defs)?;
-    let subtype_upcast = gen_subtypes_upcast_to_self(name, def, defs)?;
-    Ok(quote! {
-        #type_def
-        #serialize_impl
-        #deserialize_impl
-        #subtypes_def
-        #subtypes_deserialize_impl
-        #upcasts
-        #subtype_upcast
-    })
+fn collect_ancestors<'a>(
+    type_def: &'a TypeDef,
+    full_defs: &'a HashMap<String, TypeDef>,
+) -> anyhow::Result<HashSet<&'a str>> {
+    let mut ancestors = HashSet::new();
+    for super_name in &type_def.extends {
+        let super_def = full_defs
+            .get(super_name)
+            .with_context(|| format!("type {super_name} not found"))?;
+        ancestors.insert(super_name.as_str());
+        ancestors.extend(collect_ancestors(super_def, full_defs)?);
+    }
+    Ok(ancestors)
 }
 
-pub fn gen(defs: &HashMap<String, TypeDef>) -> anyhow::Result<String> {
-    let src = defs
+/// `TryFrom` the subtypes of the ancestors, into the type itself if `subtypes` is aliased to it.
+fn gen_downcasts_to_subtypes(
+    type_name: &str,
+    type_def: &TypeDef,
+    full_defs: &HashMap<String, TypeDef>,
+    subtypes: bool,
+) -> anyhow::Result<TokenStream> {
+    let subtype_ident = ident(&format!("{type_name}Subtypes"));
+    let sub_idents = collect_subtypes(type_name, type_def, full_defs)?
+        .keys()
+        .map(|name| ident(name))
+        .collect::<Vec<_>>();
+    let downcasts = sub_idents
         .iter()
-        .map(|(name, def)| gen_set(name, def, defs))
-        .collect::<anyhow::Result<TokenStream>>()?;
-    Ok(src.to_string())
+        .map(|sub_ident| {
+            if subtypes {
+                quote!(Ok(Self::#sub_ident(inner)))
+            } else {
+                quote!(Ok(inner))
+            }
+        })
+        .collect::<Vec<_>>();
+    collect_ancestors(type_def, full_defs)?
+        .into_iter()
+        .map(|ancestor| {
+            let ancestor_ident = ident(&format!("{ancestor}Subtypes"));
+            Ok(quote! {
+                impl TryFrom<#ancestor_ident> for #subtype_ident {
+                    type Error = #ancestor_ident;
+
+                    fn try_from(value: #ancestor_ident) -> Result<Self, Self::Error> {
+                        match value {
+                            #(#ancestor_ident::#sub_idents(inner) => #downcasts,)*
+                            other => Err(other),
+                        }
+                    }
+                }
+            })
+        })
+        .collect()
+}
+
+fn gen_upcasts_to_ancestor_subtypes(
+    type_name: &str,
+    type_def: &TypeDef,
+    full_defs: &HashMap<String, TypeDef>,
+) -> anyhow::Result<TokenStream> {
+    let subtype_ident = ident(&format!("{type_name}Subtypes"));
+    let sub_idents = collect_subtypes(type_name, type_def, full_defs)?
+        .keys()
+        .map(|name| ident(name))
+        .collect::<Vec<_>>();
+    collect_ancestors(type_def, full_defs)?
+        .into_iter()
+        .map(|ancestor| {
+            let ancestor_ident = ident(&format!("{ancestor}Subtypes"));
+            Ok(quote! {
+                impl From<#subtype_ident> for #ancestor_ident {
+                    fn from(value: #subtype_ident) -> Self {
+                        match value {
+                            #(#subtype_ident::#sub_idents(inner) => Self::#sub_idents(inner),)*
+                        }
+                    }
+                }
+            })
+        })
+        .collect()
+}
+
+fn gen_object_id_impl(
+    type_name: &str,
+    type_def: &TypeDef,
+    full_defs: &HashMap<String, TypeDef>,
+    subtypes: bool,
+) -> anyhow::Result<TokenStream> {
+    let has_id = collect_properties(type_def, full_defs)?
+        .get("id")
+        .is_some_and(|def| def.kind() == &PropertyKind::Functional);
+    if !has_id {
+        return Ok(quote! {});
+    }
+    let type_ident = ident(type_name);
+    let subtype_ident = ident(&format!("{type_name}Subtypes"));
+    let sub_idents = collect_subtypes(type_name, type_def, full_defs)?
+        .keys()
+        .map(|name| ident(name))
+        .collect::<Vec<_>>();
+    let subtypes_impl = subtypes.then_some(quote! {
+        impl ::activity_vocabulary_core::ObjectId for #subtype_ident {
+            fn object_id(&self) -> Option<&url::Url> {
+                match self {
+                    #(#subtype_ident::#sub_idents(inner) => inner.id.as_deref(),)*
+                }
+            }
+        }
+    });
+    Ok(quote! {
+        impl ::activity_vocabulary_core::ObjectId for #type_ident {
+            fn object_id(&self) -> Option<&url::Url> {
+                self.id.as_deref()
+            }
+        }
+
+        #subtypes_impl
+    })
+}
+
+/// Properties of `audience::Addressed` of the generated crate, in its order.
+const ADDRESSING: &[&str] = &["to", "bto", "cc", "bcc", "audience"];
+
+fn gen_addressed_impl(
+    type_name: &str,
+    type_def: &TypeDef,
+    full_defs: &HashMap<String, TypeDef>,
+    subtypes: bool,
+) -> anyhow::Result<TokenStream> {
+    let properties = collect_properties(type_def, full_defs)?;
+    if !ADDRESSING.iter().all(|name| properties.contains_key(*name)) {
+        return Ok(quote! {});
+    }
+    let type_ident = ident(type_name);
+    let fields = ADDRESSING
+        .iter()
+        .map(|name| ident(name))
+        .collect::<Vec<_>>();
+    let subtype_ident = ident(&format!("{type_name}Subtypes"));
+    let sub_idents = collect_subtypes(type_name, type_def, full_defs)?
+        .keys()
+        .map(|name| ident(name))
+        .collect::<Vec<_>>();
+    let subtypes_impl = subtypes.then_some(quote! {
+        impl crate::audience::Addressed for #subtype_ident {
+            fn addressing(&self) -> [&[crate::audience::Recipient]; 5] {
+                match self {
+                    #(#subtype_ident::#sub_idents(inner) => crate::audience::Addressed::addressing(inner),)*
+                }
+            }
+
+            fn addressing_mut(&mut self) -> [&mut Vec<crate::audience::Recipient>; 5] {
+                match self {
+                    #(#subtype_ident::#sub_idents(inner) => crate::audience::Addressed::addressing_mut(inner),)*
+                }
+            }
+        }
+    });
+    Ok(quote! {
+        impl crate::audience::Addressed for #type_ident {
+            fn addressing(&self) -> [&[crate::audience::Recipient]; 5] {
+                [#(&self.#fields.0),*]
+            }
+
+            fn addressing_mut(&mut self) -> [&mut Vec<crate::audience::Recipient>; 5] {
+                [#(&mut self.#fields.0),*]
+            }
+        }
+
+        #subtypes_impl
+    })
+}
+
+fn gen_tagged_impl(
+    type_name: &str,
+    type_def: &TypeDef,
+    full_defs: &HashMap<String, TypeDef>,
+    subtypes: bool,
+) -> anyhow::Result<TokenStream> {
+    let properties = collect_properties(type_def, full_defs)?;
+    if !properties.contains_key("tag") {
+        return Ok(quote! {});
+    }
+    let type_ident = ident(type_name);
+    let subtype_ident = ident(&format!("{type_name}Subtypes"));
+    let sub_idents = collect_subtypes(type_name, type_def, full_defs)?
+        .keys()
+        .map(|name| ident(name))
+        .collect::<Vec<_>>();
+    let subtypes_impl = subtypes.then_some(quote! {
+        impl crate::tag::Tagged for #subtype_ident {
+            fn tags(&self) -> &[crate::tag::Tag] {
+                match self {
+                    #(#subtype_ident::#sub_idents(inner) => crate::tag::Tagged::tags(inner),)*
+                }
+            }
+        }
+    });
+    Ok(quote! {
+        impl crate::tag::Tagged for #type_ident {
+            fn tags(&self) -> &[crate::tag::Tag] {
+                &self.tag.0
+            }
+        }
+
+        #subtypes_impl
+    })
+}
+
+fn gen_content_warning_impl(
+    type_name: &str,
+    type_def: &TypeDef,
+    full_defs: &HashMap<String, TypeDef>,
+    subtypes: bool,
+) -> anyhow::Result<TokenStream> {
+    let properties = collect_properties(type_def, full_defs)?;
+    if !properties.contains_key("summary") || !properties.contains_key("sensitive") {
+        return Ok(quote! {});
+    }
+    let type_ident = ident(type_name);
+    let subtype_ident = ident(&format!("{type_name}Subtypes"));
+    let sub_idents = collect_subtypes(type_name, type_def, full_defs)?
+        .keys()
+        .map(|name| ident(name))
+        .collect::<Vec<_>>();
+    let subtypes_impl = subtypes.then_some(quote! {
+        impl crate::content_warning::ContentWarning for #subtype_ident {
+            fn warning(&self) -> (&crate::content_warning::Summary, Option<bool>) {
+                match self {
+                    #(#subtype_ident::#sub_idents(inner) => crate::content_warning::ContentWarning::warning(inner),)*
+                }
+            }
+
+            fn warning_mut(&mut self) -> (&mut crate::content_warning::Summary, &mut Option<bool>) {
+                match self {
+                    #(#subtype_ident::#sub_idents(inner) => crate::content_warning::ContentWarning::warning_mut(inner),)*
+                }
+            }
+        }
+    });
+    Ok(quote! {
+        impl crate::content_warning::ContentWarning for #type_ident {
+            fn warning(&self) -> (&crate::content_warning::Summary, Option<bool>) {
+                (&self.summary, self.sensitive)
+            }
+
+            fn warning_mut(&mut self) -> (&mut crate::content_warning::Summary, &mut Option<bool>) {
+                (&mut self.summary, &mut self.sensitive)
+            }
+        }
+
+        #subtypes_impl
+    })
+}
+
+fn gen_quoting_impl(
+    type_name: &str,
+    type_def: &TypeDef,
+    full_defs: &HashMap<String, TypeDef>,
+    subtypes: bool,
+) -> anyhow::Result<TokenStream> {
+    const QUOTES: [&str; 3] = ["quote_url", "misskey_quote", "quote_uri"];
+    let properties = collect_properties(type_def, full_defs)?;
+    if !properties.contains_key("tag") || !QUOTES.iter().all(|name| properties.contains_key(*name))
+    {
+        return Ok(quote! {});
+    }
+    let type_ident = ident(type_name);
+    let fields = QUOTES.iter().map(|name| ident(name)).collect::<Vec<_>>();
+    let subtype_ident = ident(&format!("{type_name}Subtypes"));
+    let sub_idents = collect_subtypes(type_name, type_def, full_defs)?
+        .keys()
+        .map(|name| ident(name))
+        .collect::<Vec<_>>();
+    let subtypes_impl = subtypes.then_some(quote! {
+        impl crate::quote::Quoting for #subtype_ident {
+            fn quote_ids(&self) -> [Option<&url::Url>; 3] {
+                match self {
+                    #(#subtype_ident::#sub_idents(inner) => crate::quote::Quoting::quote_ids(inner),)*
+                }
+            }
+        }
+    });
+    Ok(quote! {
+        impl crate::quote::Quoting for #type_ident {
+            fn quote_ids(&self) -> [Option<&url::Url>; 3] {
+                [#(self.#fields.as_ref()),*]
+            }
+        }
+
+        #subtypes_impl
+    })
+}
+
+/// `wrap_object` of the activities with an object, implemented with `outbox::copy_addressing` of
+/// the generated crate.
+fn gen_wrap_object_impl(
+    type_name: &str,
+    type_def: &TypeDef,
+    full_defs: &HashMap<String, TypeDef>,
+) -> anyhow::Result<TokenStream> {
+    let properties = collect_properties(type_def, full_defs)?;
+    if !is_kind_of(type_name, "Activity", full_defs)
+        || !properties.contains_key("object")
+        || !ADDRESSING.iter().all(|name| properties.contains_key(*name))
+    {
+        return Ok(quote! {});
+    }
+    let type_ident = ident(type_name);
+    Ok(quote! {
+        impl #type_ident {
+            /// Set `object` as the object of this activity, copying the recipients of each to the other
+            /// as ActivityPub recommends so that their addressing does not mismatch.
+            pub fn wrap_object(&mut self, object: impl Into<crate::ObjectSubtypes>) {
+                let mut object = object.into();
+                crate::outbox::copy_addressing(self, &mut object);
+                self.object = ::activity_vocabulary_core::Property(vec![
+                    ::activity_vocabulary_core::Or::Snd(::activity_vocabulary_core::Remotable::Inline(object)),
+                ]);
+            }
+        }
+    })
+}
+
+/// Properties which must be removed before delivery.
+const BLIND_ADDRESSING: &[&str] = &["bto", "bcc"];
+
+fn gen_blind_addressing_impl(
+    type_name: &str,
+    type_def: &TypeDef,
+    full_defs: &HashMap<String, TypeDef>,
+    subtypes: bool,
+) -> anyhow::Result<TokenStream> {
+    let type_ident = ident(type_name);
+    let stmts = collect_properties(type_def, full_defs)?
+        .into_iter()
+        .filter_map(|(name, def)| {
+            let name_ident = ident(&name);
+            match def {
+                _ if BLIND_ADDRESSING.contains(&name.as_str()) => Some(quote! {
+                    self.#name_ident = Default::default();
+                }),
+                // only the generated types may inline objects
+                PropertyDef::Simple { property_type, .. }
+                    if may_have_generated(&property_type, full_defs) =>
+                {
+                    Some(quote! {
+                        ::activity_vocabulary_core::BlindAddressing::strip_blind_addressing(&mut self.#name_ident);
+                    })
+                }
+                _ => None,
+            }
+        })
+        .collect::<TokenStream>();
+    let subtype_ident = ident(&format!("{type_name}Subtypes"));
+    let arms = collect_subtypes(type_name, type_def, full_defs)?
+        .keys()
+        .map(|name| {
+            let sub_ident = ident(name);
+            quote! {
+                #subtype_ident::#sub_ident(inner) => {
+                    ::activity_vocabulary_core::BlindAddressing::strip_blind_addressing(inner)
+                }
+            }
+        })
+        .collect::<TokenStream>();
+    let subtypes_impl = subtypes.then_some(quote! {
+        impl ::activity_vocabulary_core::BlindAddressing for #subtype_ident {
+            fn strip_blind_addressing(&mut self) {
+                match self {
+                    #arms
+                }
+            }
+        }
+    });
+    Ok(quote! {
+        impl ::activity_vocabulary_core::BlindAddressing for #type_ident {
+            fn strip_blind_addressing(&mut self) {
+                #stmts
+            }
+        }
+
+        #subtypes_impl
+    })
+}
+
+fn gen_normalize_impl(
+    type_name: &str,
+    type_def: &TypeDef,
+    full_defs: &HashMap<String, TypeDef>,
+    subtypes: bool,
+) -> anyhow::Result<TokenStream> {
+    let type_ident = ident(type_name);
+    let stmts = collect_properties(type_def, full_defs)?
+        .into_iter()
+        .filter_map(|(name, def)| match def {
+            // only the generated types may inline objects
+            PropertyDef::Simple { property_type, .. }
+                if may_have_generated_objects(&property_type, full_defs) =>
+            {
+                let name_ident = ident(&name);
+                Some(quote! {
+                    crate::normalize::Normalize::normalize_into(&mut self.#name_ident, extracted);
+                })
+            }
+            _ => None,
+        })
+        .collect::<TokenStream>();
+    let subtype_ident = ident(&format!("{type_name}Subtypes"));
+    let sub_idents = collect_subtypes(type_name, type_def, full_defs)?
+        .keys()
+        .map(|name| ident(name))
+        .collect::<Vec<_>>();
+    let subtypes_impl = subtypes.then_some(quote! {
+        impl crate::normalize::Normalize for #subtype_ident {
+            fn normalize_into(&mut self, extracted: &mut Vec<crate::ObjectSubtypes>) {
+                match self {
+                    #(#subtype_ident::#sub_idents(inner) => {
+                        crate::normalize::Normalize::normalize_into(inner, extracted)
+                    })*
+                }
+            }
+        }
+    });
+    Ok(quote! {
+        impl crate::normalize::Normalize for #type_ident {
+            #[allow(unused_variables)]
+            fn normalize_into(&mut self, extracted: &mut Vec<crate::ObjectSubtypes>) {
+                #stmts
+            }
+        }
+
+        #subtypes_impl
+    })
+}
+
+fn gen_hydrate_impl(
+    type_name: &str,
+    type_def: &TypeDef,
+    full_defs: &HashMap<String, TypeDef>,
+    subtypes: bool,
+) -> anyhow::Result<TokenStream> {
+    let type_ident = ident(type_name);
+    let stmts = collect_properties(type_def, full_defs)?
+        .into_iter()
+        .filter_map(|(name, def)| match def {
+            // only the generated types may inline objects
+            PropertyDef::Simple { property_type, .. }
+                if may_have_generated_objects(&property_type, full_defs) =>
+            {
+                let name_ident = ident(&name);
+                Some(quote! {
+                    crate::hydrate::Hydrate::hydrate_with(&mut self.#name_ident, depth, lookup);
+                })
+            }
+            _ => None,
+        })
+        .collect::<TokenStream>();
+    let subtype_ident = ident(&format!("{type_name}Subtypes"));
+    let sub_idents = collect_subtypes(type_name, type_def, full_defs)?
+        .keys()
+        .map(|name| ident(name))
+        .collect::<Vec<_>>();
+    let subtypes_impl = subtypes.then_some(quote! {
+        impl crate::hydrate::Hydrate for #subtype_ident {
+            fn hydrate_with(
+                &mut self,
+                depth: usize,
+                lookup: &mut dyn FnMut(&url::Url) -> Option<crate::ObjectSubtypes>,
+            ) {
+                match self {
+                    #(#subtype_ident::#sub_idents(inner) => {
+                        crate::hydrate::Hydrate::hydrate_with(inner, depth, lookup)
+                    })*
+                }
+            }
+        }
+    });
+    Ok(quote! {
+        impl crate::hydrate::Hydrate for #type_ident {
+            #[allow(unused_variables)]
+            fn hydrate_with(
+                &mut self,
+                depth: usize,
+                lookup: &mut dyn FnMut(&url::Url) -> Option<crate::ObjectSubtypes>,
+            ) {
+                #stmts
+            }
+        }
+
+        #subtypes_impl
+    })
+}
+
+/// Names of the types mentioned in `property_type`, e.g. `Or`, `LinkSubtypes` and `Remotable`.
+fn mentioned_types(property_type: &str) -> impl Iterator<Item = &str> {
+    property_type.split(|c: char| !c.is_alphanumeric() && c != '_')
+}
+
+/// Whether a property of `property_type` may have the generated types.
+fn may_have_generated(property_type: &str, full_defs: &HashMap<String, TypeDef>) -> bool {
+    mentioned_types(property_type).any(|name| {
+        full_defs.contains_key(name)
+            || name
+                .strip_suffix("Subtypes")
+                .is_some_and(|name| full_defs.contains_key(name))
+    })
+}
+
+/// Whether `type_name` is `ancestor` or extends it.
+fn is_kind_of(type_name: &str, ancestor: &str, full_defs: &HashMap<String, TypeDef>) -> bool {
+    type_name == ancestor
+        || full_defs.get(type_name).is_some_and(|def| {
+            def.extends
+                .iter()
+                .any(|super_name| is_kind_of(super_name, ancestor, full_defs))
+        })
+}
+
+/// Whether a property of `property_type` may have the generated types, which are objects where
+/// they may be referred by id, so that they can be extracted into `ObjectSubtypes` and back.
+fn may_have_generated_objects(property_type: &str, full_defs: &HashMap<String, TypeDef>) -> bool {
+    may_have_generated(property_type, full_defs)
+        && property_type
+            .split("Remotable<")
+            .skip(1)
+            .filter_map(|referred| mentioned_types(referred).next())
+            .all(|name| {
+                is_kind_of(
+                    name.strip_suffix("Subtypes").unwrap_or(name),
+                    "Object",
+                    full_defs,
+                )
+            })
+}
+
+/// Whether a property of `property_type` may have urls, that is it mentions urls or the generated types.
+fn may_have_urls(property_type: &str, full_defs: &HashMap<String, TypeDef>) -> bool {
+    mentioned_types(property_type).any(|name| matches!(name, "Url" | "ObjectUri"))
+        || may_have_generated(property_type, full_defs)
+}
+
+fn gen_rewrite_urls_impl(
+    type_name: &str,
+    type_def: &TypeDef,
+    full_defs: &HashMap<String, TypeDef>,
+    subtypes: bool,
+) -> anyhow::Result<TokenStream> {
+    let type_ident = ident(type_name);
+    let stmts = collect_properties(type_def, full_defs)?
+        .into_iter()
+        .filter_map(|(name, def)| match def {
+            PropertyDef::Simple { property_type, .. }
+                if may_have_urls(&property_type, full_defs) =>
+            {
+                let name_ident = ident(&name);
+                Some(quote! {
+                    crate::rewrite::RewriteUrls::rewrite_urls_with(&mut self.#name_ident, map);
+                })
+            }
+            _ => None,
+        })
+        .collect::<TokenStream>();
+    let subtype_ident = ident(&format!("{type_name}Subtypes"));
+    let sub_idents = collect_subtypes(type_name, type_def, full_defs)?
+        .keys()
+        .map(|name| ident(name))
+        .collect::<Vec<_>>();
+    let subtypes_impl = subtypes.then_some(quote! {
+        impl crate::rewrite::RewriteUrls for #subtype_ident {
+            fn rewrite_urls_with(&mut self, map: &mut dyn FnMut(&url::Url) -> Option<url::Url>) {
+                match self {
+                    #(#subtype_ident::#sub_idents(inner) => {
+                        crate::rewrite::RewriteUrls::rewrite_urls_with(inner, map)
+                    })*
+                }
+            }
+        }
+    });
+    Ok(quote! {
+        impl crate::rewrite::RewriteUrls for #type_ident {
+            #[allow(unused_variables)]
+            fn rewrite_urls_with(&mut self, map: &mut dyn FnMut(&url::Url) -> Option<url::Url>) {
+                #stmts
+            }
+        }
+
+        #subtypes_impl
+    })
+}
+
+fn gen_walk_impl(
+    type_name: &str,
+    type_def: &TypeDef,
+    full_defs: &HashMap<String, TypeDef>,
+    subtypes: bool,
+) -> anyhow::Result<TokenStream> {
+    let type_ident = ident(type_name);
+    let mut names = collect_properties(type_def, full_defs)?
+        .into_iter()
+        .filter_map(|(name, def)| match def {
+            PropertyDef::Simple { property_type, .. }
+                if may_have_generated(&property_type, full_defs) =>
+            {
+                Some(name)
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    // visit in the same order on every build
+    names.sort();
+    let fields = names.iter().map(|name| ident(name)).collect::<Vec<_>>();
+    let subtype_ident = ident(&format!("{type_name}Subtypes"));
+    let sub_idents = collect_subtypes(type_name, type_def, full_defs)?
+        .keys()
+        .map(|name| ident(name))
+        .collect::<Vec<_>>();
+    let subtypes_impl = subtypes.then_some(quote! {
+        impl crate::walk::Walk for #subtype_ident {
+            fn walk<'a>(&'a self, f: &mut impl FnMut(crate::Node<'a>)) {
+                match self {
+                    #(#subtype_ident::#sub_idents(inner) => crate::walk::Walk::walk(inner, f),)*
+                }
+            }
+
+            fn walk_mut(&mut self, f: &mut impl FnMut(crate::NodeMut<'_>)) {
+                match self {
+                    #(#subtype_ident::#sub_idents(inner) => crate::walk::Walk::walk_mut(inner, f),)*
+                }
+            }
+        }
+    });
+    Ok(quote! {
+        impl crate::walk::Walk for #type_ident {
+            fn walk<'a>(&'a self, f: &mut impl FnMut(crate::Node<'a>)) {
+                f(crate::Node::#type_ident(self));
+                #(crate::walk::Walk::walk(&self.#fields, f);)*
+            }
+
+            fn walk_mut(&mut self, f: &mut impl FnMut(crate::NodeMut<'_>)) {
+                f(crate::NodeMut::#type_ident(self));
+                #(crate::walk::Walk::walk_mut(&mut self.#fields, f);)*
+            }
+        }
+
+        #subtypes_impl
+    })
+}
+
+/// Statement calling `f` with the texts of the property `name` of `inner`.
+fn gen_visit_text(name: &str, def: &PropertyDef) -> TokenStream {
+    let name_ident = ident(name);
+    match def {
+        PropertyDef::Simple { tag, kind, .. } => {
+            let key = tag.as_deref().unwrap_or(name);
+            match kind {
+                PropertyKind::Required => quote! {
+                    f(#key, &mut inner.#name_ident);
+                },
+                PropertyKind::Functional => quote! {
+                    if let Some(text) = &mut inner.#name_ident {
+                        f(#key, text);
+                    }
+                },
+                PropertyKind::Normal => quote! {
+                    for text in &mut inner.#name_ident.0 {
+                        f(#key, text);
+                    }
+                },
+            }
+        }
+        PropertyDef::LangContainer { tag, kind, .. } => {
+            let key = tag.as_deref().unwrap_or(name);
+            let values = quote! {
+                inner.#name_ident.default.iter_mut().chain(inner.#name_ident.per_lang.values_mut())
+            };
+            if kind == &PropertyKind::Normal {
+                quote! {
+                    for text in #values.flat_map(|texts| texts.0.iter_mut()) {
+                        f(#key, text);
+                    }
+                }
+            } else {
+                quote! {
+                    for text in #values {
+                        f(#key, text);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// `Node` and `NodeMut` of the generated crate, with a variant for each of the types.
+fn gen_nodes(full_defs: &HashMap<String, TypeDef>) -> anyhow::Result<TokenStream> {
+    let mut names = full_defs.keys().collect::<Vec<_>>();
+    names.sort();
+    let idents = names.iter().map(|name| ident(name)).collect::<Vec<_>>();
+    let mut with_id = Vec::new();
+    let mut with_href = Vec::new();
+    let mut texts = Vec::new();
+    let mut clears = Vec::new();
+    for name in &names {
+        let properties = collect_properties(&full_defs[*name], full_defs)?;
+        let mut text_properties = properties
+            .iter()
+            .filter(|(_, def)| match def {
+                PropertyDef::Simple { property_type, .. }
+                | PropertyDef::LangContainer { property_type, .. } => property_type == "String",
+            })
+            .collect::<Vec<_>>();
+        text_properties.sort_by_key(|(name, _)| *name);
+        let stmts = text_properties
+            .into_iter()
+            .map(|(name, def)| gen_visit_text(name, def))
+            .collect::<TokenStream>();
+        let type_ident = ident(name);
+        texts.push(quote! {
+            NodeMut::#type_ident(inner) => {
+                #stmts
+            }
+        });
+        let mut optional_properties = properties
+            .iter()
+            .filter(|(_, def)| def.kind() != &PropertyKind::Required)
+            .collect::<Vec<_>>();
+        optional_properties.sort_by_key(|(name, _)| *name);
+        let stmts = optional_properties
+            .into_iter()
+            .map(|(name, def)| {
+                let name_ident = ident(name);
+                let (PropertyDef::Simple { tag, .. } | PropertyDef::LangContainer { tag, .. }) =
+                    def;
+                let key = tag.as_deref().unwrap_or(name);
+                quote! {
+                    if f(#key) {
+                        inner.#name_ident = Default::default();
+                    }
+                }
+            })
+            .collect::<TokenStream>();
+        clears.push(quote! {
+            NodeMut::#type_ident(inner) => {
+                #stmts
+            }
+        });
+        if properties
+            .get("id")
+            .is_some_and(|def| def.kind() == &PropertyKind::Functional)
+        {
+            with_id.push(ident(name));
+        }
+        if properties
+            .get("href")
+            .is_some_and(|def| def.kind() == &PropertyKind::Required)
+        {
+            with_href.push(ident(name));
+        }
+    }
+    Ok(quote! {
+        /// An object or a link, or a reference to an object, visited by [walk::Walk::walk].
+        #[derive(Debug, Clone, Copy)]
+        pub enum Node<'a> {
+            #(#idents(&'a #idents),)*
+            Remote(&'a url::Url),
+        }
+
+        /// [Node] to modify, visited by [walk::Walk::walk_mut].
+        #[derive(Debug)]
+        pub enum NodeMut<'a> {
+            #(#idents(&'a mut #idents),)*
+            Remote(&'a mut url::Url),
+        }
+
+        impl<'a> Node<'a> {
+            /// Id of the object, or the referred one.
+            pub fn object_id(&self) -> Option<&'a url::Url> {
+                match self {
+                    #(Node::#with_id(inner) => inner.id.as_deref(),)*
+                    Node::Remote(id) => Some(id),
+                    #[allow(unreachable_patterns)]
+                    _ => None,
+                }
+            }
+
+            /// Url of the link.
+            pub fn href(&self) -> Option<&'a url::Url> {
+                match self {
+                    #(Node::#with_href(inner) => Some(&inner.href),)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl NodeMut<'_> {
+            /// Visit the texts of the object or link with the names of their properties in JSON,
+            /// also the ones per language under the name of the property such as `content`.
+            #[allow(unused_variables, clippy::possible_missing_else)]
+            pub fn texts_mut(&mut self, f: &mut impl FnMut(&str, &mut String)) {
+                match self {
+                    #(#texts)*
+                    NodeMut::Remote(_) => (),
+                }
+            }
+
+            /// Reset the optional properties of the object or link for which `f` is true of their
+            /// names in JSON. A language map goes with its property such as `content`.
+            #[allow(unused_variables, clippy::possible_missing_else)]
+            pub fn clear_properties(&mut self, f: &mut impl FnMut(&str) -> bool) {
+                match self {
+                    #(#clears)*
+                    NodeMut::Remote(_) => (),
+                }
+            }
+        }
+    })
+}
+
+/// `property_names` of the generated crate, the JSON names of the properties by the types of
+/// their values. A name of the values of different types in some types is left out.
+fn gen_property_names(full_defs: &HashMap<String, TypeDef>) -> TokenStream {
+    let mut categories = HashMap::<&str, HashSet<&str>>::new();
+    for def in full_defs.values() {
+        for (name, property) in &def.properties {
+            let (tag, aka, category) = match property {
+                PropertyDef::Simple {
+                    tag,
+                    aka,
+                    property_type,
+                    ..
+                } => {
+                    let category = match property_type.as_str() {
+                        "bool" => "BOOL",
+                        "xsd::DateTime" => "DATE_TIME",
+                        "url::Url" | "ObjectUri" => "URL",
+                        ty if may_have_generated(ty, full_defs) => "REFERENCE",
+                        _ => "",
+                    };
+                    (tag, aka, category)
+                }
+                PropertyDef::LangContainer { tag, aka, .. } => (tag, aka, ""),
+            };
+            let keys = std::iter::once(tag.as_deref().unwrap_or(name))
+                .chain(aka.iter().map(String::as_str));
+            for key in keys {
+                categories.entry(key).or_default().insert(category);
+            }
+        }
+    }
+    let names_of = |category: &str| {
+        let mut names = categories
+            .iter()
+            .filter(|(_, categories)| categories.len() == 1 && categories.contains(category))
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    };
+    let bool_names = names_of("BOOL");
+    let date_time_names = names_of("DATE_TIME");
+    let url_names = names_of("URL");
+    let reference_names = names_of("REFERENCE");
+    quote! {
+        /// JSON names of the properties by the types of their values, when they are the same in
+        /// all the types.
+        pub mod property_names {
+            /// Properties of `bool`.
+            pub const BOOL: &[&str] = &[#(#bool_names),*];
+            /// Properties of [xsd::DateTime](activity_vocabulary_core::xsd::DateTime).
+            pub const DATE_TIME: &[&str] = &[#(#date_time_names),*];
+            /// Properties of a bare [url::Url] or [ObjectUri](activity_vocabulary_core::ObjectUri).
+            pub const URL: &[&str] = &[#(#url_names),*];
+            /// Properties of objects or links, or references to them.
+            pub const REFERENCE: &[&str] = &[#(#reference_names),*];
+        }
+    }
+}
+
+/// Properties shown by the generated `Display`, in its order.
+const SUMMARY: &[&str] = &[
+    "id",
+    "href",
+    "actor",
+    "object",
+    "target",
+    "attributed_to",
+    "in_reply_to",
+];
+
+fn gen_display_impl(
+    type_name: &str,
+    type_def: &TypeDef,
+    full_defs: &HashMap<String, TypeDef>,
+    subtypes: bool,
+) -> anyhow::Result<TokenStream> {
+    let properties = collect_properties(type_def, full_defs)?;
+    let entries = SUMMARY
+        .iter()
+        .filter_map(|name| match properties.get(*name) {
+            Some(PropertyDef::Simple { tag, kind, .. }) => Some((name, tag, kind)),
+            _ => None,
+        })
+        .map(|(name, tag, kind)| {
+            let name_ident = ident(name);
+            let key = format!("{}=", tag.as_deref().unwrap_or(name));
+            let write = quote! {
+                f.write_str(sep)?;
+                f.write_str(#key)?;
+                value.fmt(f)?;
+                sep = ", ";
+            };
+            match kind {
+                PropertyKind::Required => quote! {
+                    let value = &self.#name_ident;
+                    #write
+                },
+                PropertyKind::Functional => quote! {
+                    if let Some(value) = &self.#name_ident {
+                        #write
+                    }
+                },
+                PropertyKind::Normal => quote! {
+                    if !self.#name_ident.0.is_empty() {
+                        let value = &self.#name_ident;
+                        #write
+                    }
+                },
+            }
+        })
+        .collect::<Vec<_>>();
+    let type_ident = ident(type_name);
+    let subtype_ident = ident(&format!("{type_name}Subtypes"));
+    let sub_idents = collect_subtypes(type_name, type_def, full_defs)?
+        .keys()
+        .map(|name| ident(name))
+        .collect::<Vec<_>>();
+    let open = format!("{type_name}(");
+    let subtypes_impl = subtypes.then_some(quote! {
+        impl std::fmt::Display for #subtype_ident {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    #(#subtype_ident::#sub_idents(inner) => inner.fmt(f),)*
+                }
+            }
+        }
+
+        impl #subtype_ident {
+            /// Pretty-printed JSON of the object.
+            pub fn to_json_string_pretty(&self) -> String {
+                ::activity_vocabulary_core::to_json_string_pretty(self)
+            }
+        }
+    });
+    Ok(quote! {
+        /// Compact summary of the type, the id and the properties linking to other objects.
+        impl std::fmt::Display for #type_ident {
+            #[allow(
+                unused_assignments,
+                unused_mut,
+                unused_variables,
+                clippy::possible_missing_else
+            )]
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(#open)?;
+                let mut sep = "";
+                #(#entries)*
+                f.write_str(")")
+            }
+        }
+
+        impl #type_ident {
+            /// Pretty-printed JSON of the object.
+            pub fn to_json_string_pretty(&self) -> String {
+                ::activity_vocabulary_core::to_json_string_pretty(self)
+            }
+        }
+
+        #subtypes_impl
+    })
+}
+
+fn gen_set(
+    name: &str,
+    def: &TypeDef,
+    defs: &HashMap<String, TypeDef>,
+    options: &Options,
+) -> anyhow::Result<TokenStream> {
+    let subtypes = options.leaf_subtypes || !is_leaf(name, defs);
+    let type_def = gen_type(name, def, defs)?;
+    let serialize_impl = gen_serialize_impl(name, def, defs, options)?;
+    let deserialize_impl = gen_deserialize_impl(name, def, defs)?;
+    let upcasts = gen_upcasts_from_subs(name, def, defs)?;
+    let blind_addressing_impl = gen_blind_addressing_impl(name, def, defs, subtypes)?;
+    let addressed_impl = gen_addressed_impl(name, def, defs, subtypes)?;
+    let tagged_impl = gen_tagged_impl(name, def, defs, subtypes)?;
+    let content_warning_impl = gen_content_warning_impl(name, def, defs, subtypes)?;
+    let quoting_impl = gen_quoting_impl(name, def, defs, subtypes)?;
+    let wrap_object_impl = gen_wrap_object_impl(name, def, defs)?;
+    let object_id_impl = gen_object_id_impl(name, def, defs, subtypes)?;
+    let display_impl = gen_display_impl(name, def, defs, subtypes)?;
+    let normalize_impl = gen_normalize_impl(name, def, defs, subtypes)?;
+    let hydrate_impl = gen_hydrate_impl(name, def, defs, subtypes)?;
+    let rewrite_urls_impl = gen_rewrite_urls_impl(name, def, defs, subtypes)?;
+    let walk_impl = gen_walk_impl(name, def, defs, subtypes)?;
+    let downcasts = gen_downcasts_to_subtypes(name, def, defs, subtypes)?;
+    let reflection_impl = gen_reflection_impl(name, def, defs)?;
+    let dynamic_impl = gen_dynamic_impl(name);
+    let subtypes_set = if subtypes {
+        let subtypes_def = gen_subtypes(name, def, defs)?;
+        let subtypes_deserialize_impl = gen_subtypes_deserialize(name, def, defs, options)?;
+        let subtype_upcast = gen_subtypes_upcast_to_self(name, def, defs)?;
+        let subtypes_accessors = gen_subtypes_accessors(name, def, defs)?;
+        let subtypes_upcasts = gen_upcasts_to_ancestor_subtypes(name, def, defs)?;
+        quote! {
+            #subtypes_def
+            #subtypes_deserialize_impl
+            #subtype_upcast
+            #subtypes_accessors
+            #subtypes_upcasts
+        }
+    } else {
+        let type_ident = ident(name);
+        let subtype_ident = ident(&format!("{name}Subtypes"));
+        let doc = format!("[{name}] itself, which has no subtypes.");
+        quote! {
+            #[doc = #doc]
+            pub type #subtype_ident = #type_ident;
+        }
+    };
+    Ok(quote! {
+        #type_def
+        #serialize_impl
+        #deserialize_impl
+        #subtypes_set
+        #upcasts
+        #downcasts
+        #blind_addressing_impl
+        #addressed_impl
+        #tagged_impl
+        #content_warning_impl
+        #quoting_impl
+        #wrap_object_impl
+        #object_id_impl
+        #display_impl
+        #normalize_impl
+        #hydrate_impl
+        #rewrite_urls_impl
+        #walk_impl
+        #reflection_impl
+        #dynamic_impl
+    })
+}
+
+/// Conversions of `type_name` from and into `DynObject`, with the type name leading the types.
+fn gen_dynamic_impl(type_name: &str) -> TokenStream {
+    let type_ident = ident(type_name);
+    quote! {
+        impl From<#type_ident> for ::activity_vocabulary_core::dynamic::DynObject {
+            fn from(object: #type_ident) -> Self {
+                let mut object = Self::from_typed(&object);
+                object.insert_type(#type_name);
+                object
+            }
+        }
+
+        impl TryFrom<::activity_vocabulary_core::dynamic::DynObject> for #type_ident {
+            type Error = ::activity_vocabulary_core::dynamic::Error;
+
+            fn try_from(
+                object: ::activity_vocabulary_core::dynamic::DynObject,
+            ) -> Result<Self, Self::Error> {
+                object.into_typed()
+            }
+        }
+    }
+}
+
+/// `snake_case` of a property as `PascalCase` of a variant.
+fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|head| head.to_ascii_uppercase())
+                .into_iter()
+                .chain(chars)
+        })
+        .collect()
+}
+
+/// `{type_name}Property` enumerating the properties by their names in JSON, with the language
+/// maps apart from their defaults.
+fn gen_reflection_impl(
+    type_name: &str,
+    type_def: &TypeDef,
+    full_defs: &HashMap<String, TypeDef>,
+) -> anyhow::Result<TokenStream> {
+    let type_ident = ident(type_name);
+    let property_ident = ident(&format!("{type_name}Property"));
+    let mut entries = Vec::new();
+    for (name, def) in collect_properties(type_def, full_defs)? {
+        let name_ident = ident(&name);
+        match def {
+            PropertyDef::Simple { tag, uri, .. } => entries.push((
+                tag.unwrap_or_else(|| name.clone()),
+                uri,
+                ident(&pascal_case(&name)),
+                quote!(#name_ident),
+            )),
+            PropertyDef::LangContainer {
+                tag,
+                container_tag,
+                uri,
+                ..
+            } => {
+                entries.push((
+                    tag.unwrap_or_else(|| name.clone()),
+                    uri.clone(),
+                    ident(&pascal_case(&name)),
+                    quote!(#name_ident.default),
+                ));
+                entries.push((
+                    container_tag,
+                    uri,
+                    ident(&format!("{}Map", pascal_case(&name))),
+                    quote!(#name_ident.per_lang),
+                ));
+            }
+        }
+    }
+    entries.sort_by(|(a, ..), (b, ..)| a.cmp(b));
+    let names = entries.iter().map(|(name, ..)| name).collect::<Vec<_>>();
+    let uris = entries.iter().map(|(_, uri, ..)| uri).collect::<Vec<_>>();
+    let variants = entries
+        .iter()
+        .map(|(_, _, variant, _)| variant)
+        .collect::<Vec<_>>();
+    let fields = entries.iter().map(|(.., field)| field).collect::<Vec<_>>();
+    let docs = names.iter().map(|name| format!("`{name}`"));
+    let doc = format!("Properties of [{type_name}], to get and set by their names in JSON.");
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum #property_ident {
+            #(
+                #[doc = #docs]
+                #variants,
+            )*
+        }
+
+        impl #property_ident {
+            /// All the properties, in the order of their names.
+            pub const ALL: &'static [Self] = &[#(Self::#variants),*];
+
+            /// Name in JSON, such as `attributedTo`.
+            pub fn name(self) -> &'static str {
+                match self {
+                    #(Self::#variants => #names,)*
+                }
+            }
+
+            /// IRI of the property, or its JSON-LD keyword such as `@id`.
+            pub fn uri(self) -> &'static str {
+                match self {
+                    #(Self::#variants => #uris,)*
+                }
+            }
+
+            /// The property of `name` in JSON.
+            pub fn from_name(name: &str) -> Option<Self> {
+                Self::ALL.iter().copied().find(|property| property.name() == name)
+            }
+
+            /// The property of `object` as JSON, `null` if it is empty.
+            pub fn get(self, object: &#type_ident) -> ::activity_vocabulary_core::reflect::Value {
+                match self {
+                    #(Self::#variants => ::activity_vocabulary_core::reflect::to_value(&object.#fields),)*
+                }
+            }
+
+            /// Set the property of `object` to `value` read as JSON.
+            pub fn set(
+                self,
+                object: &mut #type_ident,
+                value: ::activity_vocabulary_core::reflect::Value,
+            ) -> Result<(), ::activity_vocabulary_core::reflect::InvalidValue> {
+                match self {
+                    #(
+                        Self::#variants => {
+                            object.#fields =
+                                ::activity_vocabulary_core::reflect::from_value(#type_name, #names, value)?;
+                        }
+                    )*
+                }
+                Ok(())
+            }
+        }
+    })
+}
+
+/// Whether no type extends `type_name`.
+fn is_leaf(type_name: &str, full_defs: &HashMap<String, TypeDef>) -> bool {
+    !full_defs
+        .values()
+        .any(|def| def.extends.iter().any(|super_name| super_name == type_name))
+}
+
+/// Options of the generation.
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// Generate the subtypes enums also of the types without subtypes, such as `MentionSubtypes`
+    /// of the only `Mention`, instead of aliasing them to the types. `true` by default.
+    ///
+    /// It changes the generated types, so that it is a choice of the crate generating them rather
+    /// than a feature of it.
+    pub leaf_subtypes: bool,
+    /// Key of the types of the objects, `type` by default.
+    ///
+    /// Its JSON-LD keyword `@type` is also read, and written back if the object is read with it.
+    pub type_tag: String,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            leaf_subtypes: true,
+            type_tag: "type".to_owned(),
+        }
+    }
+}
+
+/// Check the definitions before generating from them, for the errors to tell the [Definition]
+/// which is wrong.
+fn validate(defs: &HashMap<String, TypeDef>) -> anyhow::Result<()> {
+    let mut names = defs.keys().collect::<Vec<_>>();
+    names.sort();
+    let mut validated = HashSet::new();
+    for type_name in names {
+        validate_type(type_name, defs, &mut validated, &mut Vec::new())?;
+    }
+    Ok(())
+}
+
+/// Check `type_name` after its supertypes, so that an error is told by the type it is in.
+/// `visiting` are the subtypes being checked down to `type_name`, to tell a cycle of `extends`.
+fn validate_type<'a>(
+    type_name: &'a str,
+    defs: &'a HashMap<String, TypeDef>,
+    validated: &mut HashSet<&'a str>,
+    visiting: &mut Vec<&'a str>,
+) -> anyhow::Result<()> {
+    if let Some(start) = visiting.iter().position(|name| *name == type_name) {
+        let cycle = visiting[start..]
+            .iter()
+            .chain([&type_name])
+            .copied()
+            .collect::<Vec<_>>();
+        let last = visiting[visiting.len() - 1];
+        return Err(anyhow!("cyclic extends: {}", cycle.join(" -> ")))
+            .context(Definition::of_type(last).entry(["extends", type_name]));
+    }
+    if validated.contains(type_name) {
+        return Ok(());
+    }
+    let def = &defs[type_name];
+    visiting.push(type_name);
+    let mut properties = Vec::new();
+    for super_name in &def.extends {
+        if !defs.contains_key(super_name) {
+            return Err(anyhow!("type {super_name} not found"))
+                .context(Definition::of_type(type_name).entry(["extends", super_name.as_str()]));
+        }
+        validate_type(super_name, defs, validated, visiting)?;
+        properties.extend(collect_properties(&defs[super_name], defs)?);
+    }
+    visiting.pop();
+    validated.insert(type_name);
+    for (name, property) in &def.properties {
+        property
+            .gen_type()
+            .with_context(|| Definition::of_property(type_name, name))?;
+    }
+    for (name, property_override) in &def.override_properties {
+        let context = || Definition::of_type(type_name).entry(["override_properties", name]);
+        let (_, property) = properties
+            .iter_mut()
+            .find(|(inherited, _)| inherited == name)
+            .ok_or_else(|| anyhow!("{name} is not inherited"))
+            .with_context(context)?;
+        property.apply_override(property_override);
+        property.gen_type().with_context(context)?;
+    }
+    for (name, property) in properties.into_iter().chain(def.properties.clone()) {
+        rename_default_name(def, &name, property).with_context(|| {
+            Definition::of_type(type_name).entry(["preferred_property_name", name.as_str()])
+        })?;
+    }
+    Ok(())
+}
+
+pub fn gen(defs: &HashMap<String, TypeDef>) -> anyhow::Result<String> {
+    gen_with(defs, &Options::default())
+}
+
+/// The definitions as the generation reads them with `options`.
+fn apply_options(defs: &HashMap<String, TypeDef>, options: &Options) -> HashMap<String, TypeDef> {
+    // the types are read from and written to the tag of the vocabulary
+    let mut defs = defs.clone();
+    for def in defs.values_mut() {
+        for property in def.properties.values_mut() {
+            if let PropertyDef::Simple { uri, tag, .. } = property {
+                if uri == "@type" {
+                    *tag = Some(options.type_tag.clone());
+                }
+            }
+        }
+    }
+    defs
+}
+
+pub fn gen_with(defs: &HashMap<String, TypeDef>, options: &Options) -> anyhow::Result<String> {
+    let defs = apply_options(defs, options);
+    let defs = &defs;
+    validate(defs)?;
+    let src = defs
+        .iter()
+        .map(|(name, def)| gen_set(name, def, defs, options))
+        .collect::<anyhow::Result<TokenStream>>()?;
+    let nodes = gen_nodes(defs)?;
+    let property_names = gen_property_names(defs);
+    Ok(quote! {
+        #src
+        #nodes
+        #property_names
+    }
+    .to_string())
+}
+
+pub fn manifest(defs: &HashMap<String, TypeDef>) -> anyhow::Result<String> {
+    manifest_with(defs, &Options::default())
+}
+
+/// JSON manifest of the types the generation emits with `options`, for the tools out of Rust.
+///
+/// The types are keyed by their names, each with its `uri`, its supertypes in `extends`, and all
+/// its `properties` also inherited, keyed by their field names with the keys they are read from.
+pub fn manifest_with(defs: &HashMap<String, TypeDef>, options: &Options) -> anyhow::Result<String> {
+    let defs = apply_options(defs, options);
+    validate(&defs)?;
+    let mut names = defs.keys().collect::<Vec<_>>();
+    names.sort();
+    let mut types = serde_json::Map::new();
+    for type_name in names {
+        let def = &defs[type_name];
+        let mut properties = collect_properties(def, &defs)?
+            .into_iter()
+            .collect::<Vec<_>>();
+        properties.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let properties = properties
+            .into_iter()
+            .map(|(name, property)| {
+                let mut entry = serde_json::json!({
+                    "uri": property.uri(),
+                    "kind": property.kind(),
+                    "inherited": !def.properties.contains_key(&name),
+                });
+                let (tag, aka, property_type) = match &property {
+                    PropertyDef::Simple {
+                        tag,
+                        aka,
+                        property_type,
+                        ..
+                    } => (tag, aka, property_type),
+                    PropertyDef::LangContainer {
+                        tag,
+                        aka,
+                        property_type,
+                        container_tag,
+                        container_aka,
+                        ..
+                    } => {
+                        entry["container_tag"] = container_tag.as_str().into();
+                        entry["container_aka"] = sorted(container_aka).into();
+                        (tag, aka, property_type)
+                    }
+                };
+                entry["type"] = property_type.as_str().into();
+                entry["tag"] = tag.as_deref().unwrap_or(&name).into();
+                entry["aka"] = sorted(aka).into();
+                (name, entry)
+            })
+            .collect::<serde_json::Map<_, _>>();
+        types.insert(
+            type_name.clone(),
+            serde_json::json!({
+                "uri": def.uri,
+                "extends": def.extends,
+                "properties": properties,
+            }),
+        );
+    }
+    let manifest = serde_json::json!({
+        "type_tag": options.type_tag,
+        "types": types,
+    });
+    Ok(serde_json::to_string_pretty(&manifest)?)
+}
+
+fn sorted(names: &HashSet<String>) -> Vec<&str> {
+    let mut names = names.iter().map(String::as_str).collect::<Vec<_>>();
+    names.sort();
+    names
 }
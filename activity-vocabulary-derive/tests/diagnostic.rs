@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use activity_vocabulary_derive::{
+    diagnostic::{Source, VocabError},
+    Extension, TypeDef,
+};
+use miette::Diagnostic;
+
+const VOCAB: &str = r#"Object:
+  uri: https://www.w3.org/ns/activitystreams#Object
+  doc: Object
+  properties:
+    name: !Simple
+      type: String
+      uri: https://www.w3.org/ns/activitystreams#name
+      doc: Name
+
+Note:
+  uri: https://www.w3.org/ns/activitystreams#Note
+  extends: [Object, Document]
+  doc: Note
+"#;
+
+/// The text the error points at in `source`.
+fn label<'a>(error: &VocabError, source: &'a str) -> &'a str {
+    let span = error.labels().unwrap().next().unwrap();
+    &source[span.offset()..span.offset() + span.len()]
+}
+
+#[test]
+fn unknown_supertype() {
+    let sources = [Source::new("vocab.yml", VOCAB)];
+    let defs: HashMap<String, TypeDef> = sources[0].parse().unwrap();
+    let error = VocabError::locate(
+        activity_vocabulary_derive::gen(&defs).unwrap_err(),
+        &sources,
+    );
+    assert_eq!(
+        error.to_string(),
+        "in the definition of Note.extends.Document: type Document not found"
+    );
+    let span = error.labels().unwrap().next().unwrap();
+    assert_eq!(VOCAB[..span.offset()].lines().count(), 12);
+    assert_eq!(label(&error, VOCAB), "Document");
+}
+
+#[test]
+fn property_of_extension() {
+    let vocab = VOCAB.replace(", Document", "");
+    let extension = r#"properties:
+  Note:
+    sensitive: !Simple
+      type: Option<
+      uri: https://www.w3.org/ns/activitystreams#sensitive
+      doc: Sensitive
+"#;
+    let sources = [
+        Source::new("vocab.yml", vocab.as_str()),
+        Source::new("mastodon.yml", extension),
+    ];
+    let mut defs: HashMap<String, TypeDef> = sources[0].parse().unwrap();
+    let parsed: Extension = sources[1].parse().unwrap();
+    parsed.apply(&mut defs).unwrap();
+    let error = VocabError::locate(
+        activity_vocabulary_derive::gen(&defs).unwrap_err(),
+        &sources,
+    );
+    assert!(error
+        .to_string()
+        .starts_with("in the definition of Note.properties.sensitive: parse Option<"));
+    assert_eq!(label(&error, extension), "sensitive");
+}
+
+#[test]
+fn invalid_yaml() {
+    let source = Source::new("vocab.yml", VOCAB.replace("doc: Note", "doc: [Note"));
+    let Err(error) = source.parse::<HashMap<String, TypeDef>>() else {
+        panic!("parsed an unclosed sequence");
+    };
+    assert!(error.labels().is_some());
+}
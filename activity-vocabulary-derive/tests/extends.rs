@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+
+use activity_vocabulary_derive::{
+    diagnostic::{Source, VocabError},
+    TypeDef,
+};
+
+fn defs(src: &str) -> HashMap<String, TypeDef> {
+    serde_yaml::from_str(src).unwrap()
+}
+
+const DIAMOND: &str = r#"Object:
+  uri: https://www.w3.org/ns/activitystreams#Object
+  doc: Object
+  properties:
+    items: !Simple
+      type: String
+      uri: https://www.w3.org/ns/activitystreams#items
+      doc: Items
+
+Ordered:
+  uri: https://example.com/ns#Ordered
+  extends: [Object]
+  doc: Ordered
+  preferred_property_name:
+    items: !Simple "orderedItems"
+
+Page:
+  uri: https://example.com/ns#Page
+  extends: [Object]
+  doc: Page
+
+OrderedPage:
+  uri: https://example.com/ns#OrderedPage
+  extends: [Ordered, Page]
+  doc: OrderedPage
+"#;
+
+/// Name in JSON of the first property of `type_name`, as the generated property enum documents it.
+fn first_tag(src: &str, type_name: &str) -> String {
+    let code = activity_vocabulary_derive::gen(&defs(src)).unwrap();
+    let variants = code
+        .split(&format!("pub enum {type_name}Property {{"))
+        .nth(1)
+        .and_then(|rest| rest.split('}').next())
+        .unwrap();
+    variants.split('`').nth(1).unwrap().to_owned()
+}
+
+#[test]
+fn diamond_resolved_by_order() {
+    assert_eq!(first_tag(DIAMOND, "OrderedPage"), "orderedItems");
+    let swapped = DIAMOND.replace("[Ordered, Page]", "[Page, Ordered]");
+    assert_eq!(first_tag(&swapped, "OrderedPage"), "items");
+}
+
+#[test]
+fn cycle() {
+    let src = DIAMOND.replace(
+        "extends: [Object]\n  doc: Page",
+        "extends: [OrderedPage]\n  doc: Page",
+    );
+    let sources = [Source::new("vocab.yml", src.as_str())];
+    let error = activity_vocabulary_derive::gen(&defs(&src)).unwrap_err();
+    assert_eq!(
+        format!("{error:#}"),
+        "in the definition of Page.extends.OrderedPage: cyclic extends: OrderedPage -> Page -> OrderedPage"
+    );
+    let error = VocabError::locate(error, &sources);
+    assert!(miette::Diagnostic::labels(&error).is_some());
+}
+
+const OVERRIDE: &str = r#"Object:
+  uri: https://www.w3.org/ns/activitystreams#Object
+  doc: Object
+  properties:
+    closed: !Simple
+      type: String
+      uri: https://www.w3.org/ns/activitystreams#closed
+      kind: !Functional
+      doc: Closed
+
+Question:
+  uri: https://www.w3.org/ns/activitystreams#Question
+  extends: [Object]
+  doc: Question
+  override_properties:
+    closed:
+      type: bool
+      kind: !Required
+      tag: isClosed
+"#;
+
+#[test]
+fn override_inherited() {
+    let code = activity_vocabulary_derive::gen(&defs(OVERRIDE)).unwrap();
+    let question = code.split("pub struct Question {").nth(1).unwrap();
+    assert!(question
+        .split('}')
+        .next()
+        .unwrap()
+        .contains("pub closed : bool"));
+    let object = code.split("pub struct Object {").nth(1).unwrap();
+    assert!(object
+        .split('}')
+        .next()
+        .unwrap()
+        .contains("pub closed : Option < String >"));
+    assert!(code.contains("closed : :: std :: convert :: Into :: into (value . closed)"));
+    assert_eq!(first_tag(OVERRIDE, "Question"), "isClosed");
+}
+
+#[test]
+fn override_not_inherited() {
+    let src = OVERRIDE.replace(
+        "    closed:\n      type: bool",
+        "    open:\n      type: bool",
+    );
+    let error = activity_vocabulary_derive::gen(&defs(&src)).unwrap_err();
+    assert_eq!(
+        format!("{error:#}"),
+        "in the definition of Question.override_properties.open: open is not inherited"
+    );
+}
@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+
+use activity_vocabulary_derive::{Options, TypeDef};
+
+const SRC: &str = r#"Object:
+  uri: https://www.w3.org/ns/activitystreams#Object
+  doc: Object
+
+Note:
+  uri: https://www.w3.org/ns/activitystreams#Note
+  extends: [Object]
+  doc: Note
+"#;
+
+fn gen(options: &Options) -> String {
+    let defs: HashMap<String, TypeDef> = serde_yaml::from_str(SRC).unwrap();
+    activity_vocabulary_derive::gen_with(&defs, options).unwrap()
+}
+
+#[test]
+fn leaf_subtypes() {
+    let code = gen(&Options::default());
+    assert!(code.contains("pub enum NoteSubtypes"));
+    assert!(code.contains("pub enum ObjectSubtypes"));
+}
+
+#[test]
+fn aliased() {
+    let code = gen(&Options {
+        leaf_subtypes: false,
+        ..Default::default()
+    });
+    assert!(code.contains("pub type NoteSubtypes = Note ;"));
+    assert!(!code.contains("pub enum NoteSubtypes"));
+    assert!(code.contains("pub enum ObjectSubtypes"));
+}
@@ -0,0 +1,72 @@
+//! Round-trip the documents and the directories of documents given as the arguments, and print the
+//! [Report] as JSON. Each document is compared against the golden file of the same name in the
+//! `--golden` directory if it has one, and against itself otherwise.
+//!
+//! Without arguments, checks the vendored examples in `activity-vocabulary/activitystreams/test`
+//! against the goldens in `activity-vocabulary/tests`.
+//!
+//! ```sh
+//! cargo run -p activity-vocabulary-test-utils --features conformance --bin conformance
+//! cargo run -p activity-vocabulary-test-utils --features conformance --bin conformance -- \
+//!     --golden my-goldens/ my-documents/
+//! ```
+//!
+//! Exits with 1 if any document fails, and with 2 if a directory cannot be read.
+
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use activity_vocabulary::link::LinkOrObject;
+use activity_vocabulary_core::WithContext;
+use activity_vocabulary_test_utils::Report;
+
+const CORPUS: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../activity-vocabulary/activitystreams/test"
+);
+const GOLDEN: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../activity-vocabulary/tests");
+const USAGE: &str = "usage: conformance [--golden <directory>] [<document or directory>...]";
+
+fn main() -> ExitCode {
+    let mut args = std::env::args_os().skip(1);
+    let mut golden = None;
+    let mut paths = Vec::<OsString>::new();
+    while let Some(arg) = args.next() {
+        if arg == "--golden" {
+            let Some(dir) = args.next() else {
+                eprintln!("{USAGE}");
+                return ExitCode::from(2);
+            };
+            golden = Some(PathBuf::from(dir));
+        } else {
+            paths.push(arg);
+        }
+    }
+    if paths.is_empty() {
+        if !Path::new(CORPUS).is_dir() {
+            eprintln!("{CORPUS} not found, run `git submodule update --init`");
+            return ExitCode::from(2);
+        }
+        paths.push(CORPUS.into());
+        golden.get_or_insert_with(|| GOLDEN.into());
+    }
+    let mut report = Report::default();
+    if let Err(e) =
+        report.check_all_golden::<WithContext<LinkOrObject>, _>(&paths, golden.as_deref())
+    {
+        eprintln!("{e}");
+        return ExitCode::from(2);
+    }
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&report).expect("serialize report")
+    );
+    if report.failed > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -0,0 +1,292 @@
+//! Checks that documents survive the round trip through a type, deserialized and serialized back,
+//! for the generated types and the extension types alike.
+
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+/// Lines of the changes kept in [DocumentReport::diff], the rest are cut.
+pub const SNIPPET_LINES: usize = 20;
+
+/// Environment variable which makes [check_golden] write the golden files instead of comparing.
+pub const UPDATE_GOLDEN: &str = "UPDATE_GOLDEN";
+
+#[derive(Debug)]
+pub enum CheckError {
+    Io(PathBuf, std::io::Error),
+    /// The file is not JSON.
+    Json(PathBuf, serde_json::Error),
+    Deserialize(serde_json::Error),
+    Serialize(serde_json::Error),
+    /// The document re-serialized differs from the expected one, in the lines of [json_diff].
+    Differ(String),
+}
+
+impl Display for CheckError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(path, e) => f.write_fmt(format_args!("{}: {e}", path.display())),
+            Self::Json(path, e) => f.write_fmt(format_args!("{}: {e}", path.display())),
+            Self::Deserialize(e) => f.write_fmt(format_args!("deserialize: {e}")),
+            Self::Serialize(e) => f.write_fmt(format_args!("serialize: {e}")),
+            Self::Differ(diff) => f.write_fmt(format_args!("differ:\n{diff}")),
+        }
+    }
+}
+
+impl std::error::Error for CheckError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(_, e) => Some(e),
+            Self::Json(_, e) | Self::Deserialize(e) | Self::Serialize(e) => Some(e),
+            Self::Differ(_) => None,
+        }
+    }
+}
+
+/// `value` with the keys of its objects sorted, also if serde_json preserves their order.
+fn sorted(value: &Value) -> Value {
+    match value {
+        Value::Array(values) => Value::Array(values.iter().map(sorted).collect()),
+        Value::Object(map) => {
+            let mut entries = map.iter().collect::<Vec<_>>();
+            entries.sort_by_key(|(key, _)| *key);
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key.clone(), sorted(value)))
+                    .collect(),
+            )
+        }
+        value => value.clone(),
+    }
+}
+
+/// Lines of the pretty JSON of `expected` and `actual`, prefixed by `-` if only in `expected` and
+/// by `+` if only in `actual`, or [None] if they are equal. The keys are sorted on both sides, so
+/// that only the differing properties are listed.
+pub fn json_diff(expected: &Value, actual: &Value) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+    let expected = serde_json::to_string_pretty(&sorted(expected)).expect("serialize JSON");
+    let actual = serde_json::to_string_pretty(&sorted(actual)).expect("serialize JSON");
+    let lines = diff::lines(&expected, &actual)
+        .into_iter()
+        .map(|diff| match diff {
+            diff::Result::Left(l) => format!("-{l}"),
+            diff::Result::Both(l, _) => format!(" {l}"),
+            diff::Result::Right(r) => format!("+{r}"),
+        })
+        .collect::<Vec<_>>();
+    Some(lines.join("\n"))
+}
+
+/// `document` deserialized as `T` and serialized back.
+pub fn round_trip<T: DeserializeOwned + Serialize>(document: Value) -> Result<Value, CheckError> {
+    let deserialized: T = serde_json::from_value(document).map_err(CheckError::Deserialize)?;
+    serde_json::to_value(deserialized).map_err(CheckError::Serialize)
+}
+
+/// The JSON document at `path`.
+pub fn read_json(path: impl AsRef<Path>) -> Result<Value, CheckError> {
+    let path = path.as_ref();
+    let json = fs::read_to_string(path).map_err(|e| CheckError::Io(path.to_owned(), e))?;
+    serde_json::from_str(&json).map_err(|e| CheckError::Json(path.to_owned(), e))
+}
+
+/// Check the document at `input` re-serializes as `T` into the one at `output`, returning the diff
+/// otherwise.
+pub fn check<T, P: AsRef<Path>>(input: P, output: P) -> Result<(), CheckError>
+where
+    T: DeserializeOwned + Serialize,
+{
+    let expected = read_json(output)?;
+    let re_serialized = round_trip::<T>(read_json(input)?)?;
+    match json_diff(&expected, &re_serialized) {
+        Some(diff) => Err(CheckError::Differ(diff)),
+        None => Ok(()),
+    }
+}
+
+/// [check] against the golden file at `golden`, which is written with the re-serialized document
+/// instead if [UPDATE_GOLDEN] is set.
+pub fn check_golden<T, P: AsRef<Path>>(input: P, golden: P) -> Result<(), CheckError>
+where
+    T: DeserializeOwned + Serialize,
+{
+    if std::env::var_os(UPDATE_GOLDEN).is_none() {
+        return check::<T, _>(input, golden);
+    }
+    let golden = golden.as_ref();
+    let re_serialized = round_trip::<T>(read_json(input)?)?;
+    let json = serde_json::to_string_pretty(&re_serialized).map_err(CheckError::Serialize)?;
+    fs::write(golden, json + "\n").map_err(|e| CheckError::Io(golden.to_owned(), e))
+}
+
+/// Paths of the JSON documents in `dir`, sorted.
+pub fn corpus(dir: impl AsRef<Path>) -> Result<Vec<PathBuf>, CheckError> {
+    let dir = dir.as_ref();
+    let entries = fs::read_dir(dir).map_err(|e| CheckError::Io(dir.to_owned(), e))?;
+    let mut paths = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|e| CheckError::Io(dir.to_owned(), e))?.path();
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// [check] each document of the [corpus] in `dir` round-trips as `T` into itself, or into the golden
+/// file of the same name in `golden_dir` if given, returning the failures.
+pub fn check_corpus<T>(
+    dir: impl AsRef<Path>,
+    golden_dir: Option<&Path>,
+) -> Result<Vec<(PathBuf, CheckError)>, CheckError>
+where
+    T: DeserializeOwned + Serialize,
+{
+    let mut failures = Vec::new();
+    for input in corpus(dir)? {
+        let result = match (golden_dir, input.file_name()) {
+            (Some(golden_dir), Some(name)) => check_golden::<T, _>(&input, &golden_dir.join(name)),
+            _ => check::<T, _>(&input, &input),
+        };
+        if let Err(e) = result {
+            failures.push((input, e));
+        }
+    }
+    Ok(failures)
+}
+
+/// The result of a document in a [Report].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocumentReport {
+    pub path: PathBuf,
+    pub passed: bool,
+    /// Why the document failed, if it did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The changed lines of [json_diff] up to [SNIPPET_LINES], if the document re-serialized differs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+}
+
+/// Machine-readable conformance report, whether each document round-trips into itself or into its
+/// golden file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Report {
+    pub passed: usize,
+    pub failed: usize,
+    pub documents: Vec<DocumentReport>,
+}
+
+fn snippet(diff: &str) -> String {
+    let changes = diff
+        .lines()
+        .filter(|line| !line.starts_with(' '))
+        .collect::<Vec<_>>();
+    let mut snippet = changes
+        .iter()
+        .take(SNIPPET_LINES)
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n");
+    if changes.len() > SNIPPET_LINES {
+        snippet += &format!("\n... {} more lines", changes.len() - SNIPPET_LINES);
+    }
+    snippet
+}
+
+impl Report {
+    /// Check the document at `path` round-trips as `T` into itself, and add the result.
+    pub fn check<T>(&mut self, path: impl AsRef<Path>)
+    where
+        T: DeserializeOwned + Serialize,
+    {
+        let path = path.as_ref();
+        self.check_into::<T>(path, path)
+    }
+
+    /// [Report::check] against the golden file of the same name in `golden_dir`, or against the
+    /// document itself if it has none, as the documents re-serialized unchanged need no golden file.
+    pub fn check_golden<T>(&mut self, path: impl AsRef<Path>, golden_dir: &Path)
+    where
+        T: DeserializeOwned + Serialize,
+    {
+        let path = path.as_ref();
+        match path.file_name().map(|name| golden_dir.join(name)) {
+            Some(golden) if golden.is_file() => self.check_into::<T>(path, &golden),
+            _ => self.check_into::<T>(path, path),
+        }
+    }
+
+    fn check_into<T>(&mut self, path: &Path, expected: &Path)
+    where
+        T: DeserializeOwned + Serialize,
+    {
+        let result = check::<T, _>(path, expected);
+        let (error, diff) = match result {
+            Ok(()) => (None, None),
+            Err(CheckError::Differ(diff)) => (Some("differ".to_owned()), Some(snippet(&diff))),
+            Err(e) => (Some(e.to_string()), None),
+        };
+        if error.is_some() {
+            self.failed += 1;
+        } else {
+            self.passed += 1;
+        }
+        self.documents.push(DocumentReport {
+            path: path.to_owned(),
+            passed: error.is_none(),
+            error,
+            diff,
+        });
+    }
+
+    /// [Report::check] each document of `paths`, and of the [corpus] of those which are directories.
+    pub fn check_all<T, P>(&mut self, paths: impl IntoIterator<Item = P>) -> Result<(), CheckError>
+    where
+        T: DeserializeOwned + Serialize,
+        P: AsRef<Path>,
+    {
+        self.check_all_golden::<T, P>(paths, None)
+    }
+
+    /// [Report::check_all] with [Report::check_golden] against `golden_dir` if given.
+    pub fn check_all_golden<T, P>(
+        &mut self,
+        paths: impl IntoIterator<Item = P>,
+        golden_dir: Option<&Path>,
+    ) -> Result<(), CheckError>
+    where
+        T: DeserializeOwned + Serialize,
+        P: AsRef<Path>,
+    {
+        for path in paths {
+            let path = path.as_ref();
+            let documents = if path.is_dir() {
+                corpus(path)?
+            } else {
+                vec![path.to_owned()]
+            };
+            for document in documents {
+                match golden_dir {
+                    Some(golden_dir) => self.check_golden::<T>(document, golden_dir),
+                    None => self.check::<T>(document),
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+use std::{fs, path::PathBuf};
+
+use activity_vocabulary_test_utils::*;
+use serde::{Deserialize, Serialize};
+
+/// Drops the properties other than `type` and `name`.
+#[derive(Serialize, Deserialize)]
+struct Named {
+    #[serde(rename = "type")]
+    kind: String,
+    name: String,
+}
+
+fn corpus_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("activity-vocabulary-test-utils-{name}"));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("a.json"), r#"{"type": "Note", "name": "a"}"#).unwrap();
+    fs::write(
+        dir.join("b.json"),
+        r#"{"type": "Note", "name": "b", "id": "b"}"#,
+    )
+    .unwrap();
+    fs::write(dir.join("c.txt"), "not a document").unwrap();
+    dir
+}
+
+#[test]
+fn diff() {
+    let expected = serde_json::json!({ "name": "a", "type": "Note" });
+    assert_eq!(json_diff(&expected, &expected), None);
+    let actual = serde_json::json!({ "name": "b", "type": "Note" });
+    assert_eq!(
+        json_diff(&expected, &actual).unwrap(),
+        " {\n-  \"name\": \"a\",\n+  \"name\": \"b\",\n   \"type\": \"Note\"\n }"
+    );
+}
+
+#[test]
+fn check_corpus_into_itself() {
+    let dir = corpus_dir("itself");
+    assert_eq!(
+        corpus(&dir).unwrap(),
+        [dir.join("a.json"), dir.join("b.json")]
+    );
+    let failures = check_corpus::<Named>(&dir, None).unwrap();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].0, dir.join("b.json"));
+    assert!(
+        matches!(&failures[0].1, CheckError::Differ(diff) if diff.contains("-  \"id\": \"b\","))
+    );
+}
+
+#[test]
+fn check_corpus_into_golden() {
+    let dir = corpus_dir("golden");
+    let golden = dir.join("golden");
+    fs::create_dir(&golden).unwrap();
+    fs::write(golden.join("a.json"), r#"{"type": "Note", "name": "a"}"#).unwrap();
+    fs::write(golden.join("b.json"), r#"{"type": "Note", "name": "b"}"#).unwrap();
+    assert!(check_corpus::<Named>(&dir, Some(&golden))
+        .unwrap()
+        .is_empty());
+    assert!(matches!(
+        check::<Named, _>(dir.join("missing.json"), dir.join("a.json")),
+        Err(CheckError::Io(..))
+    ));
+}
+
+#[test]
+fn report() {
+    let dir = corpus_dir("report");
+    let mut report = Report::default();
+    report
+        .check_all::<Named, _>([dir.clone(), dir.join("missing.json")])
+        .unwrap();
+    assert_eq!(report.passed, 1);
+    assert_eq!(report.failed, 2);
+    assert_eq!(
+        report.documents[1],
+        DocumentReport {
+            path: dir.join("b.json"),
+            passed: false,
+            error: Some("differ".to_owned()),
+            diff: Some("-  \"id\": \"b\",".to_owned()),
+        }
+    );
+    assert!(report.documents[2].error.is_some());
+    assert_eq!(report.documents[2].diff, None);
+}
+
+#[test]
+fn report_golden() {
+    let dir = corpus_dir("report-golden");
+    let golden = dir.join("golden");
+    fs::create_dir(&golden).unwrap();
+    fs::write(golden.join("b.json"), r#"{"type": "Note", "name": "b"}"#).unwrap();
+    let mut report = Report::default();
+    report
+        .check_all_golden::<Named, _>([&dir], Some(&golden))
+        .unwrap();
+    assert_eq!(report.passed, 2);
+    assert_eq!(report.failed, 0);
+}
@@ -0,0 +1,148 @@
+//! Deserialization and serialization of the documents stressing [Property], [Remotable], [Or] and
+//! [LangContainer](activity_vocabulary_core::LangContainer).
+//!
+//! ```sh
+//! cargo bench -p activity-vocabulary --bench deserialize
+//! ```
+
+use activity_vocabulary::*;
+use activity_vocabulary_core::{Or, Property, Remotable};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use serde_json::{json, Value};
+
+/// An outbox page of `len` notes created by the same actor, addressed to the public and the
+/// followers.
+fn outbox(len: usize) -> Value {
+    let items = (0..len)
+        .map(|i| {
+            json!({
+                "type": "Create",
+                "id": format!("https://example.com/activities/{i}"),
+                "actor": "https://example.com/users/alice",
+                "published": "2023-11-14T22:13:20Z",
+                "to": ["https://www.w3.org/ns/activitystreams#Public"],
+                "cc": ["https://example.com/users/alice/followers"],
+                "object": {
+                    "type": "Note",
+                    "id": format!("https://example.com/notes/{i}"),
+                    "attributedTo": "https://example.com/users/alice",
+                    "content": format!("<p>note {i}</p>"),
+                    "tag": [
+                        { "type": "Mention", "href": "https://example.com/users/bob", "name": "@bob" },
+                        { "type": "Hashtag", "href": "https://example.com/tags/rust", "name": "#rust" }
+                    ]
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "type": "OrderedCollectionPage",
+        "id": "https://example.com/users/alice/outbox?page=1",
+        "partOf": "https://example.com/users/alice/outbox",
+        "orderedItems": items,
+    })
+}
+
+/// Announces of announces `depth` deep, of a note at the bottom.
+fn nested(depth: usize) -> Value {
+    (0..depth).fold(
+        json!({ "type": "Note", "id": "https://example.com/notes/0", "content": "hello" }),
+        |object, i| {
+            json!({
+                "type": "Announce",
+                "id": format!("https://example.com/activities/{i}"),
+                "actor": { "type": "Person", "id": format!("https://example.com/users/{i}") },
+                "object": object,
+            })
+        },
+    )
+}
+
+/// An actor with the name and the summary in `langs` languages.
+fn multilingual_actor(langs: usize) -> Value {
+    let map = |prefix: &str| {
+        (0..langs)
+            .map(|i| (format!("x-lang{i}"), Value::String(format!("{prefix} {i}"))))
+            .collect::<serde_json::Map<_, _>>()
+    };
+    json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "type": "Person",
+        "id": "https://example.com/users/alice",
+        "inbox": "https://example.com/users/alice/inbox",
+        "outbox": "https://example.com/users/alice/outbox",
+        "name": "Alice",
+        "nameMap": map("name"),
+        "summary": "Hello",
+        "summaryMap": map("summary"),
+    })
+}
+
+fn bench_document<T>(c: &mut Criterion, name: &str, document: &Value)
+where
+    T: serde::de::DeserializeOwned + serde::Serialize,
+{
+    let json = serde_json::to_string(document).unwrap();
+    let mut group = c.benchmark_group(name);
+    group.throughput(Throughput::Bytes(json.len() as u64));
+    group.bench_function("from_str", |b| {
+        b.iter(|| serde_json::from_str::<T>(&json).unwrap())
+    });
+    group.bench_function("from_str as ObjectSubtypes", |b| {
+        b.iter(|| serde_json::from_str::<ObjectSubtypes>(&json).unwrap())
+    });
+    let parsed = serde_json::from_str::<T>(&json).unwrap();
+    group.bench_function("to_string", |b| {
+        b.iter(|| serde_json::to_string(&parsed).unwrap())
+    });
+    group.finish();
+}
+
+fn documents(c: &mut Criterion) {
+    bench_document::<OrderedCollectionPage>(c, "outbox", &outbox(200));
+    bench_document::<Announce>(c, "nested", &nested(32));
+    bench_document::<Person>(c, "multilingual_actor", &multilingual_actor(64));
+}
+
+/// The recipients of an activity, as [Remotable::Remote] tried after the left of the [Or] fails.
+fn references(c: &mut Criterion) {
+    let mut group = c.benchmark_group("references");
+    for len in [1, 16, 256] {
+        let json = serde_json::to_string(
+            &(0..len)
+                .map(|i| format!("https://example.com/users/{i}"))
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
+        group.throughput(Throughput::Elements(len));
+        group.bench_with_input(BenchmarkId::new("Property<Url>", len), &json, |b, json| {
+            b.iter(|| serde_json::from_str::<Property<url::Url>>(json).unwrap())
+        });
+        group.bench_with_input(
+            BenchmarkId::new("Property<Remotable<ObjectSubtypes>>", len),
+            &json,
+            |b, json| {
+                b.iter(|| {
+                    serde_json::from_str::<Property<Remotable<ObjectSubtypes>>>(json).unwrap()
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("Property<Or<LinkSubtypes, Remotable<ObjectSubtypes>>>", len),
+            &json,
+            |b, json| {
+                b.iter(|| {
+                    serde_json::from_str::<Property<Or<LinkSubtypes, Remotable<ObjectSubtypes>>>>(
+                        json,
+                    )
+                    .unwrap()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, documents, references);
+criterion_main!(benches);
@@ -1,11 +1,47 @@
 use std::{env, fs, path::Path};
 
-fn main() {
-    let src = fs::read_to_string("vocab.yml").unwrap();
-    let src = serde_yaml::from_str(&src).unwrap();
-    let src = activity_vocabulary_derive::gen(&src).unwrap();
+use activity_vocabulary_derive::{
+    diagnostic::{Source, VocabError},
+    Extension, Options,
+};
+
+/// Extension vocabularies, enabled by the feature of the paired environment variable.
+const EXTENSIONS: &[(&str, &str)] = &[
+    ("CARGO_FEATURE_EVENTS", "events.yml"),
+    ("CARGO_FEATURE_LEMMY", "lemmy.yml"),
+    ("CARGO_FEATURE_MASTODON", "mastodon.yml"),
+    ("CARGO_FEATURE_MISSKEY", "misskey.yml"),
+    ("CARGO_FEATURE_PLEROMA", "pleroma.yml"),
+];
+
+fn main() -> miette::Result<()> {
+    let vocab = Source::read("vocab.yml")?;
+    let mut src = vocab.parse()?;
+    let mut sources = vec![vocab];
+    println!("cargo:rerun-if-changed=vocab.yml");
+    for (feature, path) in EXTENSIONS {
+        println!("cargo:rerun-if-changed={path}");
+        if env::var_os(feature).is_some() {
+            sources.push(Source::read(path)?);
+            let extension: Extension = sources.last().unwrap().parse()?;
+            extension
+                .apply(&mut src)
+                .map_err(|e| VocabError::locate(e, &sources))?;
+        }
+    }
+    println!("cargo:rerun-if-env-changed=ACTIVITY_VOCABULARY_TYPE_TAG");
+    let mut options = Options::default();
+    // for the deployments which tag the objects with another key than `type`
+    if let Ok(type_tag) = env::var("ACTIVITY_VOCABULARY_TYPE_TAG") {
+        options.type_tag = type_tag;
+    }
+    let manifest = activity_vocabulary_derive::manifest_with(&src, &options)
+        .map_err(|e| VocabError::locate(e, &sources))?;
+    let src = activity_vocabulary_derive::gen_with(&src, &options)
+        .map_err(|e| VocabError::locate(e, &sources))?;
     let out_path = env::var("OUT_DIR").unwrap();
     let out_path: &Path = out_path.as_ref();
-    println!("cargo:rerun-if-changed=vocab.yml");
     fs::write(out_path.join("vocab.rs"), src.as_bytes()).unwrap();
+    fs::write(out_path.join("vocab.json"), manifest.as_bytes()).unwrap();
+    Ok(())
 }
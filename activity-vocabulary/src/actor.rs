@@ -0,0 +1,102 @@
+use std::fmt::Display;
+
+use activity_vocabulary_core::{ObjectUri, Remotable};
+
+use crate::{Application, Group, Organization, Person, Service};
+
+/// Properties common to the actor types.
+pub trait Actor {
+    fn id(&self) -> Option<&url::Url>;
+    fn also_known_as(&self) -> &[url::Url];
+    fn moved_to(&self) -> Option<&url::Url>;
+    fn inbox(&self) -> Option<&url::Url>;
+    /// `sharedInbox` of the [crate::Endpoints], if inlined.
+    fn shared_inbox(&self) -> Option<&url::Url>;
+    /// `publicKeyPem` of the [crate::PublicKey], if inlined.
+    fn public_key_pem(&self) -> Option<&str>;
+}
+
+macro_rules! impl_actor {
+    ($($ty:ty),*) => {
+        $(
+            impl Actor for $ty {
+                fn id(&self) -> Option<&url::Url> {
+                    self.id.as_deref()
+                }
+
+                fn also_known_as(&self) -> &[url::Url] {
+                    &self.also_known_as.0
+                }
+
+                fn moved_to(&self) -> Option<&url::Url> {
+                    self.moved_to.as_ref()
+                }
+
+                fn inbox(&self) -> Option<&url::Url> {
+                    self.inbox.as_ref()
+                }
+
+                fn shared_inbox(&self) -> Option<&url::Url> {
+                    match &self.endpoints {
+                        Some(Remotable::Inline(endpoints)) => endpoints.shared_inbox.as_ref(),
+                        _ => None,
+                    }
+                }
+
+                fn public_key_pem(&self) -> Option<&str> {
+                    match &self.public_key {
+                        Some(Remotable::Inline(key)) => key.public_key_pem.as_deref(),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_actor!(Application, Group, Organization, Person, Service);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationError {
+    MissingId,
+    NotMovedTo,
+    NotAlias,
+}
+
+impl Display for MigrationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingId => f.write_str("actor has no id"),
+            Self::NotMovedTo => f.write_str("movedTo of the old account is not the new account"),
+            Self::NotAlias => f.write_str("alsoKnownAs of the new account lacks the old account"),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+/// Check that `old` and `new` refer each other, as a `Move` from `old` to `new` requires.
+///
+/// `old` must point `new` by [Actor::moved_to], and `new` must list `old` in [Actor::also_known_as].
+/// Both actors should be freshly fetched from their origins rather than taken from the `Move`.
+/// The ids are compared as [ObjectUri]s.
+pub fn verify_migration(old: &impl Actor, new: &impl Actor) -> Result<(), MigrationError> {
+    let (Some(old_id), Some(new_id)) = (old.id(), new.id()) else {
+        return Err(MigrationError::MissingId);
+    };
+    let (old_id, new_id) = (
+        ObjectUri::new(old_id.clone()),
+        ObjectUri::new(new_id.clone()),
+    );
+    if old.moved_to().cloned().map(ObjectUri::new) != Some(new_id) {
+        return Err(MigrationError::NotMovedTo);
+    }
+    if !new
+        .also_known_as()
+        .iter()
+        .any(|alias| ObjectUri::new(alias.clone()) == old_id)
+    {
+        return Err(MigrationError::NotAlias);
+    }
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use activity_vocabulary_core::{MediaType, Or, Property};
+
+use crate::{Audio, Document, Image, Link, LinkSubtypes, ObjectSubtypes, Video};
+
+macro_rules! impl_from_url {
+    ($($ty:ident),*) => {
+        $(
+            impl $ty {
+                /// The media at `url`, linked with its `media_type`.
+                pub fn from_url(url: url::Url, media_type: MediaType) -> Self {
+                    let mut link = Link::new(url);
+                    link.media_type = Some(media_type.clone());
+                    Self {
+                        object_type: Property(vec![stringify!($ty).to_owned()]),
+                        media_type: Some(media_type),
+                        url: Property(vec![Or::Snd(LinkSubtypes::Link(link))]),
+                        ..Default::default()
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_url!(Audio, Document, Image, Video);
+
+/// Builder of an attachment, which becomes an [Image], [Video], [Audio] or [Document] by its media type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attachment {
+    url: url::Url,
+    media_type: MediaType,
+    name: Option<String>,
+    dimensions: Option<(u64, u64)>,
+    #[cfg(feature = "mastodon")]
+    blurhash: Option<String>,
+    #[cfg(feature = "mastodon")]
+    focal_point: Option<(f32, f32)>,
+}
+
+impl Attachment {
+    pub fn new(url: url::Url, media_type: MediaType) -> Self {
+        Self {
+            url,
+            media_type,
+            name: None,
+            dimensions: None,
+            #[cfg(feature = "mastodon")]
+            blurhash: None,
+            #[cfg(feature = "mastodon")]
+            focal_point: None,
+        }
+    }
+
+    /// The alternative text.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// `width` and `height` of the linked media.
+    pub fn dimensions(mut self, width: u64, height: u64) -> Self {
+        self.dimensions = Some((width, height));
+        self
+    }
+
+    #[cfg(feature = "mastodon")]
+    pub fn blurhash(mut self, blurhash: impl Into<String>) -> Self {
+        self.blurhash = Some(blurhash.into());
+        self
+    }
+
+    #[cfg(feature = "mastodon")]
+    pub fn focal_point(mut self, focal_point: (f32, f32)) -> Self {
+        self.focal_point = Some(focal_point);
+        self
+    }
+
+    pub fn build(self) -> ObjectSubtypes {
+        macro_rules! build {
+            ($ty:ident) => {{
+                let mut object = $ty::from_url(self.url, self.media_type);
+                if let (Some(Or::Snd(LinkSubtypes::Link(link))), Some((width, height))) =
+                    (object.url.0.first_mut(), self.dimensions)
+                {
+                    link.width = Some(width);
+                    link.height = Some(height);
+                }
+                object.name.default = self.name.map(|name| Property(vec![name]));
+                #[cfg(feature = "mastodon")]
+                {
+                    object.blurhash = self.blurhash;
+                    if let Some(focal_point) = self.focal_point {
+                        object.set_focal_point(focal_point);
+                    }
+                }
+                object.into()
+            }};
+        }
+        if self.media_type.is_image() {
+            build!(Image)
+        } else if self.media_type.is_video() {
+            build!(Video)
+        } else if self.media_type.is_audio() {
+            build!(Audio)
+        } else {
+            build!(Document)
+        }
+    }
+}
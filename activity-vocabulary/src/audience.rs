@@ -0,0 +1,90 @@
+use activity_vocabulary_core::{is_public_iri, ObjectId, Or};
+
+use crate::{link::LinkOrObject, LinkSubtypes};
+
+/// A recipient in the addressing properties, e.g. [Object::to].
+pub type Recipient = LinkOrObject;
+
+/// Visibility of an object, as Mastodon tells it from the addressing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Visibility {
+    /// Addressed to [activity_vocabulary_core::PUBLIC] in `to`.
+    Public,
+    /// Addressed to [activity_vocabulary_core::PUBLIC] elsewhere, typically in `cc`,
+    /// so that it is visible to anyone but kept out of the public timelines.
+    Unlisted,
+    /// Addressed to a followers collection.
+    Followers,
+    /// Addressed to the given actors only.
+    Direct,
+}
+
+/// Id of `recipient`, the `href` of a link or the id of an object, also of an inline one.
+pub fn recipient_id(recipient: &Recipient) -> Option<&url::Url> {
+    match recipient {
+        Or::Prim(LinkSubtypes::Link(link)) => Some(&link.href),
+        Or::Prim(_) => None,
+        Or::Snd(object) => object.object_id(),
+    }
+}
+
+fn is_public(recipient: &Recipient) -> bool {
+    recipient_id(recipient).is_some_and(|id| is_public_iri(id.as_str()))
+}
+
+/// Addressing properties common to the object types, implemented by the generated types.
+pub trait Addressed {
+    /// `to`, `bto`, `cc`, `bcc` and `audience` in this order.
+    fn addressing(&self) -> [&[Recipient]; 5];
+    /// [Addressed::addressing] to modify.
+    fn addressing_mut(&mut self) -> [&mut Vec<Recipient>; 5];
+
+    fn to(&self) -> &[Recipient] {
+        self.addressing()[0]
+    }
+
+    fn bto(&self) -> &[Recipient] {
+        self.addressing()[1]
+    }
+
+    fn cc(&self) -> &[Recipient] {
+        self.addressing()[2]
+    }
+
+    fn bcc(&self) -> &[Recipient] {
+        self.addressing()[3]
+    }
+
+    fn audience(&self) -> &[Recipient] {
+        self.addressing()[4]
+    }
+
+    fn recipients(&self) -> impl Iterator<Item = &Recipient> {
+        self.addressing().into_iter().flatten()
+    }
+
+    /// Whether the object is addressed to the public collection in any addressing property.
+    fn is_public(&self) -> bool {
+        self.recipients().any(is_public)
+    }
+
+    /// Heuristic [Visibility] of the object.
+    ///
+    /// A followers collection is told by the conventional `/followers` path, since the
+    /// followers collection of the author is not known here.
+    fn visibility(&self) -> Visibility {
+        if self.to().iter().any(is_public) {
+            Visibility::Public
+        } else if self.is_public() {
+            Visibility::Unlisted
+        } else if self
+            .recipients()
+            .filter_map(recipient_id)
+            .any(|id| id.path().ends_with("/followers"))
+        {
+            Visibility::Followers
+        } else {
+            Visibility::Direct
+        }
+    }
+}
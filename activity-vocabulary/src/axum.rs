@@ -0,0 +1,94 @@
+use std::fmt::Display;
+
+use ::axum::{
+    body::Bytes,
+    extract::{rejection::BytesRejection, FromRequest, Request},
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use activity_vocabulary_core::WithContext;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{is_activitystreams, ACTIVITY_MEDIA_TYPE};
+
+/// An ActivityStreams document in the body of a request or a response.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivityJson<T>(pub T);
+
+/// Why a request body was not accepted as an [ActivityJson].
+#[derive(Debug)]
+pub enum ActivityJsonRejection {
+    UnsupportedMediaType,
+    Body(BytesRejection),
+    Json(serde_json::Error),
+}
+
+impl Display for ActivityJsonRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedMediaType => f.write_str("content type is not activitystreams"),
+            Self::Body(e) => f.write_fmt(format_args!("read body: {e}")),
+            Self::Json(e) => f.write_fmt(format_args!("malformed document: {e}")),
+        }
+    }
+}
+
+impl std::error::Error for ActivityJsonRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::UnsupportedMediaType => None,
+            Self::Body(e) => Some(e),
+            Self::Json(e) => Some(e),
+        }
+    }
+}
+
+impl IntoResponse for ActivityJsonRejection {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            Self::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Self::Body(e) => e.status(),
+            Self::Json(_) => StatusCode::BAD_REQUEST,
+        };
+        (status, self.to_string()).into_response()
+    }
+}
+
+/// Accept a body of `application/activity+json`, or `application/ld+json` with the ActivityStreams profile.
+impl<T: DeserializeOwned, S: Send + Sync> FromRequest<S> for ActivityJson<T> {
+    type Rejection = ActivityJsonRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let is_activity = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .is_some_and(is_activitystreams);
+        if !is_activity {
+            return Err(ActivityJsonRejection::UnsupportedMediaType);
+        }
+        let body = Bytes::from_request(req, state)
+            .await
+            .map_err(ActivityJsonRejection::Body)?;
+        serde_json::from_slice(&body)
+            .map(Self)
+            .map_err(ActivityJsonRejection::Json)
+    }
+}
+
+/// Respond with the document as `application/activity+json`.
+impl<T: Serialize> IntoResponse for ActivityJson<WithContext<T>> {
+    fn into_response(self) -> Response {
+        match serde_json::to_vec(&self.0) {
+            Ok(body) => (
+                [(
+                    header::CONTENT_TYPE,
+                    HeaderValue::from_static(ACTIVITY_MEDIA_TYPE),
+                )],
+                body,
+            )
+                .into_response(),
+            Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        }
+    }
+}
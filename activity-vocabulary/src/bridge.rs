@@ -0,0 +1,7 @@
+use serde::{de::DeserializeOwned, Serialize};
+
+/// `value` as `U`, the same document typed by another crate such as `activitystreams`, converted
+/// through their common JSON representation.
+pub fn convert<T: Serialize, U: DeserializeOwned>(value: &T) -> Result<U, serde_json::Error> {
+    serde_json::from_value(serde_json::to_value(value)?)
+}
@@ -0,0 +1,281 @@
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    fmt::Display,
+    future::{ready, Future},
+    sync::Mutex,
+    time::{Duration, SystemTime},
+};
+
+use activity_vocabulary_core::{CacheHeaders, Dereferenced, Dereferencer};
+use serde::{Deserialize, Serialize};
+
+/// A document kept by a [CacheStore].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CachedDocument {
+    pub document: serde_json::Value,
+    pub headers: CacheHeaders,
+    /// Until when the document is used without revalidation.
+    pub fresh_until: SystemTime,
+}
+
+/// Storage of a [CachingDereferencer].
+pub trait CacheStore: Sync {
+    type Error;
+
+    fn get(
+        &self,
+        url: &url::Url,
+    ) -> impl Future<Output = Result<Option<CachedDocument>, Self::Error>> + Send;
+
+    /// Store `document`, which may be dropped after `retain`.
+    fn put(
+        &self,
+        url: &url::Url,
+        document: CachedDocument,
+        retain: Duration,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
+/// In-process [CacheStore].
+///
+/// A document past its retention is dropped when it is looked up, and the ones not looked up
+/// again are dropped by [MemoryStore::purge].
+#[derive(Debug, Default)]
+pub struct MemoryStore(Mutex<HashMap<url::Url, (CachedDocument, SystemTime)>>);
+
+impl MemoryStore {
+    /// Drop the documents past their retention, to be called from time to time.
+    pub fn purge(&self) {
+        let mut documents = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let now = SystemTime::now();
+        documents.retain(|_, (_, until)| *until > now);
+    }
+}
+
+impl CacheStore for MemoryStore {
+    type Error = Infallible;
+
+    fn get(
+        &self,
+        url: &url::Url,
+    ) -> impl Future<Output = Result<Option<CachedDocument>, Self::Error>> + Send {
+        let mut documents = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let document = match documents.get(url) {
+            Some((_, until)) if *until <= SystemTime::now() => {
+                documents.remove(url);
+                None
+            }
+            document => document.map(|(document, _)| document.clone()),
+        };
+        ready(Ok(document))
+    }
+
+    fn put(
+        &self,
+        url: &url::Url,
+        document: CachedDocument,
+        retain: Duration,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        let mut documents = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        documents.insert(url.clone(), (document, SystemTime::now() + retain));
+        ready(Ok(()))
+    }
+}
+
+#[derive(Debug)]
+pub enum CacheError<D, S> {
+    Dereference(D),
+    Store(S),
+}
+
+impl<D: Display, S: Display> Display for CacheError<D, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Dereference(e) => f.write_fmt(format_args!("dereference: {e}")),
+            Self::Store(e) => f.write_fmt(format_args!("cache store: {e}")),
+        }
+    }
+}
+
+impl<D, S> std::error::Error for CacheError<D, S>
+where
+    D: std::error::Error + 'static,
+    S: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Dereference(e) => Some(e),
+            Self::Store(e) => Some(e),
+        }
+    }
+}
+
+/// How long a response may be used without revalidation, or [None] if it must not be stored.
+///
+/// `s-maxage` is preferred over `max-age`, as the cache is shared by the users of a server, and
+/// `private` responses are not stored for the same reason.
+fn freshness(headers: &CacheHeaders) -> Option<Option<Duration>> {
+    let Some(cache_control) = &headers.cache_control else {
+        return Some(None);
+    };
+    let mut max_age = None;
+    let mut s_maxage = None;
+    for directive in cache_control.split(',') {
+        let (name, value) = directive
+            .split_once('=')
+            .map_or((directive, None), |(name, value)| (name, Some(value)));
+        let seconds = || {
+            value
+                .and_then(|value| value.trim().trim_matches('"').parse().ok())
+                .map(Duration::from_secs)
+        };
+        match name.trim().to_ascii_lowercase().as_str() {
+            "no-store" | "private" => return None,
+            "no-cache" => return Some(Some(Duration::ZERO)),
+            "max-age" => max_age = seconds(),
+            "s-maxage" => s_maxage = seconds(),
+            _ => (),
+        }
+    }
+    Some(s_maxage.or(max_age))
+}
+
+/// [Dereferencer] caching the documents of another one.
+///
+/// Fresh documents are served from the store, and stale ones are revalidated with their `ETag`
+/// and `Last-Modified` through [Dereferencer::dereference_conditional]. The freshness given by
+/// `Cache-Control` is bounded by [CachingDereferencer::ttl], documents with `no-cache` or
+/// `max-age=0` are always revalidated, and documents with `no-store` or `private` are never
+/// stored.
+#[derive(Debug)]
+pub struct CachingDereferencer<D, S> {
+    inner: D,
+    store: S,
+    min_ttl: Duration,
+    max_ttl: Duration,
+    default_ttl: Duration,
+    retain: Duration,
+}
+
+impl<D, S> CachingDereferencer<D, S> {
+    /// Cache with a default TTL of 5 minutes bounded to between 1 minute and 1 day, and keep
+    /// stale documents for revalidation for a week.
+    pub fn new(inner: D, store: S) -> Self {
+        Self {
+            inner,
+            store,
+            min_ttl: Duration::from_secs(60),
+            max_ttl: Duration::from_secs(24 * 60 * 60),
+            default_ttl: Duration::from_secs(5 * 60),
+            retain: Duration::from_secs(7 * 24 * 60 * 60),
+        }
+    }
+
+    /// Bound the freshness of the documents between `min` and `max`, and use `default` for those
+    /// without `max-age`.
+    ///
+    /// `min` does not make fresh the documents which must be revalidated.
+    pub fn ttl(mut self, min: Duration, max: Duration, default: Duration) -> Self {
+        self.min_ttl = min;
+        self.max_ttl = max.max(min);
+        self.default_ttl = default;
+        self
+    }
+
+    /// How long documents are stored after they get stale.
+    pub fn retain(mut self, retain: Duration) -> Self {
+        self.retain = retain;
+        self
+    }
+
+    pub fn inner(&self) -> &D {
+        &self.inner
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    fn ttl_of(&self, headers: &CacheHeaders) -> Option<Duration> {
+        freshness(headers).map(|ttl| match ttl.unwrap_or(self.default_ttl) {
+            Duration::ZERO => Duration::ZERO,
+            ttl => ttl.clamp(self.min_ttl, self.max_ttl),
+        })
+    }
+}
+
+impl<D: Dereferencer + Sync, S: CacheStore> CachingDereferencer<D, S> {
+    async fn store_document(
+        &self,
+        url: &url::Url,
+        document: serde_json::Value,
+        headers: CacheHeaders,
+    ) -> Result<serde_json::Value, CacheError<D::Error, S::Error>> {
+        let Some(ttl) = self.ttl_of(&headers) else {
+            return Ok(document);
+        };
+        let cached = CachedDocument {
+            document: document.clone(),
+            headers,
+            fresh_until: SystemTime::now() + ttl,
+        };
+        self.store
+            .put(url, cached, ttl + self.retain)
+            .await
+            .map_err(CacheError::Store)?;
+        Ok(document)
+    }
+}
+
+impl<D: Dereferencer + Sync, S: CacheStore> Dereferencer for CachingDereferencer<D, S>
+where
+    D::Error: Send,
+    S::Error: Send,
+{
+    type Error = CacheError<D::Error, S::Error>;
+
+    async fn dereference(&self, url: &url::Url) -> Result<serde_json::Value, Self::Error> {
+        let cached = self.store.get(url).await.map_err(CacheError::Store)?;
+        if let Some(cached) = &cached {
+            if cached.fresh_until > SystemTime::now() {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(%url, "cache hit");
+                return Ok(cached.document.clone());
+            }
+        }
+        #[cfg(feature = "tracing")]
+        tracing::debug!(%url, stale = cached.is_some(), "cache miss");
+        let dereferenced = self
+            .inner
+            .dereference_conditional(url, cached.as_ref().map(|cached| &cached.headers))
+            .await
+            .map_err(CacheError::Dereference)?;
+        match (dereferenced, cached) {
+            (Dereferenced::Modified { document, headers }, _) => {
+                self.store_document(url, document, headers).await
+            }
+            (Dereferenced::NotModified { headers }, Some(cached)) => {
+                let headers = CacheHeaders {
+                    etag: headers.etag.or(cached.headers.etag),
+                    last_modified: headers.last_modified.or(cached.headers.last_modified),
+                    cache_control: headers.cache_control.or(cached.headers.cache_control),
+                };
+                self.store_document(url, cached.document, headers).await
+            }
+            (Dereferenced::NotModified { .. }, None) => {
+                let document = self
+                    .inner
+                    .dereference(url)
+                    .await
+                    .map_err(CacheError::Dereference)?;
+                self.store_document(url, document, CacheHeaders::default())
+                    .await
+            }
+        }
+    }
+
+    fn is_gone(error: &Self::Error) -> bool {
+        matches!(error, CacheError::Dereference(e) if D::is_gone(e))
+    }
+}
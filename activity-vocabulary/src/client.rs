@@ -0,0 +1,716 @@
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    fmt::Display,
+    hash::{BuildHasher, Hasher},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
+};
+
+use activity_vocabulary_core::{CacheHeaders, Dereferenced, Dereferencer};
+use reqwest::{
+    dns::{Addrs, Name, Resolve, Resolving},
+    header, redirect, StatusCode,
+};
+use serde::de::DeserializeOwned;
+
+#[cfg(feature = "http-signatures")]
+use crate::http_signature::{sign_fetch, HttpSignatureError};
+
+/// `Accept` of the requests [Client] sends.
+pub const ACCEPT: &str = r#"application/activity+json, application/ld+json; profile="https://www.w3.org/ns/activitystreams""#;
+
+/// Responses larger than this are rejected by default.
+pub const DEFAULT_MAX_BODY_SIZE: usize = 1024 * 1024;
+
+/// Redirects followed by default.
+pub const DEFAULT_MAX_REDIRECTS: usize = 5;
+
+/// Concurrent requests to a host allowed by default.
+pub const DEFAULT_MAX_REQUESTS_PER_HOST: usize = 8;
+
+/// How long a [Client] waits to connect to a host by default.
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a [Client] waits for a response and its body by default.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub enum ClientError {
+    Http(reqwest::Error),
+    Status(StatusCode),
+    InvalidRedirect(String),
+    TooManyRedirects,
+    Denied(url::Url),
+    HostBusy(String),
+    CircuitOpen(String),
+    TooLarge,
+    Malformed(serde_json::Error),
+    #[cfg(feature = "http-signatures")]
+    Signature(HttpSignatureError),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Http(e) => f.write_fmt(format_args!("http: {e}")),
+            Self::Status(status) => f.write_fmt(format_args!("unexpected status {status}")),
+            Self::InvalidRedirect(location) => {
+                f.write_fmt(format_args!("invalid redirect to {location}"))
+            }
+            Self::TooManyRedirects => f.write_str("too many redirects"),
+            Self::Denied(url) => f.write_fmt(format_args!("{url} is denied by the url policy")),
+            Self::HostBusy(host) => {
+                f.write_fmt(format_args!("too many concurrent requests to {host}"))
+            }
+            Self::CircuitOpen(host) => {
+                f.write_fmt(format_args!("{host} is skipped after repeated failures"))
+            }
+            Self::TooLarge => f.write_str("response body is too large"),
+            Self::Malformed(e) => f.write_fmt(format_args!("malformed document: {e}")),
+            #[cfg(feature = "http-signatures")]
+            Self::Signature(e) => f.write_fmt(format_args!("sign request: {e}")),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Http(e) => Some(e),
+            Self::Malformed(e) => Some(e),
+            #[cfg(feature = "http-signatures")]
+            Self::Signature(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Which urls a [Client] may fetch, guarding against server-side request forgery by
+/// attacker-supplied ids.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlPolicy {
+    /// Schemes allowed to fetch.
+    pub schemes: Vec<String>,
+    /// Whether hosts may be or resolve to loopback, private, link-local and other non-global addresses.
+    pub allow_private: bool,
+    /// Redirects followed per fetch.
+    pub max_redirects: usize,
+    /// Concurrent requests to a host; more fail with [ClientError::HostBusy].
+    pub max_requests_per_host: usize,
+}
+
+impl Default for UrlPolicy {
+    fn default() -> Self {
+        Self {
+            schemes: vec!["https".to_owned()],
+            allow_private: false,
+            max_redirects: DEFAULT_MAX_REDIRECTS,
+            max_requests_per_host: DEFAULT_MAX_REQUESTS_PER_HOST,
+        }
+    }
+}
+
+impl UrlPolicy {
+    /// Whether `url` may be fetched, before resolving its host.
+    pub fn allows(&self, url: &url::Url) -> bool {
+        if !self.schemes.iter().any(|scheme| scheme == url.scheme()) {
+            return false;
+        }
+        match url.host() {
+            Some(url::Host::Domain(_)) => true,
+            Some(url::Host::Ipv4(ip)) => self.allows_ip(IpAddr::V4(ip)),
+            Some(url::Host::Ipv6(ip)) => self.allows_ip(IpAddr::V6(ip)),
+            None => false,
+        }
+    }
+
+    /// Whether a host may resolve to `ip`.
+    pub fn allows_ip(&self, ip: IpAddr) -> bool {
+        self.allow_private || is_global(ip)
+    }
+}
+
+fn is_global_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        || a == 0
+        // shared address space
+        || (a == 100 && (64..128).contains(&b))
+        // IETF protocol assignments
+        || (a == 192 && b == 0 && c == 0)
+        // benchmarking
+        || (a == 198 && (18..20).contains(&b))
+        // reserved
+        || a >= 240)
+}
+
+/// The IPv4 address embedded in `ip` by IPv4-mapped, IPv4-compatible, NAT64 (`64:ff9b::/96`)
+/// and 6to4 (`2002::/16`) addresses, through which it may be reached.
+fn embedded_v4(ip: Ipv6Addr) -> Option<Ipv4Addr> {
+    let octets = ip.octets();
+    let last = |octets: &[u8]| Ipv4Addr::new(octets[0], octets[1], octets[2], octets[3]);
+    match ip.segments() {
+        [0, 0, 0, 0, 0, 0xffff, ..] => ip.to_ipv4_mapped(),
+        // also `::` and `::1`, which embed `0.0.0.0/8` and so are not global either
+        [0, 0, 0, 0, 0, 0, ..] => Some(last(&octets[12..])),
+        [0x64, 0xff9b, 0, 0, 0, 0, ..] => Some(last(&octets[12..])),
+        [0x2002, ..] => Some(last(&octets[2..6])),
+        _ => None,
+    }
+}
+
+fn is_global_v6(ip: Ipv6Addr) -> bool {
+    if let Some(ip) = embedded_v4(ip) {
+        return is_global_v4(ip);
+    }
+    let [first, second, ..] = ip.segments();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // unique local
+        || (first & 0xfe00) == 0xfc00
+        // link-local
+        || (first & 0xffc0) == 0xfe80
+        // site-local, deprecated but still routed by some networks
+        || (first & 0xffc0) == 0xfec0
+        // documentation
+        || (first == 0x2001 && second == 0x0db8))
+}
+
+fn is_global(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_global_v4(ip),
+        IpAddr::V6(ip) => is_global_v6(ip),
+    }
+}
+
+/// How long a [Client] waits for a host, so that a slow or silent host cannot hold a fetch forever.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timeouts {
+    /// Connecting to the host.
+    pub connect: Duration,
+    /// A request, from connecting until its body is read.
+    pub request: Duration,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self {
+            connect: DEFAULT_CONNECT_TIMEOUT,
+            request: DEFAULT_REQUEST_TIMEOUT,
+        }
+    }
+}
+
+/// Error of [PolicyResolver] for a host resolving only to denied addresses.
+#[derive(Debug)]
+struct NoAllowedAddress(String);
+
+impl Display for NoAllowedAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{} has no allowed address", self.0))
+    }
+}
+
+impl std::error::Error for NoAllowedAddress {}
+
+/// Resolver dropping the addresses [UrlPolicy] denies, so that a host cannot be rebound to them
+/// after the url is checked.
+struct PolicyResolver {
+    allow_private: bool,
+}
+
+impl Resolve for PolicyResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let allow_private = self.allow_private;
+        Box::pin(async move {
+            let addrs = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|addr| allow_private || is_global(addr.ip()))
+                .collect::<Vec<SocketAddr>>();
+            if addrs.is_empty() {
+                return Err(Box::new(NoAllowedAddress(name.as_str().to_owned())) as _);
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Requests in flight per host.
+#[derive(Debug, Default)]
+struct HostCounts(Mutex<HashMap<String, usize>>);
+
+struct HostPermit<'a> {
+    counts: &'a HostCounts,
+    host: String,
+}
+
+impl HostCounts {
+    fn acquire(&self, host: &str, limit: usize) -> Result<HostPermit<'_>, ClientError> {
+        let mut counts = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let count = counts.entry(host.to_owned()).or_default();
+        if *count >= limit {
+            return Err(ClientError::HostBusy(host.to_owned()));
+        }
+        *count += 1;
+        Ok(HostPermit {
+            counts: self,
+            host: host.to_owned(),
+        })
+    }
+}
+
+impl Drop for HostPermit<'_> {
+    fn drop(&mut self) {
+        let mut counts = self.counts.0.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(count) = counts.get_mut(&self.host) {
+            *count -= 1;
+            if *count == 0 {
+                counts.remove(&self.host);
+            }
+        }
+    }
+}
+
+/// How a [Client] paces its requests to each host and retries the failed ones.
+///
+/// Connection failures, timeouts and `429`, `500`, `502`, `503` and `504` responses are retried
+/// with exponential backoff and jitter. A host failing `failure_threshold` fetches in a row is
+/// not requested for `open_duration`.
+///
+/// The `Retry-After` of a `429` or `503` response is waited instead of the backoff, by every
+/// request to the host. A host asking for longer than `max_delay` is not retried, and is not
+/// requested until then.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt.
+    pub max_retries: u32,
+    /// Backoff before the first retry, doubled for every next one.
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    /// Interval between the requests to a host.
+    pub min_interval: Duration,
+    pub failure_threshold: u32,
+    pub open_duration: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            min_interval: Duration::from_millis(100),
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Backoff before the retry following `attempt`, half fixed and half random.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay);
+        let jitter = RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64;
+        delay / 2 + delay.mul_f64(jitter / 2.0)
+    }
+}
+
+fn is_retryable(result: &Result<reqwest::Response, ClientError>) -> bool {
+    match result {
+        Ok(response) => matches!(
+            response.status(),
+            StatusCode::TOO_MANY_REQUESTS
+                | StatusCode::INTERNAL_SERVER_ERROR
+                | StatusCode::BAD_GATEWAY
+                | StatusCode::SERVICE_UNAVAILABLE
+                | StatusCode::GATEWAY_TIMEOUT
+        ),
+        Err(ClientError::Http(e)) => e.is_connect() || e.is_timeout(),
+        Err(_) => false,
+    }
+}
+
+/// The delay a `429` or `503` response asks for by `Retry-After`, in seconds or until a date.
+fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+    if !matches!(
+        response.status(),
+        StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+    ) {
+        return None;
+    }
+    let value = response.headers().get(header::RETRY_AFTER)?.to_str().ok()?;
+    if let Ok(secs) = value.trim().parse() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = httpdate::parse_http_date(value.trim()).ok()?;
+    Some(date.duration_since(SystemTime::now()).unwrap_or_default())
+}
+
+/// Hosts paced are swept for idle ones when there are this many of them at least.
+const MIN_HOSTS_SWEPT: usize = 64;
+
+#[derive(Debug, Default)]
+struct Pacing {
+    next_request: Option<Instant>,
+    failures: u32,
+    open_until: Option<Instant>,
+}
+
+impl Pacing {
+    /// Whether the host is not waited for, and has not been requested for `open_duration`, after
+    /// which its failures are forgotten.
+    fn is_idle(&self, now: Instant, policy: &RetryPolicy) -> bool {
+        self.open_until.is_none_or(|until| until <= now)
+            && self
+                .next_request
+                .is_none_or(|next| next + policy.open_duration <= now)
+    }
+}
+
+#[derive(Debug, Default)]
+struct PacingTable {
+    hosts: HashMap<String, Pacing>,
+    /// Number of hosts at which the idle ones are swept next.
+    sweep_at: usize,
+}
+
+/// Request pacing and failures per host.
+///
+/// Idle hosts are swept when the number of hosts doubles since the last sweep, so that the
+/// hosts once requested do not pile up.
+#[derive(Debug, Default)]
+struct HostPacing(Mutex<PacingTable>);
+
+impl HostPacing {
+    /// When the next request to `host` may be sent.
+    fn reserve(&self, host: &str, policy: &RetryPolicy) -> Result<Instant, ClientError> {
+        let mut table = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        if table.hosts.len() >= table.sweep_at {
+            table.hosts.retain(|_, pacing| !pacing.is_idle(now, policy));
+            table.sweep_at = (table.hosts.len() * 2).max(MIN_HOSTS_SWEPT);
+        }
+        let pacing = table.hosts.entry(host.to_owned()).or_default();
+        if pacing.open_until.is_some_and(|until| until > now) {
+            return Err(ClientError::CircuitOpen(host.to_owned()));
+        }
+        let slot = pacing.next_request.map_or(now, |next| next.max(now));
+        pacing.next_request = Some(slot + policy.min_interval);
+        Ok(slot)
+    }
+
+    /// Hold the requests to `host` for `delay` the host asked for, or open its circuit for a
+    /// delay longer than [RetryPolicy::max_delay].
+    fn defer(&self, host: &str, delay: Duration, policy: &RetryPolicy) {
+        let mut table = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let pacing = table.hosts.entry(host.to_owned()).or_default();
+        let until = Instant::now() + delay;
+        if delay > policy.max_delay {
+            pacing.open_until = Some(pacing.open_until.map_or(until, |open| open.max(until)));
+        } else {
+            pacing.next_request = Some(pacing.next_request.map_or(until, |next| next.max(until)));
+        }
+    }
+
+    fn record(&self, host: &str, failed: bool, policy: &RetryPolicy) {
+        let mut table = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let pacing = table.hosts.entry(host.to_owned()).or_default();
+        if !failed {
+            pacing.failures = 0;
+            pacing.open_until = None;
+            return;
+        }
+        pacing.failures += 1;
+        if pacing.failures >= policy.failure_threshold {
+            pacing.failures = 0;
+            let until = Instant::now() + policy.open_duration;
+            pacing.open_until = Some(pacing.open_until.map_or(until, |open| open.max(until)));
+        }
+    }
+}
+
+/// Fetches ActivityStreams documents over HTTP.
+///
+/// Every url, including those redirected to, is checked against the [UrlPolicy], and redirects
+/// are followed by the client itself up to [UrlPolicy::max_redirects] times.
+#[derive(Debug, Clone)]
+pub struct Client {
+    http: reqwest::Client,
+    policy: UrlPolicy,
+    hosts: Arc<HostCounts>,
+    retry: RetryPolicy,
+    pacing: Arc<HostPacing>,
+    max_body_size: usize,
+    #[cfg(feature = "http-signatures")]
+    signing_key: Option<Arc<(url::Url, rsa::RsaPrivateKey)>>,
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Client {
+    pub fn new() -> Self {
+        Self::with_policy(UrlPolicy::default())
+    }
+
+    pub fn with_policy(policy: UrlPolicy) -> Self {
+        Self::with_timeouts(policy, Timeouts::default())
+    }
+
+    pub fn with_timeouts(policy: UrlPolicy, timeouts: Timeouts) -> Self {
+        let http = reqwest::Client::builder()
+            .redirect(redirect::Policy::none())
+            .no_proxy()
+            .connect_timeout(timeouts.connect)
+            .timeout(timeouts.request)
+            .dns_resolver(Arc::new(PolicyResolver {
+                allow_private: policy.allow_private,
+            }))
+            .build()
+            .expect("default client");
+        Self::with_client(http, policy)
+    }
+
+    /// Send the requests through `http`, which should not follow redirects by itself and should
+    /// time out as [Timeouts] do.
+    ///
+    /// The addresses hosts resolve to are checked only when `http` resolves them through the
+    /// same policy, as the clients of [Client::with_policy] do.
+    pub fn with_client(http: reqwest::Client, policy: UrlPolicy) -> Self {
+        Self {
+            http,
+            policy,
+            hosts: Default::default(),
+            retry: RetryPolicy::default(),
+            pacing: Default::default(),
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            #[cfg(feature = "http-signatures")]
+            signing_key: None,
+        }
+    }
+
+    pub fn retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// The largest response body in bytes.
+    pub fn max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// Sign every fetch with `key` of the instance actor, identified by `key_id`, for the servers
+    /// requiring authorized fetch.
+    #[cfg(feature = "http-signatures")]
+    pub fn signing_key(mut self, key_id: url::Url, key: rsa::RsaPrivateKey) -> Self {
+        self.signing_key = Some(Arc::new((key_id, key)));
+        self
+    }
+
+    /// The document at `url` as `T`.
+    pub async fn fetch_object<T: DeserializeOwned>(
+        &self,
+        url: &url::Url,
+    ) -> Result<T, ClientError> {
+        let (body, _) = self.fetch_body(url, None).await?;
+        let body = body.ok_or(ClientError::Status(StatusCode::NOT_MODIFIED))?;
+        serde_json::from_slice(&body).map_err(|e| {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(%url, error = %e, "malformed document");
+            ClientError::Malformed(e)
+        })
+    }
+
+    async fn send(
+        &self,
+        url: &url::Url,
+        cached: Option<&CacheHeaders>,
+    ) -> Result<reqwest::Response, ClientError> {
+        let mut request = self.http.get(url.clone()).header(header::ACCEPT, ACCEPT);
+        if let Some(etag) = cached.and_then(|cached| cached.etag.as_ref()) {
+            request = request.header(header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = cached.and_then(|cached| cached.last_modified.as_ref()) {
+            request = request.header(header::IF_MODIFIED_SINCE, last_modified);
+        }
+        let request = request.build().map_err(ClientError::Http)?;
+        #[cfg(feature = "http-signatures")]
+        let request = match &self.signing_key {
+            Some(signing_key) => {
+                let (key_id, key) = signing_key.as_ref();
+                let mut request = http::Request::<reqwest::Body>::try_from(request)
+                    .map_err(ClientError::Http)?
+                    .map(|_| Vec::new());
+                sign_fetch(&mut request, key_id, key).map_err(ClientError::Signature)?;
+                reqwest::Request::try_from(request).map_err(ClientError::Http)?
+            }
+            None => request,
+        };
+        self.http.execute(request).await.map_err(|e| {
+            let mut source: Option<&(dyn std::error::Error + 'static)> = Some(&e);
+            while let Some(e) = source {
+                if e.is::<NoAllowedAddress>() {
+                    return ClientError::Denied(url.clone());
+                }
+                source = e.source();
+            }
+            ClientError::Http(e)
+        })
+    }
+
+    /// [Client::send] following the [RetryPolicy].
+    async fn send_paced(
+        &self,
+        url: &url::Url,
+        cached: Option<&CacheHeaders>,
+    ) -> Result<reqwest::Response, ClientError> {
+        let host = url.host_str().unwrap_or_default();
+        let mut attempt = 0;
+        loop {
+            let slot = self.pacing.reserve(host, &self.retry)?;
+            tokio::time::sleep_until(slot.into()).await;
+            let result = self.send(url, cached).await;
+            let retryable = is_retryable(&result);
+            let retry_after = result.as_ref().ok().and_then(retry_after);
+            if let Some(delay) = retry_after {
+                self.pacing.defer(host, delay, &self.retry);
+            }
+            if !retryable
+                || attempt >= self.retry.max_retries
+                || retry_after.is_some_and(|delay| delay > self.retry.max_delay)
+            {
+                self.pacing.record(host, retryable, &self.retry);
+                return result;
+            }
+            #[cfg(feature = "tracing")]
+            tracing::debug!(%url, attempt, "retry");
+            // the slot reserved next is after `Retry-After`
+            if retry_after.is_none() {
+                tokio::time::sleep(self.retry.backoff(attempt)).await;
+            }
+            attempt += 1;
+        }
+    }
+
+    /// The body at `url` and its caching headers, or [None] for the body if `cached` is still valid.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", name = "fetch", skip_all, fields(%url))
+    )]
+    async fn fetch_body(
+        &self,
+        url: &url::Url,
+        cached: Option<&CacheHeaders>,
+    ) -> Result<(Option<Vec<u8>>, CacheHeaders), ClientError> {
+        let mut url = url.clone();
+        let mut redirects = 0;
+        let (mut response, _permit) = loop {
+            if !self.policy.allows(&url) {
+                return Err(ClientError::Denied(url));
+            }
+            let permit = self.hosts.acquire(
+                url.host_str().unwrap_or_default(),
+                self.policy.max_requests_per_host,
+            )?;
+            let response = self.send_paced(&url, cached).await?;
+            if !response.status().is_redirection() || response.status() == StatusCode::NOT_MODIFIED
+            {
+                break (response, permit);
+            }
+            let location = response
+                .headers()
+                .get(header::LOCATION)
+                .ok_or(ClientError::Status(response.status()))?;
+            let location = location
+                .to_str()
+                .map_err(|_| ClientError::InvalidRedirect(format!("{location:?}")))?;
+            url = url
+                .join(location)
+                .map_err(|_| ClientError::InvalidRedirect(location.to_owned()))?;
+            #[cfg(feature = "tracing")]
+            tracing::debug!(to = %url, "redirect");
+            redirects += 1;
+            if redirects > self.policy.max_redirects {
+                return Err(ClientError::TooManyRedirects);
+            }
+        };
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(ToOwned::to_owned)
+        };
+        #[cfg(feature = "tracing")]
+        tracing::debug!(status = %response.status(), "response");
+        let headers = CacheHeaders {
+            etag: header(header::ETAG),
+            last_modified: header(header::LAST_MODIFIED),
+            cache_control: header(header::CACHE_CONTROL),
+        };
+        if response.status() == StatusCode::NOT_MODIFIED && cached.is_some() {
+            return Ok((None, headers));
+        }
+        if !response.status().is_success() {
+            return Err(ClientError::Status(response.status()));
+        }
+        if response
+            .content_length()
+            .is_some_and(|len| len > self.max_body_size as u64)
+        {
+            return Err(ClientError::TooLarge);
+        }
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(ClientError::Http)? {
+            if body.len() + chunk.len() > self.max_body_size {
+                return Err(ClientError::TooLarge);
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok((Some(body), headers))
+    }
+}
+
+impl Dereferencer for Client {
+    type Error = ClientError;
+
+    async fn dereference(&self, url: &url::Url) -> Result<serde_json::Value, Self::Error> {
+        self.fetch_object(url).await
+    }
+
+    async fn dereference_conditional(
+        &self,
+        url: &url::Url,
+        cached: Option<&CacheHeaders>,
+    ) -> Result<Dereferenced, Self::Error> {
+        match self.fetch_body(url, cached).await? {
+            (Some(body), headers) => Ok(Dereferenced::Modified {
+                document: serde_json::from_slice(&body).map_err(ClientError::Malformed)?,
+                headers,
+            }),
+            (None, headers) => Ok(Dereferenced::NotModified { headers }),
+        }
+    }
+
+    fn is_gone(error: &ClientError) -> bool {
+        matches!(
+            error,
+            ClientError::Status(StatusCode::NOT_FOUND | StatusCode::GONE)
+        )
+    }
+}
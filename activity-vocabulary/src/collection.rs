@@ -0,0 +1,203 @@
+use std::collections::HashSet;
+
+use activity_vocabulary_core::{ObjectUri, Or, Property, Remotable};
+
+use crate::{
+    audience::{recipient_id, Recipient},
+    Collection, CollectionPage, CollectionPageSubtypes, LinkSubtypes, Object, OrderedCollection,
+    OrderedCollectionPage,
+};
+
+fn page_url(base_url: &url::Url, page: usize) -> url::Url {
+    let mut url = base_url.clone();
+    url.query_pairs_mut()
+        .append_pair("page", &(page + 1).to_string());
+    url
+}
+
+fn page_link(url: url::Url) -> Option<Box<Or<LinkSubtypes, Remotable<CollectionPageSubtypes>>>> {
+    Some(Box::new(Or::Snd(Remotable::Remote(url))))
+}
+
+/// Split `items` into [OrderedCollectionPage]s of `page_size` items, linked each other,
+/// and the root [OrderedCollection] at `base_url` linking the first and the last of them.
+///
+/// Page `n`, counted from 1, is at `base_url` with `?page=n`. `items` should be in the order
+/// to serve, i.e. reverse chronological for an outbox.
+pub fn paginate(
+    items: Vec<Recipient>,
+    page_size: usize,
+    base_url: &url::Url,
+) -> (OrderedCollection, Vec<OrderedCollectionPage>) {
+    let page_size = page_size.max(1);
+    let total_items = items.len();
+    let page_count = total_items.div_ceil(page_size);
+    let mut items = items.into_iter();
+    let pages = (0..page_count)
+        .map(|page| OrderedCollectionPage {
+            object_type: Property(vec!["OrderedCollectionPage".to_owned()]),
+            id: Some(page_url(base_url, page).into()),
+            part_of: Some(Box::new(Or::Prim(Remotable::Remote(base_url.clone())))),
+            prev: page
+                .checked_sub(1)
+                .and_then(|prev| page_link(page_url(base_url, prev))),
+            next: (page + 1 < page_count)
+                .then(|| page_url(base_url, page + 1))
+                .and_then(page_link),
+            start_index: Some(page * page_size),
+            items: Property(items.by_ref().take(page_size).collect()),
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+    let collection = OrderedCollection {
+        object_type: Property(vec!["OrderedCollection".to_owned()]),
+        id: Some(base_url.clone().into()),
+        total_items: Some(total_items),
+        first: pages
+            .first()
+            .and_then(|page| page.id.clone())
+            .map(ObjectUri::into_url)
+            .and_then(page_link),
+        last: pages
+            .last()
+            .and_then(|page| page.id.clone())
+            .map(ObjectUri::into_url)
+            .and_then(page_link),
+        ..Default::default()
+    };
+    (collection, pages)
+}
+
+/// Id of `item`, also of an inline object.
+fn item_id(item: &Recipient) -> Option<url::Url> {
+    match item {
+        Or::Snd(Remotable::Inline(object)) => {
+            Object::from(object.clone()).id.map(ObjectUri::into_url)
+        }
+        item => recipient_id(item).cloned(),
+    }
+}
+
+impl OrderedCollection {
+    /// Empty [OrderedCollection] at `id`.
+    pub fn new(id: url::Url) -> Self {
+        Self {
+            object_type: Property(vec!["OrderedCollection".to_owned()]),
+            id: Some(id.into()),
+            total_items: Some(0),
+            ..Default::default()
+        }
+    }
+
+    /// Add `item` as the newest, i.e. the first in the reverse chronological order.
+    pub fn push_item(&mut self, item: impl Into<Recipient>) {
+        self.items.0.insert(0, item.into());
+        self.total_items = Some(self.total_items.unwrap_or_default() + 1);
+    }
+
+    /// Remove the item of `id`, returning it.
+    pub fn remove_item_by_id(&mut self, id: &url::Url) -> Option<Recipient> {
+        let index = self
+            .items
+            .0
+            .iter()
+            .position(|item| item_id(item).as_ref() == Some(id))?;
+        self.total_items = self.total_items.map(|total| total.saturating_sub(1));
+        Some(self.items.0.remove(index))
+    }
+
+    /// Keep the `len` newest items, removing the older ones.
+    pub fn truncate(&mut self, len: usize) {
+        let removed = self.items.0.len().saturating_sub(len);
+        self.items.0.truncate(len);
+        self.total_items = self.total_items.map(|total| total.saturating_sub(removed));
+    }
+}
+
+impl Collection {
+    /// `replies` [Collection] at `id` with `replies` inlined in its first page, as Mastodon serves.
+    pub fn replies(id: url::Url, replies: Vec<Recipient>) -> Self {
+        let mut collection = Self {
+            object_type: Property(vec!["Collection".to_owned()]),
+            id: Some(id.into()),
+            total_items: Some(0),
+            ..Default::default()
+        };
+        for reply in replies {
+            collection.push_reply(reply);
+        }
+        collection
+    }
+
+    /// Append `reply` to the inline first page, which is made if missing.
+    /// If the first page is not inlined, `reply` goes to the items of the collection itself.
+    pub fn push_reply(&mut self, reply: impl Into<Recipient>) {
+        let first = self.first.get_or_insert_with(|| {
+            Box::new(Or::Snd(Remotable::Inline(
+                CollectionPage {
+                    object_type: Property(vec!["CollectionPage".to_owned()]),
+                    part_of: self
+                        .id
+                        .clone()
+                        .map(|id| Box::new(Or::Prim(Remotable::Remote(id.into_url())))),
+                    ..Default::default()
+                }
+                .into(),
+            )))
+        });
+        match first.as_mut() {
+            Or::Snd(Remotable::Inline(CollectionPageSubtypes::CollectionPage(page))) => {
+                page.items.0.push(reply.into())
+            }
+            _ => self.items.0.push(reply.into()),
+        }
+        self.total_items = Some(self.total_items.unwrap_or_default() + 1);
+    }
+}
+
+/// Ids added and removed between two snapshots of a collection, made by [diff_collections].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CollectionDiff {
+    pub added: Vec<url::Url>,
+    pub removed: Vec<url::Url>,
+}
+
+/// Ids of the items of `collection` and of its inline first page, in order without duplicates.
+fn collection_ids(collection: &Collection) -> Vec<url::Url> {
+    let first_page = match collection.first.as_deref() {
+        Some(Or::Snd(Remotable::Inline(page))) => CollectionPage::from(page.clone()).items.0,
+        _ => Vec::new(),
+    };
+    let mut seen = HashSet::new();
+    collection
+        .items
+        .0
+        .iter()
+        .chain(&first_page)
+        .filter_map(item_id)
+        .filter(|id| seen.insert(id.clone()))
+        .collect()
+}
+
+/// Ids in `new` but not in `old` and vice versa, whether the items are inlined or referred by id.
+///
+/// The items and those in an inline first page are compared; other pages are not fetched.
+/// [crate::OrderedCollection]s compare the same after [Into::into] a [Collection].
+pub fn diff_collections(old: &Collection, new: &Collection) -> CollectionDiff {
+    let old = collection_ids(old);
+    let new = collection_ids(new);
+    let old_set = old.iter().collect::<HashSet<_>>();
+    let new_set = new.iter().collect::<HashSet<_>>();
+    CollectionDiff {
+        added: new
+            .iter()
+            .filter(|id| !old_set.contains(id))
+            .cloned()
+            .collect(),
+        removed: old
+            .iter()
+            .filter(|id| !new_set.contains(id))
+            .cloned()
+            .collect(),
+    }
+}
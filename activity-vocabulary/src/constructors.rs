@@ -0,0 +1,126 @@
+use activity_vocabulary_core::{LangContainer, Or, Property, Remotable};
+
+use crate::{
+    audience::Recipient, Accept, Activity, ActivitySubtypes, Announce, Create, Follow, Like, Note,
+    ObjectSubtypes, Reject, Undo,
+};
+
+fn remote(id: url::Url) -> Property<Recipient> {
+    Property(vec![Or::Snd(Remotable::Remote(id))])
+}
+
+fn inline(object: impl Into<ObjectSubtypes>) -> Property<Recipient> {
+    Property(vec![Or::Snd(Remotable::Inline(object.into()))])
+}
+
+fn type_of(name: &str) -> Property<String> {
+    Property(vec![name.to_owned()])
+}
+
+impl Note {
+    /// [Note] of `content` without a language, to be given an id and addressing.
+    pub fn new(content: impl Into<String>) -> Self {
+        Self {
+            object_type: type_of("Note"),
+            content: LangContainer {
+                default: Some(Property(vec![content.into()])),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+}
+
+impl Create {
+    /// [Create] of `object` inlined, by `actor`.
+    ///
+    /// Unlike [Create::wrap], nothing is copied from the object.
+    pub fn new(actor: url::Url, object: impl Into<ObjectSubtypes>) -> Self {
+        Self {
+            object_type: type_of("Create"),
+            actor: remote(actor),
+            object: inline(object),
+            ..Default::default()
+        }
+    }
+}
+
+impl Follow {
+    /// [Follow] of the actor of id `object` by `actor`.
+    pub fn new(actor: url::Url, object: url::Url) -> Self {
+        Self {
+            object_type: type_of("Follow"),
+            actor: remote(actor),
+            object: remote(object),
+            ..Default::default()
+        }
+    }
+}
+
+impl Like {
+    /// [Like] of the object of id `object` by `actor`.
+    pub fn new(actor: url::Url, object: url::Url) -> Self {
+        Self {
+            object_type: type_of("Like"),
+            actor: remote(actor),
+            object: remote(object),
+            ..Default::default()
+        }
+    }
+}
+
+impl Announce {
+    /// [Announce] of the object of id `object` by `actor`.
+    pub fn new(actor: url::Url, object: url::Url) -> Self {
+        Self {
+            object_type: type_of("Announce"),
+            actor: remote(actor),
+            object: remote(object),
+            ..Default::default()
+        }
+    }
+}
+
+impl Accept {
+    /// [Accept] of `follow` by the followed actor, addressed to the follower.
+    pub fn of(follow: Follow) -> Self {
+        Self {
+            object_type: type_of("Accept"),
+            actor: follow.object.clone(),
+            to: follow.actor.clone(),
+            object: inline(follow),
+            ..Default::default()
+        }
+    }
+}
+
+impl Reject {
+    /// [Reject] of `follow` by the followed actor, addressed to the follower.
+    pub fn of(follow: Follow) -> Self {
+        Self {
+            object_type: type_of("Reject"),
+            actor: follow.object.clone(),
+            to: follow.actor.clone(),
+            object: inline(follow),
+            ..Default::default()
+        }
+    }
+}
+
+impl Undo {
+    /// [Undo] of `activity` inlined, by its actor and with its addressing.
+    pub fn of(activity: impl Into<ObjectSubtypes>) -> Self {
+        let activity = activity.into();
+        let base = ActivitySubtypes::try_from(activity.clone())
+            .map(Activity::from)
+            .unwrap_or_default();
+        Self {
+            object_type: type_of("Undo"),
+            actor: base.actor,
+            to: base.to,
+            cc: base.cc,
+            object: inline(activity),
+            ..Default::default()
+        }
+    }
+}
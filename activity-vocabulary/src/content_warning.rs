@@ -0,0 +1,48 @@
+use activity_vocabulary_core::{LangContainer, Property};
+
+/// [Object::summary](crate::Object::summary), which Mastodon shows as the content warning.
+pub type Summary = LangContainer<Property<String>>;
+
+/// Content warning of an object, implemented by the generated types.
+pub trait ContentWarning {
+    /// `summary` and `sensitive`.
+    fn warning(&self) -> (&Summary, Option<bool>);
+    /// [ContentWarning::warning] to modify.
+    fn warning_mut(&mut self) -> (&mut Summary, &mut Option<bool>);
+
+    /// The content warning in `summary`, preferring the one without a language, then the one of
+    /// the first language in the alphabetical order.
+    fn content_warning(&self) -> Option<&str> {
+        let (summary, _) = self.warning();
+        let mut per_lang = summary.per_lang.iter().collect::<Vec<_>>();
+        per_lang.sort_by_key(|(lang, _)| *lang);
+        summary
+            .default
+            .iter()
+            .chain(per_lang.into_iter().map(|(_, summary)| summary))
+            .flat_map(|summary| summary.0.iter())
+            .map(String::as_str)
+            .find(|summary| !summary.is_empty())
+    }
+
+    /// Put `text` as the only `summary` and mark the object `sensitive`.
+    fn set_content_warning(&mut self, text: impl Into<String>) {
+        let (summary, sensitive) = self.warning_mut();
+        *summary = LangContainer {
+            default: Some(Property(vec![text.into()])),
+            per_lang: Default::default(),
+        };
+        *sensitive = Some(true);
+    }
+
+    /// Remove `summary` in all the languages, leaving the `sensitive` flag for media.
+    fn clear_content_warning(&mut self) {
+        *self.warning_mut().0 = LangContainer::default();
+    }
+
+    /// Whether the object is flagged `sensitive` or has a content warning, either of which
+    /// Mastodon hides the content behind.
+    fn is_sensitive(&self) -> bool {
+        self.warning().1 == Some(true) || self.content_warning().is_some()
+    }
+}
@@ -0,0 +1,15 @@
+use rsa::Pkcs1v15Sign;
+
+/// DER encoded `DigestInfo` prefix of SHA-256 for EMSA-PKCS1-v1_5.
+const SHA256_DIGEST_INFO_PREFIX: [u8; 19] = [
+    0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05,
+    0x00, 0x04, 0x20,
+];
+
+/// RSASSA-PKCS1-v1_5 with SHA-256, the scheme of both `RsaSignature2017` and `rsa-sha256`.
+pub(crate) fn pkcs1v15_sha256() -> Pkcs1v15Sign {
+    Pkcs1v15Sign {
+        hash_len: Some(32),
+        prefix: SHA256_DIGEST_INFO_PREFIX.into(),
+    }
+}
@@ -0,0 +1,42 @@
+use std::fmt::Display;
+
+use activity_vocabulary_core::{canonical_json, ObjectId, ObjectUri};
+use sha2::{Digest, Sha256};
+
+/// Key of an activity in a seen-cache, so that an activity delivered to several inboxes is
+/// processed once.
+///
+/// Activities with the same id have the same key as compared by [ObjectUri]. An activity without
+/// id is keyed by the SHA-256 of its canonical JSON instead, so that its copies still match.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DedupKey {
+    Id(ObjectUri),
+    Digest([u8; 32]),
+}
+
+impl DedupKey {
+    pub fn of<T: ObjectId + serde::Serialize>(activity: &T) -> Result<Self, serde_json::Error> {
+        match activity.object_uri() {
+            Some(id) => Ok(Self::Id(id)),
+            None => {
+                let json = canonical_json::to_canonical_string(activity)?;
+                Ok(Self::Digest(Sha256::digest(json.as_bytes()).into()))
+            }
+        }
+    }
+}
+
+/// `id:` and the normalized id, or `sha256:` and the hex of the digest, to key a cache by strings.
+impl Display for DedupKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Id(id) => f.write_fmt(format_args!("id:{}", id.normalize(&Default::default()))),
+            Self::Digest(digest) => {
+                f.write_str("sha256:")?;
+                digest
+                    .iter()
+                    .try_for_each(|byte| f.write_fmt(format_args!("{byte:02x}")))
+            }
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use std::{collections::HashSet, fmt::Display};
+
+use activity_vocabulary_core::{is_public_iri, Dereferencer, ObjectUri, Or, Remotable};
+
+use crate::{
+    actor::Actor,
+    audience::{recipient_id, Addressed, Recipient},
+    Collection, CollectionPage, CollectionPageSubtypes, LinkSubtypes, ObjectSubtypes,
+};
+
+/// Options of [collect_inboxes].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeliveryOptions {
+    /// Deliver to the `sharedInbox` of the actors which have one instead of their own inboxes.
+    pub shared_inbox: bool,
+    /// Recipients not to deliver to, typically the sender itself, also when they are inlined.
+    /// They are compared as [ObjectUri]s.
+    pub exclude: Vec<url::Url>,
+    /// Maximum number of the pages fetched per collection.
+    pub max_pages: usize,
+}
+
+impl Default for DeliveryOptions {
+    fn default() -> Self {
+        Self {
+            shared_inbox: true,
+            exclude: Vec::new(),
+            max_pages: 16,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum DeliveryError<E> {
+    Dereference(E),
+    MalformedDocument(url::Url, serde_json::Error),
+}
+
+impl<E: Display> Display for DeliveryError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Dereference(e) => f.write_fmt(format_args!("fetch recipient: {e}")),
+            Self::MalformedDocument(url, e) => f.write_fmt(format_args!("malformed {url}: {e}")),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for DeliveryError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Dereference(e) => Some(e),
+            Self::MalformedDocument(_, e) => Some(e),
+        }
+    }
+}
+
+fn actor_inbox(actor: &impl Actor, options: &DeliveryOptions) -> Option<url::Url> {
+    options
+        .shared_inbox
+        .then(|| actor.shared_inbox())
+        .flatten()
+        .or(actor.inbox())
+        .cloned()
+}
+
+enum Resolved {
+    Inbox(url::Url),
+    Members(Vec<Recipient>, Option<url::Url>),
+    Nothing,
+}
+
+fn page_link(page: &Or<LinkSubtypes, Remotable<CollectionPageSubtypes>>) -> Option<url::Url> {
+    match page {
+        Or::Prim(LinkSubtypes::Link(link)) => Some(link.href.clone()),
+        Or::Snd(Remotable::Remote(id)) => Some(id.clone()),
+        Or::Snd(Remotable::Inline(page)) => CollectionPage::from(page.clone()).id.map(Into::into),
+        _ => None,
+    }
+}
+
+fn page_members(page: CollectionPage) -> Resolved {
+    Resolved::Members(page.items.0, page.next.as_deref().and_then(page_link))
+}
+
+fn collection_members(collection: Collection) -> Resolved {
+    let mut items = collection.items.0;
+    match collection.first.map(|first| *first) {
+        // Mastodon inlines the first page
+        Some(Or::Snd(Remotable::Inline(page))) => {
+            let page = CollectionPage::from(page);
+            items.extend(page.items.0);
+            Resolved::Members(items, page.next.as_deref().and_then(page_link))
+        }
+        first => Resolved::Members(items, first.as_ref().and_then(page_link)),
+    }
+}
+
+fn resolve(object: ObjectSubtypes, options: &DeliveryOptions) -> Resolved {
+    let inbox = match object {
+        ObjectSubtypes::Application(actor) => actor_inbox(&actor, options),
+        ObjectSubtypes::Group(actor) => actor_inbox(&actor, options),
+        ObjectSubtypes::Organization(actor) => actor_inbox(&actor, options),
+        ObjectSubtypes::Person(actor) => actor_inbox(&actor, options),
+        ObjectSubtypes::Service(actor) => actor_inbox(&actor, options),
+        ObjectSubtypes::CollectionPage(page) => return page_members(page),
+        ObjectSubtypes::OrderedCollectionPage(page) => return page_members(page.into()),
+        ObjectSubtypes::Collection(collection) => return collection_members(collection),
+        ObjectSubtypes::OrderedCollection(collection) => {
+            return collection_members(collection.into())
+        }
+        _ => None,
+    };
+    inbox.map(Resolved::Inbox).unwrap_or(Resolved::Nothing)
+}
+
+async fn fetch<D: Dereferencer>(
+    dereferencer: &D,
+    id: &url::Url,
+) -> Result<ObjectSubtypes, DeliveryError<D::Error>> {
+    let document = dereferencer
+        .dereference(id)
+        .await
+        .map_err(DeliveryError::Dereference)?;
+    serde_json::from_value(document).map_err(|e| DeliveryError::MalformedDocument(id.clone(), e))
+}
+
+/// Inboxes to deliver `activity` to, collected from its `to`, `cc`, `bto`, `bcc` and `audience`.
+///
+/// Addressed actors are dereferenced for their inboxes, and addressed collections such as
+/// followers are expanded into their members. Collections in collections are not expanded,
+/// as ActivityPub forbids recursing indefinitely. The public collection and
+/// [DeliveryOptions::exclude] are skipped, and each inbox appears once, so that actors on a
+/// server sharing an inbox get a single delivery. Objects other than actors and collections
+/// are ignored.
+pub async fn collect_inboxes<D: Dereferencer>(
+    activity: &impl Addressed,
+    dereferencer: &D,
+    options: &DeliveryOptions,
+) -> Result<Vec<url::Url>, DeliveryError<D::Error>> {
+    let mut seen = options
+        .exclude
+        .iter()
+        .cloned()
+        .map(ObjectUri::new)
+        .collect::<HashSet<_>>();
+    let mut inboxes = Vec::new();
+    let mut pushed = HashSet::new();
+    let mut push = |inbox: url::Url| {
+        if pushed.insert(inbox.clone()) {
+            inboxes.push(inbox);
+        }
+    };
+    for recipient in activity.recipients() {
+        let id = recipient_id(recipient);
+        if id.is_some_and(|id| {
+            is_public_iri(id.as_str()) || !seen.insert(ObjectUri::new(id.clone()))
+        }) {
+            continue;
+        }
+        let members = match (recipient, id) {
+            (Or::Snd(Remotable::Inline(object)), _) => resolve(object.clone(), options),
+            (_, Some(id)) => resolve(fetch(dereferencer, id).await?, options),
+            (_, None) => continue,
+        };
+        let (mut members, mut next) = match members {
+            Resolved::Inbox(inbox) => {
+                push(inbox);
+                continue;
+            }
+            Resolved::Members(members, next) => (members, next),
+            Resolved::Nothing => continue,
+        };
+        let mut pages = 0;
+        loop {
+            for member in members {
+                let id = recipient_id(&member).cloned();
+                if id
+                    .as_ref()
+                    .is_some_and(|id| !seen.insert(ObjectUri::new(id.clone())))
+                {
+                    continue;
+                }
+                let object = match (member, id) {
+                    (Or::Snd(Remotable::Inline(object)), _) => object,
+                    (_, Some(id)) => fetch(dereferencer, &id).await?,
+                    (_, None) => continue,
+                };
+                if let Resolved::Inbox(inbox) = resolve(object, options) {
+                    push(inbox);
+                }
+            }
+            match next.take() {
+                Some(page)
+                    if pages < options.max_pages && seen.insert(ObjectUri::new(page.clone())) =>
+                {
+                    pages += 1;
+                    let Resolved::Members(page_members, page_next) =
+                        resolve(fetch(dereferencer, &page).await?, options)
+                    else {
+                        break;
+                    };
+                    members = page_members;
+                    next = page_next;
+                }
+                _ => break,
+            }
+        }
+    }
+    Ok(inboxes)
+}
@@ -0,0 +1,63 @@
+use std::{convert::Infallible, fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// How actors may participate in an [crate::Event], see [crate::Event::join_mode].
+#[derive(Clone, PartialEq, Eq, Debug, Hash, Default)]
+pub enum JoinMode {
+    /// Anyone may participate.
+    #[default]
+    Free,
+    /// Participation requires an approval of the organizer.
+    Restricted,
+    /// Only invited actors may participate.
+    Invite,
+    /// Participation happens outside of the fediverse, e.g. on a ticketing site.
+    External,
+    Other(String),
+}
+
+impl Display for JoinMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Free => f.write_str("free"),
+            Self::Restricted => f.write_str("restricted"),
+            Self::Invite => f.write_str("invite"),
+            Self::External => f.write_str("external"),
+            Self::Other(mode) => f.write_str(mode),
+        }
+    }
+}
+
+impl FromStr for JoinMode {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "free" => Self::Free,
+            "restricted" => Self::Restricted,
+            "invite" => Self::Invite,
+            "external" => Self::External,
+            mode => Self::Other(mode.to_owned()),
+        })
+    }
+}
+
+impl Serialize for JoinMode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for JoinMode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mode = String::deserialize(deserializer)?;
+        Ok(mode.parse().unwrap_or_else(|e: Infallible| match e {}))
+    }
+}
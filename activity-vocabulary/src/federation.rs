@@ -0,0 +1,216 @@
+use std::{fmt::Debug, future::Future, marker::PhantomData};
+
+use activity_vocabulary_core::ObjectId;
+use activitypub_federation::{
+    config::Data,
+    error::Error,
+    protocol::verification::verify_domains_match,
+    traits::{self, Object},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use url::Url;
+
+use crate::actor::Actor;
+
+/// Storage of the objects of type `T`, the app data of the `FederationConfig` of the adapters.
+pub trait Repository<T>: Clone + Send + Sync + 'static {
+    /// Also the failures of the framework, such as an object from another domain than expected.
+    type Error: From<Error> + Send;
+
+    fn read(&self, id: &Url) -> impl Future<Output = Result<Option<T>, Self::Error>> + Send;
+
+    /// Store `object`, received from another server.
+    fn write(&self, object: &T) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    fn delete(&self, _id: &Url) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
+    }
+}
+
+/// Adapter of a generated type to [Object] of `activitypub_federation`, which is serialized as the
+/// type itself and stored in the [Repository] `R`.
+pub struct FederatedObject<T, R> {
+    pub object: T,
+    repository: PhantomData<fn() -> R>,
+}
+
+impl<T, R> FederatedObject<T, R> {
+    pub fn new(object: T) -> Self {
+        Self {
+            object,
+            repository: PhantomData,
+        }
+    }
+}
+
+impl<T: Debug, R> Debug for FederatedObject<T, R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("FederatedObject")
+            .field(&self.object)
+            .finish()
+    }
+}
+
+#[async_trait::async_trait]
+impl<T, R> Object for FederatedObject<T, R>
+where
+    T: ObjectId + Serialize + DeserializeOwned + Debug + Send + Sync + 'static,
+    R: Repository<T>,
+{
+    type DataType = R;
+    type Kind = T;
+    type Error = R::Error;
+
+    async fn read_from_id(object_id: Url, data: &Data<R>) -> Result<Option<Self>, R::Error> {
+        Ok(data.read(&object_id).await?.map(Self::new))
+    }
+
+    async fn delete(self, data: &Data<R>) -> Result<(), R::Error> {
+        match self.object.object_id() {
+            Some(id) => data.delete(id).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn into_json(self, _data: &Data<R>) -> Result<T, R::Error> {
+        Ok(self.object)
+    }
+
+    async fn verify(json: &T, expected_domain: &Url, _data: &Data<R>) -> Result<(), R::Error> {
+        let id = json
+            .object_id()
+            .ok_or(Error::UrlVerificationError("object has no id"))?;
+        Ok(verify_domains_match(id, expected_domain)?)
+    }
+
+    async fn from_json(json: T, data: &Data<R>) -> Result<Self, R::Error> {
+        data.write(&json).await?;
+        Ok(Self::new(json))
+    }
+}
+
+/// Adapter of an actor type to [traits::Actor] of `activitypub_federation`, as [FederatedObject].
+///
+/// The actor has the id, the inbox and the inlined public key which the framework requires of it,
+/// as they are checked on construction and in [Object::verify].
+pub struct FederatedActor<T, R> {
+    actor: T,
+    id: Url,
+    inbox: Url,
+    public_key_pem: String,
+    private_key_pem: Option<String>,
+    repository: PhantomData<fn() -> R>,
+}
+
+fn required<T: Actor>(actor: &T) -> Result<(&Url, &Url, &str), Error> {
+    let id = actor
+        .id()
+        .ok_or(Error::UrlVerificationError("actor has no id"))?;
+    let inbox = actor
+        .inbox()
+        .ok_or(Error::UrlVerificationError("actor has no inbox"))?;
+    let public_key_pem = actor.public_key_pem().ok_or(Error::UrlVerificationError(
+        "actor has no inlined publicKeyPem",
+    ))?;
+    Ok((id, inbox, public_key_pem))
+}
+
+impl<T: Actor, R> FederatedActor<T, R> {
+    pub fn new(actor: T) -> Result<Self, Error> {
+        let (id, inbox, public_key_pem) = required(&actor)?;
+        Ok(Self {
+            id: id.clone(),
+            inbox: inbox.clone(),
+            public_key_pem: public_key_pem.to_owned(),
+            private_key_pem: None,
+            actor,
+            repository: PhantomData,
+        })
+    }
+
+    /// The private key to sign the activities sent by a local actor, which the [Repository] does not
+    /// keep.
+    pub fn with_private_key_pem(self, private_key_pem: String) -> Self {
+        Self {
+            private_key_pem: Some(private_key_pem),
+            ..self
+        }
+    }
+
+    pub fn actor(&self) -> &T {
+        &self.actor
+    }
+
+    pub fn into_inner(self) -> T {
+        self.actor
+    }
+}
+
+impl<T: Debug, R> Debug for FederatedActor<T, R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("FederatedActor").field(&self.actor).finish()
+    }
+}
+
+#[async_trait::async_trait]
+impl<T, R> Object for FederatedActor<T, R>
+where
+    T: Actor + ObjectId + Serialize + DeserializeOwned + Debug + Send + Sync + 'static,
+    R: Repository<T>,
+{
+    type DataType = R;
+    type Kind = T;
+    type Error = R::Error;
+
+    async fn read_from_id(object_id: Url, data: &Data<R>) -> Result<Option<Self>, R::Error> {
+        match data.read(&object_id).await? {
+            Some(actor) => Ok(Some(Self::new(actor)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn delete(self, data: &Data<R>) -> Result<(), R::Error> {
+        data.delete(&self.id).await
+    }
+
+    async fn into_json(self, _data: &Data<R>) -> Result<T, R::Error> {
+        Ok(self.actor)
+    }
+
+    async fn verify(json: &T, expected_domain: &Url, _data: &Data<R>) -> Result<(), R::Error> {
+        let (id, _, _) = required(json)?;
+        Ok(verify_domains_match(id, expected_domain)?)
+    }
+
+    async fn from_json(json: T, data: &Data<R>) -> Result<Self, R::Error> {
+        let actor = Self::new(json)?;
+        data.write(&actor.actor).await?;
+        Ok(actor)
+    }
+}
+
+impl<T, R> traits::Actor for FederatedActor<T, R>
+where
+    T: Actor + ObjectId + Serialize + DeserializeOwned + Debug + Send + Sync + 'static,
+    R: Repository<T>,
+{
+    fn id(&self) -> Url {
+        self.id.clone()
+    }
+
+    fn public_key_pem(&self) -> &str {
+        &self.public_key_pem
+    }
+
+    fn private_key_pem(&self) -> Option<String> {
+        self.private_key_pem.clone()
+    }
+
+    fn inbox(&self) -> Url {
+        self.inbox.clone()
+    }
+
+    fn shared_inbox(&self) -> Option<Url> {
+        self.actor.shared_inbox().cloned()
+    }
+}
@@ -0,0 +1,69 @@
+use std::fmt::Display;
+
+use activity_vocabulary_core::{ObjectUri, Or, Property, Remotable};
+
+use crate::{actor::Actor, Accept, Follow};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FollowRequestError {
+    /// The local actor has no id.
+    MissingId,
+    /// The follow has no actor, or more than one.
+    MissingFollower,
+    /// The follow is of another actor than the local one.
+    NotFollowing,
+}
+
+impl Display for FollowRequestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingId => f.write_str("local actor has no id"),
+            Self::MissingFollower => f.write_str("follow has no single actor"),
+            Self::NotFollowing => f.write_str("follow is not of the local actor"),
+        }
+    }
+}
+
+impl std::error::Error for FollowRequestError {}
+
+/// Follow request accepted by [accept_follow].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AcceptedFollow {
+    /// The [Accept] to deliver to the follower.
+    pub accept: Accept,
+    /// The actor to add to the followers of the local actor.
+    pub follower: url::Url,
+}
+
+/// Accept `follow` of `local` automatically, as bots do, by an [Accept] of id `id` by `local` and
+/// addressed to the follower.
+///
+/// The object of the follow must be `local` as compared by [ObjectUri], and its actor is the
+/// follower. Both are taken by their ids, also when they are inlined.
+pub fn accept_follow(
+    follow: Follow,
+    local: &impl Actor,
+    id: url::Url,
+) -> Result<AcceptedFollow, FollowRequestError> {
+    let local = local.id().ok_or(FollowRequestError::MissingId)?;
+    let follower = match follow.actor.0.as_slice() {
+        [actor] => actor
+            .id_or_href()
+            .ok_or(FollowRequestError::MissingFollower)?,
+        _ => return Err(FollowRequestError::MissingFollower),
+    }
+    .clone();
+    if !follow
+        .object
+        .0
+        .iter()
+        .filter_map(|object| object.id_or_href())
+        .any(|object| ObjectUri::new(object.clone()) == *local)
+    {
+        return Err(FollowRequestError::NotFollowing);
+    }
+    let mut accept = Accept::of(follow);
+    accept.id = Some(id.into());
+    accept.actor = Property(vec![Or::Snd(Remotable::Remote(local.clone()))]);
+    Ok(AcceptedFollow { accept, follower })
+}
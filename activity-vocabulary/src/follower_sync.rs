@@ -0,0 +1,172 @@
+use std::{fmt::Display, str::FromStr};
+
+use activity_vocabulary_core::{Or, Remotable};
+use sha2::{Digest, Sha256};
+
+use crate::OrderedCollection;
+
+/// Name of the header Mastodon attaches to deliveries to synchronize followers.
+pub const COLLECTION_SYNCHRONIZATION: &str = "collection-synchronization";
+
+/// Followers among `followers` hosted on `domain`.
+fn on_domain<'a, 'b>(
+    followers: impl IntoIterator<Item = &'a url::Url> + 'b,
+    domain: &'b str,
+) -> impl Iterator<Item = &'a url::Url> + 'b
+where
+    'a: 'b,
+{
+    followers
+        .into_iter()
+        .filter(move |follower| follower.host_str() == Some(domain))
+}
+
+/// XOR of the SHA-256 of the ids of `followers` hosted on `domain`.
+pub fn followers_digest<'a>(
+    followers: impl IntoIterator<Item = &'a url::Url>,
+    domain: &str,
+) -> [u8; 32] {
+    on_domain(followers, domain).fold([0; 32], |mut digest, follower| {
+        let hash = Sha256::digest(follower.as_str().as_bytes());
+        digest
+            .iter_mut()
+            .zip(hash.iter())
+            .for_each(|(digest, hash)| *digest ^= hash);
+        digest
+    })
+}
+
+/// The partial followers collection at `id` listing `followers` hosted on `domain`.
+pub fn partial_followers<'a>(
+    id: url::Url,
+    followers: impl IntoIterator<Item = &'a url::Url>,
+    domain: &str,
+) -> OrderedCollection {
+    let mut collection = OrderedCollection::new(id);
+    collection.items.0 = on_domain(followers, domain)
+        .map(|follower| Or::Snd(Remotable::Remote(follower.clone())))
+        .collect();
+    collection.total_items = Some(collection.items.0.len());
+    collection
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CollectionSynchronizationError {
+    MissingParameter(&'static str),
+    Url(url::ParseError),
+    MalformedDigest(String),
+}
+
+impl Display for CollectionSynchronizationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingParameter(name) => f.write_fmt(format_args!("missing parameter {name}")),
+            Self::Url(e) => f.write_fmt(format_args!("invalid url: {e}")),
+            Self::MalformedDigest(digest) => f.write_fmt(format_args!("malformed digest {digest}")),
+        }
+    }
+}
+
+impl std::error::Error for CollectionSynchronizationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Url(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<url::ParseError> for CollectionSynchronizationError {
+    fn from(e: url::ParseError) -> Self {
+        Self::Url(e)
+    }
+}
+
+/// Value of the `Collection-Synchronization` header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollectionSynchronization {
+    /// The followers collection of the sender.
+    pub collection_id: url::Url,
+    /// The partial followers collection for the receiving domain.
+    pub url: url::Url,
+    pub digest: [u8; 32],
+}
+
+impl CollectionSynchronization {
+    /// The header for delivering to `domain`, whose followers of `collection_id` are listed at `url`.
+    pub fn new<'a>(
+        collection_id: url::Url,
+        url: url::Url,
+        followers: impl IntoIterator<Item = &'a url::Url>,
+        domain: &str,
+    ) -> Self {
+        Self {
+            collection_id,
+            url,
+            digest: followers_digest(followers, domain),
+        }
+    }
+
+    /// Whether the `followers` known on `domain` agree with the sender.
+    pub fn is_synchronized<'a>(
+        &self,
+        followers: impl IntoIterator<Item = &'a url::Url>,
+        domain: &str,
+    ) -> bool {
+        self.digest == followers_digest(followers, domain)
+    }
+}
+
+impl Display for CollectionSynchronization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "collectionId=\"{}\", url=\"{}\", digest=\"",
+            self.collection_id, self.url
+        ))?;
+        self.digest
+            .iter()
+            .try_for_each(|byte| f.write_fmt(format_args!("{byte:02x}")))?;
+        f.write_str("\"")
+    }
+}
+
+fn parse_digest(hex: &str) -> Result<[u8; 32], CollectionSynchronizationError> {
+    let malformed = || CollectionSynchronizationError::MalformedDigest(hex.to_owned());
+    if hex.len() != 64 || !hex.is_ascii() {
+        return Err(malformed());
+    }
+    let mut digest = [0; 32];
+    for (i, byte) in digest.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).map_err(|_| malformed())?;
+    }
+    Ok(digest)
+}
+
+impl FromStr for CollectionSynchronization {
+    type Err = CollectionSynchronizationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut collection_id = None;
+        let mut url = None;
+        let mut digest = None;
+        for param in s.split(',') {
+            let Some((key, value)) = param.split_once('=') else {
+                continue;
+            };
+            let value = value.trim().trim_matches('"');
+            match key.trim() {
+                "collectionId" => collection_id = Some(value.parse()?),
+                "url" => url = Some(value.parse()?),
+                "digest" => digest = Some(parse_digest(value)?),
+                _ => {}
+            }
+        }
+        Ok(Self {
+            collection_id: collection_id.ok_or(
+                CollectionSynchronizationError::MissingParameter("collectionId"),
+            )?,
+            url: url.ok_or(CollectionSynchronizationError::MissingParameter("url"))?,
+            digest: digest.ok_or(CollectionSynchronizationError::MissingParameter("digest"))?,
+        })
+    }
+}
@@ -0,0 +1,541 @@
+use std::{
+    convert::Infallible,
+    fmt::Display,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use activity_vocabulary_core::{Dereferencer, Property, Remotable, WithContext};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use http::{HeaderValue, Request};
+use rsa::{pkcs1::DecodeRsaPublicKey, pkcs8::DecodePublicKey, RsaPrivateKey, RsaPublicKey};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::{crypto::pkcs1v15_sha256, PublicKey};
+
+/// Headers covered by the signatures [sign_request] makes.
+const SIGNED_HEADERS: &[&str] = &["(request-target)", "host", "date", "digest"];
+
+/// Headers covered by the signatures [sign_fetch] makes.
+const FETCH_SIGNED_HEADERS: &[&str] = &["(request-target)", "host", "date"];
+
+/// Requests signed further than this from now are rejected to limit replays.
+pub const MAX_CLOCK_SKEW: Duration = Duration::from_secs(12 * 60 * 60);
+
+#[derive(Debug)]
+pub enum HttpSignatureError<E = Infallible> {
+    MissingHeader(String),
+    MalformedSignature(String),
+    UnsupportedAlgorithm(String),
+    DigestMismatch,
+    Expired,
+    InvalidHeaderValue(http::header::InvalidHeaderValue),
+    Dereference(E),
+    MalformedKey(String),
+    KeyMismatch,
+    Rsa(rsa::Error),
+}
+
+impl<E: Display> Display for HttpSignatureError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingHeader(name) => f.write_fmt(format_args!("missing header {name}")),
+            Self::MalformedSignature(msg) => {
+                f.write_fmt(format_args!("malformed signature: {msg}"))
+            }
+            Self::UnsupportedAlgorithm(alg) => {
+                f.write_fmt(format_args!("unsupported algorithm {alg}"))
+            }
+            Self::DigestMismatch => f.write_str("digest of the body does not match"),
+            Self::Expired => f.write_str("signature is expired or not yet valid"),
+            Self::InvalidHeaderValue(e) => f.write_fmt(format_args!("invalid header value: {e}")),
+            Self::Dereference(e) => f.write_fmt(format_args!("fetch key: {e}")),
+            Self::MalformedKey(msg) => f.write_fmt(format_args!("malformed key: {msg}")),
+            Self::KeyMismatch => f.write_str("key id does not match the fetched key"),
+            Self::Rsa(e) => f.write_fmt(format_args!("rsa: {e}")),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for HttpSignatureError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidHeaderValue(e) => Some(e),
+            Self::Dereference(e) => Some(e),
+            Self::Rsa(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+fn sha256_base64(data: &[u8]) -> String {
+    STANDARD.encode(Sha256::digest(data))
+}
+
+fn header_value<B, E>(request: &Request<B>, name: &str) -> Result<String, HttpSignatureError<E>> {
+    let values = request
+        .headers()
+        .get_all(name)
+        .iter()
+        .map(|value| {
+            value
+                .to_str()
+                .map(str::trim)
+                .map_err(|_| HttpSignatureError::MalformedSignature(format!("{name} is not ASCII")))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if values.is_empty() {
+        return Err(HttpSignatureError::MissingHeader(name.to_owned()));
+    }
+    Ok(values.join(", "))
+}
+
+/// Value of the `Digest` header for `body`.
+pub fn digest_header(body: &[u8]) -> String {
+    format!("SHA-256={}", sha256_base64(body))
+}
+
+/// Serialized body of an outgoing request, paired with its `Digest` header value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigestedBody {
+    pub body: Vec<u8>,
+    pub digest: String,
+}
+
+impl DigestedBody {
+    /// Serialize `document` once, so that the digest is always of the bytes actually sent.
+    pub fn new<T: Serialize>(document: &WithContext<T>) -> Result<Self, serde_json::Error> {
+        let body = serde_json::to_vec(document)?;
+        let digest = digest_header(&body);
+        Ok(Self { body, digest })
+    }
+}
+
+fn request_target<B>(request: &Request<B>) -> &str {
+    request
+        .uri()
+        .path_and_query()
+        .map(|path| path.as_str())
+        .unwrap_or("/")
+}
+
+/// Signing string of draft-cavage-http-signatures, with `(created)` and `(expires)` taken from
+/// `params` of the signature.
+fn cavage_signing_string<B, E>(
+    request: &Request<B>,
+    headers: &[&str],
+    params: &[(&str, &str)],
+) -> Result<String, HttpSignatureError<E>> {
+    headers
+        .iter()
+        .map(|name| match *name {
+            "(request-target)" => Ok(format!(
+                "(request-target): {} {}",
+                request.method().as_str().to_lowercase(),
+                request_target(request)
+            )),
+            "(created)" | "(expires)" => {
+                let param = name.trim_matches(|c| c == '(' || c == ')');
+                params
+                    .iter()
+                    .find(|(key, _)| *key == param)
+                    .map(|(_, value)| format!("{name}: {value}"))
+                    .ok_or_else(|| {
+                        HttpSignatureError::MalformedSignature(format!("{param} is missing"))
+                    })
+            }
+            name => Ok(format!("{name}: {}", header_value(request, name)?)),
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(|lines| lines.join("\n"))
+}
+
+/// Sign `request` with draft-cavage-http-signatures (`rsa-sha256`), the scheme every major
+/// ActivityPub implementation accepts.
+///
+/// `Host` and `Date` are filled if absent, and `Digest` of the body is always set.
+pub fn sign_request<B: AsRef<[u8]>>(
+    request: &mut Request<B>,
+    key_id: &url::Url,
+    key: &RsaPrivateKey,
+) -> Result<(), HttpSignatureError> {
+    let digest = digest_header(request.body().as_ref());
+    request.headers_mut().insert(
+        "digest",
+        HeaderValue::try_from(digest).map_err(HttpSignatureError::InvalidHeaderValue)?,
+    );
+    sign(request, SIGNED_HEADERS, key_id, key)
+}
+
+/// Sign a bodiless `request`, such as the `GET` of an authorized fetch, like [sign_request]
+/// but without `Digest`.
+pub fn sign_fetch<B>(
+    request: &mut Request<B>,
+    key_id: &url::Url,
+    key: &RsaPrivateKey,
+) -> Result<(), HttpSignatureError> {
+    sign(request, FETCH_SIGNED_HEADERS, key_id, key)
+}
+
+fn sign<B>(
+    request: &mut Request<B>,
+    signed_headers: &[&str],
+    key_id: &url::Url,
+    key: &RsaPrivateKey,
+) -> Result<(), HttpSignatureError> {
+    let host = request
+        .uri()
+        .authority()
+        .map(|authority| authority.to_string());
+    let headers = request.headers_mut();
+    if let (false, Some(host)) = (headers.contains_key(http::header::HOST), host) {
+        headers.insert(
+            http::header::HOST,
+            HeaderValue::try_from(host).map_err(HttpSignatureError::InvalidHeaderValue)?,
+        );
+    }
+    if !headers.contains_key(http::header::DATE) {
+        headers.insert(
+            http::header::DATE,
+            HeaderValue::try_from(httpdate::fmt_http_date(SystemTime::now()))
+                .map_err(HttpSignatureError::InvalidHeaderValue)?,
+        );
+    }
+    let signing_string = cavage_signing_string(request, signed_headers, &[])?;
+    let signature = key
+        .sign(
+            pkcs1v15_sha256(),
+            &Sha256::digest(signing_string.as_bytes()),
+        )
+        .map_err(HttpSignatureError::Rsa)?;
+    let signature = format!(
+        r#"keyId="{key_id}",algorithm="rsa-sha256",headers="{}",signature="{}""#,
+        signed_headers.join(" "),
+        STANDARD.encode(signature)
+    );
+    request.headers_mut().insert(
+        "signature",
+        HeaderValue::try_from(signature).map_err(HttpSignatureError::InvalidHeaderValue)?,
+    );
+    Ok(())
+}
+
+/// Parsed signature, common to both schemes.
+struct Signature {
+    key_id: url::Url,
+    signature: Vec<u8>,
+    signing_string: String,
+    created: SystemTime,
+    expires: Option<SystemTime>,
+    covers_digest: bool,
+}
+
+/// Split `key="value",key=value` parameters, keeping commas inside quotes.
+fn split_params(src: &str, separator: char) -> Vec<(&str, &str)> {
+    let mut params = Vec::new();
+    let mut rest = src;
+    while !rest.trim().is_empty() {
+        let Some((key, value)) = rest.split_once('=') else {
+            break;
+        };
+        let value = value.trim_start();
+        let (value, next) = match value.strip_prefix('"') {
+            Some(quoted) => match quoted.split_once('"') {
+                Some((value, next)) => (value, next),
+                None => (quoted, ""),
+            },
+            None => value.split_once(separator).unwrap_or((value, "")),
+        };
+        params.push((key.trim(), value));
+        rest = next.trim_start().trim_start_matches(separator);
+    }
+    params
+}
+
+fn unix_time<E>(value: &str) -> Result<SystemTime, HttpSignatureError<E>> {
+    value
+        .parse()
+        .map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
+        .map_err(|_| HttpSignatureError::MalformedSignature(format!("invalid timestamp {value}")))
+}
+
+fn parse_cavage<B, E>(request: &Request<B>) -> Result<Signature, HttpSignatureError<E>> {
+    let header = match header_value(request, "signature") {
+        Ok(header) => header,
+        Err(e) => header_value(request, "authorization")?
+            .strip_prefix("Signature ")
+            .map(ToOwned::to_owned)
+            .ok_or(e)?,
+    };
+    let params = split_params(&header, ',');
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| *value)
+    };
+    match param("algorithm") {
+        None | Some("rsa-sha256") | Some("hs2019") => (),
+        Some(algorithm) => return Err(HttpSignatureError::UnsupportedAlgorithm(algorithm.into())),
+    }
+    let key_id = param("keyId")
+        .ok_or_else(|| HttpSignatureError::MalformedSignature("keyId is missing".into()))?
+        .parse()
+        .map_err(|e| HttpSignatureError::MalformedSignature(format!("keyId: {e}")))?;
+    let signature = param("signature")
+        .ok_or_else(|| HttpSignatureError::MalformedSignature("signature is missing".into()))
+        .and_then(|signature| {
+            STANDARD
+                .decode(signature)
+                .map_err(|e| HttpSignatureError::MalformedSignature(e.to_string()))
+        })?;
+    let headers = param("headers")
+        .unwrap_or("date")
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>();
+    let headers = headers.iter().map(String::as_str).collect::<Vec<_>>();
+    // the time of the signature is trusted only if it is signed
+    let created = if headers.contains(&"date") {
+        let date = header_value(request, "date")?;
+        httpdate::parse_http_date(&date)
+            .map_err(|e| HttpSignatureError::MalformedSignature(format!("date {date}: {e}")))?
+    } else if headers.contains(&"(created)") {
+        param("created")
+            .ok_or_else(|| HttpSignatureError::MalformedSignature("created is missing".into()))
+            .and_then(unix_time)?
+    } else {
+        return Err(HttpSignatureError::MalformedSignature(
+            "neither date nor (created) is signed".into(),
+        ));
+    };
+    let expires = if headers.contains(&"(expires)") {
+        param("expires").map(unix_time).transpose()?
+    } else {
+        None
+    };
+    Ok(Signature {
+        key_id,
+        signature,
+        signing_string: cavage_signing_string(request, &headers, &params)?,
+        created,
+        expires,
+        covers_digest: headers.contains(&"digest"),
+    })
+}
+
+/// Value of a derived component or a header for RFC 9421 signature base.
+fn component_value<B, E>(
+    request: &Request<B>,
+    name: &str,
+) -> Result<String, HttpSignatureError<E>> {
+    let uri = request.uri();
+    let authority = || -> Result<String, HttpSignatureError<E>> {
+        match uri.authority() {
+            Some(authority) => Ok(authority.as_str().to_lowercase()),
+            None => header_value(request, "host").map(|host| host.to_lowercase()),
+        }
+    };
+    Ok(match name {
+        "@method" => request.method().as_str().to_owned(),
+        "@scheme" => uri.scheme_str().unwrap_or("https").to_lowercase(),
+        "@authority" => authority()?,
+        "@target-uri" => format!(
+            "{}://{}{}",
+            uri.scheme_str().unwrap_or("https"),
+            authority()?,
+            request_target(request)
+        ),
+        "@request-target" => request_target(request).to_owned(),
+        "@path" => uri.path().to_owned(),
+        "@query" => format!("?{}", uri.query().unwrap_or_default()),
+        name if name.starts_with('@') => {
+            return Err(HttpSignatureError::MalformedSignature(format!(
+                "unsupported component {name}"
+            )))
+        }
+        name => header_value(request, name)?,
+    })
+}
+
+fn parse_rfc9421<B, E>(request: &Request<B>) -> Result<Signature, HttpSignatureError<E>> {
+    let input = header_value(request, "signature-input")?;
+    // only the first signature is verified
+    let member = input.split(", ").next().unwrap_or_default();
+    let (label, params) = member
+        .split_once('=')
+        .ok_or_else(|| HttpSignatureError::MalformedSignature(input.clone()))?;
+    let (components, metadata) = params
+        .strip_prefix('(')
+        .and_then(|params| params.split_once(')'))
+        .ok_or_else(|| HttpSignatureError::MalformedSignature(params.to_owned()))?;
+    let components = components
+        .split_whitespace()
+        .map(|component| component.trim_matches('"').to_lowercase())
+        .collect::<Vec<_>>();
+    let metadata = split_params(metadata.trim_start_matches(';'), ';');
+    let param = |name: &str| {
+        metadata
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| *value)
+    };
+    match param("alg") {
+        None | Some("rsa-v1_5-sha256") => (),
+        Some(alg) => return Err(HttpSignatureError::UnsupportedAlgorithm(alg.into())),
+    }
+    let key_id = param("keyid")
+        .ok_or_else(|| HttpSignatureError::MalformedSignature("keyid is missing".into()))?
+        .parse()
+        .map_err(|e| HttpSignatureError::MalformedSignature(format!("keyid: {e}")))?;
+    let signature = header_value(request, "signature")?
+        .split(", ")
+        .find_map(|member| {
+            member
+                .strip_prefix(label)?
+                .strip_prefix("=:")?
+                .strip_suffix(':')
+                .map(ToOwned::to_owned)
+        })
+        .ok_or_else(|| HttpSignatureError::MalformedSignature(format!("no signature {label}")))?;
+    let signature = STANDARD
+        .decode(signature)
+        .map_err(|e| HttpSignatureError::MalformedSignature(e.to_string()))?;
+    let mut signing_string = String::new();
+    for component in &components {
+        signing_string.push_str(&format!(
+            "\"{component}\": {}\n",
+            component_value(request, component)?
+        ));
+    }
+    signing_string.push_str(&format!("\"@signature-params\": {params}"));
+    Ok(Signature {
+        key_id,
+        signature,
+        signing_string,
+        created: param("created")
+            .ok_or_else(|| HttpSignatureError::MalformedSignature("created is missing".into()))
+            .and_then(unix_time)?,
+        expires: param("expires").map(unix_time).transpose()?,
+        covers_digest: components.iter().any(|c| c == "content-digest"),
+    })
+}
+
+fn verify_digest<B: AsRef<[u8]>, E>(
+    request: &Request<B>,
+    signature: &Signature,
+) -> Result<(), HttpSignatureError<E>> {
+    let body = request.body().as_ref();
+    if body.is_empty() {
+        return Ok(());
+    }
+    if !signature.covers_digest {
+        return Err(HttpSignatureError::DigestMismatch);
+    }
+    let expected = sha256_base64(body);
+    let matched = match header_value::<B, E>(request, "content-digest") {
+        Ok(digest) => digest.split(',').any(|digest| {
+            digest.trim().strip_prefix("sha-256=:").and_then(|d| d.strip_suffix(':'))
+                == Some(expected.as_str())
+        }),
+        Err(_) => header_value(request, "digest")?.split(',').any(|digest| {
+            matches!(digest.trim().split_once('='), Some((alg, d)) if alg.eq_ignore_ascii_case("sha-256") && d == expected)
+        }),
+    };
+    if matched {
+        Ok(())
+    } else {
+        Err(HttpSignatureError::DigestMismatch)
+    }
+}
+
+fn verify_time<E>(signature: &Signature) -> Result<(), HttpSignatureError<E>> {
+    let now = SystemTime::now();
+    let skew = now
+        .duration_since(signature.created)
+        .unwrap_or_else(|e| e.duration());
+    if skew > MAX_CLOCK_SKEW {
+        return Err(HttpSignatureError::Expired);
+    }
+    if matches!(signature.expires, Some(expires) if expires < now) {
+        return Err(HttpSignatureError::Expired);
+    }
+    Ok(())
+}
+
+/// Fetch the key of `key_id`, which is either the key itself or its owner actor.
+///
+/// The key must tell its id and it must be `key_id`, so that a key cannot be passed off as
+/// another.
+async fn fetch_key<D: Dereferencer>(
+    dereferencer: &D,
+    key_id: &url::Url,
+) -> Result<PublicKey, HttpSignatureError<D::Error>> {
+    let document = dereferencer
+        .dereference(key_id)
+        .await
+        .map_err(HttpSignatureError::Dereference)?;
+    let keys = match document.get("publicKeyPem") {
+        Some(_) => document,
+        None => document
+            .get("publicKey")
+            .cloned()
+            .ok_or_else(|| HttpSignatureError::MalformedKey("publicKey is missing".into()))?,
+    };
+    let keys: Property<Remotable<PublicKey>> = serde_json::from_value(keys)
+        .map_err(|e| HttpSignatureError::MalformedKey(e.to_string()))?;
+    for key in keys.0 {
+        let key = match key {
+            Remotable::Inline(key) => key,
+            Remotable::Remote(id) if &id == key_id => {
+                let document = dereferencer
+                    .dereference(&id)
+                    .await
+                    .map_err(HttpSignatureError::Dereference)?;
+                serde_json::from_value(document)
+                    .map_err(|e| HttpSignatureError::MalformedKey(e.to_string()))?
+            }
+            Remotable::Remote(_) => continue,
+        };
+        if key.id.as_ref().is_some_and(|id| id == key_id) {
+            return Ok(key);
+        }
+    }
+    Err(HttpSignatureError::KeyMismatch)
+}
+
+/// Verify the signature of an inbound request, in either RFC 9421 (`Signature-Input`) or
+/// draft-cavage-http-signatures form, with the key fetched through `dereferencer`.
+///
+/// The signature must cover `Date` or its creation time, and is rejected when that is further
+/// than [MAX_CLOCK_SKEW] from now.
+///
+/// The key is returned so that callers can check [PublicKey::owner] against the actor of the activity.
+pub async fn verify_request<B: AsRef<[u8]>, D: Dereferencer>(
+    request: &Request<B>,
+    dereferencer: &D,
+) -> Result<PublicKey, HttpSignatureError<D::Error>> {
+    let signature = if request.headers().contains_key("signature-input") {
+        parse_rfc9421(request)?
+    } else {
+        parse_cavage(request)?
+    };
+    verify_time(&signature)?;
+    verify_digest(request, &signature)?;
+    let key = fetch_key(dereferencer, &signature.key_id).await?;
+    let pem = key
+        .public_key_pem
+        .as_deref()
+        .ok_or_else(|| HttpSignatureError::MalformedKey("publicKeyPem is missing".into()))?;
+    let public_key = RsaPublicKey::from_public_key_pem(pem)
+        .or_else(|_| RsaPublicKey::from_pkcs1_pem(pem))
+        .map_err(|e| HttpSignatureError::MalformedKey(e.to_string()))?;
+    public_key
+        .verify(
+            pkcs1v15_sha256(),
+            &Sha256::digest(signature.signing_string.as_bytes()),
+            &signature.signature,
+        )
+        .map_err(HttpSignatureError::Rsa)?;
+    Ok(key)
+}
@@ -0,0 +1,160 @@
+use std::collections::{HashMap, HashSet};
+
+use activity_vocabulary_core::{
+    is_public_iri, xsd, Dereferencer, Or, Property, Remotable, ResolveError,
+};
+use serde::Deserialize;
+
+use crate::ObjectSubtypes;
+
+/// Inlining of the objects referred by id, the inverse of [crate::normalize::Normalize],
+/// implemented by the generated types.
+pub trait Hydrate {
+    /// Inline the objects `lookup` finds by id, down to `depth` levels of nesting.
+    ///
+    /// The objects not found, or not of the type of the property, are kept referred by id.
+    /// [activity_vocabulary_core::PUBLIC] is never looked up, as it is never fetched.
+    fn hydrate_with(
+        &mut self,
+        depth: usize,
+        lookup: &mut dyn FnMut(&url::Url) -> Option<ObjectSubtypes>,
+    );
+}
+
+/// `object` with the objects `lookup` finds inlined, down to `depth` levels of nesting.
+pub fn hydrate<T: Hydrate>(
+    mut object: T,
+    depth: usize,
+    mut lookup: impl FnMut(&url::Url) -> Option<ObjectSubtypes>,
+) -> T {
+    object.hydrate_with(depth, &mut lookup);
+    object
+}
+
+/// [hydrate] with the objects fetched through `dereferencer`, each of them once.
+///
+/// The objects which are gone, as told by [Dereferencer::is_gone], are kept referred by id.
+pub async fn hydrate_with_dereferencer<T: Hydrate + Clone, D: Dereferencer>(
+    object: T,
+    depth: usize,
+    dereferencer: &D,
+) -> Result<T, ResolveError<D::Error>> {
+    let mut fetched = HashMap::new();
+    let mut gone = HashSet::new();
+    loop {
+        // the objects just fetched may refer to others in turn
+        let mut missing = Vec::new();
+        let hydrated = hydrate(object.clone(), depth, |id| {
+            let found = fetched.get(id).cloned();
+            if found.is_none() && !gone.contains(id) {
+                missing.push(id.clone());
+            }
+            found
+        });
+        if missing.is_empty() {
+            return Ok(hydrated);
+        }
+        for id in missing {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(%id, depth, "hydrate");
+            let document = match dereferencer.dereference(&id).await {
+                Ok(document) => document,
+                Err(e) if D::is_gone(&e) => {
+                    gone.insert(id);
+                    continue;
+                }
+                Err(e) => return Err(ResolveError::Dereference(e)),
+            };
+            let object = ObjectSubtypes::deserialize(document).map_err(ResolveError::Malformed)?;
+            fetched.insert(id, object);
+        }
+    }
+}
+
+macro_rules! leaf {
+    ($($ty:ty),*) => {
+        $(
+            impl Hydrate for $ty {
+                fn hydrate_with(
+                    &mut self,
+                    _: usize,
+                    _: &mut dyn FnMut(&url::Url) -> Option<ObjectSubtypes>,
+                ) {
+                }
+            }
+        )*
+    };
+}
+
+leaf!(url::Url, bool, xsd::DateTime);
+
+impl<T: Hydrate> Hydrate for Box<T> {
+    fn hydrate_with(
+        &mut self,
+        depth: usize,
+        lookup: &mut dyn FnMut(&url::Url) -> Option<ObjectSubtypes>,
+    ) {
+        self.as_mut().hydrate_with(depth, lookup)
+    }
+}
+
+impl<T: Hydrate> Hydrate for Option<T> {
+    fn hydrate_with(
+        &mut self,
+        depth: usize,
+        lookup: &mut dyn FnMut(&url::Url) -> Option<ObjectSubtypes>,
+    ) {
+        if let Some(inner) = self {
+            inner.hydrate_with(depth, lookup)
+        }
+    }
+}
+
+impl<T: Hydrate> Hydrate for Property<T> {
+    fn hydrate_with(
+        &mut self,
+        depth: usize,
+        lookup: &mut dyn FnMut(&url::Url) -> Option<ObjectSubtypes>,
+    ) {
+        self.0
+            .iter_mut()
+            .for_each(|value| value.hydrate_with(depth, lookup))
+    }
+}
+
+impl<T: Hydrate, U: Hydrate> Hydrate for Or<T, U> {
+    fn hydrate_with(
+        &mut self,
+        depth: usize,
+        lookup: &mut dyn FnMut(&url::Url) -> Option<ObjectSubtypes>,
+    ) {
+        match self {
+            Or::Prim(value) => value.hydrate_with(depth, lookup),
+            Or::Snd(value) => value.hydrate_with(depth, lookup),
+        }
+    }
+}
+
+impl<T: Hydrate + TryFrom<ObjectSubtypes>> Hydrate for Remotable<T> {
+    fn hydrate_with(
+        &mut self,
+        depth: usize,
+        lookup: &mut dyn FnMut(&url::Url) -> Option<ObjectSubtypes>,
+    ) {
+        if depth == 0 {
+            return;
+        }
+        if let Remotable::Remote(id) = self {
+            if is_public_iri(id.as_str()) {
+                return;
+            }
+            match lookup(id).map(T::try_from) {
+                Some(Ok(object)) => *self = Remotable::Inline(object),
+                _ => return,
+            }
+        }
+        if let Remotable::Inline(object) = self {
+            object.hydrate_with(depth - 1, lookup)
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use std::{fmt::Display, future::Future};
+
+use activity_vocabulary_core::{
+    Dereferencer, ObjectId, ObjectUri, Property, Remotable, WithContext,
+};
+use http::Request;
+
+use crate::{
+    audience::recipient_id,
+    http_signature::{verify_request, HttpSignatureError},
+    *,
+};
+
+/// Handler of the activities posted to an inbox, called by [dispatch].
+///
+/// Every method does nothing by default, so that a server implements only the activities it supports.
+/// Activities without a dedicated method, including those of the extension vocabularies, go to
+/// [InboxHandler::on_other].
+pub trait InboxHandler: Sync {
+    type Error;
+
+    fn on_accept(&self, _activity: Accept) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
+    }
+
+    fn on_add(&self, _activity: Add) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
+    }
+
+    fn on_announce(
+        &self,
+        _activity: Announce,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
+    }
+
+    fn on_block(&self, _activity: Block) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
+    }
+
+    fn on_create(&self, _activity: Create) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
+    }
+
+    fn on_delete(&self, _activity: Delete) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
+    }
+
+    fn on_flag(&self, _activity: Flag) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
+    }
+
+    fn on_follow(&self, _activity: Follow) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
+    }
+
+    fn on_like(&self, _activity: Like) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
+    }
+
+    fn on_move(&self, _activity: Move) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
+    }
+
+    fn on_reject(&self, _activity: Reject) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
+    }
+
+    fn on_remove(&self, _activity: Remove) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
+    }
+
+    fn on_undo(&self, _activity: Undo) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
+    }
+
+    fn on_update(&self, _activity: Update) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
+    }
+
+    fn on_other(
+        &self,
+        _activity: ActivitySubtypes,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
+    }
+}
+
+#[derive(Debug)]
+pub enum InboxError<D, H> {
+    Signature(HttpSignatureError<D>),
+    MalformedActivity(serde_json::Error),
+    Envelope(EnvelopeError),
+    /// The owner of the key could not be fetched.
+    Dereference(D),
+    Handler(H),
+}
+
+impl<D: Display, H: Display> Display for InboxError<D, H> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Signature(e) => f.write_fmt(format_args!("signature: {e}")),
+            Self::MalformedActivity(e) => f.write_fmt(format_args!("malformed activity: {e}")),
+            Self::Envelope(e) => e.fmt(f),
+            Self::Dereference(e) => f.write_fmt(format_args!("fetch key owner: {e}")),
+            Self::Handler(e) => f.write_fmt(format_args!("handler: {e}")),
+        }
+    }
+}
+
+impl<D: std::error::Error + 'static, H: std::error::Error + 'static> std::error::Error
+    for InboxError<D, H>
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Signature(e) => Some(e),
+            Self::MalformedActivity(e) => Some(e),
+            Self::Envelope(e) => Some(e),
+            Self::Dereference(e) => Some(e),
+            Self::Handler(e) => Some(e),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvelopeError {
+    /// The activity is not of the actor which signed the request.
+    ActorMismatch,
+    /// The key, its owner and the id of the activity are not on one origin.
+    OriginMismatch,
+    /// The activity has no id.
+    MissingId,
+    /// The owner of the key does not list it in its `publicKey`.
+    KeyNotOfOwner,
+}
+
+impl Display for EnvelopeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ActorMismatch => f.write_str("activity is not of the signer"),
+            Self::OriginMismatch => {
+                f.write_str("key, its owner and activity id are not on one origin")
+            }
+            Self::MissingId => f.write_str("activity has no id"),
+            Self::KeyNotOfOwner => f.write_str("key is not listed by its owner"),
+        }
+    }
+}
+
+impl std::error::Error for EnvelopeError {}
+
+/// The actor owning `key`.
+fn owner_of(key: &PublicKey) -> Option<url::Url> {
+    match (&key.owner, &key.id) {
+        (Some(owner), _) => Some(owner.clone()),
+        // without an owner, the key must be a fragment of the actor, e.g. `{actor}#main-key`
+        (None, Some(id)) => {
+            let mut id = id.clone().into_url();
+            id.set_fragment(None);
+            Some(id)
+        }
+        (None, None) => None,
+    }
+}
+
+/// Check that `activity` is of the owner of `key`, and that the key, its owner and the id of the
+/// activity are on one origin.
+pub fn verify_envelope(activity: &Activity, key: &PublicKey) -> Result<(), EnvelopeError> {
+    let (Some(signer), Some(key_id)) = (owner_of(key), &key.id) else {
+        return Err(EnvelopeError::ActorMismatch);
+    };
+    if !activity
+        .actor
+        .0
+        .iter()
+        .filter_map(recipient_id)
+        .any(|actor| actor == &signer)
+    {
+        return Err(EnvelopeError::ActorMismatch);
+    }
+    let id = activity.id.as_ref().ok_or(EnvelopeError::MissingId)?;
+    if key_id.origin() != signer.origin() || id.origin() != signer.origin() {
+        return Err(EnvelopeError::OriginMismatch);
+    }
+    Ok(())
+}
+
+/// Whether the actor `owner` lists the key of `key_id` in its `publicKey`.
+async fn lists_key<D: Dereferencer>(
+    dereferencer: &D,
+    owner: &url::Url,
+    key_id: &ObjectUri,
+) -> Result<bool, D::Error> {
+    let document = dereferencer.dereference(owner).await?;
+    let keys = document
+        .get("publicKey")
+        .cloned()
+        .and_then(|keys| serde_json::from_value::<Property<Remotable<PublicKey>>>(keys).ok())
+        .unwrap_or_default();
+    Ok(keys
+        .0
+        .iter()
+        .filter_map(ObjectId::object_id)
+        .any(|id| key_id == id))
+}
+
+/// Verify the signature of a request posted to an inbox and the envelope of the activity in it
+/// (see [verify_envelope]), then call the method of `handler` for the type of the activity.
+///
+/// The owner of the key is fetched through `dereferencer`, and must list the key as its own.
+pub async fn dispatch<B: AsRef<[u8]>, D: Dereferencer, H: InboxHandler>(
+    request: &Request<B>,
+    dereferencer: &D,
+    handler: &H,
+) -> Result<(), InboxError<D::Error, H::Error>> {
+    let key = verify_request(request, dereferencer)
+        .await
+        .map_err(InboxError::Signature)?;
+    let activity: WithContext<ActivitySubtypes> =
+        serde_json::from_slice(request.body().as_ref()).map_err(InboxError::MalformedActivity)?;
+    let activity = activity.body;
+    verify_envelope(&activity.clone().into(), &key).map_err(InboxError::Envelope)?;
+    if let (Some(owner), Some(key_id)) = (owner_of(&key), &key.id) {
+        if !lists_key(dereferencer, &owner, key_id)
+            .await
+            .map_err(InboxError::Dereference)?
+        {
+            return Err(InboxError::Envelope(EnvelopeError::KeyNotOfOwner));
+        }
+    }
+    match activity {
+        ActivitySubtypes::Accept(activity) => handler.on_accept(activity).await,
+        ActivitySubtypes::Add(activity) => handler.on_add(activity).await,
+        ActivitySubtypes::Announce(activity) => handler.on_announce(activity).await,
+        ActivitySubtypes::Block(activity) => handler.on_block(activity).await,
+        ActivitySubtypes::Create(activity) => handler.on_create(activity).await,
+        ActivitySubtypes::Delete(activity) => handler.on_delete(activity).await,
+        ActivitySubtypes::Flag(activity) => handler.on_flag(activity).await,
+        ActivitySubtypes::Follow(activity) => handler.on_follow(activity).await,
+        ActivitySubtypes::Like(activity) => handler.on_like(activity).await,
+        ActivitySubtypes::Move(activity) => handler.on_move(activity).await,
+        ActivitySubtypes::Reject(activity) => handler.on_reject(activity).await,
+        ActivitySubtypes::Remove(activity) => handler.on_remove(activity).await,
+        ActivitySubtypes::Undo(activity) => handler.on_undo(activity).await,
+        ActivitySubtypes::Update(activity) => handler.on_update(activity).await,
+        activity => handler.on_other(activity).await,
+    }
+    .map_err(InboxError::Handler)
+}
@@ -0,0 +1,136 @@
+use std::fmt::Display;
+
+use activity_vocabulary_core::{
+    rdf::{canonicalize_value, sha256_hex, CanonicalizationError},
+    xsd, Property,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use rsa::{RsaPrivateKey, RsaPublicKey};
+use sha2::{Digest, Sha256};
+
+use crate::{crypto::pkcs1v15_sha256, LdSignature};
+
+/// The signature suite Mastodon uses for [LdSignature].
+pub const RSA_SIGNATURE_2017: &str = "RsaSignature2017";
+
+/// Signature options are canonicalized with this context instead of the one of the document.
+const IDENTITY_CONTEXT: &str = "https://w3id.org/identity/v1";
+
+#[derive(Debug)]
+pub enum LdSignatureError {
+    Serialize(serde_json::Error),
+    Canonicalize(CanonicalizationError),
+    UnsupportedType(String),
+    MissingSignatureValue,
+    Base64(base64::DecodeError),
+    Rsa(rsa::Error),
+}
+
+impl Display for LdSignatureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Serialize(e) => f.write_fmt(format_args!("serialize document: {e}")),
+            Self::Canonicalize(e) => f.write_fmt(format_args!("canonicalize: {e}")),
+            Self::UnsupportedType(ty) => {
+                f.write_fmt(format_args!("unsupported signature type {ty}"))
+            }
+            Self::MissingSignatureValue => f.write_str("signatureValue is missing"),
+            Self::Base64(e) => f.write_fmt(format_args!("decode signatureValue: {e}")),
+            Self::Rsa(e) => f.write_fmt(format_args!("rsa: {e}")),
+        }
+    }
+}
+
+impl std::error::Error for LdSignatureError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Serialize(e) => Some(e),
+            Self::Canonicalize(e) => Some(e),
+            Self::Base64(e) => Some(e),
+            Self::Rsa(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<CanonicalizationError> for LdSignatureError {
+    fn from(e: CanonicalizationError) -> Self {
+        Self::Canonicalize(e)
+    }
+}
+
+/// SHA-256 of the concatenated hashes of the canonicalized options and document.
+fn signing_digest(
+    document: &serde_json::Value,
+    signature: &LdSignature,
+) -> Result<Vec<u8>, LdSignatureError> {
+    let mut options = serde_json::to_value(signature).map_err(LdSignatureError::Serialize)?;
+    if let serde_json::Value::Object(options) = &mut options {
+        for key in ["type", "id", "signatureValue"] {
+            options.remove(key);
+        }
+        options.insert("@context".to_owned(), IDENTITY_CONTEXT.into());
+    }
+    let mut document = document.clone();
+    if let serde_json::Value::Object(document) = &mut document {
+        document.remove("signature");
+    }
+    let to_sign =
+        sha256_hex(&canonicalize_value(&options)?) + &sha256_hex(&canonicalize_value(&document)?);
+    Ok(Sha256::digest(to_sign.as_bytes()).to_vec())
+}
+
+/// Make a `RsaSignature2017` signature of `document`, which must carry its own `@context`
+/// (e.g. serialized from [activity_vocabulary_core::WithContext]).
+///
+/// Any `signature` already present in `document` is not covered by the new signature.
+pub fn sign(
+    document: &serde_json::Value,
+    creator: url::Url,
+    created: xsd::DateTime,
+    key: &RsaPrivateKey,
+) -> Result<LdSignature, LdSignatureError> {
+    let mut signature = LdSignature {
+        signature_type: Property(vec![RSA_SIGNATURE_2017.to_owned()]),
+        creator: Some(creator),
+        created: Some(created),
+        nonce: None,
+        signature_value: None,
+        keywords: Default::default(),
+    };
+    let digest = signing_digest(document, &signature)?;
+    let signed = key
+        .sign(pkcs1v15_sha256(), &digest)
+        .map_err(LdSignatureError::Rsa)?;
+    signature.signature_value = Some(STANDARD.encode(signed));
+    Ok(signature)
+}
+
+/// Verify `signature` of `document` with the public key of [LdSignature::creator].
+///
+/// `document` is the JSON as received, since deserializing and serializing it again would drop
+/// the properties outside of the vocabulary and break the signature.
+pub fn verify(
+    document: &serde_json::Value,
+    signature: &LdSignature,
+    key: &RsaPublicKey,
+) -> Result<(), LdSignatureError> {
+    if let Some(ty) = signature
+        .signature_type
+        .0
+        .iter()
+        .find(|ty| *ty != RSA_SIGNATURE_2017)
+    {
+        return Err(LdSignatureError::UnsupportedType(ty.clone()));
+    }
+    let signature_value = signature
+        .signature_value
+        .as_ref()
+        .ok_or(LdSignatureError::MissingSignatureValue)?;
+    let signature_value = STANDARD
+        .decode(signature_value)
+        .map_err(LdSignatureError::Base64)?;
+    let digest = signing_digest(document, signature)?;
+    key.verify(pkcs1v15_sha256(), &digest, &signature_value)
+        .map_err(LdSignatureError::Rsa)
+}
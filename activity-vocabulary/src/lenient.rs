@@ -0,0 +1,155 @@
+use activity_vocabulary_core::{
+    is_public_iri,
+    metrics::{self, ParseEvent},
+    xsd, PUBLIC,
+};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::property_names;
+
+/// Timestamps from this are taken as in milliseconds, as in seconds it is after the year 5000.
+const MILLIS_FROM: i64 = 100_000_000_000;
+
+/// A known deviation of a document from the vocabulary, coerced by [coerce].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Quirk {
+    /// A number or a string for a boolean, such as `1` and `"true"`.
+    Boolean { property: String },
+    /// A Unix time in seconds or milliseconds for a time.
+    Timestamp { property: String },
+    /// The compact form `Public` or `as:Public` of the public collection for a url.
+    CompactPublic { property: String },
+    /// An empty string for a url or a reference, removed.
+    EmptyUrl { property: String },
+}
+
+impl From<Quirk> for ParseEvent {
+    fn from(quirk: Quirk) -> Self {
+        let (quirk, property) = match quirk {
+            Quirk::Boolean { property } => ("boolean", property),
+            Quirk::Timestamp { property } => ("timestamp", property),
+            Quirk::CompactPublic { property } => ("compact_public", property),
+            Quirk::EmptyUrl { property } => ("empty_url", property),
+        };
+        ParseEvent::Coerced { quirk, property }
+    }
+}
+
+fn coerce_bool(value: &Value) -> Option<bool> {
+    match value {
+        Value::Number(number) => number.as_f64().map(|number| number != 0.0),
+        Value::String(string) => match string.as_str() {
+            "true" | "1" => Some(true),
+            "false" | "0" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn coerce_timestamp(value: &Value) -> Option<xsd::DateTime> {
+    let timestamp = value.as_i64()?;
+    if timestamp.abs() < MILLIS_FROM {
+        xsd::DateTime::from_timestamp_millis(timestamp.checked_mul(1000)?)
+    } else {
+        xsd::DateTime::from_timestamp_millis(timestamp)
+    }
+}
+
+fn is_empty_string(value: &Value) -> bool {
+    value.as_str().is_some_and(str::is_empty)
+}
+
+fn coerce_properties(object: &mut Map<String, Value>, quirks: &mut Vec<Quirk>) {
+    let keys = object.keys().cloned().collect::<Vec<_>>();
+    for key in keys {
+        let property = key.as_str();
+        let Some(value) = object.get_mut(property) else {
+            continue;
+        };
+        let quirk = if property_names::BOOL.contains(&property) {
+            coerce_bool(value).map(|coerced| {
+                *value = Value::Bool(coerced);
+                Quirk::Boolean {
+                    property: key.clone(),
+                }
+            })
+        } else if property_names::DATE_TIME.contains(&property) {
+            coerce_timestamp(value).map(|coerced| {
+                *value = Value::String(coerced.to_string());
+                Quirk::Timestamp {
+                    property: key.clone(),
+                }
+            })
+        } else if property_names::URL.contains(&property)
+            && value
+                .as_str()
+                .is_some_and(|iri| is_public_iri(iri) && iri != PUBLIC)
+        {
+            *value = Value::String(PUBLIC.to_owned());
+            Some(Quirk::CompactPublic {
+                property: key.clone(),
+            })
+        } else if property_names::URL.contains(&property)
+            || property_names::REFERENCE.contains(&property)
+        {
+            let mut removed = false;
+            if let Value::Array(values) = value {
+                let len = values.len();
+                values.retain(|value| !is_empty_string(value));
+                removed = values.len() < len;
+            }
+            if is_empty_string(value) {
+                object.remove(property);
+                removed = true;
+            }
+            removed.then(|| Quirk::EmptyUrl {
+                property: key.clone(),
+            })
+        } else {
+            None
+        };
+        quirks.extend(quirk);
+    }
+}
+
+/// Coerce the known quirks of `document` and the objects nested in it into the vocabulary,
+/// reporting what are coerced.
+///
+/// The properties are found by their names in [property_names], whichever type the object is of.
+pub fn coerce(document: &mut Value) -> Vec<Quirk> {
+    let mut quirks = Vec::new();
+    let mut stack = vec![document];
+    while let Some(value) = stack.pop() {
+        match value {
+            Value::Object(object) => {
+                coerce_properties(object, &mut quirks);
+                stack.extend(object.values_mut());
+            }
+            Value::Array(values) => stack.extend(values.iter_mut()),
+            _ => (),
+        }
+    }
+    quirks
+}
+
+/// `T` deserialized after the known quirks are coerced by [coerce], each recorded to the
+/// [metrics](activity_vocabulary_core::metrics).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lenient<T>(pub T);
+
+impl<'de, T: serde::de::DeserializeOwned> Deserialize<'de> for Lenient<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut document = Value::deserialize(deserializer)?;
+        for quirk in coerce(&mut document) {
+            metrics::record(|| quirk.into());
+        }
+        serde_json::from_value(document)
+            .map(Self)
+            .map_err(serde::de::Error::custom)
+    }
+}
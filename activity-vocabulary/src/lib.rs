@@ -6,6 +6,122 @@ use serde::{de::Visitor, Deserialize, Serialize};
 
 include!(concat!(env!("OUT_DIR"), "/vocab.rs"));
 
+pub mod actor;
+pub mod attachment;
+pub mod audience;
+#[cfg(feature = "axum")]
+pub mod axum;
+#[cfg(feature = "bridge")]
+pub mod bridge;
+#[cfg(feature = "cache")]
+pub mod cache;
+#[cfg(feature = "client")]
+pub mod client;
+pub mod collection;
+pub mod constructors;
+#[cfg(feature = "mastodon")]
+pub mod content_warning;
+#[cfg(any(feature = "ld-signatures", feature = "http-signatures"))]
+mod crypto;
+#[cfg(feature = "dedup")]
+pub mod dedup;
+#[cfg(feature = "delivery")]
+pub mod delivery;
+#[cfg(feature = "events")]
+pub mod event;
+#[cfg(feature = "activitypub-federation")]
+pub mod federation;
+pub mod follow;
+#[cfg(feature = "follower-sync")]
+pub mod follower_sync;
+#[cfg(feature = "http-signatures")]
+pub mod http_signature;
+pub mod hydrate;
+#[cfg(feature = "inbox")]
+pub mod inbox;
+#[cfg(feature = "ld-signatures")]
+pub mod ld_signature;
+#[cfg(feature = "lenient")]
+pub mod lenient;
+pub mod link;
+#[cfg(feature = "lint")]
+pub mod lint;
+#[cfg(feature = "mastodon")]
+pub mod media;
+#[cfg(feature = "nodeinfo")]
+pub mod nodeinfo;
+pub mod normalize;
+pub mod outbox;
+#[cfg(feature = "patch")]
+pub mod patch;
+pub mod place;
+pub mod poll;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "misskey")]
+pub mod quote;
+#[cfg(feature = "pleroma")]
+pub mod reaction;
+pub mod redact;
+#[cfg(feature = "redis")]
+pub mod redis;
+pub mod report;
+pub mod rewrite;
+#[cfg(feature = "sanitize")]
+pub mod sanitize;
+#[cfg(feature = "sqlx")]
+mod sqlx;
+pub mod tag;
+pub mod thread;
+pub mod tombstone;
+#[cfg(feature = "tower")]
+pub mod tower;
+pub mod undo;
+pub mod walk;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "webfinger")]
+pub mod webfinger;
+
+/// JSON manifest of the generated types, with their URIs, supertypes and properties, for the
+/// tools out of Rust. It is written as `vocab.json` next to the generated code at build.
+pub fn embedded_manifest() -> &'static str {
+    include_str!(concat!(env!("OUT_DIR"), "/vocab.json"))
+}
+
+/// The media type of ActivityStreams documents.
+pub const ACTIVITY_MEDIA_TYPE: &str = "application/activity+json";
+
+/// Whether `media_type` is one of the ActivityStreams media types,
+/// `application/activity+json` or `application/ld+json` with the ActivityStreams profile.
+pub fn is_activitystreams(media_type: &str) -> bool {
+    let media_type = media_type.trim();
+    media_type.starts_with("application/activity+json")
+        || (media_type.starts_with("application/ld+json")
+            && media_type.contains("https://www.w3.org/ns/activitystreams"))
+}
+
+/// Whether an `Accept` header prefers ActivityStreams, that is one of the ActivityStreams media types
+/// is acceptable and weighed no less than `text/html`.
+pub fn accepts_activitystreams(accept: &str) -> bool {
+    let mut activity = 0.0f32;
+    let mut html = 0.0f32;
+    for range in accept.split(',') {
+        let mut params = range.split(';');
+        let essence = params.next().unwrap_or_default().trim();
+        let q = params
+            .filter_map(|param| param.trim().strip_prefix("q="))
+            .find_map(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        if is_activitystreams(range) {
+            activity = activity.max(q);
+        } else if essence.eq_ignore_ascii_case("text/html") {
+            html = html.max(q);
+        }
+    }
+    activity > 0.0 && activity >= html
+}
+
 #[derive(Clone, PartialEq, Eq, Debug, Hash, Default)]
 pub enum Unit {
     Cm,
@@ -18,6 +134,39 @@ pub enum Unit {
     Uri(url::Url),
 }
 
+impl Unit {
+    /// Meters in one unit, or [None] for a [Unit::Uri].
+    fn meters_per_unit(&self) -> Option<f64> {
+        match self {
+            Self::Cm => Some(0.01),
+            Self::Feet => Some(0.3048),
+            Self::Inches => Some(0.0254),
+            Self::Km => Some(1000.0),
+            Self::M => Some(1.0),
+            Self::Miles => Some(1609.344),
+            Self::Uri(_) => None,
+        }
+    }
+
+    /// `value` in this unit converted to meters, or [None] for a [Unit::Uri].
+    pub fn to_meters(&self, value: f64) -> Option<f64> {
+        Some(value * self.meters_per_unit()?)
+    }
+
+    /// `value` in meters converted to this unit, or [None] for a [Unit::Uri].
+    pub fn from_meters(&self, meters: f64) -> Option<f64> {
+        Some(meters / self.meters_per_unit()?)
+    }
+
+    /// `value` converted from `from` to `to`, or [None] to convert from or to a [Unit::Uri].
+    pub fn convert(value: f64, from: &Unit, to: &Unit) -> Option<f64> {
+        if from == to {
+            return Some(value);
+        }
+        to.from_meters(from.to_meters(value)?)
+    }
+}
+
 impl Display for Unit {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -0,0 +1,12 @@
+use activity_vocabulary_core::Href;
+
+use crate::{LinkSubtypes, ObjectSubtypes};
+
+/// [activity_vocabulary_core::LinkOrObject] of the links of this crate, by default of any object.
+pub type LinkOrObject<T = ObjectSubtypes> = activity_vocabulary_core::LinkOrObject<LinkSubtypes, T>;
+
+impl Href for LinkSubtypes {
+    fn href(&self) -> &url::Url {
+        LinkSubtypes::href(self)
+    }
+}
@@ -0,0 +1,115 @@
+use std::fmt::Display;
+
+use activity_vocabulary_core::{
+    metrics::{self, ParseEvent},
+    Or, Remotable, WithContext,
+};
+use serde_json::Value;
+
+use crate::{
+    lenient::{coerce, Quirk},
+    link::LinkOrObject,
+};
+
+/// Properties an object should have though the vocabulary does not require them.
+pub const RECOMMENDED: &[&str] = &["id", "published"];
+
+/// A finding of [lint], by which a document parses but may not be understood as meant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LintWarning {
+    /// The document is not of the vocabulary even with its quirks coerced.
+    Invalid(String),
+    /// A type of the document not of the vocabulary, parsed as the base type.
+    UnknownType { received: String },
+    /// A property `type_name` does not have, dropped on parsing, as told by
+    /// [ParseEvent::UnknownProperty]. Properties which are null or `[]` drop nothing.
+    UnknownProperty {
+        type_name: &'static str,
+        property: String,
+    },
+    /// A value of the wrong shape, parsed only when coerced leniently.
+    Coerced(Quirk),
+    /// A property of [RECOMMENDED] missing from the document.
+    MissingRecommended { property: &'static str },
+}
+
+impl Display for LintWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invalid(e) => f.write_fmt(format_args!("invalid: {e}")),
+            Self::UnknownType { received } => {
+                f.write_fmt(format_args!("unknown type '{received}'"))
+            }
+            Self::UnknownProperty {
+                type_name,
+                property,
+            } => f.write_fmt(format_args!("unknown property '{property}' of {type_name}")),
+            Self::Coerced(Quirk::Boolean { property }) => {
+                f.write_fmt(format_args!("not a boolean for '{property}'"))
+            }
+            Self::Coerced(Quirk::Timestamp { property }) => {
+                f.write_fmt(format_args!("a Unix time for '{property}'"))
+            }
+            Self::Coerced(Quirk::CompactPublic { property }) => {
+                f.write_fmt(format_args!("a compact public collection for '{property}'"))
+            }
+            Self::Coerced(Quirk::EmptyUrl { property }) => {
+                f.write_fmt(format_args!("an empty url for '{property}'"))
+            }
+            Self::MissingRecommended { property } => {
+                f.write_fmt(format_args!("missing recommended '{property}'"))
+            }
+        }
+    }
+}
+
+/// Parse `document` as an object or a link with its known quirks coerced, and find what the parse
+/// does not tell: the values of the wrong shape coerced, the unknown types and properties it
+/// drops, and the [RECOMMENDED] properties missing from an object.
+pub fn lint(document: &Value) -> Vec<LintWarning> {
+    let mut coerced = document.clone();
+    let mut warnings = coerce(&mut coerced)
+        .into_iter()
+        .map(LintWarning::Coerced)
+        .collect::<Vec<_>>();
+    let (parsed, events) =
+        metrics::capture(|| serde_json::from_value::<WithContext<LinkOrObject>>(coerced.clone()));
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            warnings.push(LintWarning::Invalid(e.to_string()));
+            return warnings;
+        }
+    };
+    let type_name = match &parsed.body {
+        Or::Prim(link) => Some(link.type_name()),
+        Or::Snd(Remotable::Inline(object)) => Some(object.type_name()),
+        Or::Snd(Remotable::Remote(_)) => None,
+    };
+    match coerced.get("type").and_then(Value::as_str) {
+        Some(received) if type_name.is_some_and(|type_name| type_name != received) => warnings
+            .push(LintWarning::UnknownType {
+                received: received.to_owned(),
+            }),
+        _ => (),
+    }
+    if let Or::Snd(Remotable::Inline(_)) = &parsed.body {
+        warnings.extend(
+            RECOMMENDED
+                .iter()
+                .filter(|property| coerced.get(property).is_none())
+                .map(|property| LintWarning::MissingRecommended { property }),
+        );
+    }
+    warnings.extend(events.into_iter().filter_map(|event| match event {
+        ParseEvent::UnknownProperty {
+            type_name,
+            property,
+        } => Some(LintWarning::UnknownProperty {
+            type_name,
+            property,
+        }),
+        _ => None,
+    }));
+    warnings
+}
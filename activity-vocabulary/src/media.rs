@@ -0,0 +1,25 @@
+use activity_vocabulary_core::Property;
+
+use crate::{Audio, Document, Image, Page, Video};
+
+macro_rules! impl_focal_point {
+    ($($ty:ty),*) => {
+        $(
+            impl $ty {
+                /// `focalPoint` as `(x, y)`, if it is a valid pair.
+                pub fn focal_point(&self) -> Option<(f32, f32)> {
+                    match self.focal_point.0.as_slice() {
+                        &[x, y] => Some((x, y)),
+                        _ => None,
+                    }
+                }
+
+                pub fn set_focal_point(&mut self, (x, y): (f32, f32)) {
+                    self.focal_point = Property(vec![x, y]);
+                }
+            }
+        )*
+    };
+}
+
+impl_focal_point!(Audio, Document, Image, Page, Video);
@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Path of the [WellKnown] discovery document.
+pub const WELL_KNOWN_PATH: &str = "/.well-known/nodeinfo";
+pub const SCHEMA_2_0: &str = "http://nodeinfo.diaspora.software/ns/schema/2.0";
+pub const SCHEMA_2_1: &str = "http://nodeinfo.diaspora.software/ns/schema/2.1";
+
+/// Discovery document served at [WELL_KNOWN_PATH], linking to the [NodeInfo] of each schema version.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct WellKnown {
+    pub links: Vec<WellKnownLink>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct WellKnownLink {
+    /// Schema of the linked document, e.g. [SCHEMA_2_1].
+    pub rel: String,
+    pub href: url::Url,
+}
+
+impl WellKnown {
+    /// Link to the newest [NodeInfo] schema version this crate knows.
+    pub fn nodeinfo(&self) -> Option<&url::Url> {
+        [SCHEMA_2_1, SCHEMA_2_0].into_iter().find_map(|schema| {
+            self.links
+                .iter()
+                .find(|link| link.rel.trim_end_matches('#') == schema)
+                .map(|link| &link.href)
+        })
+    }
+}
+
+/// NodeInfo document of [schema 2.0](http://nodeinfo.diaspora.software/ns/schema/2.0)
+/// or [2.1](http://nodeinfo.diaspora.software/ns/schema/2.1), told by [NodeInfo::version].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeInfo {
+    /// `2.0` or `2.1`.
+    pub version: String,
+    pub software: Software,
+    /// Protocols the server supports, e.g. `activitypub`.
+    pub protocols: Vec<String>,
+    pub services: Services,
+    pub open_registrations: bool,
+    pub usage: Usage,
+    /// Free form metadata of the server, e.g. `nodeName`.
+    pub metadata: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Software {
+    /// Canonical name of the software, in `[a-z0-9-]`.
+    pub name: String,
+    pub version: String,
+    /// Only in 2.1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository: Option<url::Url>,
+    /// Only in 2.1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<url::Url>,
+}
+
+/// Third party sites the server can retrieve messages from or publish messages to.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Services {
+    pub inbound: Vec<String>,
+    pub outbound: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Usage {
+    pub users: Users,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_posts: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_comments: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Users {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_halfyear: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_month: Option<u64>,
+}
@@ -0,0 +1,77 @@
+use activity_vocabulary_core::{xsd, ObjectId, Or, Property, Remotable};
+
+use crate::ObjectSubtypes;
+
+/// Replacement of the inline objects by their ids, implemented by the generated types.
+pub trait Normalize {
+    /// Replace the inline objects with an id by the id, pushing them normalized into `extracted`.
+    ///
+    /// The anonymous objects are kept inline, as nothing could refer to them.
+    fn normalize_into(&mut self, extracted: &mut Vec<ObjectSubtypes>);
+}
+
+/// `object` with its inline objects replaced by their ids, and the objects replaced,
+/// each of them normalized and after the ones it inlined.
+pub fn normalize<T: Normalize>(mut object: T) -> (T, Vec<ObjectSubtypes>) {
+    let mut extracted = Vec::new();
+    object.normalize_into(&mut extracted);
+    (object, extracted)
+}
+
+macro_rules! leaf {
+    ($($ty:ty),*) => {
+        $(
+            impl Normalize for $ty {
+                fn normalize_into(&mut self, _: &mut Vec<ObjectSubtypes>) {}
+            }
+        )*
+    };
+}
+
+leaf!(url::Url, bool, xsd::DateTime);
+
+impl<T: Normalize> Normalize for Box<T> {
+    fn normalize_into(&mut self, extracted: &mut Vec<ObjectSubtypes>) {
+        self.as_mut().normalize_into(extracted)
+    }
+}
+
+impl<T: Normalize> Normalize for Option<T> {
+    fn normalize_into(&mut self, extracted: &mut Vec<ObjectSubtypes>) {
+        if let Some(inner) = self {
+            inner.normalize_into(extracted)
+        }
+    }
+}
+
+impl<T: Normalize> Normalize for Property<T> {
+    fn normalize_into(&mut self, extracted: &mut Vec<ObjectSubtypes>) {
+        self.0
+            .iter_mut()
+            .for_each(|value| value.normalize_into(extracted))
+    }
+}
+
+impl<T: Normalize, U: Normalize> Normalize for Or<T, U> {
+    fn normalize_into(&mut self, extracted: &mut Vec<ObjectSubtypes>) {
+        match self {
+            Or::Prim(value) => value.normalize_into(extracted),
+            Or::Snd(value) => value.normalize_into(extracted),
+        }
+    }
+}
+
+impl<T: Normalize + ObjectId + Into<ObjectSubtypes>> Normalize for Remotable<T> {
+    fn normalize_into(&mut self, extracted: &mut Vec<ObjectSubtypes>) {
+        let Remotable::Inline(object) = self else {
+            return;
+        };
+        object.normalize_into(extracted);
+        let Some(id) = object.object_id().cloned() else {
+            return;
+        };
+        if let Remotable::Inline(object) = std::mem::replace(self, Remotable::Remote(id)) {
+            extracted.push(object.into());
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use activity_vocabulary_core::Property;
+
+use crate::{
+    audience::{Addressed, Recipient},
+    *,
+};
+
+fn merge(into: &mut Vec<Recipient>, from: &[Recipient]) {
+    for recipient in from {
+        if !into.contains(recipient) {
+            into.push(recipient.clone());
+        }
+    }
+}
+
+/// Make `a` and `b` addressed to the union of their recipients, per addressing property.
+///
+/// The activities with an object wrap it by this with their generated `wrap_object`, such as
+/// [Create::wrap_object].
+pub fn copy_addressing(a: &mut impl Addressed, b: &mut impl Addressed) {
+    for (a, b) in a.addressing_mut().into_iter().zip(b.addressing_mut()) {
+        merge(a, b);
+        merge(b, a);
+    }
+}
+
+/// `{id}/activity`, the id Mastodon gives to the [Create] of an object.
+fn activity_id(id: &url::Url) -> Option<url::Url> {
+    let mut id = id.clone();
+    id.set_fragment(None);
+    id.path_segments_mut().ok()?.pop_if_empty().push("activity");
+    Some(id)
+}
+
+impl Create {
+    /// Wrap a bare object posted to an outbox in a [Create], as ActivityPub requires.
+    ///
+    /// The addressing is copied between the two, and the actor and the published time are taken
+    /// from [Object::attributed_to] and [Object::published]. The id defaults to `{id}/activity`
+    /// of the object; set [Create::id] afterwards to use another.
+    pub fn wrap(object: impl Into<ObjectSubtypes>) -> Self {
+        let object = object.into();
+        let base = Object::from(object.clone());
+        let mut create = Create {
+            object_type: Property(vec!["Create".to_owned()]),
+            id: base.id.as_deref().and_then(activity_id).map(Into::into),
+            actor: base.attributed_to,
+            published: base.published,
+            ..Default::default()
+        };
+        create.wrap_object(object);
+        create
+    }
+}
@@ -0,0 +1,152 @@
+use std::fmt::Display;
+
+use activity_vocabulary_core::{Or, Property, Remotable};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::value::RawValue;
+
+use crate::{audience::Addressed, Update};
+
+#[derive(Debug)]
+pub enum PatchError {
+    Serde(serde_json::Error),
+    /// The patch is not a JSON object.
+    NotAnObject,
+    /// The patch is of another object than the stored one.
+    IdMismatch,
+}
+
+impl Display for PatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Serde(e) => f.write_fmt(format_args!("serde: {e}")),
+            Self::NotAnObject => f.write_str("patch is not an object"),
+            Self::IdMismatch => f.write_str("patch is of another object"),
+        }
+    }
+}
+
+impl std::error::Error for PatchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Serde(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for PatchError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Serde(e)
+    }
+}
+
+/// Keys a patch never changes.
+const IMMUTABLE_KEYS: &[&str] = &["@context", "type"];
+
+/// Apply the object of an `Update` from a client to the `stored` JSON as a partial update, and
+/// return the updated object as `T`.
+///
+/// Properties present in `update` replace the stored ones, properties set to `null` are removed,
+/// and absent properties are left as they are, which a deserialized object can not tell from `null`.
+/// The patch is merged into the JSON, so that the properties `T` does not know are kept.
+/// `@context` and `type` are kept, and `id`, if present, must be the id of `stored`.
+/// `stored` is left untouched on error, also if the updated object is not a valid `T`.
+pub fn apply_update<T: DeserializeOwned>(
+    stored: &mut serde_json::Value,
+    update: &RawValue,
+) -> Result<T, PatchError> {
+    let serde_json::Value::Object(patch) = serde_json::from_str(update.get())? else {
+        return Err(PatchError::NotAnObject);
+    };
+    let serde_json::Value::Object(value) = stored else {
+        return Err(PatchError::NotAnObject);
+    };
+    if patch
+        .get("id")
+        .is_some_and(|id| value.get("id").is_some_and(|stored| stored != id))
+    {
+        return Err(PatchError::IdMismatch);
+    }
+    let mut value = value.clone();
+    for (key, property) in patch {
+        if IMMUTABLE_KEYS.contains(&key.as_str()) {
+            continue;
+        }
+        if property.is_null() {
+            value.remove(&key);
+        } else {
+            value.insert(key, property);
+        }
+    }
+    let value = serde_json::Value::Object(value);
+    let updated = T::deserialize(&value)?;
+    *stored = value;
+    Ok(updated)
+}
+
+/// Changed properties of an object, as the object of an `Update`, made by [diff].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct UpdatePatch {
+    /// `id` and `type` of the object, the changed properties, and `null` for the removed ones.
+    pub body: serde_json::Map<String, serde_json::Value>,
+}
+
+impl UpdatePatch {
+    /// Whether no property changed.
+    pub fn is_empty(&self) -> bool {
+        self.body.keys().all(|key| key == "id" || key == "type")
+    }
+
+    /// [UpdatePatch::body] as the JSON [apply_update] takes.
+    pub fn to_raw_value(&self) -> Result<Box<RawValue>, serde_json::Error> {
+        serde_json::value::to_raw_value(&self.body)
+    }
+
+    /// `Update` of `actor` with this patch as its object, addressed as `addressing`.
+    ///
+    /// The activity is built as JSON, since the `null` of the removed properties would not
+    /// survive [Update].
+    pub fn into_update(
+        self,
+        actor: url::Url,
+        addressing: &impl Addressed,
+    ) -> Result<serde_json::Value, serde_json::Error> {
+        let mut update = Update {
+            object_type: Property(vec!["Update".to_owned()]),
+            actor: Property(vec![Or::Snd(Remotable::Remote(actor))]),
+            ..Default::default()
+        };
+        for (to, from) in update
+            .addressing_mut()
+            .into_iter()
+            .zip(addressing.addressing())
+        {
+            *to = from.to_vec();
+        }
+        let mut update = serde_json::to_value(update)?;
+        update["object"] = serde_json::Value::Object(self.body);
+        Ok(update)
+    }
+}
+
+/// Minimal [UpdatePatch] turning `old` into `new`, which [apply_update] applies.
+pub fn diff<T: Serialize>(old: &T, new: &T) -> Result<UpdatePatch, PatchError> {
+    let (serde_json::Value::Object(mut old), serde_json::Value::Object(new)) =
+        (serde_json::to_value(old)?, serde_json::to_value(new)?)
+    else {
+        return Err(PatchError::NotAnObject);
+    };
+    let mut body = serde_json::Map::new();
+    for (key, property) in new {
+        if key == "id" || key == "type" || old.get(&key) != Some(&property) {
+            body.insert(key.clone(), property);
+        }
+        old.remove(&key);
+    }
+    for key in old.into_iter().map(|(key, _)| key) {
+        if !IMMUTABLE_KEYS.contains(&key.as_str()) {
+            body.insert(key, serde_json::Value::Null);
+        }
+    }
+    Ok(UpdatePatch { body })
+}
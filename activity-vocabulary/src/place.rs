@@ -0,0 +1,97 @@
+use std::fmt::Display;
+
+use activity_vocabulary_core::xsd;
+
+use crate::{Place, Unit};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlaceError {
+    Latitude(f64),
+    Longitude(f64),
+    Accuracy(f64),
+    Radius(f64),
+}
+
+impl Display for PlaceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Latitude(latitude) => {
+                f.write_fmt(format_args!("latitude {latitude} is out of [-90, 90]"))
+            }
+            Self::Longitude(longitude) => {
+                f.write_fmt(format_args!("longitude {longitude} is out of [-180, 180]"))
+            }
+            Self::Accuracy(accuracy) => {
+                f.write_fmt(format_args!("accuracy {accuracy} is out of [0, 100]"))
+            }
+            Self::Radius(radius) => f.write_fmt(format_args!("radius {radius} is negative")),
+        }
+    }
+}
+
+impl std::error::Error for PlaceError {}
+
+impl Place {
+    /// Check the coordinates, [Place::accuracy] and [Place::radius] are in their ranges.
+    pub fn validate(&self) -> Result<(), PlaceError> {
+        let value = |value: &Option<xsd::Float>| value.as_ref().map(xsd::Float::to_f64);
+        match (
+            value(&self.latitude),
+            value(&self.longitude),
+            value(&self.accuracy),
+            value(&self.radius),
+        ) {
+            (Some(latitude), ..) if !(-90.0..=90.0).contains(&latitude) => {
+                Err(PlaceError::Latitude(latitude))
+            }
+            (_, Some(longitude), ..) if !(-180.0..=180.0).contains(&longitude) => {
+                Err(PlaceError::Longitude(longitude))
+            }
+            (_, _, Some(accuracy), _) if !(0.0..=100.0).contains(&accuracy) => {
+                Err(PlaceError::Accuracy(accuracy))
+            }
+            (.., Some(radius)) if radius.is_nan() || radius < 0.0 => {
+                Err(PlaceError::Radius(radius))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn in_unit(&self, value: &xsd::Float, unit: &Unit) -> Option<f64> {
+        Unit::convert(
+            value.to_f64(),
+            self.units.as_ref().unwrap_or(&Unit::M),
+            unit,
+        )
+    }
+
+    /// [Place::radius] converted from [Place::units] to `unit`, or [None] to convert from or to a [Unit::Uri].
+    pub fn radius_in(&self, unit: &Unit) -> Option<f64> {
+        self.in_unit(self.radius.as_ref()?, unit)
+    }
+
+    /// [Place::altitude] converted from [Place::units] to `unit`, or [None] to convert from or to a [Unit::Uri].
+    pub fn altitude_in(&self, unit: &Unit) -> Option<f64> {
+        self.in_unit(self.altitude.as_ref()?, unit)
+    }
+
+    /// Convert [Place::radius] and [Place::altitude] to meters and drop [Place::units],
+    /// so that places in different units compare equal.
+    /// Places in a [Unit::Uri] are left as they are.
+    pub fn normalize_units(&mut self) {
+        let Some(from) = self.units.take() else {
+            return;
+        };
+        if matches!(from, Unit::Uri(_)) {
+            self.units = Some(from);
+            return;
+        }
+        let to_meters = |value: Option<xsd::Float>| {
+            value
+                .and_then(|value| from.to_meters(value.to_f64()))
+                .and_then(xsd::Float::from_f64)
+        };
+        self.radius = to_meters(self.radius.take());
+        self.altitude = to_meters(self.altitude.take());
+    }
+}
@@ -0,0 +1,90 @@
+use activity_vocabulary_core::{xsd, Or, Property, Remotable};
+
+use crate::{Collection, CollectionSubtypes, Link, Object, Question};
+
+fn option(name: String) -> Or<Remotable<Object>, Link> {
+    let replies = Collection {
+        object_type: Property(vec!["Collection".to_owned()]),
+        total_items: Some(0),
+        ..Default::default()
+    };
+    let mut option = Object {
+        object_type: Property(vec!["Note".to_owned()]),
+        replies: Property(vec![Remotable::Inline(replies.into())]),
+        ..Default::default()
+    };
+    option.name.default = Some(Property(vec![name]));
+    Or::Prim(Remotable::Inline(option))
+}
+
+fn total_items(collection: &CollectionSubtypes) -> Option<usize> {
+    match collection {
+        CollectionSubtypes::Collection(collection) => collection.total_items,
+        CollectionSubtypes::OrderedCollection(collection) => collection.total_items,
+        CollectionSubtypes::CollectionPage(page) => page.total_items,
+        CollectionSubtypes::OrderedCollectionPage(page) => page.total_items,
+    }
+}
+
+impl Question {
+    fn new() -> Self {
+        Self {
+            object_type: Property(vec!["Question".to_owned()]),
+            ..Default::default()
+        }
+    }
+
+    /// A poll accepting one of `options`, as Notes with no votes in [Question::one_of].
+    pub fn single_choice(options: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        let mut question = Self::new();
+        question.one_of = Property(options.into_iter().map(|o| option(o.into())).collect());
+        question
+    }
+
+    /// A poll accepting any of `options`, as Notes with no votes in [Question::any_of].
+    pub fn multi_choice(options: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        let mut question = Self::new();
+        question.any_of = Property(options.into_iter().map(|o| option(o.into())).collect());
+        question
+    }
+
+    /// Whether [Question::closed] says so, or without it whether [Object::end_time](crate::Object::end_time) has passed.
+    pub fn is_closed(&self) -> bool {
+        let now = xsd::DateTime::now().to_utc();
+        match &self.closed {
+            Some(Or::Prim(Or::Prim(closed))) => closed.to_utc() <= now,
+            Some(Or::Prim(Or::Snd(closed))) => *closed,
+            Some(Or::Snd(_)) => true,
+            None => self
+                .end_time
+                .as_ref()
+                .is_some_and(|end_time| end_time.to_utc() <= now),
+        }
+    }
+
+    /// Name and votes of the inline options, which count the votes in `replies.totalItems`.
+    pub fn tally(&self) -> Vec<(&str, usize)> {
+        self.one_of
+            .0
+            .iter()
+            .chain(&self.any_of.0)
+            .filter_map(|option| match option {
+                Or::Prim(Remotable::Inline(option)) => Some(option),
+                _ => None,
+            })
+            .filter_map(|option| {
+                let name = option.name.default.as_ref()?.0.first()?;
+                let votes = option
+                    .replies
+                    .0
+                    .iter()
+                    .find_map(|replies| match replies {
+                        Remotable::Inline(replies) => total_items(replies),
+                        Remotable::Remote(_) => None,
+                    })
+                    .unwrap_or_default();
+                Some((name.as_str(), votes))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,108 @@
+use activity_vocabulary_core::{canonical_json::to_canonical_string, WithContext};
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::{
+    audience::{recipient_id, Recipient},
+    Object, ObjectSubtypes,
+};
+
+fn invalid(e: serde_json::Error) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+fn ids(recipients: &[Recipient]) -> Vec<String> {
+    recipients
+        .iter()
+        .filter_map(recipient_id)
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// A document parsed strictly as an object of any type.
+#[pyclass(
+    name = "Document",
+    module = "activity_vocabulary",
+    frozen,
+    skip_from_py_object
+)]
+#[derive(Debug, Clone)]
+pub struct PyDocument(pub WithContext<ObjectSubtypes>);
+
+#[pymethods]
+impl PyDocument {
+    #[getter(r#type)]
+    fn type_name(&self) -> &'static str {
+        self.0.body.type_name()
+    }
+
+    #[getter]
+    fn id(&self) -> Option<String> {
+        Object::from(self.0.body.clone()).id.map(String::from)
+    }
+
+    /// Ids of the recipients in `to`, without the inline ones.
+    #[getter]
+    fn to(&self) -> Vec<String> {
+        ids(self.0.body.to())
+    }
+
+    #[getter]
+    fn bto(&self) -> Vec<String> {
+        ids(self.0.body.bto())
+    }
+
+    #[getter]
+    fn cc(&self) -> Vec<String> {
+        ids(self.0.body.cc())
+    }
+
+    #[getter]
+    fn bcc(&self) -> Vec<String> {
+        ids(self.0.body.bcc())
+    }
+
+    #[getter]
+    fn audience(&self) -> Vec<String> {
+        ids(self.0.body.audience())
+    }
+
+    /// The document in the JSON Canonicalization Scheme form.
+    fn to_json(&self) -> PyResult<String> {
+        to_canonical_string(&self.0).map_err(invalid)
+    }
+
+    fn __repr__(&self) -> String {
+        match self.id() {
+            Some(id) => format!("<Document {} {id}>", self.type_name()),
+            None => format!("<Document {}>", self.type_name()),
+        }
+    }
+}
+
+/// Parse `json`, raising `ValueError` unless it is a valid object.
+#[pyfunction]
+fn parse(json: &str) -> PyResult<PyDocument> {
+    serde_json::from_str(json).map(PyDocument).map_err(invalid)
+}
+
+/// Check `json` is a valid object, raising `ValueError` otherwise.
+#[pyfunction]
+fn validate(json: &str) -> PyResult<()> {
+    parse(json).map(|_| ())
+}
+
+/// Re-serialize `json` canonically, raising `ValueError` unless it is a valid object.
+#[pyfunction]
+fn canonicalize(json: &str) -> PyResult<String> {
+    parse(json)?.to_json()
+}
+
+/// The `activity_vocabulary` module, to be exported by an extension crate.
+#[pymodule]
+pub fn activity_vocabulary(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyDocument>()?;
+    m.add_function(wrap_pyfunction!(parse, m)?)?;
+    m.add_function(wrap_pyfunction!(validate, m)?)?;
+    m.add_function(wrap_pyfunction!(canonicalize, m)?)?;
+    Ok(())
+}
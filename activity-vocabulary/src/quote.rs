@@ -0,0 +1,30 @@
+use activity_vocabulary_core::Or;
+
+use crate::{is_activitystreams, tag::Tagged, LinkSubtypes};
+
+/// `rel` of a [FEP-e232](https://codeberg.org/fediverse/fep/src/branch/main/fep/e232/fep-e232.md)
+/// object link to the quoted object.
+pub const QUOTE_REL: &str = "https://misskey-hub.net/ns#_misskey_quote";
+
+/// Quote of another object, implemented by the generated types.
+pub trait Quoting: Tagged {
+    /// `quoteUrl`, `_misskey_quote` and `quoteUri` in this order.
+    fn quote_ids(&self) -> [Option<&url::Url>; 3];
+
+    /// Id of the object this object quotes, checking a FEP-e232 object link in the tags, that is
+    /// a link of an ActivityStreams media type with [QUOTE_REL], then [Quoting::quote_ids].
+    fn quoted_object(&self) -> Option<&url::Url> {
+        self.tags()
+            .iter()
+            .find_map(|tag| match tag {
+                Or::Prim(LinkSubtypes::Link(link))
+                    if link.media_type.as_deref().is_some_and(is_activitystreams)
+                        && link.rel.0.iter().any(|rel| rel == QUOTE_REL) =>
+                {
+                    Some(&link.href)
+                }
+                _ => None,
+            })
+            .or_else(|| self.quote_ids().into_iter().flatten().next())
+    }
+}
@@ -0,0 +1,75 @@
+use activity_vocabulary_core::{Or, Property, Remotable};
+
+use crate::{Emoji, EmojiReact, Like, ObjectSubtypes};
+
+/// Emoji of a reaction.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reaction<'a> {
+    Unicode(&'a str),
+    /// Custom emoji referred by its shortcode (e.g. `:blobcat:`), with its definition if it is tagged.
+    Custom {
+        shortcode: &'a str,
+        emoji: Option<&'a Emoji>,
+    },
+}
+
+fn is_shortcode(content: &str) -> bool {
+    content.len() > 2 && content.starts_with(':') && content.ends_with(':')
+}
+
+macro_rules! impl_reaction {
+    ($($ty:ty),*) => {
+        $(
+            impl $ty {
+                /// Emoji of the reaction in [Object::content](crate::Object::content), resolving a custom emoji
+                /// shortcode with the [Emoji] of the same name in the tags.
+                ///
+                /// Misskey federates reactions as [Like] with the emoji in the content, so this works for [Like] too.
+                pub fn reaction(&self) -> Option<Reaction<'_>> {
+                    let content = self.content.default.as_ref()?.0.first()?;
+                    if !is_shortcode(content) {
+                        return Some(Reaction::Unicode(content));
+                    }
+                    let emoji = self.tag.0.iter().find_map(|tag| match tag {
+                        Or::Snd(Remotable::Inline(ObjectSubtypes::Emoji(emoji)))
+                            if emoji
+                                .name
+                                .default
+                                .as_ref()
+                                .is_some_and(|name| name.0.iter().any(|name| name == content)) =>
+                        {
+                            Some(emoji)
+                        }
+                        _ => None,
+                    });
+                    Some(Reaction::Custom {
+                        shortcode: content,
+                        emoji,
+                    })
+                }
+
+                /// Set the emoji of the reaction. A custom `emoji` is tagged, and its name is set as the content.
+                pub fn set_reaction(&mut self, unicode_or_emoji: Or<String, Emoji>) {
+                    let content = match unicode_or_emoji {
+                        Or::Prim(unicode) => unicode,
+                        Or::Snd(emoji) => {
+                            let shortcode = emoji
+                                .name
+                                .default
+                                .as_ref()
+                                .and_then(|name| name.0.first().cloned())
+                                .unwrap_or_default();
+                            self.tag
+                                .0
+                                .push(Or::Snd(Remotable::Inline(ObjectSubtypes::Emoji(emoji))));
+                            shortcode
+                        }
+                    };
+                    self.content.default = Some(Property(vec![content]));
+                }
+            }
+        )*
+    };
+}
+
+impl_reaction!(EmojiReact, Like);
@@ -0,0 +1,56 @@
+use std::collections::HashSet;
+
+use crate::walk::Walk;
+
+/// Which properties [Redact::redact] removes or masks, by their names in JSON such as `bcc`.
+///
+/// A language map goes with its property, e.g. `contentMap` with `content`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedactionPolicy {
+    /// Properties removed. The required ones such as `href` of a link are kept.
+    pub remove: HashSet<String>,
+    /// Text properties whose values are replaced by [RedactionPolicy::mask_with].
+    pub mask: HashSet<String>,
+    pub mask_with: String,
+}
+
+/// Removes `bto`, `bcc` and `signature`, which must not be exported, and masks nothing.
+impl Default for RedactionPolicy {
+    fn default() -> Self {
+        Self {
+            remove: ["bto", "bcc", "signature"].map(String::from).into(),
+            mask: HashSet::new(),
+            mask_with: "[redacted]".to_owned(),
+        }
+    }
+}
+
+impl RedactionPolicy {
+    pub fn removing(mut self, property: impl Into<String>) -> Self {
+        self.remove.insert(property.into());
+        self
+    }
+
+    pub fn masking(mut self, property: impl Into<String>) -> Self {
+        self.mask.insert(property.into());
+        self
+    }
+}
+
+/// Redaction of the properties of an object and the objects and links nested in it.
+pub trait Redact {
+    fn redact(&mut self, policy: &RedactionPolicy);
+}
+
+impl<T: Walk> Redact for T {
+    fn redact(&mut self, policy: &RedactionPolicy) {
+        self.walk_mut(&mut |mut node| {
+            node.clear_properties(&mut |property| policy.remove.contains(property));
+            node.texts_mut(&mut |property, text| {
+                if policy.mask.contains(property) {
+                    text.clone_from(&policy.mask_with);
+                }
+            })
+        })
+    }
+}
@@ -0,0 +1,91 @@
+use std::{fmt::Display, time::Duration};
+
+use ::redis::{aio::ConnectionLike, AsyncCommands, RedisError};
+
+use crate::cache::{CacheStore, CachedDocument};
+
+/// Keys of [RedisStore] are prefixed with this by default.
+pub const DEFAULT_PREFIX: &str = "activity-vocabulary:";
+
+#[derive(Debug)]
+pub enum RedisStoreError {
+    Redis(RedisError),
+    Malformed(serde_json::Error),
+}
+
+impl Display for RedisStoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Redis(e) => f.write_fmt(format_args!("redis: {e}")),
+            Self::Malformed(e) => f.write_fmt(format_args!("malformed cached document: {e}")),
+        }
+    }
+}
+
+impl std::error::Error for RedisStoreError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Redis(e) => Some(e),
+            Self::Malformed(e) => Some(e),
+        }
+    }
+}
+
+/// [CacheStore] shared through Redis, keeping each document as JSON under its id with an expiry.
+///
+/// `C` is a cheaply cloned connection such as [::redis::aio::MultiplexedConnection].
+#[derive(Debug, Clone)]
+pub struct RedisStore<C> {
+    connection: C,
+    prefix: String,
+}
+
+impl<C> RedisStore<C> {
+    pub fn new(connection: C) -> Self {
+        Self {
+            connection,
+            prefix: DEFAULT_PREFIX.to_owned(),
+        }
+    }
+
+    /// Prefix of the keys, to share a database with others.
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    fn key(&self, url: &url::Url) -> String {
+        format!("{}{url}", self.prefix)
+    }
+}
+
+impl<C: ConnectionLike + Clone + Send + Sync> CacheStore for RedisStore<C> {
+    type Error = RedisStoreError;
+
+    async fn get(&self, url: &url::Url) -> Result<Option<CachedDocument>, Self::Error> {
+        let value: Option<Vec<u8>> = self
+            .connection
+            .clone()
+            .get(self.key(url))
+            .await
+            .map_err(RedisStoreError::Redis)?;
+        value
+            .map(|value| serde_json::from_slice(&value))
+            .transpose()
+            .map_err(RedisStoreError::Malformed)
+    }
+
+    async fn put(
+        &self,
+        url: &url::Url,
+        document: CachedDocument,
+        retain: Duration,
+    ) -> Result<(), Self::Error> {
+        let value = serde_json::to_vec(&document).map_err(RedisStoreError::Malformed)?;
+        self.connection
+            .clone()
+            .set_ex(self.key(url), value, retain.as_secs().max(1))
+            .await
+            .map_err(RedisStoreError::Redis)
+    }
+}
@@ -0,0 +1,124 @@
+use activity_vocabulary_core::{LangContainer, ObjectId, Or, Property, Remotable};
+
+use crate::{audience::recipient_id, Flag, ObjectSubtypes};
+
+/// Object flagged by a report, see [Flag::report].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReportTarget {
+    /// The reported account.
+    Account(url::Url),
+    /// A reported post of the account.
+    Object(url::Url),
+}
+
+impl ReportTarget {
+    pub fn id(&self) -> &url::Url {
+        match self {
+            Self::Account(id) | Self::Object(id) => id,
+        }
+    }
+}
+
+fn is_actor(object: &ObjectSubtypes) -> bool {
+    matches!(
+        object,
+        ObjectSubtypes::Application(_)
+            | ObjectSubtypes::Group(_)
+            | ObjectSubtypes::Organization(_)
+            | ObjectSubtypes::Person(_)
+            | ObjectSubtypes::Service(_)
+    )
+}
+
+impl Flag {
+    /// Moderation report by `actor` of `account` and its `objects` with `comment`, as Mastodon
+    /// and Misskey send.
+    ///
+    /// A report is of a single account. The objects are the id of the account followed by those
+    /// of the posts, and the comment is the content unless empty.
+    pub fn report(
+        actor: url::Url,
+        account: url::Url,
+        objects: impl IntoIterator<Item = url::Url>,
+        comment: impl Into<String>,
+    ) -> Self {
+        let comment = comment.into();
+        Self {
+            object_type: Property(vec!["Flag".to_owned()]),
+            actor: Property(vec![Or::Snd(Remotable::Remote(actor))]),
+            object: Property(
+                std::iter::once(account)
+                    .chain(objects)
+                    .map(|id| Or::Snd(Remotable::Remote(id)))
+                    .collect(),
+            ),
+            content: LangContainer {
+                default: (!comment.is_empty()).then(|| Property(vec![comment])),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// The flagged objects classified by [ReportTarget].
+    ///
+    /// The inlined objects are classified by their types. The ids are by the convention of
+    /// [Flag::report], that is the first id is the account unless an account is inlined, and the
+    /// rest are the posts.
+    pub fn report_targets(&self) -> Vec<ReportTarget> {
+        let mut has_account =
+            self.object.0.iter().any(
+                |object| matches!(object, Or::Snd(Remotable::Inline(object)) if is_actor(object)),
+            );
+        self.object
+            .0
+            .iter()
+            .filter_map(|object| match object {
+                Or::Snd(Remotable::Inline(object)) => {
+                    let id = object.object_id()?.clone();
+                    Some(if is_actor(object) {
+                        ReportTarget::Account(id)
+                    } else {
+                        ReportTarget::Object(id)
+                    })
+                }
+                object => {
+                    let id = recipient_id(object)?.clone();
+                    Some(if has_account {
+                        ReportTarget::Object(id)
+                    } else {
+                        has_account = true;
+                        ReportTarget::Account(id)
+                    })
+                }
+            })
+            .collect()
+    }
+
+    /// The reported accounts, see [Flag::report_targets].
+    pub fn reported_accounts(&self) -> Vec<url::Url> {
+        self.report_targets()
+            .into_iter()
+            .filter_map(|target| match target {
+                ReportTarget::Account(id) => Some(id),
+                ReportTarget::Object(_) => None,
+            })
+            .collect()
+    }
+
+    /// The reported posts, see [Flag::report_targets].
+    pub fn reported_objects(&self) -> Vec<url::Url> {
+        self.report_targets()
+            .into_iter()
+            .filter_map(|target| match target {
+                ReportTarget::Object(id) => Some(id),
+                ReportTarget::Account(_) => None,
+            })
+            .collect()
+    }
+
+    /// The comment of the reporter, the content without a language.
+    pub fn report_comment(&self) -> Option<&str> {
+        self.content.default.as_ref()?.0.first().map(String::as_str)
+    }
+}
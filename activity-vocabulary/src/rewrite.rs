@@ -0,0 +1,92 @@
+use activity_vocabulary_core::{xsd, ObjectUri, Or, Property, Remotable};
+
+/// Rewriting of the urls in the properties, implemented by the generated types.
+pub trait RewriteUrls {
+    /// Replace each url, in the objects and links inlined as well, by the one `map` returns if any.
+    fn rewrite_urls_with(&mut self, map: &mut dyn FnMut(&url::Url) -> Option<url::Url>);
+
+    /// [RewriteUrls::rewrite_urls_with] taking the function by value.
+    fn rewrite_urls(&mut self, mut map: impl FnMut(&url::Url) -> Option<url::Url>)
+    where
+        Self: Sized,
+    {
+        self.rewrite_urls_with(&mut map)
+    }
+}
+
+/// Map of the urls under `from` to the same paths under `to`, to re-host objects with
+/// [RewriteUrls::rewrite_urls].
+///
+/// `from` and `to` are prefixes, so they should end with `/` unless they are origins.
+pub fn rebase(from: url::Url, to: url::Url) -> impl FnMut(&url::Url) -> Option<url::Url> {
+    move |url| {
+        let rest = url.as_str().strip_prefix(from.as_str())?;
+        format!("{}{rest}", to.as_str()).parse().ok()
+    }
+}
+
+impl RewriteUrls for url::Url {
+    fn rewrite_urls_with(&mut self, map: &mut dyn FnMut(&url::Url) -> Option<url::Url>) {
+        if let Some(url) = map(self) {
+            *self = url;
+        }
+    }
+}
+
+impl RewriteUrls for ObjectUri {
+    fn rewrite_urls_with(&mut self, map: &mut dyn FnMut(&url::Url) -> Option<url::Url>) {
+        (**self).rewrite_urls_with(map)
+    }
+}
+
+macro_rules! without_urls {
+    ($($ty:ty),*) => {
+        $(
+            impl RewriteUrls for $ty {
+                fn rewrite_urls_with(&mut self, _: &mut dyn FnMut(&url::Url) -> Option<url::Url>) {}
+            }
+        )*
+    };
+}
+
+without_urls!(bool, xsd::DateTime);
+
+impl<T: RewriteUrls> RewriteUrls for Box<T> {
+    fn rewrite_urls_with(&mut self, map: &mut dyn FnMut(&url::Url) -> Option<url::Url>) {
+        self.as_mut().rewrite_urls_with(map)
+    }
+}
+
+impl<T: RewriteUrls> RewriteUrls for Option<T> {
+    fn rewrite_urls_with(&mut self, map: &mut dyn FnMut(&url::Url) -> Option<url::Url>) {
+        if let Some(inner) = self {
+            inner.rewrite_urls_with(map)
+        }
+    }
+}
+
+impl<T: RewriteUrls> RewriteUrls for Property<T> {
+    fn rewrite_urls_with(&mut self, map: &mut dyn FnMut(&url::Url) -> Option<url::Url>) {
+        self.0
+            .iter_mut()
+            .for_each(|value| value.rewrite_urls_with(map))
+    }
+}
+
+impl<T: RewriteUrls, U: RewriteUrls> RewriteUrls for Or<T, U> {
+    fn rewrite_urls_with(&mut self, map: &mut dyn FnMut(&url::Url) -> Option<url::Url>) {
+        match self {
+            Or::Prim(value) => value.rewrite_urls_with(map),
+            Or::Snd(value) => value.rewrite_urls_with(map),
+        }
+    }
+}
+
+impl<T: RewriteUrls> RewriteUrls for Remotable<T> {
+    fn rewrite_urls_with(&mut self, map: &mut dyn FnMut(&url::Url) -> Option<url::Url>) {
+        match self {
+            Remotable::Remote(id) => id.rewrite_urls_with(map),
+            Remotable::Inline(inline) => inline.rewrite_urls_with(map),
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use std::collections::HashSet;
+
+use serde::Deserialize;
+
+use crate::walk::Walk;
+
+/// HTML sanitizer of the text properties, by the allowlist of an [ammonia::Builder].
+#[derive(Debug)]
+pub struct Sanitizer {
+    pub html: ammonia::Builder<'static>,
+    /// Properties sanitized, by their names in JSON, also their values per language.
+    pub properties: HashSet<String>,
+}
+
+/// Sanitizes `content` and `summary` by the default allowlist of [ammonia].
+impl Default for Sanitizer {
+    fn default() -> Self {
+        Self {
+            html: ammonia::Builder::default(),
+            properties: ["content", "summary"].map(String::from).into(),
+        }
+    }
+}
+
+impl Sanitizer {
+    pub fn clean(&self, html: &str) -> String {
+        self.html.clean(html).to_string()
+    }
+}
+
+/// Sanitization of an object and the objects and links nested in it.
+pub trait Sanitize {
+    fn sanitize(&mut self, sanitizer: &Sanitizer);
+}
+
+impl<T: Walk> Sanitize for T {
+    fn sanitize(&mut self, sanitizer: &Sanitizer) {
+        self.walk_mut(&mut |mut node| {
+            node.texts_mut(&mut |property, text| {
+                if sanitizer.properties.contains(property) {
+                    *text = sanitizer.clean(text);
+                }
+            })
+        })
+    }
+}
+
+/// `T` sanitized by [Sanitizer::default] on deserialization.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sanitized<T>(pub T);
+
+impl<'de, T: Deserialize<'de> + Walk> Deserialize<'de> for Sanitized<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut inner = T::deserialize(deserializer)?;
+        inner.sanitize(&Sanitizer::default());
+        Ok(Self(inner))
+    }
+}
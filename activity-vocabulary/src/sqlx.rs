@@ -0,0 +1,34 @@
+/// Store a type as `jsonb` through [::sqlx::types::Json], as `WithContext` does.
+macro_rules! json_type {
+    ($ty:ty) => {
+        impl ::sqlx::Type<::sqlx::Postgres> for $ty {
+            fn type_info() -> ::sqlx::postgres::PgTypeInfo {
+                <::sqlx::types::Json<Self> as ::sqlx::Type<::sqlx::Postgres>>::type_info()
+            }
+
+            fn compatible(ty: &::sqlx::postgres::PgTypeInfo) -> bool {
+                <::sqlx::types::Json<Self> as ::sqlx::Type<::sqlx::Postgres>>::compatible(ty)
+            }
+        }
+
+        impl ::sqlx::Encode<'_, ::sqlx::Postgres> for $ty {
+            fn encode_by_ref(
+                &self,
+                buf: &mut ::sqlx::postgres::PgArgumentBuffer,
+            ) -> Result<::sqlx::encode::IsNull, ::sqlx::error::BoxDynError> {
+                ::sqlx::types::Json(self).encode_by_ref(buf)
+            }
+        }
+
+        impl<'r> ::sqlx::Decode<'r, ::sqlx::Postgres> for $ty {
+            fn decode(
+                value: ::sqlx::postgres::PgValueRef<'r>,
+            ) -> Result<Self, ::sqlx::error::BoxDynError> {
+                <::sqlx::types::Json<Self> as ::sqlx::Decode<::sqlx::Postgres>>::decode(value)
+                    .map(|json| json.0)
+            }
+        }
+    };
+}
+
+pub(crate) use json_type;
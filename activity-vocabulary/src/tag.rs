@@ -0,0 +1,48 @@
+use activity_vocabulary_core::{Or, Remotable};
+
+#[cfg(feature = "mastodon")]
+use crate::{Emoji, Hashtag};
+use crate::{LinkSubtypes, Mention, ObjectSubtypes};
+
+/// A tag in [Object::tag](crate::Object::tag).
+pub type Tag = Or<LinkSubtypes, Remotable<ObjectSubtypes>>;
+
+/// Tags of an object, implemented by the generated types.
+pub trait Tagged {
+    fn tags(&self) -> &[Tag];
+
+    /// The [Mention]s in [Tagged::tags].
+    fn mentions(&self) -> Vec<&Mention> {
+        self.tags()
+            .iter()
+            .filter_map(|tag| match tag {
+                Or::Prim(LinkSubtypes::Mention(mention)) => Some(mention),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The [Hashtag]s in [Tagged::tags].
+    #[cfg(feature = "mastodon")]
+    fn hashtags(&self) -> Vec<&Hashtag> {
+        self.tags()
+            .iter()
+            .filter_map(|tag| match tag {
+                Or::Prim(LinkSubtypes::Hashtag(hashtag)) => Some(hashtag),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The custom [Emoji]s inlined in [Tagged::tags].
+    #[cfg(feature = "mastodon")]
+    fn custom_emojis(&self) -> Vec<&Emoji> {
+        self.tags()
+            .iter()
+            .filter_map(|tag| match tag {
+                Or::Snd(Remotable::Inline(ObjectSubtypes::Emoji(emoji))) => Some(emoji),
+                _ => None,
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::{HashMap, HashSet};
+
+use activity_vocabulary_core::{Or, Remotable};
+
+use crate::{audience::recipient_id, Object, ObjectSubtypes};
+
+/// Conversation tree of objects, linked by their `inReplyTo`.
+///
+/// Objects without an id are ignored. An `inReplyTo` to an object not in the thread is kept
+/// as a node without an object, so that remote parents remain leaves to fetch later.
+#[derive(Debug, Clone, Default)]
+pub struct Thread {
+    objects: HashMap<url::Url, ObjectSubtypes>,
+    parents: HashMap<url::Url, url::Url>,
+    children: HashMap<url::Url, Vec<url::Url>>,
+}
+
+fn parent_of(object: &Object) -> Option<url::Url> {
+    object.in_reply_to.0.iter().find_map(|parent| match parent {
+        Or::Snd(Remotable::Inline(parent)) => Object::from(parent.clone()).id.map(Into::into),
+        parent => recipient_id(parent).cloned(),
+    })
+}
+
+impl Thread {
+    pub fn new(objects: impl IntoIterator<Item = ObjectSubtypes>) -> Self {
+        let mut thread = Self::default();
+        let mut published = HashMap::new();
+        for object in objects {
+            let base = Object::from(object.clone());
+            let Some(id) = base.id.clone().map(url::Url::from) else {
+                continue;
+            };
+            if let Some(parent) = parent_of(&base).filter(|parent| parent != &id) {
+                thread.parents.insert(id.clone(), parent);
+            }
+            if let Some(time) = &base.published {
+                published.insert(id.clone(), time.to_utc());
+            }
+            thread.objects.insert(id, object);
+        }
+        for (child, parent) in &thread.parents {
+            thread
+                .children
+                .entry(parent.clone())
+                .or_default()
+                .push(child.clone());
+        }
+        // chronological, the ones without the published time last
+        for children in thread.children.values_mut() {
+            children.sort_by(|a, b| {
+                match (published.get(a), published.get(b)) {
+                    (Some(a), Some(b)) => a.cmp(b),
+                    (Some(_), None) => std::cmp::Ordering::Less,
+                    (None, Some(_)) => std::cmp::Ordering::Greater,
+                    (None, None) => std::cmp::Ordering::Equal,
+                }
+                .then_with(|| a.as_str().cmp(b.as_str()))
+            });
+        }
+        thread
+    }
+
+    /// The object of `id`, unless it is a remote parent not in the thread.
+    pub fn get(&self, id: &url::Url) -> Option<&ObjectSubtypes> {
+        self.objects.get(id)
+    }
+
+    pub fn parent(&self, id: &url::Url) -> Option<&url::Url> {
+        self.parents.get(id)
+    }
+
+    /// Direct replies to `id` in chronological order.
+    pub fn children(&self, id: &url::Url) -> &[url::Url] {
+        self.children.get(id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Nodes without a parent, including the remote parents not in the thread, sorted by id.
+    pub fn roots(&self) -> Vec<&url::Url> {
+        let mut roots = self
+            .objects
+            .keys()
+            .chain(self.children.keys())
+            .filter(|id| !self.parents.contains_key(*id))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        roots.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        roots
+    }
+
+    /// Parent, grandparent and so on of `id`, stopping at a cycle.
+    pub fn ancestors(&self, id: &url::Url) -> Vec<&url::Url> {
+        let mut seen = HashSet::from([id]);
+        let mut ancestors = Vec::new();
+        let mut current = id;
+        while let Some(parent) = self.parents.get(current) {
+            if !seen.insert(parent) {
+                break;
+            }
+            ancestors.push(parent);
+            current = parent;
+        }
+        ancestors
+    }
+
+    /// Replies to `id` and their replies recursively, depth first in chronological order.
+    pub fn descendants(&self, id: &url::Url) -> Vec<&url::Url> {
+        let mut seen = HashSet::from([id]);
+        let mut descendants = Vec::new();
+        let mut stack = self.children(id).iter().rev().collect::<Vec<_>>();
+        while let Some(child) = stack.pop() {
+            if !seen.insert(child) {
+                continue;
+            }
+            descendants.push(child);
+            stack.extend(self.children(child).iter().rev());
+        }
+        descendants
+    }
+}
@@ -0,0 +1,48 @@
+use activity_vocabulary_core::{xsd, Or, Property, Remotable};
+
+use crate::{audience::Addressed, Delete, Object, ObjectSubtypes, Tombstone};
+
+impl Tombstone {
+    /// [Tombstone] of `object` deleted at `deleted`, keeping its id and its type as [Tombstone::former_type].
+    pub fn of(object: &ObjectSubtypes, deleted: xsd::DateTime) -> Self {
+        let base = Object::from(object.clone());
+        // a type unknown to this crate is kept in `object_type` of the fallback variant
+        let former_type = match base.object_type.0.first() {
+            Some(object_type) => object_type.clone(),
+            None => object.type_name().to_owned(),
+        };
+        Self {
+            object_type: Property(vec!["Tombstone".to_owned()]),
+            id: base.id,
+            former_type: Property(vec![former_type]),
+            deleted: Some(deleted),
+            ..Default::default()
+        }
+    }
+}
+
+impl Delete {
+    /// [Delete] of `object`, replacing it with its [Tombstone] deleted now.
+    ///
+    /// The actor and the addressing are taken from the object, so that the deletion reaches
+    /// everyone who received it. The id defaults to `{id}#delete` of the object.
+    pub fn for_object(object: &ObjectSubtypes) -> Self {
+        let base = Object::from(object.clone());
+        let mut delete = Delete {
+            object_type: Property(vec!["Delete".to_owned()]),
+            id: base.id.clone().map(|mut id| {
+                id.set_fragment(Some("delete"));
+                id
+            }),
+            actor: base.attributed_to.clone(),
+            object: Property(vec![Or::Snd(Remotable::Inline(
+                Tombstone::of(object, xsd::DateTime::now()).into(),
+            ))]),
+            ..Default::default()
+        };
+        for (to, from) in delete.addressing_mut().into_iter().zip(base.addressing()) {
+            *to = from.to_vec();
+        }
+        delete
+    }
+}
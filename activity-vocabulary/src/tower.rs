@@ -0,0 +1,124 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use http::{header, HeaderValue, Request, Response};
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{accepts_activitystreams, ACTIVITY_MEDIA_TYPE};
+
+/// Serve ActivityStreams and another representation, usually HTML, from the same routes.
+///
+/// Requests whose `Accept` prefers ActivityStreams (see [accepts_activitystreams]) go to the
+/// service given to [NegotiateLayer::new], and the others to the wrapped service.
+#[derive(Clone, Debug)]
+pub struct NegotiateLayer<A> {
+    activity: A,
+}
+
+impl<A> NegotiateLayer<A> {
+    pub fn new(activity: A) -> Self {
+        Self { activity }
+    }
+}
+
+impl<S, A: Clone> Layer<S> for NegotiateLayer<A> {
+    type Service = Negotiate<S, A>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Negotiate {
+            inner,
+            activity: self.activity.clone(),
+        }
+    }
+}
+
+/// Service made by [NegotiateLayer].
+///
+/// Every response gets `Vary: Accept`. Responses of the ActivityStreams service without a
+/// `Content-Type`, or with plain `application/json`, are labeled as [ACTIVITY_MEDIA_TYPE].
+#[derive(Clone, Debug)]
+pub struct Negotiate<S, A> {
+    inner: S,
+    activity: A,
+}
+
+impl<S, A, ReqBody, ResBody> Service<Request<ReqBody>> for Negotiate<S, A>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    A: Service<Request<ReqBody>, Response = Response<ResBody>, Error = S::Error>,
+{
+    type Response = Response<ResBody>;
+    type Error = S::Error;
+    type Future = ResponseFuture<S::Future, A::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        match (self.inner.poll_ready(cx)?, self.activity.poll_ready(cx)?) {
+            (Poll::Ready(()), Poll::Ready(())) => Poll::Ready(Ok(())),
+            _ => Poll::Pending,
+        }
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let activity = req
+            .headers()
+            .get_all(header::ACCEPT)
+            .iter()
+            .filter_map(|accept| accept.to_str().ok())
+            .any(accepts_activitystreams);
+        if activity {
+            ResponseFuture::Activity {
+                future: self.activity.call(req),
+            }
+        } else {
+            ResponseFuture::Other {
+                future: self.inner.call(req),
+            }
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// Response future of [Negotiate].
+    #[project = ResponseFutureProj]
+    pub enum ResponseFuture<F, G> {
+        Other { #[pin] future: F },
+        Activity { #[pin] future: G },
+    }
+}
+
+impl<F, G, B, E> Future for ResponseFuture<F, G>
+where
+    F: Future<Output = Result<Response<B>, E>>,
+    G: Future<Output = Result<Response<B>, E>>,
+{
+    type Output = Result<Response<B>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let (mut response, activity) = match self.project() {
+            ResponseFutureProj::Other { future } => (std::task::ready!(future.poll(cx))?, false),
+            ResponseFutureProj::Activity { future } => (std::task::ready!(future.poll(cx))?, true),
+        };
+        let headers = response.headers_mut();
+        headers.append(header::VARY, HeaderValue::from_static("accept"));
+        let unlabeled = headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .is_none_or(|content_type| {
+                content_type
+                    .split(';')
+                    .next()
+                    .is_some_and(|essence| essence.trim() == "application/json")
+            });
+        if activity && unlabeled {
+            headers.insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(ACTIVITY_MEDIA_TYPE),
+            );
+        }
+        Poll::Ready(Ok(response))
+    }
+}
@@ -0,0 +1,109 @@
+use std::fmt::Display;
+
+use activity_vocabulary_core::{Dereferencer, Or, Remotable};
+use serde::Deserialize;
+
+use crate::{audience::recipient_id, Activity, ActivitySubtypes, ObjectSubtypes, Undo};
+
+/// Kind of the activity an [Undo] undoes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UndoKind {
+    Like,
+    Follow,
+    Announce,
+    Block,
+    Other,
+}
+
+impl UndoKind {
+    pub fn of(object: &ObjectSubtypes) -> Self {
+        match object {
+            ObjectSubtypes::Like(_) => Self::Like,
+            #[cfg(feature = "pleroma")]
+            ObjectSubtypes::EmojiReact(_) => Self::Like,
+            ObjectSubtypes::Follow(_) => Self::Follow,
+            ObjectSubtypes::Announce(_) => Self::Announce,
+            ObjectSubtypes::Block(_) => Self::Block,
+            _ => Self::Other,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum UndoError<E> {
+    MissingTarget,
+    Dereference(E),
+    MalformedTarget(String),
+    /// The undone activity is of another actor than the [Undo].
+    ActorMismatch,
+}
+
+impl<E: Display> Display for UndoError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingTarget => f.write_str("undo has no object"),
+            Self::Dereference(e) => f.write_fmt(format_args!("fetch undone activity: {e}")),
+            Self::MalformedTarget(msg) => {
+                f.write_fmt(format_args!("malformed undone activity: {msg}"))
+            }
+            Self::ActorMismatch => f.write_str("undone activity is of another actor"),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for UndoError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Dereference(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl Undo {
+    /// [UndoKind] of the undone activity if it is inlined, as Mastodon and Misskey do.
+    /// Use [Undo::resolve_target] for one referred by id.
+    pub fn target_kind(&self) -> Option<UndoKind> {
+        self.object.0.iter().find_map(|object| match object {
+            Or::Snd(Remotable::Inline(object)) => Some(UndoKind::of(object)),
+            _ => None,
+        })
+    }
+
+    /// The undone activity, fetched through `dereferencer` unless inlined, with its [UndoKind].
+    ///
+    /// The activity must be of the actor of the [Undo], so that one cannot undo others' activities.
+    pub async fn resolve_target<D: Dereferencer>(
+        &self,
+        dereferencer: &D,
+    ) -> Result<(UndoKind, ObjectSubtypes), UndoError<D::Error>> {
+        let target = match self.object.0.first().ok_or(UndoError::MissingTarget)? {
+            Or::Snd(Remotable::Inline(object)) => object.clone(),
+            object => {
+                let id = recipient_id(object).ok_or(UndoError::MissingTarget)?;
+                let document = dereferencer
+                    .dereference(id)
+                    .await
+                    .map_err(UndoError::Dereference)?;
+                ObjectSubtypes::deserialize(document)
+                    .map_err(|e| UndoError::MalformedTarget(e.to_string()))?
+            }
+        };
+        let activity = ActivitySubtypes::try_from(target.clone())
+            .map_err(|_| UndoError::MalformedTarget("not an activity".into()))?;
+        let actors = |activity: Activity| {
+            activity
+                .actor
+                .0
+                .iter()
+                .filter_map(recipient_id)
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        let undone = actors(activity.into());
+        if undone.is_empty() || undone != actors(self.clone().into()) {
+            return Err(UndoError::ActorMismatch);
+        }
+        Ok((UndoKind::of(&target), target))
+    }
+}
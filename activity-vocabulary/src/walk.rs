@@ -0,0 +1,117 @@
+use std::collections::HashSet;
+
+use activity_vocabulary_core::{is_public_iri, xsd, Or, Property, Remotable};
+
+use crate::{Node, NodeMut};
+
+/// Traversal of the nested objects and links, implemented by the generated types.
+pub trait Walk {
+    /// Visit the objects and links, and the references to objects, parents first.
+    fn walk<'a>(&'a self, f: &mut impl FnMut(Node<'a>));
+
+    /// [Walk::walk] to modify them, the children visited after their parent is modified.
+    fn walk_mut(&mut self, f: &mut impl FnMut(NodeMut<'_>));
+
+    /// Ids of the objects referred and urls of the links, once each in the order of [Walk::walk],
+    /// to prefetch them.
+    ///
+    /// [activity_vocabulary_core::PUBLIC] is left out, as it is never fetched.
+    fn remote_refs(&self) -> Vec<&url::Url> {
+        let mut seen = HashSet::new();
+        let mut refs = Vec::new();
+        self.walk(&mut |node| {
+            let url = match node {
+                Node::Remote(id) => id,
+                node => match node.href() {
+                    Some(href) => href,
+                    None => return,
+                },
+            };
+            if !is_public_iri(url.as_str()) && seen.insert(url) {
+                refs.push(url);
+            }
+        });
+        refs
+    }
+}
+
+macro_rules! leaf {
+    ($($ty:ty),*) => {
+        $(
+            impl Walk for $ty {
+                fn walk<'a>(&'a self, _: &mut impl FnMut(Node<'a>)) {}
+
+                fn walk_mut(&mut self, _: &mut impl FnMut(NodeMut<'_>)) {}
+            }
+        )*
+    };
+}
+
+// a bare url is not a reference to an object, e.g. `inbox`
+leaf!(url::Url, bool, xsd::DateTime);
+
+impl<T: Walk> Walk for Box<T> {
+    fn walk<'a>(&'a self, f: &mut impl FnMut(Node<'a>)) {
+        self.as_ref().walk(f)
+    }
+
+    fn walk_mut(&mut self, f: &mut impl FnMut(NodeMut<'_>)) {
+        self.as_mut().walk_mut(f)
+    }
+}
+
+impl<T: Walk> Walk for Option<T> {
+    fn walk<'a>(&'a self, f: &mut impl FnMut(Node<'a>)) {
+        if let Some(inner) = self {
+            inner.walk(f)
+        }
+    }
+
+    fn walk_mut(&mut self, f: &mut impl FnMut(NodeMut<'_>)) {
+        if let Some(inner) = self {
+            inner.walk_mut(f)
+        }
+    }
+}
+
+impl<T: Walk> Walk for Property<T> {
+    fn walk<'a>(&'a self, f: &mut impl FnMut(Node<'a>)) {
+        self.0.iter().for_each(|value| value.walk(f))
+    }
+
+    fn walk_mut(&mut self, f: &mut impl FnMut(NodeMut<'_>)) {
+        self.0.iter_mut().for_each(|value| value.walk_mut(f))
+    }
+}
+
+impl<T: Walk, U: Walk> Walk for Or<T, U> {
+    fn walk<'a>(&'a self, f: &mut impl FnMut(Node<'a>)) {
+        match self {
+            Or::Prim(value) => value.walk(f),
+            Or::Snd(value) => value.walk(f),
+        }
+    }
+
+    fn walk_mut(&mut self, f: &mut impl FnMut(NodeMut<'_>)) {
+        match self {
+            Or::Prim(value) => value.walk_mut(f),
+            Or::Snd(value) => value.walk_mut(f),
+        }
+    }
+}
+
+impl<T: Walk> Walk for Remotable<T> {
+    fn walk<'a>(&'a self, f: &mut impl FnMut(Node<'a>)) {
+        match self {
+            Remotable::Remote(id) => f(Node::Remote(id)),
+            Remotable::Inline(inline) => inline.walk(f),
+        }
+    }
+
+    fn walk_mut(&mut self, f: &mut impl FnMut(NodeMut<'_>)) {
+        match self {
+            Remotable::Remote(id) => f(NodeMut::Remote(id)),
+            Remotable::Inline(inline) => inline.walk_mut(f),
+        }
+    }
+}
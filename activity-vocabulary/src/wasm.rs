@@ -0,0 +1,178 @@
+use activity_vocabulary_core::{Context, WithContext};
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    audience::{recipient_id, Recipient},
+    Object, ObjectSubtypes,
+};
+
+fn ids(recipients: &[Recipient]) -> Vec<String> {
+    recipients
+        .iter()
+        .filter_map(recipient_id)
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// An object of any type for JavaScript, parsed from JSON and serialized back.
+///
+/// The accessors cover the properties common to the object types. The setters go through the
+/// JSON form, so that the properties of the specific type are kept.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivityObject(WithContext<ObjectSubtypes>);
+
+impl ActivityObject {
+    pub fn into_inner(self) -> WithContext<ObjectSubtypes> {
+        self.0
+    }
+
+    fn base(&self) -> Object {
+        Object::from(self.0.body.clone())
+    }
+
+    fn update(
+        &mut self,
+        property: &str,
+        value: serde_json::Value,
+    ) -> Result<(), serde_json::Error> {
+        let mut json = serde_json::to_value(&self.0)?;
+        match (&mut json, value) {
+            (serde_json::Value::Object(object), serde_json::Value::Null) => {
+                object.remove(property);
+            }
+            (serde_json::Value::Object(object), value) => {
+                object.insert(property.to_owned(), value);
+            }
+            _ => (),
+        }
+        self.0 = serde_json::from_value(json)?;
+        Ok(())
+    }
+}
+
+impl From<WithContext<ObjectSubtypes>> for ActivityObject {
+    fn from(object: WithContext<ObjectSubtypes>) -> Self {
+        Self(object)
+    }
+}
+
+#[wasm_bindgen]
+impl ActivityObject {
+    /// An object of `type` without properties, in the ActivityStreams context.
+    #[wasm_bindgen(constructor)]
+    pub fn new(type_name: &str) -> Result<ActivityObject, JsError> {
+        let body = serde_json::from_value(serde_json::json!({ "type": type_name }))?;
+        Ok(Self(WithContext {
+            context: Some(Context::activitystreams()),
+            body,
+        }))
+    }
+
+    pub fn parse(json: &str) -> Result<ActivityObject, JsError> {
+        Ok(Self(serde_json::from_str(json)?))
+    }
+
+    pub fn stringify(&self) -> Result<String, JsError> {
+        Ok(serde_json::to_string(&self.0)?)
+    }
+
+    #[wasm_bindgen(getter = type)]
+    pub fn type_name(&self) -> String {
+        self.0.body.type_name().to_owned()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn id(&self) -> Option<String> {
+        self.base().id.map(String::from)
+    }
+
+    #[wasm_bindgen(js_name = setId)]
+    pub fn set_id(&mut self, id: Option<String>) -> Result<(), JsError> {
+        let id = id.map(|id| id.parse::<url::Url>()).transpose()?;
+        Ok(self.update("id", serde_json::json!(id))?)
+    }
+
+    /// `content` without a language.
+    #[wasm_bindgen(getter)]
+    pub fn content(&self) -> Option<String> {
+        self.base().content.default?.0.into_iter().next()
+    }
+
+    #[wasm_bindgen(js_name = setContent)]
+    pub fn set_content(&mut self, content: Option<String>) -> Result<(), JsError> {
+        Ok(self.update("content", serde_json::json!(content))?)
+    }
+
+    /// `name` without a language.
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> Option<String> {
+        self.base().name.default?.0.into_iter().next()
+    }
+
+    #[wasm_bindgen(js_name = setName)]
+    pub fn set_name(&mut self, name: Option<String>) -> Result<(), JsError> {
+        Ok(self.update("name", serde_json::json!(name))?)
+    }
+
+    /// `summary` without a language.
+    #[wasm_bindgen(getter)]
+    pub fn summary(&self) -> Option<String> {
+        self.base().summary.default?.0.into_iter().next()
+    }
+
+    #[wasm_bindgen(js_name = setSummary)]
+    pub fn set_summary(&mut self, summary: Option<String>) -> Result<(), JsError> {
+        Ok(self.update("summary", serde_json::json!(summary))?)
+    }
+
+    /// `published` in RFC 3339.
+    #[wasm_bindgen(getter)]
+    pub fn published(&self) -> Option<String> {
+        self.base().published.map(|published| published.to_string())
+    }
+
+    /// Ids of the `attributedTo`, without the inline ones.
+    #[wasm_bindgen(getter = attributedTo)]
+    pub fn attributed_to(&self) -> Vec<String> {
+        ids(&self.base().attributed_to.0)
+    }
+
+    #[wasm_bindgen(js_name = setAttributedTo)]
+    pub fn set_attributed_to(&mut self, ids: Vec<String>) -> Result<(), JsError> {
+        Ok(self.update("attributedTo", serde_json::json!(ids))?)
+    }
+
+    /// Ids of the `inReplyTo`, without the inline ones.
+    #[wasm_bindgen(getter = inReplyTo)]
+    pub fn in_reply_to(&self) -> Vec<String> {
+        ids(&self.base().in_reply_to.0)
+    }
+
+    #[wasm_bindgen(js_name = setInReplyTo)]
+    pub fn set_in_reply_to(&mut self, ids: Vec<String>) -> Result<(), JsError> {
+        Ok(self.update("inReplyTo", serde_json::json!(ids))?)
+    }
+
+    /// Ids of the `to`, without the inline ones.
+    #[wasm_bindgen(getter)]
+    pub fn to(&self) -> Vec<String> {
+        ids(self.0.body.to())
+    }
+
+    #[wasm_bindgen(js_name = setTo)]
+    pub fn set_to(&mut self, ids: Vec<String>) -> Result<(), JsError> {
+        Ok(self.update("to", serde_json::json!(ids))?)
+    }
+
+    /// Ids of the `cc`, without the inline ones.
+    #[wasm_bindgen(getter)]
+    pub fn cc(&self) -> Vec<String> {
+        ids(self.0.body.cc())
+    }
+
+    #[wasm_bindgen(js_name = setCc)]
+    pub fn set_cc(&mut self, ids: Vec<String>) -> Result<(), JsError> {
+        Ok(self.update("cc", serde_json::json!(ids))?)
+    }
+}
@@ -0,0 +1,112 @@
+use std::{collections::HashMap, fmt::Display};
+
+use activity_vocabulary_core::Dereferencer;
+use serde::{Deserialize, Serialize};
+
+use crate::is_activitystreams;
+
+/// Media type of a [ResourceDescriptor].
+pub const JRD_MEDIA_TYPE: &str = "application/jrd+json";
+
+/// JSON Resource Descriptor ([RFC 7033](https://www.rfc-editor.org/rfc/rfc7033#section-4.4)) served by WebFinger.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct ResourceDescriptor {
+    pub subject: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub properties: HashMap<String, Option<String>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<Link>,
+}
+
+/// Link of a [ResourceDescriptor].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Link {
+    pub rel: String,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub href: Option<url::Url>,
+    /// URI template of the OStatus subscribe link, e.g. `https://example.com/authorize_interaction?uri={uri}`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub titles: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub properties: HashMap<String, Option<String>>,
+}
+
+impl ResourceDescriptor {
+    /// Id of the actor, the `self` link of an ActivityStreams media type.
+    pub fn actor(&self) -> Option<&url::Url> {
+        self.links
+            .iter()
+            .filter(|link| link.rel == "self")
+            .filter(|link| link.media_type.as_deref().is_some_and(is_activitystreams))
+            .find_map(|link| link.href.as_ref())
+    }
+}
+
+#[derive(Debug)]
+pub enum WebFingerError<E> {
+    InvalidAcct(String),
+    Dereference(E),
+    MalformedDescriptor(serde_json::Error),
+    NoActor,
+}
+
+impl<E: Display> Display for WebFingerError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidAcct(acct) => f.write_fmt(format_args!("invalid acct {acct}")),
+            Self::Dereference(e) => f.write_fmt(format_args!("fetch resource descriptor: {e}")),
+            Self::MalformedDescriptor(e) => {
+                f.write_fmt(format_args!("malformed resource descriptor: {e}"))
+            }
+            Self::NoActor => f.write_str("resource descriptor has no actor link"),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for WebFingerError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Dereference(e) => Some(e),
+            Self::MalformedDescriptor(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// WebFinger query url of `acct`, which is either `acct:user@host`, `user@host` or `@user@host`.
+pub fn query_url<E>(acct: &str) -> Result<url::Url, WebFingerError<E>> {
+    let invalid = || WebFingerError::InvalidAcct(acct.to_owned());
+    let user_host = acct.strip_prefix("acct:").unwrap_or(acct);
+    let user_host = user_host.strip_prefix('@').unwrap_or(user_host);
+    let (user, host) = user_host.split_once('@').ok_or_else(invalid)?;
+    if user.is_empty() || host.is_empty() || host.contains(['@', '/', '?', '#']) {
+        return Err(invalid());
+    }
+    let mut url: url::Url = format!("https://{host}/.well-known/webfinger")
+        .parse()
+        .map_err(|_| invalid())?;
+    url.query_pairs_mut()
+        .append_pair("resource", &format!("acct:{user}@{host}"));
+    Ok(url)
+}
+
+/// Resolve `acct` (see [query_url]) to the id of the actor through WebFinger.
+pub async fn resolve_acct<D: Dereferencer>(
+    acct: &str,
+    dereferencer: &D,
+) -> Result<url::Url, WebFingerError<D::Error>> {
+    let url = query_url(acct)?;
+    let document = dereferencer
+        .dereference(&url)
+        .await
+        .map_err(WebFingerError::Dereference)?;
+    let descriptor: ResourceDescriptor =
+        serde_json::from_value(document).map_err(WebFingerError::MalformedDescriptor)?;
+    descriptor.actor().cloned().ok_or(WebFingerError::NoActor)
+}
@@ -0,0 +1,66 @@
+use activity_vocabulary::*;
+use activity_vocabulary_core::Or;
+
+fn objects() -> Vec<ObjectSubtypes> {
+    serde_json::from_value(serde_json::json!([
+        {
+            "type": "Note",
+            "id": "https://example.com/notes/1",
+            "name": "greeting",
+            "published": "2024-01-01T00:00:00Z",
+            "to": "https://www.w3.org/ns/activitystreams#Public"
+        },
+        {
+            "type": "Person",
+            "id": "https://example.com/users/alice",
+            "name": "Alice",
+            "inbox": "https://example.com/users/alice/inbox",
+            "outbox": "https://example.com/users/alice/outbox"
+        },
+        {
+            "type": "Follow",
+            "id": "https://example.com/follows/1",
+            "actor": "https://example.com/users/alice",
+            "object": "https://example.org/users/bob"
+        }
+    ]))
+    .unwrap()
+}
+
+#[test]
+fn object_subtypes() {
+    let objects = objects();
+    let ids = objects
+        .iter()
+        .map(|object| object.id().unwrap().as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ids,
+        vec![
+            "https://example.com/notes/1",
+            "https://example.com/users/alice",
+            "https://example.com/follows/1"
+        ]
+    );
+    assert_eq!(
+        objects[0].name().default.as_ref().unwrap().0,
+        vec!["greeting"]
+    );
+    assert_eq!(
+        objects[0].published().unwrap().to_string(),
+        "2024-01-01T00:00:00Z"
+    );
+    assert_eq!(objects[0].to().len(), 1);
+    assert!(objects[1].to().is_empty());
+    assert!(objects[2].published().is_none());
+}
+
+#[test]
+fn activity_subtypes() {
+    let activity = ActivitySubtypes::try_from(objects().remove(2)).unwrap();
+    assert!(matches!(activity.actor(), [Or::Snd(_)]));
+    assert_eq!(
+        activity.id().unwrap().as_str(),
+        "https://example.com/follows/1"
+    );
+}
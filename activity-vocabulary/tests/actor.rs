@@ -0,0 +1,115 @@
+use activity_vocabulary::*;
+use activity_vocabulary_core::{Property, Remotable};
+
+#[test]
+fn activitypub_actor() {
+    let input = serde_json::json!({
+        "type": "Person",
+        "id": "https://example.com/users/alice",
+        "preferredUsername": "alice",
+        "inbox": "https://example.com/users/alice/inbox",
+        "outbox": "https://example.com/users/alice/outbox",
+        "followers": "https://example.com/users/alice/followers",
+        "following": "https://example.com/users/alice/following",
+        "liked": "https://example.com/users/alice/liked",
+        "streams": "https://example.com/users/alice/featured",
+        "endpoints": {
+            "sharedInbox": "https://example.com/inbox"
+        }
+    });
+    let person: Person = serde_json::from_value(input.clone()).unwrap();
+    assert_eq!(person.preferred_username.as_deref(), Some("alice"));
+    assert_eq!(
+        person.inbox,
+        Some("https://example.com/users/alice/inbox".parse().unwrap())
+    );
+    assert_eq!(
+        person.streams,
+        Property(vec!["https://example.com/users/alice/featured"
+            .parse()
+            .unwrap()])
+    );
+    let Some(Remotable::Inline(endpoints)) = &person.endpoints else {
+        panic!("endpoints must be inline");
+    };
+    assert_eq!(
+        endpoints.shared_inbox,
+        Some("https://example.com/inbox".parse().unwrap())
+    );
+    assert_eq!(serde_json::to_value(&person).unwrap(), input);
+}
+
+#[test]
+fn migration() {
+    use activity_vocabulary::actor::{verify_migration, MigrationError};
+
+    let old: Person = serde_json::from_value(serde_json::json!({
+        "type": "Person",
+        "id": "https://old.example/users/alice",
+        "movedTo": "https://new.example/users/alice"
+    }))
+    .unwrap();
+    let new: Person = serde_json::from_value(serde_json::json!({
+        "type": "Person",
+        "id": "https://new.example/users/alice",
+        "alsoKnownAs": ["https://old.example/users/alice", "https://other.example/users/alice"]
+    }))
+    .unwrap();
+    assert_eq!(verify_migration(&old, &new), Ok(()));
+    assert_eq!(
+        verify_migration(&new, &old),
+        Err(MigrationError::NotMovedTo)
+    );
+
+    let stranger: Service = serde_json::from_value(serde_json::json!({
+        "type": "Service",
+        "id": "https://new.example/users/alice"
+    }))
+    .unwrap();
+    assert_eq!(
+        verify_migration(&old, &stranger),
+        Err(MigrationError::NotAlias)
+    );
+}
+
+#[test]
+fn migration_normalized() {
+    use activity_vocabulary::actor::verify_migration;
+
+    let old: Person = serde_json::from_value(serde_json::json!({
+        "type": "Person",
+        "id": "https://old.example/users/%7Ealice",
+        "movedTo": "https://new.example/users/alice/"
+    }))
+    .unwrap();
+    let new: Person = serde_json::from_value(serde_json::json!({
+        "type": "Person",
+        "id": "https://new.example/users/alice",
+        "alsoKnownAs": ["https://old.example/users/~alice"]
+    }))
+    .unwrap();
+    assert_eq!(verify_migration(&old, &new), Ok(()));
+}
+
+#[test]
+fn manually_approves_followers() {
+    use activity_vocabulary_core::{Context, WithContext};
+
+    let person: Person = serde_json::from_value(serde_json::json!({
+        "type": "Person",
+        "id": "https://example.com/users/alice",
+        "manuallyApprovesFollowers": true
+    }))
+    .unwrap();
+    assert_eq!(person.manually_approves_followers, Some(true));
+    let output = serde_json::to_value(WithContext {
+        context: Some(Context::activitystreams()),
+        body: person,
+    })
+    .unwrap();
+    assert_eq!(
+        output["@context"][1]["manuallyApprovesFollowers"],
+        "as:manuallyApprovesFollowers"
+    );
+    assert_eq!(output["manuallyApprovesFollowers"], true);
+}
@@ -0,0 +1,55 @@
+use activity_vocabulary::{attachment::Attachment, *};
+
+#[test]
+fn from_url() {
+    let image = Image::from_url(
+        "https://example.com/files/1.png".parse().unwrap(),
+        "image/png".parse().unwrap(),
+    );
+    assert_eq!(
+        serde_json::to_value(&image).unwrap(),
+        serde_json::json!({
+            "type": "Image",
+            "mediaType": "image/png",
+            "url": {
+                "type": "Link",
+                "href": "https://example.com/files/1.png",
+                "mediaType": "image/png"
+            }
+        })
+    );
+}
+
+#[test]
+fn builder() {
+    let attachment = Attachment::new(
+        "https://example.com/files/1.mp4".parse().unwrap(),
+        "video/mp4".parse().unwrap(),
+    )
+    .name("a cat")
+    .dimensions(1280, 720)
+    .build();
+    assert_eq!(attachment.type_name(), "Video");
+    assert_eq!(
+        serde_json::to_value(&attachment).unwrap(),
+        serde_json::json!({
+            "type": "Video",
+            "name": "a cat",
+            "mediaType": "video/mp4",
+            "url": {
+                "type": "Link",
+                "href": "https://example.com/files/1.mp4",
+                "mediaType": "video/mp4",
+                "width": 1280,
+                "height": 720
+            }
+        })
+    );
+
+    let attachment = Attachment::new(
+        "https://example.com/files/1.pdf".parse().unwrap(),
+        "application/pdf".parse().unwrap(),
+    )
+    .build();
+    assert_eq!(attachment.type_name(), "Document");
+}
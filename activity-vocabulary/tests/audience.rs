@@ -0,0 +1,124 @@
+use activity_vocabulary::{audience::*, *};
+
+fn note(addressing: serde_json::Value) -> Note {
+    let mut input = serde_json::json!({ "type": "Note" });
+    input
+        .as_object_mut()
+        .unwrap()
+        .extend(addressing.as_object().unwrap().clone());
+    serde_json::from_value(input).unwrap()
+}
+
+#[test]
+fn public_spellings() {
+    for public in [
+        "Public",
+        "as:Public",
+        "https://www.w3.org/ns/activitystreams#Public",
+    ] {
+        let note = note(serde_json::json!({ "to": public }));
+        assert!(note.is_public());
+        assert_eq!(
+            serde_json::to_value(&note).unwrap()["to"],
+            activity_vocabulary_core::PUBLIC
+        );
+    }
+}
+
+#[test]
+fn visibility() {
+    let followers = "https://example.com/users/alice/followers";
+    let bob = "https://remote.example/users/bob";
+    let cases = [
+        (
+            serde_json::json!({ "to": ["as:Public"], "cc": [followers] }),
+            Visibility::Public,
+        ),
+        (
+            serde_json::json!({ "to": [followers], "cc": ["as:Public"] }),
+            Visibility::Unlisted,
+        ),
+        (
+            serde_json::json!({ "to": [followers], "cc": [bob] }),
+            Visibility::Followers,
+        ),
+        (serde_json::json!({ "to": [bob] }), Visibility::Direct),
+        (
+            serde_json::json!({
+                "to": [{ "type": "OrderedCollection", "id": followers }],
+                "cc": [{ "type": "Person", "id": bob }]
+            }),
+            Visibility::Followers,
+        ),
+    ];
+    for (addressing, visibility) in cases {
+        assert_eq!(note(addressing).visibility(), visibility);
+    }
+    let create: Create = serde_json::from_value(serde_json::json!({
+        "type": "Create",
+        "to": "https://www.w3.org/ns/activitystreams#Public"
+    }))
+    .unwrap();
+    assert_eq!(create.visibility(), Visibility::Public);
+}
+
+#[test]
+fn strip_blind_addressing() {
+    use activity_vocabulary_core::BlindAddressing;
+
+    let mut create: Create = serde_json::from_value(serde_json::json!({
+        "type": "Create",
+        "to": "https://remote.example/users/bob",
+        "bto": "https://remote.example/users/carol",
+        "bcc": "https://remote.example/users/dave",
+        "object": {
+            "type": "Note",
+            "to": "https://remote.example/users/bob",
+            "bcc": "https://remote.example/users/dave",
+            "attachment": {
+                "type": "Image",
+                "bto": "https://remote.example/users/carol",
+                "url": "https://example.com/image.png"
+            }
+        }
+    }))
+    .unwrap();
+    create.strip_blind_addressing();
+    assert_eq!(
+        serde_json::to_value(&create).unwrap(),
+        serde_json::json!({
+            "type": "Create",
+            "to": "https://remote.example/users/bob",
+            "object": {
+                "type": "Note",
+                "to": "https://remote.example/users/bob",
+                "attachment": {
+                    "type": "Image",
+                    "url": "https://example.com/image.png"
+                }
+            }
+        })
+    );
+}
+
+#[test]
+fn strip_blind_addressing_of_closed() {
+    use activity_vocabulary_core::BlindAddressing;
+
+    let mut question: Question = serde_json::from_value(serde_json::json!({
+        "type": "Question",
+        "closed": {
+            "type": "Note",
+            "bcc": "https://remote.example/users/dave"
+        }
+    }))
+    .unwrap();
+    question.strip_blind_addressing();
+    assert_eq!(
+        serde_json::to_value(&question).unwrap(),
+        serde_json::json!({
+            "type": "Question",
+            "closed": { "type": "Note" }
+        })
+    );
+}
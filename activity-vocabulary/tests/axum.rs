@@ -0,0 +1,97 @@
+#![cfg(feature = "axum")]
+use activity_vocabulary::{axum::*, Follow};
+use activity_vocabulary_core::WithContext;
+use axum::{
+    body::Body,
+    extract::{FromRequest, Request},
+    http::{header, StatusCode},
+    response::IntoResponse,
+};
+
+fn follow() -> serde_json::Value {
+    serde_json::json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "type": "Follow",
+        "actor": "https://example.com/users/alice",
+        "object": "https://remote.example/users/bob"
+    })
+}
+
+fn request(content_type: &str, body: impl Into<Body>) -> Request {
+    Request::post("https://remote.example/inbox")
+        .header(header::CONTENT_TYPE, content_type)
+        .body(body.into())
+        .unwrap()
+}
+
+async fn extract(request: Request) -> Result<WithContext<Follow>, ActivityJsonRejection> {
+    ActivityJson::from_request(request, &())
+        .await
+        .map(|ActivityJson(document)| document)
+}
+
+#[tokio::test]
+async fn extract_activity_json() {
+    let expected: WithContext<Follow> = serde_json::from_value(follow()).unwrap();
+    let body = follow().to_string();
+    assert_eq!(
+        extract(request("application/activity+json", body.clone()))
+            .await
+            .unwrap(),
+        expected
+    );
+    assert_eq!(
+        extract(request(
+            r#"application/ld+json; profile="https://www.w3.org/ns/activitystreams""#,
+            body
+        ))
+        .await
+        .unwrap(),
+        expected
+    );
+}
+
+#[tokio::test]
+async fn reject_other_media_types() {
+    let body = follow().to_string();
+    for content_type in ["application/json", "application/ld+json"] {
+        let rejection = extract(request(content_type, body.clone()))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            rejection,
+            ActivityJsonRejection::UnsupportedMediaType
+        ));
+        assert_eq!(
+            rejection.into_response().status(),
+            StatusCode::UNSUPPORTED_MEDIA_TYPE
+        );
+    }
+}
+
+#[tokio::test]
+async fn reject_malformed_document() {
+    let rejection = extract(request("application/activity+json", "{"))
+        .await
+        .unwrap_err();
+    assert!(matches!(rejection, ActivityJsonRejection::Json(_)));
+    assert_eq!(rejection.into_response().status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn respond_activity_json() {
+    let document: WithContext<Follow> = serde_json::from_value(follow()).unwrap();
+    let response = ActivityJson(document).into_response();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "application/activity+json"
+    );
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+        follow()
+    );
+}
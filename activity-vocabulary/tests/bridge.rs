@@ -0,0 +1,43 @@
+#![cfg(feature = "bridge")]
+use activity_vocabulary::{bridge::convert, Note, ObjectSubtypes};
+use serde::{Deserialize, Serialize};
+
+/// Note as typed by another crate.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct ForeignNote {
+    #[serde(rename = "type")]
+    kind: String,
+    id: url::Url,
+    #[serde(rename = "attributedTo")]
+    attributed_to: url::Url,
+    content: String,
+}
+
+fn foreign() -> ForeignNote {
+    ForeignNote {
+        kind: "Note".to_owned(),
+        id: "https://example.com/notes/1".parse().unwrap(),
+        attributed_to: "https://example.com/users/alice".parse().unwrap(),
+        content: "hello".to_owned(),
+    }
+}
+
+#[test]
+fn roundtrip() {
+    let note: Note = convert(&foreign()).unwrap();
+    assert_eq!(
+        note.id,
+        Some("https://example.com/notes/1".parse().unwrap())
+    );
+    let back: ForeignNote = convert(&note).unwrap();
+    assert_eq!(back, foreign());
+
+    let object: ObjectSubtypes = convert(&foreign()).unwrap();
+    assert!(matches!(object, ObjectSubtypes::Note(_)));
+}
+
+#[test]
+fn mismatch() {
+    let note: Note = convert(&foreign()).unwrap();
+    assert!(convert::<_, ForeignNote>(&Note { id: None, ..note }).is_err());
+}
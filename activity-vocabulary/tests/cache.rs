@@ -0,0 +1,144 @@
+#![cfg(feature = "cache")]
+use std::{
+    future::{ready, Future},
+    sync::Mutex,
+    time::{Duration, SystemTime},
+};
+
+use activity_vocabulary::cache::*;
+use activity_vocabulary_core::{CacheHeaders, Dereferenced, Dereferencer};
+
+/// Origin serving a document with `ETag: "1"`, recording the validators of the requests.
+struct Origin {
+    cache_control: Option<&'static str>,
+    requests: Mutex<Vec<Option<CacheHeaders>>>,
+}
+
+impl Origin {
+    fn new(cache_control: Option<&'static str>) -> Self {
+        Self {
+            cache_control,
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn requests(&self) -> Vec<Option<CacheHeaders>> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Dereferencer for Origin {
+    type Error = String;
+
+    fn dereference(
+        &self,
+        url: &url::Url,
+    ) -> impl Future<Output = Result<serde_json::Value, Self::Error>> + Send {
+        ready(Err(url.to_string()))
+    }
+
+    fn dereference_conditional(
+        &self,
+        url: &url::Url,
+        cached: Option<&CacheHeaders>,
+    ) -> impl Future<Output = Result<Dereferenced, Self::Error>> + Send {
+        self.requests.lock().unwrap().push(cached.cloned());
+        let headers = CacheHeaders {
+            etag: Some(r#""1""#.to_owned()),
+            last_modified: None,
+            cache_control: self.cache_control.map(ToOwned::to_owned),
+        };
+        ready(Ok(
+            if cached.and_then(|cached| cached.etag.as_ref()) == headers.etag.as_ref() {
+                Dereferenced::NotModified { headers }
+            } else {
+                Dereferenced::Modified {
+                    document: serde_json::json!({ "id": url }),
+                    headers,
+                }
+            },
+        ))
+    }
+}
+
+fn alice() -> url::Url {
+    "https://example.com/users/alice".parse().unwrap()
+}
+
+#[tokio::test]
+async fn serve_fresh_document() {
+    let cache = CachingDereferencer::new(Origin::new(Some("max-age=600")), MemoryStore::default());
+    for _ in 0..2 {
+        let document = cache.dereference(&alice()).await.unwrap();
+        assert_eq!(document["id"], "https://example.com/users/alice");
+    }
+    assert_eq!(cache.inner().requests(), vec![None]);
+}
+
+#[tokio::test]
+async fn revalidate_stale_document() {
+    let cache = CachingDereferencer::new(Origin::new(Some("no-cache")), MemoryStore::default());
+    for _ in 0..2 {
+        let document = cache.dereference(&alice()).await.unwrap();
+        assert_eq!(document["id"], "https://example.com/users/alice");
+    }
+    let requests = cache.inner().requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0], None);
+    assert_eq!(
+        requests[1].as_ref().unwrap().etag.as_deref(),
+        Some(r#""1""#)
+    );
+}
+
+#[tokio::test]
+async fn no_store() {
+    let cache = CachingDereferencer::new(
+        Origin::new(Some("private, no-store")),
+        MemoryStore::default(),
+    );
+    for _ in 0..2 {
+        cache.dereference(&alice()).await.unwrap();
+    }
+    assert_eq!(cache.inner().requests(), vec![None, None]);
+    assert_eq!(cache.store().get(&alice()).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn private() {
+    let cache = CachingDereferencer::new(
+        Origin::new(Some("private, max-age=600")),
+        MemoryStore::default(),
+    );
+    for _ in 0..2 {
+        cache.dereference(&alice()).await.unwrap();
+    }
+    assert_eq!(cache.inner().requests(), vec![None, None]);
+    assert_eq!(cache.store().get(&alice()).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn drop_past_retention() {
+    let cache = CachingDereferencer::new(Origin::new(Some("no-cache")), MemoryStore::default())
+        .retain(Duration::ZERO);
+    cache.dereference(&alice()).await.unwrap();
+    cache.store().purge();
+    assert_eq!(cache.store().get(&alice()).await.unwrap(), None);
+    cache.dereference(&alice()).await.unwrap();
+    assert_eq!(cache.store().get(&alice()).await.unwrap(), None);
+    assert_eq!(cache.inner().requests(), vec![None, None]);
+}
+
+#[tokio::test]
+async fn bound_ttl() {
+    let max = Duration::from_secs(60 * 60);
+    let cache = CachingDereferencer::new(
+        Origin::new(Some("public, max-age=31536000")),
+        MemoryStore::default(),
+    )
+    .ttl(Duration::from_secs(60), max, Duration::from_secs(300));
+    cache.dereference(&alice()).await.unwrap();
+    let cached = cache.store().get(&alice()).await.unwrap().unwrap();
+    assert!(cached.fresh_until <= SystemTime::now() + max);
+    assert_eq!(cached.headers.etag.as_deref(), Some(r#""1""#));
+}
@@ -0,0 +1,40 @@
+use activity_vocabulary::*;
+use activity_vocabulary_core::canonical_json::to_canonical_string;
+
+#[test]
+fn sorted_keys() {
+    let note: Note = serde_json::from_value(serde_json::json!({
+        "type": "Note",
+        "id": "https://example.com/notes/1",
+        "content": "hello",
+        "to": ["https://example.com/b", "https://example.com/a"],
+        "attributedTo": "https://example.com/users/alice",
+    }))
+    .unwrap();
+    assert_eq!(
+        to_canonical_string(&note).unwrap(),
+        r#"{"attributedTo":"https://example.com/users/alice","content":"hello","id":"https://example.com/notes/1","to":["https://example.com/b","https://example.com/a"],"type":"Note"}"#
+    );
+}
+
+#[test]
+#[allow(clippy::excessive_precision)]
+fn rfc8785_numbers_and_strings() {
+    let value = serde_json::json!({
+        "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000001, 1e-7, 1e21, 1e20, -0.0, 9007199254740993u64],
+        "string": "\u{20ac}$\u{000F}\u{000a}A'\u{0042}\u{0022}\u{005c}\\\"/",
+        "literals": [null, true, false],
+        "\u{20ac}": 1,
+        "\r": 2,
+        "\u{1f600}": 3,
+        "\u{fb33}": 4,
+    });
+    assert_eq!(
+        to_canonical_string(&value).unwrap(),
+        concat!(
+            r#"{"\r":2,"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,0.000001,1e-7,1e+21,100000000000000000000,0,9007199254740992],"#,
+            r#""string":"€$\u000f\nA'B\"\\\\\"/","€":1,"😀":3,"#,
+            "\"\u{fb33}\":4}"
+        )
+    );
+}
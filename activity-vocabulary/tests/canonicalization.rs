@@ -0,0 +1,122 @@
+#![cfg(feature = "canonicalization")]
+use activity_vocabulary::*;
+use activity_vocabulary_core::rdf::{canonicalize, canonicalize_value, CanonicalizationError};
+
+#[test]
+fn embedded_blank_node() {
+    let input = serde_json::json!({
+        "@context": { "ex": "http://example.org/vocab#" },
+        "@id": "http://example.org/test#example",
+        "@type": "ex:Foo",
+        "ex:embed": { "@type": "ex:Bar" }
+    });
+    assert_eq!(
+        canonicalize_value(&input).unwrap(),
+        concat!(
+            "<http://example.org/test#example> <http://example.org/vocab#embed> _:c14n0 .\n",
+            "<http://example.org/test#example> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://example.org/vocab#Foo> .\n",
+            "_:c14n0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://example.org/vocab#Bar> .\n",
+        )
+    );
+}
+
+#[test]
+fn blank_node_labels_do_not_matter() {
+    let cycle = |a: &str, b: &str, c: &str| {
+        serde_json::json!({
+            "@context": { "ex": "http://example.org/vocab#" },
+            "@graph": [
+                { "@id": a, "ex:next": { "@id": b } },
+                { "@id": b, "ex:next": { "@id": c } },
+                { "@id": c, "ex:next": { "@id": a }, "ex:name": "end" },
+            ]
+        })
+    };
+    let canonical = canonicalize_value(&cycle("_:x", "_:y", "_:z")).unwrap();
+    assert_eq!(
+        canonical,
+        canonicalize_value(&cycle("_:z", "_:x", "_:y")).unwrap()
+    );
+    assert_eq!(
+        canonical,
+        canonicalize_value(&cycle("_:q", "_:p", "_:o")).unwrap()
+    );
+    assert_eq!(
+        canonical,
+        concat!(
+            "_:c14n0 <http://example.org/vocab#name> \"end\" .\n",
+            "_:c14n0 <http://example.org/vocab#next> _:c14n1 .\n",
+            "_:c14n1 <http://example.org/vocab#next> _:c14n2 .\n",
+            "_:c14n2 <http://example.org/vocab#next> _:c14n0 .\n",
+        )
+    );
+}
+
+#[test]
+fn activitystreams_note() {
+    let note: Note = serde_json::from_value(serde_json::json!({
+        "type": "Note",
+        "id": "https://example.com/notes/1",
+        "content": "hello\n\"world\"",
+        "published": "2024-01-01T00:00:00Z",
+    }))
+    .unwrap();
+    let document = serde_json::json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "type": "Note",
+        "id": "https://example.com/notes/1",
+        "content": "hello\n\"world\"",
+        "published": "2024-01-01T00:00:00Z",
+    });
+    assert_eq!(
+        canonicalize_value(&document).unwrap(),
+        concat!(
+            "<https://example.com/notes/1> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://www.w3.org/ns/activitystreams#Note> .\n",
+            "<https://example.com/notes/1> <https://www.w3.org/ns/activitystreams#content> \"hello\\n\\\"world\\\"\" .\n",
+            "<https://example.com/notes/1> <https://www.w3.org/ns/activitystreams#published> \"2024-01-01T00:00:00Z\"^^<http://www.w3.org/2001/XMLSchema#dateTime> .\n",
+        )
+    );
+    // without a context, no term can be expanded
+    assert_eq!(canonicalize(&note).unwrap(), "");
+}
+
+#[test]
+fn unknown_remote_context() {
+    let document = serde_json::json!({ "@context": "https://example.com/context", "type": "Note" });
+    assert!(matches!(
+        canonicalize_value(&document),
+        Err(CanonicalizationError::UnknownContext(_))
+    ));
+}
+
+#[test]
+fn activitystreams_extension_terms() {
+    let person: Person = serde_json::from_value(serde_json::json!({
+        "type": "Person",
+        "id": "https://example.com/users/alice",
+        "manuallyApprovesFollowers": true
+    }))
+    .unwrap();
+    let document = activity_vocabulary_core::WithContext {
+        context: Some(activity_vocabulary_core::Context::activitystreams()),
+        body: person,
+    };
+    assert!(canonicalize(&document).unwrap().contains(
+        "<https://example.com/users/alice> <https://www.w3.org/ns/activitystreams#manuallyApprovesFollowers> \"true\"^^<http://www.w3.org/2001/XMLSchema#boolean> .\n"
+    ));
+}
+
+#[test]
+fn indistinguishable_blank_nodes() {
+    let leaf = serde_json::json!({ "ex:leaf": true });
+    let branch = serde_json::json!({ "ex:child": vec![leaf; 9] });
+    let document = serde_json::json!({
+        "@context": { "ex": "http://example.org/vocab#" },
+        "@id": "http://example.org/test#root",
+        "ex:child": [branch.clone(), branch]
+    });
+    assert!(matches!(
+        canonicalize_value(&document),
+        Err(CanonicalizationError::TooComplex)
+    ));
+}
@@ -0,0 +1,155 @@
+use activity_vocabulary::{collection::*, *};
+use activity_vocabulary_core::{Or, Remotable};
+
+fn item(n: usize) -> audience::Recipient {
+    Or::Snd(Remotable::Remote(
+        format!("https://example.com/activities/{n}")
+            .parse()
+            .unwrap(),
+    ))
+}
+
+#[test]
+fn pages() {
+    let base_url: url::Url = "https://example.com/users/alice/outbox".parse().unwrap();
+    let (collection, pages) = paginate((0..5).map(item).collect(), 2, &base_url);
+    assert_eq!(
+        serde_json::to_value(&collection).unwrap(),
+        serde_json::json!({
+            "type": "OrderedCollection",
+            "id": "https://example.com/users/alice/outbox",
+            "totalItems": 5,
+            "first": "https://example.com/users/alice/outbox?page=1",
+            "last": "https://example.com/users/alice/outbox?page=3"
+        })
+    );
+    assert_eq!(pages.len(), 3);
+    assert_eq!(
+        serde_json::to_value(&pages[1]).unwrap(),
+        serde_json::json!({
+            "type": "OrderedCollectionPage",
+            "id": "https://example.com/users/alice/outbox?page=2",
+            "partOf": "https://example.com/users/alice/outbox",
+            "prev": "https://example.com/users/alice/outbox?page=1",
+            "next": "https://example.com/users/alice/outbox?page=3",
+            "startIndex": 2,
+            "orderedItems": [
+                "https://example.com/activities/2",
+                "https://example.com/activities/3"
+            ]
+        })
+    );
+    assert!(pages[0].prev.is_none());
+    assert!(pages[2].next.is_none());
+    assert_eq!(pages[2].items.0, vec![item(4)]);
+}
+
+/// `OrderedCollectionPage` extends `OrderedCollection` before `CollectionPage`, so it has the
+/// `items` of the former even though both inherit them from `Collection`.
+#[test]
+fn diamond_inheritance() {
+    let page: OrderedCollectionPage = serde_json::from_value(serde_json::json!({
+        "type": "OrderedCollectionPage",
+        "partOf": "https://example.com/users/alice/outbox",
+        "items": ["https://example.com/activities/1"]
+    }))
+    .unwrap();
+    assert_eq!(page.items.0, vec![item(1)]);
+    assert!(page.part_of.is_some());
+    let json = serde_json::to_value(&page).unwrap();
+    assert_eq!(json["orderedItems"], "https://example.com/activities/1");
+    assert!(json.get("items").is_none());
+}
+
+#[test]
+fn empty() {
+    let base_url: url::Url = "https://example.com/users/alice/outbox".parse().unwrap();
+    let (collection, pages) = paginate(Vec::new(), 20, &base_url);
+    assert!(pages.is_empty());
+    assert_eq!(collection.total_items, Some(0));
+    assert!(collection.first.is_none());
+}
+
+#[test]
+fn mutation() {
+    let mut outbox =
+        OrderedCollection::new("https://example.com/users/alice/outbox".parse().unwrap());
+    for n in 0..4 {
+        outbox.push_item(item(n));
+    }
+    let note: Note = serde_json::from_value(serde_json::json!({
+        "type": "Note",
+        "id": "https://example.com/notes/1"
+    }))
+    .unwrap();
+    outbox.push_item(Or::Snd(Remotable::Inline(note.into())));
+    assert_eq!(outbox.total_items, Some(5));
+    assert_eq!(outbox.items.0[1], item(3));
+
+    assert!(outbox
+        .remove_item_by_id(&"https://example.com/notes/1".parse().unwrap())
+        .is_some());
+    assert_eq!(
+        outbox.remove_item_by_id(&"https://example.com/activities/2".parse().unwrap()),
+        Some(item(2))
+    );
+    assert_eq!(
+        outbox.remove_item_by_id(&"https://example.com/activities/2".parse().unwrap()),
+        None
+    );
+    assert_eq!(outbox.total_items, Some(3));
+
+    outbox.truncate(2);
+    assert_eq!(outbox.items.0, vec![item(3), item(1)]);
+    assert_eq!(outbox.total_items, Some(2));
+}
+
+#[test]
+fn replies() {
+    let mut replies = Collection::replies(
+        "https://example.com/notes/1/replies".parse().unwrap(),
+        vec![item(0)],
+    );
+    replies.push_reply(item(1));
+    assert_eq!(
+        serde_json::to_value(&replies).unwrap(),
+        serde_json::json!({
+            "type": "Collection",
+            "id": "https://example.com/notes/1/replies",
+            "totalItems": 2,
+            "first": {
+                "type": "CollectionPage",
+                "partOf": "https://example.com/notes/1/replies",
+                "items": [
+                    "https://example.com/activities/0",
+                    "https://example.com/activities/1"
+                ]
+            }
+        })
+    );
+}
+
+#[test]
+fn diff() {
+    let old = Collection::replies(
+        "https://example.com/users/alice/followers".parse().unwrap(),
+        vec![item(0), item(1), item(2)],
+    );
+    let person: Person = serde_json::from_value(serde_json::json!({
+        "type": "Person",
+        "id": "https://example.com/activities/1"
+    }))
+    .unwrap();
+    let mut new =
+        OrderedCollection::new("https://example.com/users/alice/followers".parse().unwrap());
+    new.push_item(item(3));
+    new.push_item(Or::Snd(Remotable::Inline(person.into())));
+    new.push_item(item(0));
+    assert_eq!(
+        diff_collections(&old, &new.into()),
+        CollectionDiff {
+            added: vec!["https://example.com/activities/3".parse().unwrap()],
+            removed: vec!["https://example.com/activities/2".parse().unwrap()],
+        }
+    );
+}
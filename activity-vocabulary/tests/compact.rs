@@ -0,0 +1,56 @@
+use activity_vocabulary::*;
+use activity_vocabulary_core::{compact::Compacted, Property};
+
+#[test]
+fn aliased_terms_round_trip() {
+    let input = serde_json::json!({
+        "@context": [
+            "https://www.w3.org/ns/activitystreams",
+            {
+                "@language": "ja",
+                "titel": "as:name",
+                "inhalt": { "@id": "https://www.w3.org/ns/activitystreams#content", "@container": "@language" },
+                "Notiz": "as:Note"
+            }
+        ],
+        "type": "Notiz",
+        "id": "https://example.com/notes/1",
+        "titel": "hello",
+        "inhalt": { "en": "hello", "de": "hallo" },
+        "summary": "こんにちは"
+    });
+    let compacted: Compacted<Note> = serde_json::from_value(input.clone()).unwrap();
+    assert_eq!(
+        compacted.0.body.object_type,
+        Property(vec!["Note".to_owned()])
+    );
+    assert_eq!(
+        compacted.0.body.name.default,
+        Some(Property(vec!["hello".to_owned()]))
+    );
+    assert_eq!(serde_json::to_value(&compacted).unwrap(), input);
+}
+
+#[test]
+fn default_language_compacts_language_map() {
+    let input = serde_json::json!({
+        "@context": [
+            "https://www.w3.org/ns/activitystreams",
+            { "@language": "en" }
+        ],
+        "type": "Note",
+        "nameMap": { "en": "hello" }
+    });
+    let compacted: Compacted<Note> = serde_json::from_value(input).unwrap();
+    assert_eq!(
+        serde_json::to_value(&compacted).unwrap(),
+        serde_json::json!({
+            "@context": [
+                "https://www.w3.org/ns/activitystreams",
+                { "@language": "en" }
+            ],
+            "type": "Note",
+            "name": "hello"
+        })
+    );
+}
@@ -0,0 +1,77 @@
+use activity_vocabulary::*;
+use activity_vocabulary_core::{Or, Property, Remotable};
+
+fn alice() -> url::Url {
+    "https://example.com/users/alice".parse().unwrap()
+}
+
+fn bob() -> url::Url {
+    "https://example.org/users/bob".parse().unwrap()
+}
+
+#[test]
+fn note() {
+    assert_eq!(
+        serde_json::to_value(Note::new("hello")).unwrap(),
+        serde_json::json!({ "type": "Note", "content": "hello" })
+    );
+}
+
+#[test]
+fn create() {
+    let create = serde_json::to_value(Create::new(alice(), Note::new("hello"))).unwrap();
+    assert_eq!(
+        create,
+        serde_json::json!({
+            "type": "Create",
+            "actor": "https://example.com/users/alice",
+            "object": { "type": "Note", "content": "hello" }
+        })
+    );
+}
+
+#[test]
+fn accept_follow() {
+    let follow = Follow::new(alice(), bob());
+    assert_eq!(
+        serde_json::to_value(&follow).unwrap(),
+        serde_json::json!({
+            "type": "Follow",
+            "actor": "https://example.com/users/alice",
+            "object": "https://example.org/users/bob"
+        })
+    );
+    let accept = serde_json::to_value(Accept::of(follow.clone())).unwrap();
+    assert_eq!(accept["type"], "Accept");
+    assert_eq!(accept["actor"], "https://example.org/users/bob");
+    assert_eq!(accept["to"], "https://example.com/users/alice");
+    assert_eq!(accept["object"], serde_json::to_value(&follow).unwrap());
+
+    let reject = serde_json::to_value(Reject::of(follow)).unwrap();
+    assert_eq!(reject["type"], "Reject");
+    assert_eq!(reject["actor"], "https://example.org/users/bob");
+}
+
+#[test]
+fn undo_like() {
+    let mut like = Like::new(alice(), "https://example.org/notes/1".parse().unwrap());
+    like.to = Property(vec![Or::Snd(Remotable::Remote(bob()))]);
+    let undo = Undo::of(like);
+    assert_eq!(undo.target_kind(), Some(undo::UndoKind::Like));
+    let undo = serde_json::to_value(undo).unwrap();
+    assert_eq!(undo["type"], "Undo");
+    assert_eq!(undo["actor"], "https://example.com/users/alice");
+    assert_eq!(undo["to"], "https://example.org/users/bob");
+    assert_eq!(undo["object"]["object"], "https://example.org/notes/1");
+}
+
+#[test]
+fn announce() {
+    let announce = serde_json::to_value(Announce::new(
+        alice(),
+        "https://example.org/notes/1".parse().unwrap(),
+    ))
+    .unwrap();
+    assert_eq!(announce["type"], "Announce");
+    assert_eq!(announce["object"], "https://example.org/notes/1");
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "mastodon")]
+use activity_vocabulary::{content_warning::ContentWarning, *};
+
+#[test]
+fn content_warning() {
+    let mut note: Note = serde_json::from_value(serde_json::json!({
+        "type": "Note",
+        "summaryMap": {
+            "ja": "ネタバレ",
+            "en": "spoiler"
+        }
+    }))
+    .unwrap();
+    assert_eq!(note.content_warning(), Some("spoiler"));
+    assert!(note.is_sensitive());
+
+    note.set_content_warning("food");
+    assert_eq!(
+        serde_json::to_value(&note).unwrap(),
+        serde_json::json!({
+            "type": "Note",
+            "summary": "food",
+            "sensitive": true
+        })
+    );
+
+    note.clear_content_warning();
+    assert_eq!(note.content_warning(), None);
+    assert!(note.is_sensitive());
+    note.sensitive = Some(false);
+    assert!(!note.is_sensitive());
+}
+
+#[test]
+fn content_warning_of_any_object() {
+    let mut image: ObjectSubtypes = serde_json::from_value(serde_json::json!({
+        "type": "Image",
+        "summary": "spoiler"
+    }))
+    .unwrap();
+    assert_eq!(image.content_warning(), Some("spoiler"));
+    image.clear_content_warning();
+    assert!(!image.is_sensitive());
+
+    let mut person = Person::default();
+    person.set_content_warning("nsfw");
+    assert_eq!(person.sensitive, Some(true));
+}
@@ -0,0 +1,52 @@
+use activity_vocabulary::*;
+use activity_vocabulary_core::{Or, Property, Remotable};
+
+#[test]
+fn concrete_into_ancestor_subtypes() {
+    let object: ObjectSubtypes = Note::new("hello").into();
+    assert!(matches!(object, ObjectSubtypes::Note(_)));
+    let object: NoteSubtypes = Note::new("hello").into();
+    assert!(matches!(object, NoteSubtypes::Note(_)));
+    let follow = Follow::new(
+        "https://example.com/users/alice".parse().unwrap(),
+        "https://example.org/users/bob".parse().unwrap(),
+    );
+    let activity: ActivitySubtypes = follow.clone().into();
+    assert!(matches!(activity, ActivitySubtypes::Follow(_)));
+    let object: ObjectSubtypes = follow.into();
+    assert!(matches!(object, ObjectSubtypes::Follow(_)));
+}
+
+#[test]
+fn subtypes_into_ancestor_subtypes() {
+    let activity = ActivitySubtypes::from(Like::default());
+    let object = ObjectSubtypes::from(activity.clone());
+    assert!(matches!(object, ObjectSubtypes::Like(_)));
+    assert_eq!(ActivitySubtypes::try_from(object), Ok(activity));
+
+    let collection = CollectionSubtypes::from(OrderedCollectionPage::default());
+    assert!(matches!(
+        ObjectSubtypes::from(collection),
+        ObjectSubtypes::OrderedCollectionPage(_)
+    ));
+}
+
+#[test]
+fn heterogeneous_property() {
+    let objects: Property<Or<LinkSubtypes, Remotable<ObjectSubtypes>>> = Property(vec![
+        Or::Snd(Remotable::Inline(Note::new("hello").into())),
+        Or::Snd(Remotable::Inline(Image::default().into())),
+        Or::Snd(Remotable::Inline(
+            ActivitySubtypes::from(Announce::default()).into(),
+        )),
+    ]);
+    let types = objects
+        .0
+        .iter()
+        .filter_map(|object| match object {
+            Or::Snd(Remotable::Inline(object)) => Some(object.type_name()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(types, vec!["Note", "Image", "Announce"]);
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "dedup")]
+use activity_vocabulary::{dedup::DedupKey, Follow};
+use activity_vocabulary_core::ObjectUri;
+
+#[test]
+fn dedup_key() {
+    let follow = |json| serde_json::from_value::<Follow>(json).unwrap();
+    let by_id = DedupKey::of(&follow(serde_json::json!({
+        "type": "Follow",
+        "id": "https://example.com/activities/1/",
+        "actor": "https://example.com/users/alice",
+        "object": "https://remote.example/users/bob"
+    })))
+    .unwrap();
+    assert_eq!(
+        by_id,
+        DedupKey::Id(ObjectUri::new(
+            "https://example.com/activities/1".parse().unwrap()
+        ))
+    );
+    assert_eq!(by_id.to_string(), "id:https://example.com/activities/1");
+
+    let anonymous = DedupKey::of(&follow(serde_json::json!({
+        "type": "Follow",
+        "actor": "https://example.com/users/alice",
+        "object": "https://remote.example/users/bob"
+    })))
+    .unwrap();
+    let reordered = DedupKey::of(&follow(serde_json::json!({
+        "object": "https://remote.example/users/bob",
+        "actor": "https://example.com/users/alice",
+        "type": "Follow"
+    })))
+    .unwrap();
+    assert!(matches!(anonymous, DedupKey::Digest(_)));
+    assert_eq!(anonymous, reordered);
+    assert!(anonymous.to_string().starts_with("sha256:"));
+    assert_eq!(anonymous.to_string().len(), "sha256:".len() + 64);
+}
@@ -0,0 +1,160 @@
+#![cfg(feature = "delivery")]
+use std::{
+    collections::HashMap,
+    future::{ready, Future},
+};
+
+use activity_vocabulary::{delivery::*, *};
+use activity_vocabulary_core::Dereferencer;
+
+struct Documents(HashMap<url::Url, serde_json::Value>);
+
+impl Dereferencer for Documents {
+    type Error = String;
+
+    fn dereference(
+        &self,
+        url: &url::Url,
+    ) -> impl Future<Output = Result<serde_json::Value, Self::Error>> + Send {
+        ready(self.0.get(url).cloned().ok_or_else(|| url.to_string()))
+    }
+}
+
+fn actor(id: &str, inbox: &str, shared_inbox: Option<&str>) -> (url::Url, serde_json::Value) {
+    let mut actor = serde_json::json!({
+        "type": "Person",
+        "id": id,
+        "inbox": inbox
+    });
+    if let Some(shared_inbox) = shared_inbox {
+        actor["endpoints"] = serde_json::json!({ "sharedInbox": shared_inbox });
+    }
+    (id.parse().unwrap(), actor)
+}
+
+fn documents() -> Documents {
+    Documents(HashMap::from([
+        actor(
+            "https://a.example/users/bob",
+            "https://a.example/users/bob/inbox",
+            Some("https://a.example/inbox"),
+        ),
+        actor(
+            "https://a.example/users/carol",
+            "https://a.example/users/carol/inbox",
+            Some("https://a.example/inbox"),
+        ),
+        actor(
+            "https://b.example/users/dave",
+            "https://b.example/users/dave/inbox",
+            None,
+        ),
+        actor(
+            "https://example.com/users/alice",
+            "https://example.com/users/alice/inbox",
+            None,
+        ),
+        (
+            "https://example.com/users/alice/followers".parse().unwrap(),
+            serde_json::json!({
+                "type": "OrderedCollection",
+                "id": "https://example.com/users/alice/followers",
+                "first": {
+                    "type": "OrderedCollectionPage",
+                    "orderedItems": ["https://a.example/users/bob"],
+                    "next": "https://example.com/users/alice/followers?page=2"
+                }
+            }),
+        ),
+        (
+            "https://example.com/users/alice/followers?page=2"
+                .parse()
+                .unwrap(),
+            serde_json::json!({
+                "type": "OrderedCollectionPage",
+                "orderedItems": [
+                    "https://a.example/users/carol",
+                    "https://b.example/users/dave"
+                ]
+            }),
+        ),
+    ]))
+}
+
+fn create() -> Create {
+    serde_json::from_value(serde_json::json!({
+        "type": "Create",
+        "actor": "https://example.com/users/alice",
+        "to": "https://www.w3.org/ns/activitystreams#Public",
+        "cc": [
+            "https://example.com/users/alice/followers",
+            "https://b.example/users/dave"
+        ]
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn shared_inbox() {
+    let inboxes = collect_inboxes(&create(), &documents(), &DeliveryOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(
+        inboxes.iter().map(url::Url::as_str).collect::<Vec<_>>(),
+        [
+            "https://a.example/inbox",
+            "https://b.example/users/dave/inbox"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn personal_inbox() {
+    let options = DeliveryOptions {
+        shared_inbox: false,
+        exclude: vec!["https://a.example/users/carol".parse().unwrap()],
+        ..Default::default()
+    };
+    let inboxes = collect_inboxes(&create(), &documents(), &options)
+        .await
+        .unwrap();
+    assert_eq!(
+        inboxes.iter().map(url::Url::as_str).collect::<Vec<_>>(),
+        [
+            "https://a.example/users/bob/inbox",
+            "https://b.example/users/dave/inbox"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn exclude_inline() {
+    let create: Create = serde_json::from_value(serde_json::json!({
+        "type": "Create",
+        "actor": "https://example.com/users/alice",
+        "to": [
+            {
+                "type": "Person",
+                "id": "https://example.com/users/alice",
+                "inbox": "https://example.com/users/alice/inbox"
+            },
+            {
+                "type": "Person",
+                "id": "https://b.example/users/dave",
+                "inbox": "https://b.example/users/dave/inbox"
+            }
+        ]
+    }))
+    .unwrap();
+    let options = DeliveryOptions {
+        exclude: vec!["https://EXAMPLE.com:443/users/alice/".parse().unwrap()],
+        ..Default::default()
+    };
+    let inboxes = collect_inboxes(&create, &documents(), &options)
+        .await
+        .unwrap();
+    assert_eq!(
+        inboxes.iter().map(url::Url::as_str).collect::<Vec<_>>(),
+        ["https://b.example/users/dave/inbox"]
+    );
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "diesel")]
+use activity_vocabulary::{Note, ObjectSubtypes};
+use activity_vocabulary_core::{Context, WithContext};
+use diesel::{
+    deserialize::{FromSql, FromSqlRow},
+    expression::AsExpression,
+    pg::Pg,
+    serialize::ToSql,
+    sql_types::Jsonb,
+    IntoSql,
+};
+
+fn storable<T>()
+where
+    T: AsExpression<Jsonb> + ToSql<Jsonb, Pg> + FromSql<Jsonb, Pg> + FromSqlRow<Jsonb, Pg>,
+    for<'a> &'a T: AsExpression<Jsonb>,
+{
+}
+
+#[test]
+fn jsonb_types() {
+    storable::<WithContext<Note>>();
+    storable::<ObjectSubtypes>();
+    storable::<WithContext<ObjectSubtypes>>();
+}
+
+#[test]
+fn bind() {
+    let note = WithContext {
+        context: Some(Context::activitystreams()),
+        body: ObjectSubtypes::Note(Note {
+            id: Some("https://example.com/notes/1".parse().unwrap()),
+            ..Default::default()
+        }),
+    };
+    let query = diesel::select(note.into_sql::<Jsonb>());
+    let sql = diesel::debug_query::<Pg, _>(&query).to_string();
+    assert!(sql.starts_with("SELECT $1"), "{sql}");
+    assert!(sql.contains(r#"path: "/notes/1""#), "{sql}");
+}
@@ -0,0 +1,51 @@
+use activity_vocabulary::*;
+
+fn alice() -> url::Url {
+    "https://example.com/users/alice".parse().unwrap()
+}
+
+#[test]
+fn summary() {
+    let mut note = Note::new("hello");
+    note.id = Some("https://example.com/notes/1".parse().unwrap());
+    let mut create = Create::new(alice(), note);
+    create.id = Some("https://example.com/activities/1".parse().unwrap());
+    assert_eq!(
+        create.to_string(),
+        "Create(id=https://example.com/activities/1, actor=https://example.com/users/alice, \
+         object=Note(id=https://example.com/notes/1))"
+    );
+    assert_eq!(
+        ObjectSubtypes::from(create.clone()).to_string(),
+        create.to_string()
+    );
+}
+
+#[test]
+fn summary_without_properties() {
+    assert_eq!(Note::new("hello").to_string(), "Note()");
+}
+
+#[test]
+fn summary_of_many() {
+    let mut like = Like::new(alice(), "https://example.com/notes/1".parse().unwrap());
+    like.object.0.extend(like.object.0.clone());
+    assert_eq!(
+        like.to_string(),
+        "Like(actor=https://example.com/users/alice, \
+         object=[https://example.com/notes/1, https://example.com/notes/1])"
+    );
+}
+
+#[test]
+fn pretty_json() {
+    let note = Note::new("hello");
+    assert_eq!(
+        note.to_json_string_pretty(),
+        "{\n  \"content\": \"hello\",\n  \"type\": \"Note\"\n}"
+    );
+    assert_eq!(
+        ObjectSubtypes::from(note.clone()).to_json_string_pretty(),
+        note.to_json_string_pretty()
+    );
+}
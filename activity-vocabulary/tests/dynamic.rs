@@ -0,0 +1,66 @@
+use activity_vocabulary::*;
+use activity_vocabulary_core::dynamic::{DynObject, DynValue};
+use serde_json::json;
+
+const AS: &str = "https://www.w3.org/ns/activitystreams#";
+
+#[test]
+fn read_with_context() {
+    let object: DynObject = serde_json::from_value(json!({
+        "@context": [
+            "https://www.w3.org/ns/activitystreams",
+            { "toot": "http://joinmastodon.org/ns#", "featured": "toot:featured" }
+        ],
+        "type": "Person",
+        "id": "https://example.com/users/alice",
+        "nameMap": { "en": "Alice", "ja": "アリス" },
+        "featured": "https://example.com/users/alice/featured",
+        "icon": { "type": "Image", "url": "https://example.com/alice.png" }
+    }))
+    .unwrap();
+    assert_eq!(object.id(), Some("https://example.com/users/alice"));
+    assert!(object.has_type("Person"));
+    let name = object.get(&format!("{AS}name")).unwrap();
+    assert!(name.values.is_empty());
+    assert_eq!(name.per_lang["ja"], vec![DynValue::from("アリス")]);
+    assert_eq!(
+        object.values("http://joinmastodon.org/ns#featured")[0].id(),
+        Some("https://example.com/users/alice/featured")
+    );
+    let icon = object.values(&format!("{AS}icon"))[0].as_node().unwrap();
+    assert!(icon.has_type("Image"));
+
+    let json = object.to_json(None);
+    assert_eq!(json["nameMap"]["ja"], "アリス");
+    assert_eq!(
+        json["http://joinmastodon.org/ns#featured"],
+        "https://example.com/users/alice/featured"
+    );
+    assert_eq!(json["icon"]["url"], "https://example.com/alice.png");
+}
+
+#[test]
+fn typed_round_trip() {
+    let mut note = Note::new("hello");
+    note.id = Some("https://example.com/notes/1".parse().unwrap());
+    let mut object = DynObject::from(note.clone());
+    assert_eq!(object.types().collect::<Vec<_>>(), vec!["Note"]);
+    assert_eq!(
+        object.values(&format!("{AS}content")),
+        &[DynValue::from("hello")]
+    );
+
+    object.push("https://example.com/ns#extension", "kept");
+    assert_eq!(
+        object.to_json(None)["https://example.com/ns#extension"],
+        "kept"
+    );
+    assert_eq!(Note::try_from(object).unwrap(), note);
+}
+
+#[test]
+fn invalid_typed() {
+    let mut object = DynObject::new();
+    object.push("@id", json!(42));
+    assert!(Note::try_from(object).is_err());
+}
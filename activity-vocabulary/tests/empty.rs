@@ -0,0 +1,66 @@
+use activity_vocabulary::*;
+use activity_vocabulary_core::{empty_behavior, with_empty_behavior, EmptyBehavior, Property};
+use serde_json::{json, Value};
+
+fn note() -> Note {
+    serde_json::from_value(json!({ "type": "Note", "content": "hello" })).unwrap()
+}
+
+#[test]
+fn omit() {
+    assert_eq!(empty_behavior(), EmptyBehavior::Omit);
+    assert_eq!(
+        serde_json::to_value(note()).unwrap(),
+        json!({ "type": "Note", "content": "hello" })
+    );
+    assert_eq!(
+        serde_json::to_value(Property::<url::Url>::default()).unwrap(),
+        Value::Null
+    );
+}
+
+#[test]
+fn array() {
+    let (note, property) = with_empty_behavior(EmptyBehavior::Array, || {
+        (
+            serde_json::to_value(note()).unwrap(),
+            serde_json::to_value(Property::<url::Url>::default()).unwrap(),
+        )
+    });
+    assert_eq!(note["to"], json!([]));
+    assert_eq!(note["content"], json!("hello"));
+    assert_eq!(note.get("summary"), None);
+    assert_eq!(property, json!([]));
+    assert_eq!(empty_behavior(), EmptyBehavior::Omit);
+}
+
+#[test]
+fn null() {
+    let note = with_empty_behavior(EmptyBehavior::Null, || {
+        serde_json::to_value(note()).unwrap()
+    });
+    assert_eq!(note["to"], Value::Null);
+    assert!(note.get("to").is_some());
+    assert_eq!(note.get("summary"), None);
+    assert_eq!(
+        serde_json::from_value::<Note>(note).unwrap().to,
+        Property::default()
+    );
+}
+
+#[test]
+fn is_empty() {
+    assert!(Object::default().is_empty());
+    assert!(Image::default().is_empty());
+    let image: Image = serde_json::from_value(json!({
+        "type": "Image",
+        "nameMap": { "en": "avatar" }
+    }))
+    .unwrap();
+    assert!(!image.is_empty());
+    // empty even if an empty property is written out, as it reads back as nothing
+    let image = with_empty_behavior(EmptyBehavior::Null, || {
+        serde_json::to_value(Image::default()).unwrap()
+    });
+    assert!(serde_json::from_value::<Image>(image).unwrap().is_empty());
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "events")]
+use activity_vocabulary::{event::JoinMode, *};
+use activity_vocabulary_core::{Or, Remotable};
+
+#[test]
+fn mobilizon_event() {
+    let input = serde_json::json!({
+        "type": "Event",
+        "name": "Rust meetup",
+        "joinMode": "restricted",
+        "participantCount": 12,
+        "maximumAttendeeCapacity": 30,
+        "anonymousParticipationEnabled": false,
+        "timezone": "Europe/Paris",
+        "location": {
+            "type": "Place",
+            "name": "Café",
+            "address": {
+                "type": "PostalAddress",
+                "addressCountry": "France",
+                "addressLocality": "Lyon",
+                "postalCode": "69001",
+                "streetAddress": "1 rue de la République"
+            }
+        }
+    });
+    let event: Event = serde_json::from_value(input.clone()).unwrap();
+    assert_eq!(event.join_mode, Some(JoinMode::Restricted));
+    assert_eq!(event.participant_count, Some(12));
+    assert_eq!(event.maximum_attendee_capacity, Some(30));
+    assert_eq!(event.anonymous_participation_enabled, Some(false));
+    assert_eq!(event.timezone.as_deref(), Some("Europe/Paris"));
+    let Or::Snd(Remotable::Inline(ObjectSubtypes::Place(place))) = &event.location.0[0] else {
+        panic!("location is not a Place");
+    };
+    let Some(PostalAddressSubtypes::PostalAddress(address)) = &place.address else {
+        panic!("no address");
+    };
+    assert_eq!(address.address_locality.as_deref(), Some("Lyon"));
+    assert_eq!(serde_json::to_value(&event).unwrap(), input);
+}
+
+#[test]
+fn unknown_join_mode() {
+    let event: Event =
+        serde_json::from_value(serde_json::json!({ "type": "Event", "joinMode": "open" })).unwrap();
+    assert_eq!(event.join_mode, Some(JoinMode::Other("open".to_owned())));
+}
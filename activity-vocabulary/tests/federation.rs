@@ -0,0 +1,148 @@
+#![cfg(feature = "activitypub-federation")]
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use activity_vocabulary::{federation::*, Note, Person};
+use activity_vocabulary_core::ObjectId as _;
+use activitypub_federation::{
+    config::{Data, FederationConfig},
+    error::Error,
+    fetch::object_id::ObjectId,
+    traits::{Actor as _, Object},
+};
+use url::Url;
+
+const PUBLIC_KEY: &str = "-----BEGIN PUBLIC KEY-----\nMIGfMA0GCSqGSIb3DQEBAQUAA4GNADCBiQKBgQC4P9p+fxQNuq0DWbBkKfYcsFW6\njkyf1E05vIbk2HbITIBrvR2qDpDUK7GoDjqkwiyAdzelZ+iRspmUP1r8+RJmbEUd\nIIYDx1rSmdquift8cFdLG99H+XkGkjCe4MVe9srK0bxzc4wTIdn+aRYWips+YP4q\nJ/pwhuWkuKqlyAGBwQIDAQAB\n-----END PUBLIC KEY-----";
+
+#[derive(Clone, Default)]
+struct Documents(Arc<Mutex<HashMap<Url, serde_json::Value>>>);
+
+impl<T> Repository<T> for Documents
+where
+    T: serde::Serialize + serde::de::DeserializeOwned + activity_vocabulary_core::ObjectId,
+    T: Send + Sync + 'static,
+{
+    type Error = Error;
+
+    async fn read(&self, id: &Url) -> Result<Option<T>, Error> {
+        let documents = self.0.lock().unwrap();
+        Ok(documents
+            .get(id)
+            .map(|json| serde_json::from_value(json.clone()).unwrap()))
+    }
+
+    async fn write(&self, object: &T) -> Result<(), Error> {
+        let id = object.object_id().unwrap().clone();
+        let json = serde_json::to_value(object).unwrap();
+        self.0.lock().unwrap().insert(id, json);
+        Ok(())
+    }
+}
+
+async fn data() -> Data<Documents> {
+    FederationConfig::builder()
+        .domain("example.com")
+        .app_data(Documents::default())
+        .build()
+        .await
+        .unwrap()
+        .to_request_data()
+}
+
+fn person() -> serde_json::Value {
+    serde_json::json!({
+        "type": "Person",
+        "id": "https://example.com/users/alice",
+        "inbox": "https://example.com/users/alice/inbox",
+        "endpoints": { "sharedInbox": "https://example.com/inbox" },
+        "publicKey": {
+            "id": "https://example.com/users/alice#main-key",
+            "owner": "https://example.com/users/alice",
+            "publicKeyPem": PUBLIC_KEY
+        }
+    })
+}
+
+#[tokio::test]
+async fn actor() {
+    let data = data().await;
+    let id: Url = "https://example.com/users/alice".parse().unwrap();
+    let json = serde_json::from_value::<Person>(person()).unwrap();
+    FederatedActor::<Person, Documents>::verify(&json, &id, &data)
+        .await
+        .unwrap();
+    let actor = FederatedActor::<Person, Documents>::from_json(json, &data)
+        .await
+        .unwrap();
+    assert_eq!(actor.id(), id);
+    assert_eq!(
+        actor.inbox().as_str(),
+        "https://example.com/users/alice/inbox"
+    );
+    assert_eq!(
+        actor.shared_inbox_or_inbox().as_str(),
+        "https://example.com/inbox"
+    );
+    assert_eq!(actor.public_key_pem(), PUBLIC_KEY);
+    assert_eq!(actor.private_key_pem(), None);
+
+    let read = ObjectId::<FederatedActor<Person, Documents>>::from(id)
+        .dereference_local(&data)
+        .await
+        .unwrap();
+    let json = serde_json::to_value(read.into_json(&data).await.unwrap()).unwrap();
+    assert_eq!(json, person());
+}
+
+#[tokio::test]
+async fn verify() {
+    let data = data().await;
+    let mut json = serde_json::from_value::<Person>(person()).unwrap();
+    assert!(matches!(
+        FederatedActor::<Person, Documents>::verify(
+            &json,
+            &"https://remote.example/".parse().unwrap(),
+            &data
+        )
+        .await,
+        Err(Error::UrlVerificationError(_))
+    ));
+    json.public_key = None;
+    assert!(matches!(
+        FederatedActor::<Person, Documents>::verify(
+            &json,
+            &"https://example.com/".parse().unwrap(),
+            &data
+        )
+        .await,
+        Err(Error::UrlVerificationError(_))
+    ));
+    assert!(FederatedActor::<Person, Documents>::new(json).is_err());
+}
+
+#[tokio::test]
+async fn object() {
+    let data = data().await;
+    let note = serde_json::json!({
+        "type": "Note",
+        "id": "https://example.com/notes/1",
+        "attributedTo": "https://example.com/users/alice",
+        "content": "hello"
+    });
+    let json = serde_json::from_value::<Note>(note.clone()).unwrap();
+    let id = json.object_id().unwrap().clone();
+    FederatedObject::<Note, Documents>::verify(&json, &id, &data)
+        .await
+        .unwrap();
+    FederatedObject::<Note, Documents>::from_json(json, &data)
+        .await
+        .unwrap();
+    let read = ObjectId::<FederatedObject<Note, Documents>>::from(id)
+        .dereference_local(&data)
+        .await
+        .unwrap();
+    let json = serde_json::to_value(read.into_json(&data).await.unwrap()).unwrap();
+    assert_eq!(json, note);
+}
@@ -0,0 +1,79 @@
+use activity_vocabulary::{follow::*, *};
+
+fn url(s: &str) -> url::Url {
+    s.parse().unwrap()
+}
+
+fn bot() -> Service {
+    Service {
+        id: Some(url("https://example.com/users/bot").into()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn accept() {
+    let mut follow = Follow::new(
+        url("https://example.org/users/alice"),
+        url("https://example.com/users/bot/"),
+    );
+    follow.id = Some(url("https://example.org/follows/1").into());
+    let accepted =
+        accept_follow(follow.clone(), &bot(), url("https://example.com/accepts/1")).unwrap();
+    assert_eq!(accepted.follower, url("https://example.org/users/alice"));
+    let accept = serde_json::to_value(&accepted.accept).unwrap();
+    assert_eq!(
+        accept,
+        serde_json::json!({
+            "type": "Accept",
+            "id": "https://example.com/accepts/1",
+            "actor": "https://example.com/users/bot",
+            "to": "https://example.org/users/alice",
+            "object": serde_json::to_value(&follow).unwrap()
+        })
+    );
+}
+
+#[test]
+fn reject_others() {
+    let id = || url("https://example.com/accepts/1");
+    let other = Follow::new(
+        url("https://example.org/users/alice"),
+        url("https://example.com/users/carol"),
+    );
+    assert_eq!(
+        accept_follow(other, &bot(), id()),
+        Err(FollowRequestError::NotFollowing)
+    );
+    let anonymous = Follow {
+        actor: Default::default(),
+        ..Follow::new(
+            url("https://example.org/users/alice"),
+            url("https://example.com/users/bot"),
+        )
+    };
+    assert_eq!(
+        accept_follow(anonymous, &bot(), id()),
+        Err(FollowRequestError::MissingFollower)
+    );
+    let follow = Follow::new(
+        url("https://example.org/users/alice"),
+        url("https://example.com/users/bot"),
+    );
+    assert_eq!(
+        accept_follow(follow, &Service::default(), id()),
+        Err(FollowRequestError::MissingId)
+    );
+}
+
+#[test]
+fn accept_inline() {
+    let follow: Follow = serde_json::from_value(serde_json::json!({
+        "type": "Follow",
+        "actor": { "type": "Person", "id": "https://example.org/users/alice" },
+        "object": { "type": "Service", "id": "https://example.com/users/bot" }
+    }))
+    .unwrap();
+    let accepted = accept_follow(follow, &bot(), url("https://example.com/accepts/1")).unwrap();
+    assert_eq!(accepted.follower, url("https://example.org/users/alice"));
+}
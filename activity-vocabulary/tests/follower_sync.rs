@@ -0,0 +1,65 @@
+#![cfg(feature = "follower-sync")]
+use activity_vocabulary::follower_sync::*;
+
+fn followers() -> Vec<url::Url> {
+    [
+        "https://remote.example/users/bob",
+        "https://other.example/users/dave",
+        "https://remote.example/users/carol",
+    ]
+    .iter()
+    .map(|follower| follower.parse().unwrap())
+    .collect()
+}
+
+const DIGEST: &str = "0f7fa9dcc2c54ce5c4aca2d551e6f7be697bff4d2dc30029c9b8565608c9d096";
+
+#[test]
+fn header() {
+    let header = CollectionSynchronization::new(
+        "https://example.com/users/alice/followers".parse().unwrap(),
+        "https://example.com/users/alice/followers_synchronization"
+            .parse()
+            .unwrap(),
+        &followers(),
+        "remote.example",
+    );
+    let value = format!(
+        r#"collectionId="https://example.com/users/alice/followers", url="https://example.com/users/alice/followers_synchronization", digest="{DIGEST}""#
+    );
+    assert_eq!(header.to_string(), value);
+    assert_eq!(value.parse::<CollectionSynchronization>().unwrap(), header);
+
+    assert!(header.is_synchronized(followers().iter().rev(), "remote.example"));
+    assert!(!header.is_synchronized(&followers()[..2], "remote.example"));
+    assert_eq!(
+        "collectionId=\"https://example.com/users/alice/followers\", digest=\"00\""
+            .parse::<CollectionSynchronization>(),
+        Err(CollectionSynchronizationError::MalformedDigest(
+            "00".to_owned()
+        ))
+    );
+}
+
+#[test]
+fn partial_collection() {
+    let collection = partial_followers(
+        "https://example.com/users/alice/followers_synchronization"
+            .parse()
+            .unwrap(),
+        &followers(),
+        "remote.example",
+    );
+    assert_eq!(
+        serde_json::to_value(&collection).unwrap(),
+        serde_json::json!({
+            "type": "OrderedCollection",
+            "id": "https://example.com/users/alice/followers_synchronization",
+            "totalItems": 2,
+            "orderedItems": [
+                "https://remote.example/users/bob",
+                "https://remote.example/users/carol"
+            ]
+        })
+    );
+}
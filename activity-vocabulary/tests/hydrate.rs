@@ -0,0 +1,156 @@
+use std::{
+    collections::HashMap,
+    future::{ready, Future},
+};
+
+use activity_vocabulary::{hydrate::*, normalize::normalize, *};
+use activity_vocabulary_core::{Dereferencer, ObjectId, Or, Property, Remotable, ResolveError};
+
+struct Documents(HashMap<url::Url, serde_json::Value>);
+
+impl Dereferencer for Documents {
+    type Error = String;
+
+    fn dereference(
+        &self,
+        url: &url::Url,
+    ) -> impl Future<Output = Result<serde_json::Value, Self::Error>> + Send {
+        ready(self.0.get(url).cloned().ok_or_else(|| url.to_string()))
+    }
+
+    /// The documents under `/deleted/` are gone.
+    fn is_gone(error: &String) -> bool {
+        error.contains("/deleted/")
+    }
+}
+
+fn url(url: &str) -> url::Url {
+    url.parse().unwrap()
+}
+
+fn author() -> Person {
+    Person {
+        object_type: Property(vec!["Person".to_owned()]),
+        id: Some(url("https://example.com/users/alice").into()),
+        ..Default::default()
+    }
+}
+
+fn create() -> Create {
+    let mut note = Note::new("hello");
+    note.id = Some(url("https://example.com/notes/1").into());
+    note.attributed_to = Property(vec![Or::Snd(Remotable::Inline(author().into()))]);
+    Create::new(url("https://example.com/users/alice"), note)
+}
+
+/// [create] with also the actor inlined, as it is found by id as well.
+fn inlined() -> Create {
+    Create {
+        actor: Property(vec![Or::Snd(Remotable::Inline(author().into()))]),
+        ..create()
+    }
+}
+
+fn table() -> (Create, HashMap<url::Url, ObjectSubtypes>) {
+    let (create, extracted) = normalize(create());
+    let table = extracted
+        .into_iter()
+        .map(|object| (object.object_id().unwrap().clone(), object))
+        .collect();
+    (create, table)
+}
+
+#[test]
+fn inverse_of_normalize() {
+    let (normalized, table) = table();
+    let hydrated = hydrate(normalized, 2, |id| table.get(id).cloned());
+    assert_eq!(hydrated, inlined());
+}
+
+#[test]
+fn limited_depth() {
+    let (normalized, table) = table();
+    assert_eq!(
+        hydrate(normalized.clone(), 0, |id| table.get(id).cloned()),
+        normalized
+    );
+    let hydrated = hydrate(normalized, 1, |id| table.get(id).cloned());
+    let Some(Or::Snd(Remotable::Inline(ObjectSubtypes::Note(note)))) = hydrated.object.0.first()
+    else {
+        panic!("the note is not inlined");
+    };
+    assert_eq!(
+        note.attributed_to,
+        Property(vec![Or::Snd(Remotable::Remote(url(
+            "https://example.com/users/alice"
+        )))])
+    );
+    assert_eq!(hydrated.actor, inlined().actor);
+}
+
+#[tokio::test]
+async fn with_dereferencer() {
+    let (normalized, table) = table();
+    let documents = Documents(
+        table
+            .into_iter()
+            .map(|(id, object)| (id, serde_json::to_value(object).unwrap()))
+            .collect(),
+    );
+    let hydrated = hydrate_with_dereferencer(normalized, 2, &documents)
+        .await
+        .unwrap();
+    assert_eq!(hydrated, inlined());
+}
+
+#[tokio::test]
+async fn dereference_error() {
+    let (normalized, _) = table();
+    let documents = Documents(HashMap::new());
+    assert!(matches!(
+        hydrate_with_dereferencer(normalized, 2, &documents).await,
+        Err(ResolveError::Dereference(_))
+    ));
+}
+
+#[tokio::test]
+async fn keep_gone_objects_remote() {
+    let mut note = Note::new("hello");
+    note.attributed_to = Property(vec![Or::Snd(Remotable::Remote(url(
+        "https://example.com/deleted/bob",
+    )))]);
+    let hydrated = hydrate_with_dereferencer(note.clone(), 2, &Documents(HashMap::new()))
+        .await
+        .unwrap();
+    assert_eq!(hydrated, note);
+}
+
+#[tokio::test]
+async fn skip_public() {
+    let mut note = Note::new("hello");
+    note.to = Property(vec![Or::Snd(Remotable::Remote(url(
+        "https://www.w3.org/ns/activitystreams#Public",
+    )))]);
+    let hydrated = hydrate_with_dereferencer(note.clone(), 2, &Documents(HashMap::new()))
+        .await
+        .unwrap();
+    assert_eq!(hydrated, note);
+}
+
+#[test]
+fn hydrate_closed() {
+    let question: Question = serde_json::from_value(serde_json::json!({
+        "type": "Question",
+        "closed": "https://example.com/notes/1"
+    }))
+    .unwrap();
+    let mut note = Note::new("hello");
+    note.id = Some(url("https://example.com/notes/1").into());
+    let hydrated = hydrate(question, 1, |id| {
+        (id == &url("https://example.com/notes/1")).then(|| note.clone().into())
+    });
+    assert_eq!(
+        serde_json::to_value(&hydrated).unwrap()["closed"]["content"],
+        "hello"
+    );
+}
@@ -0,0 +1,106 @@
+use std::collections::{HashMap, HashSet};
+
+use activity_vocabulary::*;
+use activity_vocabulary_core::{
+    eq_by_id, eq_by_id_with, object_uri::Normalization, IdKey, ObjectUri, Remotable,
+};
+
+fn note(id: Option<&str>, content: &str) -> Note {
+    let mut note = Note::new(content);
+    note.id = id.map(|id| id.parse().unwrap());
+    note
+}
+
+#[test]
+fn equal_by_id() {
+    let a = note(Some("https://example.com/notes/1"), "hello");
+    let b = note(Some("https://example.com/notes/1"), "edited");
+    assert_ne!(a, b);
+    assert!(eq_by_id(&a, &b));
+    assert!(eq_by_id(&ObjectSubtypes::from(a.clone()), &b));
+    assert!(eq_by_id(
+        &Remotable::<Note>::Remote("https://example.com/notes/1".parse().unwrap()),
+        &a
+    ));
+    assert!(!eq_by_id(
+        &a,
+        &note(Some("https://example.com/notes/2"), "hello")
+    ));
+}
+
+#[test]
+fn anonymous_objects_differ() {
+    let a = note(None, "hello");
+    assert!(!eq_by_id(&a, &a.clone()));
+    assert!(IdKey::new(a).is_none());
+}
+
+#[test]
+fn dedup() {
+    let notes = [
+        note(Some("https://example.com/notes/1"), "hello"),
+        note(Some("https://example.com/notes/2"), "world"),
+        note(Some("https://example.com/notes/1/"), "edited"),
+    ];
+    let set = notes
+        .iter()
+        .cloned()
+        .filter_map(IdKey::new)
+        .collect::<HashSet<_>>();
+    assert_eq!(set.len(), 2);
+
+    let map = notes
+        .into_iter()
+        .filter_map(IdKey::new)
+        .map(|key| (key, ()))
+        .collect::<HashMap<_, _>>();
+    let id: ObjectUri = "https://example.com/notes/2/".parse().unwrap();
+    let (key, ()) = map.get_key_value(&id).unwrap();
+    assert_eq!(key.id().as_str(), "https://example.com/notes/2");
+    assert_eq!(
+        key.get(),
+        &note(Some("https://example.com/notes/2"), "world")
+    );
+}
+
+#[test]
+fn normalized_ids() {
+    let uri = |uri: &str| uri.parse::<ObjectUri>().unwrap();
+    assert_eq!(
+        uri("https://example.com/users/alice/"),
+        uri("https://example.com:443/users/alice")
+    );
+    assert_eq!(
+        uri("https://example.com/users/%7Ealice%2f"),
+        uri("https://example.com/users/~alice%2F")
+    );
+    assert_ne!(
+        uri("https://example.com/users/alice"),
+        uri("https://example.com/users/alice#main-key")
+    );
+    assert_eq!(
+        HashSet::from([uri("https://example.com/a/"), uri("https://example.com/a")]).len(),
+        1
+    );
+    assert_eq!(
+        uri("https://example.com/users/alice/").to_string(),
+        "https://example.com/users/alice/"
+    );
+
+    let actor = note(Some("https://example.com/users/alice/"), "hello");
+    let key = note(Some("https://example.com/users/alice#main-key"), "hello");
+    assert!(eq_by_id(
+        &actor,
+        &note(Some("https://example.com/users/alice"), "hello")
+    ));
+    assert!(!eq_by_id(&actor, &key));
+    let normalization = Normalization {
+        strip_fragment: true,
+        ..Default::default()
+    };
+    assert!(eq_by_id_with(&actor, &key, &normalization));
+    assert!(uri("https://example.com/users/alice#main-key").matches(
+        &"https://example.com/users/alice/".parse().unwrap(),
+        &normalization
+    ));
+}
@@ -0,0 +1,45 @@
+use activity_vocabulary::*;
+use serde_json::json;
+
+#[test]
+fn round_trip() {
+    let document = json!({
+        "@id": "https://example.com/notes/1",
+        "@type": "Note",
+        "content": "hello",
+    });
+    let note: Note = serde_json::from_value(document.clone()).unwrap();
+    assert_eq!(
+        note.id,
+        Some("https://example.com/notes/1".parse().unwrap())
+    );
+    assert_eq!(serde_json::to_value(&note).unwrap(), document);
+
+    let object: ObjectSubtypes = serde_json::from_value(document.clone()).unwrap();
+    assert_eq!(object.type_name(), "Note");
+    assert_eq!(serde_json::to_value(&object).unwrap(), document);
+    assert_eq!(object, ObjectSubtypes::Note(note));
+}
+
+#[test]
+fn nested() {
+    let document = json!({
+        "type": "Create",
+        "@id": "https://example.com/activities/1",
+        "actor": "https://example.com/users/alice",
+        "object": { "@type": "Note", "id": "https://example.com/notes/1" },
+    });
+    let create: ObjectSubtypes = serde_json::from_value(document.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&create).unwrap(), document);
+}
+
+#[test]
+fn short_names() {
+    let document = json!({ "id": "https://example.com/notes/1", "type": "Note" });
+    let object: ObjectSubtypes = serde_json::from_value(document.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&object).unwrap(), document);
+    assert_eq!(
+        serde_json::to_string(&ObjectSubtypes::Note(Note::default())).unwrap(),
+        r#"{"type":"Note"}"#
+    );
+}
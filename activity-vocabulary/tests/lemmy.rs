@@ -0,0 +1,61 @@
+#![cfg(feature = "lemmy")]
+use activity_vocabulary::*;
+
+#[test]
+fn lock_post() {
+    let input = serde_json::json!({
+        "type": "Lock",
+        "id": "https://lemmy.example/activities/lock/1",
+        "actor": "https://lemmy.example/u/mod",
+        "object": "https://lemmy.example/post/1",
+        "audience": "https://lemmy.example/c/rust",
+        "to": "https://www.w3.org/ns/activitystreams#Public",
+        "cc": "https://lemmy.example/c/rust"
+    });
+    let lock: ObjectSubtypes = serde_json::from_value(input.clone()).unwrap();
+    assert!(matches!(lock, ObjectSubtypes::Lock(_)));
+    assert_eq!(serde_json::to_value(&lock).unwrap(), input);
+}
+
+#[test]
+fn group_moderation() {
+    let group: Group = serde_json::from_value(serde_json::json!({
+        "type": "Group",
+        "id": "https://lemmy.example/c/rust",
+        "inbox": "https://lemmy.example/c/rust/inbox",
+        "moderators": "https://lemmy.example/c/rust/moderators",
+        "postingRestrictedToMods": true
+    }))
+    .unwrap();
+    assert_eq!(
+        group.moderators.as_ref().map(url::Url::as_str),
+        Some("https://lemmy.example/c/rust/moderators")
+    );
+    assert_eq!(group.posting_restricted_to_mods, Some(true));
+
+    let input = serde_json::json!({
+        "type": "Block",
+        "actor": "https://lemmy.example/u/mod",
+        "object": "https://remote.example/u/spammer",
+        "target": "https://lemmy.example/c/rust",
+        "removeData": true,
+        "expires": "2024-01-01T00:00:00Z"
+    });
+    let block: Block = serde_json::from_value(input.clone()).unwrap();
+    assert_eq!(block.remove_data, Some(true));
+    assert!(block.expires.is_some());
+    assert_eq!(serde_json::to_value(&block).unwrap(), input);
+}
+
+#[test]
+fn page_post() {
+    let page: Page = serde_json::from_value(serde_json::json!({
+        "type": "Page",
+        "name": "Rust 2.0 released",
+        "stickied": true,
+        "commentsEnabled": false
+    }))
+    .unwrap();
+    assert_eq!(page.stickied, Some(true));
+    assert_eq!(page.comments_enabled, Some(false));
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "lenient")]
+use activity_vocabulary::{lenient::*, *};
+
+fn create() -> serde_json::Value {
+    serde_json::json!({
+        "type": "Create",
+        "actor": {
+            "type": "Person",
+            "id": "https://example.com/users/alice",
+            "inbox": "https://example.com/users/alice/inbox",
+            "outbox": "https://example.com/users/alice/outbox",
+            "manuallyApprovesFollowers": 1,
+            "published": 1700000000
+        },
+        "published": 1700000000000i64,
+        "to": ["Public"],
+        "object": {
+            "type": "Note",
+            "content": "hello",
+            "inReplyTo": "",
+            "tag": ["", "https://example.com/tags/rust"]
+        }
+    })
+}
+
+#[test]
+fn strict_fails() {
+    assert!(serde_json::from_value::<Create>(create()).is_err());
+}
+
+#[test]
+fn coerce_quirks() {
+    let mut document = create();
+    let mut quirks = coerce(&mut document);
+    quirks.sort_by_key(|quirk| format!("{quirk:?}"));
+    assert_eq!(
+        quirks,
+        [
+            Quirk::Boolean {
+                property: "manuallyApprovesFollowers".to_owned()
+            },
+            Quirk::EmptyUrl {
+                property: "inReplyTo".to_owned()
+            },
+            Quirk::EmptyUrl {
+                property: "tag".to_owned()
+            },
+            Quirk::Timestamp {
+                property: "published".to_owned()
+            },
+            Quirk::Timestamp {
+                property: "published".to_owned()
+            },
+        ]
+    );
+    assert_eq!(document["actor"]["manuallyApprovesFollowers"], true);
+    assert_eq!(document["actor"]["published"], "2023-11-14T22:13:20Z");
+    assert_eq!(document["published"], "2023-11-14T22:13:20Z");
+    assert_eq!(document["object"].get("inReplyTo"), None);
+    assert_eq!(
+        document["object"]["tag"],
+        serde_json::json!(["https://example.com/tags/rust"])
+    );
+}
+
+#[test]
+fn lenient() {
+    let mut document = create();
+    coerce(&mut document);
+    let Lenient(create) = serde_json::from_value::<Lenient<Create>>(create()).unwrap();
+    assert_eq!(create, serde_json::from_value::<Create>(document).unwrap());
+}
+
+#[test]
+fn no_quirks() {
+    let mut document = serde_json::to_value(Note::new("hello")).unwrap();
+    assert_eq!(coerce(&mut document), []);
+}
@@ -0,0 +1,78 @@
+use std::future::{ready, Future};
+
+use activity_vocabulary::{link::LinkOrObject, *};
+use activity_vocabulary_core::{Dereferencer, Or, Remotable, ResolveError};
+
+/// Serves a note of the requested id, except under `/missing`.
+struct Notes;
+
+impl Dereferencer for Notes {
+    type Error = String;
+
+    fn dereference(
+        &self,
+        url: &url::Url,
+    ) -> impl Future<Output = Result<serde_json::Value, Self::Error>> + Send {
+        ready(if url.path().starts_with("/missing") {
+            Err(url.to_string())
+        } else {
+            Ok(serde_json::json!({ "type": "Note", "id": url, "content": "fetched" }))
+        })
+    }
+}
+
+fn parse(value: serde_json::Value) -> LinkOrObject {
+    serde_json::from_value(value).unwrap()
+}
+
+fn note_url() -> url::Url {
+    "https://example.com/notes/1".parse().unwrap()
+}
+
+#[test]
+fn accessors() {
+    let link = parse(serde_json::json!({ "type": "Link", "href": "https://example.com/notes/1" }));
+    assert_eq!(link.href(), Some(&note_url()));
+    assert_eq!(link.id_or_href(), Some(&note_url()));
+    assert!(link.as_object().is_none());
+
+    let remote = parse(serde_json::json!("https://example.com/notes/1"));
+    assert_eq!(remote.href(), None);
+    assert_eq!(remote.id_or_href(), Some(&note_url()));
+    assert!(remote.as_object().is_none());
+
+    let inline = parse(serde_json::json!({ "type": "Note", "id": "https://example.com/notes/1" }));
+    assert_eq!(inline.href(), None);
+    assert_eq!(inline.id_or_href(), Some(&note_url()));
+    assert!(matches!(inline.as_object(), Some(ObjectSubtypes::Note(_))));
+}
+
+#[tokio::test]
+async fn resolve() {
+    let inline: LinkOrObject = Or::Snd(Remotable::Inline(Note::new("inline").into()));
+    let object = inline.resolve(&Notes).await.unwrap();
+    assert_eq!(object.content().default.as_ref().unwrap().0, vec!["inline"]);
+
+    for reference in [
+        parse(serde_json::json!("https://example.com/notes/1")),
+        parse(serde_json::json!({ "type": "Link", "href": "https://example.com/notes/1" })),
+    ] {
+        let object = reference.resolve(&Notes).await.unwrap();
+        assert_eq!(object.id(), Some(&note_url().into()));
+        assert_eq!(
+            object.content().default.as_ref().unwrap().0,
+            vec!["fetched"]
+        );
+    }
+
+    let missing = parse(serde_json::json!("https://example.com/missing/1"));
+    assert!(matches!(
+        missing.resolve(&Notes).await,
+        Err(ResolveError::Dereference(_))
+    ));
+    let mismatch: LinkOrObject<Link> = Or::Snd(Remotable::Remote(note_url()));
+    assert!(matches!(
+        mismatch.resolve(&Notes).await,
+        Err(ResolveError::Malformed(_))
+    ));
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "lint")]
+use activity_vocabulary::{lenient::Quirk, lint::*};
+
+#[test]
+fn clean() {
+    let document = serde_json::json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "type": "Note",
+        "id": "https://example.com/notes/1",
+        "published": "2023-11-14T22:13:20Z",
+        "content": "hello",
+        "tag": [{ "type": "Mention", "href": "https://example.com/users/alice" }]
+    });
+    assert_eq!(lint(&document), []);
+    let link = serde_json::json!({ "type": "Link", "href": "https://example.com/" });
+    assert_eq!(lint(&link), []);
+}
+
+#[test]
+fn warnings() {
+    let document = serde_json::json!({
+        "type": "Create",
+        "id": "https://example.com/activities/1",
+        "actor": "https://example.com/users/alice",
+        "object": {
+            "type": "Note",
+            "published": 1700000000,
+            "_example_score": 1,
+            "_example_rank": null,
+            "_example_flags": [],
+            "tag": [{ "type": "Mention", "href": "https://example.com/users/bob", "rel": "x", "foo": 1 }]
+        }
+    });
+    let warnings = lint(&document);
+    assert_eq!(
+        warnings,
+        [
+            LintWarning::Coerced(Quirk::Timestamp {
+                property: "published".to_owned()
+            }),
+            LintWarning::MissingRecommended {
+                property: "published"
+            },
+            LintWarning::UnknownProperty {
+                type_name: "Note",
+                property: "_example_score".to_owned()
+            },
+            LintWarning::UnknownProperty {
+                type_name: "Mention",
+                property: "foo".to_owned()
+            },
+        ]
+    );
+    assert_eq!(
+        warnings[2].to_string(),
+        "unknown property '_example_score' of Note"
+    );
+}
+
+#[test]
+fn unknown_type() {
+    let document = serde_json::json!({ "type": "Nte", "id": "https://example.com/notes/1", "published": "2023-11-14T22:13:20Z" });
+    assert_eq!(
+        lint(&document),
+        [LintWarning::UnknownType {
+            received: "Nte".to_owned()
+        }]
+    );
+}
+
+#[test]
+fn invalid() {
+    let document = serde_json::json!({ "type": "Note", "content": 1 });
+    assert!(matches!(&lint(&document)[..], [LintWarning::Invalid(_)]));
+}
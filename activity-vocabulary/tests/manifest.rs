@@ -0,0 +1,40 @@
+use serde_json::{json, Value};
+
+fn manifest() -> Value {
+    serde_json::from_str(activity_vocabulary::embedded_manifest()).unwrap()
+}
+
+#[test]
+fn types() {
+    let manifest = manifest();
+    assert_eq!(manifest["type_tag"], "type");
+    let page = &manifest["types"]["OrderedCollectionPage"];
+    assert_eq!(
+        page["uri"],
+        "https://www.w3.org/ns/activitystreams#OrderedCollectionPage"
+    );
+    assert_eq!(
+        page["extends"],
+        json!(["OrderedCollection", "CollectionPage"])
+    );
+}
+
+#[test]
+fn properties() {
+    let manifest = manifest();
+    let page = &manifest["types"]["OrderedCollectionPage"]["properties"];
+    assert_eq!(
+        page["items"],
+        json!({
+            "uri": "https://www.w3.org/ns/activitystreams#items",
+            "type": "Or<LinkSubtypes, Remotable<ObjectSubtypes>>",
+            "kind": "Normal",
+            "tag": "orderedItems",
+            "aka": ["items", "orderedItems"],
+            "inherited": true,
+        })
+    );
+    assert_eq!(page["start_index"]["kind"], "Functional");
+    assert_eq!(page["start_index"]["inherited"], false);
+    assert_eq!(page["id"]["uri"], "@id");
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "mastodon")]
+use activity_vocabulary::*;
+use activity_vocabulary_core::{Or, Remotable};
+
+#[test]
+fn toot() {
+    let input = serde_json::json!({
+        "type": "Note",
+        "id": "https://example.com/notes/1",
+        "content": "<p>:blobcat: <a href=\"https://example.com/tags/rust\">#rust</a></p>",
+        "sensitive": true,
+        "summary": "cw",
+        "tag": [
+            {
+                "type": "Hashtag",
+                "href": "https://example.com/tags/rust",
+                "name": "#rust"
+            },
+            {
+                "type": "Emoji",
+                "id": "https://example.com/emojis/1",
+                "name": ":blobcat:",
+                "icon": {
+                    "type": "Image",
+                    "mediaType": "image/png",
+                    "url": "https://example.com/emojis/blobcat.png"
+                }
+            }
+        ]
+    });
+    let note: Note = serde_json::from_value(input.clone()).unwrap();
+    assert_eq!(note.sensitive, Some(true));
+    assert!(matches!(
+        &note.tag.0[0],
+        Or::Prim(LinkSubtypes::Hashtag(hashtag)) if hashtag.name.default == Some(activity_vocabulary_core::Property(vec!["#rust".to_owned()]))
+    ));
+    assert!(matches!(
+        &note.tag.0[1],
+        Or::Snd(Remotable::Inline(ObjectSubtypes::Emoji(_)))
+    ));
+    assert_eq!(serde_json::to_value(&note).unwrap(), input);
+}
+
+#[test]
+fn actor_and_question() {
+    let person: Person = serde_json::from_value(serde_json::json!({
+        "type": "Person",
+        "featured": "https://example.com/users/alice/collections/featured",
+        "featuredTags": "https://example.com/users/alice/collections/tags",
+        "discoverable": true,
+        "devices": "https://example.com/users/alice/collections/devices"
+    }))
+    .unwrap();
+    assert_eq!(person.discoverable, Some(true));
+    assert_eq!(
+        person.featured_tags,
+        Some(
+            "https://example.com/users/alice/collections/tags"
+                .parse()
+                .unwrap()
+        )
+    );
+
+    let question: Question = serde_json::from_value(serde_json::json!({
+        "type": "Question",
+        "votersCount": 3
+    }))
+    .unwrap();
+    assert_eq!(question.voters_count, Some(3));
+}
+
+#[test]
+fn media_attachment() {
+    let input = serde_json::json!({
+        "type": "Image",
+        "mediaType": "image/png",
+        "url": "https://example.com/media/1.png",
+        "focalPoint": [-0.5, 0.25],
+        "blurhash": "UBL_:rOpGG-oBUNG,qRj2so|=eE1w^n4S5NH"
+    });
+    let mut image: Image = serde_json::from_value(input.clone()).unwrap();
+    assert_eq!(image.focal_point(), Some((-0.5, 0.25)));
+    assert_eq!(
+        image.blurhash.as_deref(),
+        Some("UBL_:rOpGG-oBUNG,qRj2so|=eE1w^n4S5NH")
+    );
+    assert_eq!(serde_json::to_value(&image).unwrap(), input);
+
+    image.set_focal_point((0.0, 1.0));
+    assert_eq!(
+        serde_json::to_value(&image).unwrap()["focalPoint"],
+        serde_json::json!([0.0, 1.0])
+    );
+    let document: Document = image.into();
+    assert_eq!(document.focal_point(), Some((0.0, 1.0)));
+}
@@ -0,0 +1,44 @@
+use activity_vocabulary::Document;
+use activity_vocabulary_core::MediaType;
+
+#[test]
+fn helpers() {
+    let media_type: MediaType = "Text/HTML; Charset=utf-8".parse().unwrap();
+    assert_eq!(media_type.as_str(), "text/html; charset=utf-8");
+    assert_eq!(media_type.essence(), "text/html");
+    assert_eq!(media_type.parameter("charset"), Some("utf-8"));
+    assert!(!media_type.is_image());
+    assert!("image/png".parse::<MediaType>().unwrap().is_image());
+    assert!("png".parse::<MediaType>().is_err());
+}
+
+#[test]
+fn lenient() {
+    let document: Document = serde_json::from_value(serde_json::json!({
+        "type": "Document",
+        "mediaType": " IMAGE/PNG; ",
+        "url": "https://example.com/files/1.png"
+    }))
+    .unwrap();
+    let media_type = document.media_type.unwrap();
+    assert!(media_type.is_image());
+    assert_eq!(
+        serde_json::to_value(&media_type).unwrap(),
+        serde_json::json!("image/png")
+    );
+    for invalid in ["", " ", "image", "image/", "/png"] {
+        assert!(serde_json::from_value::<MediaType>(serde_json::json!(invalid)).is_err());
+        assert!(invalid.parse::<MediaType>().is_err());
+    }
+}
+
+#[cfg(feature = "mime")]
+#[test]
+fn mime() {
+    let media_type: MediaType = "image/png".parse().unwrap();
+    assert_eq!(media_type.mime(), Some(mime::IMAGE_PNG));
+    assert_eq!(
+        MediaType::from(mime::TEXT_PLAIN_UTF_8).as_str(),
+        "text/plain; charset=utf-8"
+    );
+}
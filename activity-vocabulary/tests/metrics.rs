@@ -0,0 +1,76 @@
+use std::sync::Mutex;
+
+use activity_vocabulary::*;
+use activity_vocabulary_core::metrics::*;
+
+static EVENTS: Mutex<Vec<ParseEvent>> = Mutex::new(Vec::new());
+
+struct Recorder;
+
+impl Metrics for Recorder {
+    fn record(&self, event: &ParseEvent) {
+        EVENTS.lock().unwrap().push(event.clone());
+    }
+}
+
+fn recorded() -> Vec<ParseEvent> {
+    std::mem::take(&mut EVENTS.lock().unwrap())
+}
+
+// the metrics are of the process, so that the cases run in order
+#[test]
+fn events() {
+    set_metrics(Recorder).unwrap();
+    assert_eq!(set_metrics(Recorder), Err(MetricsAlreadySet));
+
+    let create = serde_json::json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "type": "Create",
+        "actor": "https://example.com/users/alice",
+        "object": { "type": "Note", "content": "hello", "bar": 1 },
+    });
+    serde_json::from_value::<Create>(create).unwrap();
+    // not counted for the tries of the object as a link
+    assert_eq!(
+        recorded(),
+        [ParseEvent::UnknownProperty {
+            type_name: "Note",
+            property: "bar".to_owned(),
+        }]
+    );
+
+    let widget = serde_json::json!({ "type": "Widget", "name": "clock" });
+    serde_json::from_value::<ObjectSubtypes>(widget).unwrap();
+    assert_eq!(
+        recorded(),
+        [ParseEvent::UnknownType {
+            subtypes: "Object",
+            received: "Widget".to_owned(),
+            fallback: true,
+        }]
+    );
+
+    let mention = serde_json::json!({ "type": "Mentoin" });
+    serde_json::from_value::<LinkSubtypes>(mention).unwrap_err();
+    assert_eq!(
+        recorded(),
+        [ParseEvent::UnknownType {
+            subtypes: "Link",
+            received: "Mentoin".to_owned(),
+            fallback: false,
+        }]
+    );
+
+    #[cfg(feature = "lenient")]
+    {
+        let note = serde_json::json!({ "type": "Note", "published": 1700000000 });
+        serde_json::from_value::<lenient::Lenient<Note>>(note).unwrap();
+        assert_eq!(
+            recorded(),
+            [ParseEvent::Coerced {
+                quirk: "timestamp",
+                property: "published".to_owned(),
+            }]
+        );
+    }
+}
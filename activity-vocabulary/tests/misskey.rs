@@ -0,0 +1,63 @@
+#![cfg(feature = "misskey")]
+use activity_vocabulary::{quote::Quoting, *};
+
+#[test]
+fn quote_encodings() {
+    let quoted: url::Url = "https://remote.example/notes/1".parse().unwrap();
+    let misskey: Note = serde_json::from_value(serde_json::json!({
+        "type": "Note",
+        "content": "RE: https://remote.example/notes/1",
+        "quoteUrl": "https://remote.example/notes/1",
+        "_misskey_quote": "https://remote.example/notes/1"
+    }))
+    .unwrap();
+    assert_eq!(misskey.quoted_object(), Some(&quoted));
+
+    let fedibird: Note = serde_json::from_value(serde_json::json!({
+        "type": "Note",
+        "quoteUri": "https://remote.example/notes/1"
+    }))
+    .unwrap();
+    assert_eq!(fedibird.quoted_object(), Some(&quoted));
+
+    let input = serde_json::json!({
+        "type": "Note",
+        "tag": [
+            {
+                "type": "Mention",
+                "href": "https://remote.example/users/bob"
+            },
+            {
+                "type": "Link",
+                "mediaType": "application/ld+json; profile=\"https://www.w3.org/ns/activitystreams\"",
+                "rel": "https://misskey-hub.net/ns#_misskey_quote",
+                "href": "https://remote.example/notes/1",
+                "name": "RE: https://remote.example/notes/1"
+            }
+        ]
+    });
+    let fep_e232: Note = serde_json::from_value(input.clone()).unwrap();
+    assert_eq!(fep_e232.quoted_object(), Some(&quoted));
+    assert_eq!(serde_json::to_value(&fep_e232).unwrap(), input);
+
+    let unrelated: Note = serde_json::from_value(serde_json::json!({
+        "type": "Note",
+        "tag": [{
+            "type": "Link",
+            "mediaType": "application/activity+json",
+            "href": "https://remote.example/notes/2"
+        }]
+    }))
+    .unwrap();
+    assert_eq!(unrelated.quoted_object(), None);
+
+    let image: ObjectSubtypes = serde_json::from_value(serde_json::json!({
+        "type": "Image",
+        "quoteUrl": "https://remote.example/notes/1"
+    }))
+    .unwrap();
+    assert_eq!(image.quoted_object(), Some(&quoted));
+
+    let plain: Note = serde_json::from_value(serde_json::json!({ "type": "Note" })).unwrap();
+    assert_eq!(plain.quoted_object(), None);
+}
@@ -0,0 +1,83 @@
+#![cfg(feature = "nodeinfo")]
+use activity_vocabulary::nodeinfo::*;
+
+#[test]
+fn well_known() {
+    let well_known: WellKnown = serde_json::from_value(serde_json::json!({
+        "links": [
+            {
+                "rel": "http://nodeinfo.diaspora.software/ns/schema/2.0",
+                "href": "https://example.com/nodeinfo/2.0"
+            },
+            {
+                "rel": "http://nodeinfo.diaspora.software/ns/schema/2.1",
+                "href": "https://example.com/nodeinfo/2.1"
+            }
+        ]
+    }))
+    .unwrap();
+    assert_eq!(
+        well_known.nodeinfo().map(url::Url::as_str),
+        Some("https://example.com/nodeinfo/2.1")
+    );
+}
+
+#[test]
+fn nodeinfo_2_0() {
+    let input = serde_json::json!({
+        "version": "2.0",
+        "software": {
+            "name": "mastodon",
+            "version": "4.2.0"
+        },
+        "protocols": ["activitypub"],
+        "services": {
+            "inbound": [],
+            "outbound": []
+        },
+        "usage": {
+            "users": {
+                "total": 100,
+                "activeMonth": 10,
+                "activeHalfyear": 50
+            },
+            "localPosts": 1000
+        },
+        "openRegistrations": false,
+        "metadata": {
+            "nodeName": "example"
+        }
+    });
+    let nodeinfo: NodeInfo = serde_json::from_value(input.clone()).unwrap();
+    assert_eq!(nodeinfo.usage.users.active_month, Some(10));
+    assert_eq!(serde_json::to_value(&nodeinfo).unwrap(), input);
+}
+
+#[test]
+fn nodeinfo_2_1() {
+    let input = serde_json::json!({
+        "version": "2.1",
+        "software": {
+            "name": "misskey",
+            "version": "2024.1.0",
+            "repository": "https://github.com/misskey-dev/misskey",
+            "homepage": "https://misskey-hub.net/"
+        },
+        "protocols": ["activitypub"],
+        "services": {
+            "inbound": [],
+            "outbound": ["atom1.0", "rss2.0"]
+        },
+        "usage": {
+            "users": {}
+        },
+        "openRegistrations": true,
+        "metadata": {}
+    });
+    let nodeinfo: NodeInfo = serde_json::from_value(input.clone()).unwrap();
+    assert_eq!(
+        nodeinfo.software.repository.as_ref().map(url::Url::as_str),
+        Some("https://github.com/misskey-dev/misskey")
+    );
+    assert_eq!(serde_json::to_value(&nodeinfo).unwrap(), input);
+}
@@ -0,0 +1,86 @@
+use activity_vocabulary::{normalize::normalize, *};
+use activity_vocabulary_core::{Or, Property, Remotable};
+
+fn url(url: &str) -> url::Url {
+    url.parse().unwrap()
+}
+
+fn author() -> Person {
+    Person {
+        object_type: Property(vec!["Person".to_owned()]),
+        id: Some(url("https://example.com/users/alice").into()),
+        ..Default::default()
+    }
+}
+
+fn note() -> Note {
+    let mut note = Note::new("hello");
+    note.id = Some(url("https://example.com/notes/1").into());
+    note.attributed_to = Property(vec![Or::Snd(Remotable::Inline(author().into()))]);
+    note
+}
+
+#[test]
+fn extract_nested() {
+    let create = Create::new(url("https://example.com/users/alice"), note());
+    let (create, extracted) = normalize(create);
+    assert_eq!(
+        create.object,
+        Property(vec![Or::Snd(Remotable::Remote(url(
+            "https://example.com/notes/1"
+        )))])
+    );
+    let mut normalized_note = note();
+    normalized_note.attributed_to = Property(vec![Or::Snd(Remotable::Remote(url(
+        "https://example.com/users/alice",
+    )))]);
+    assert_eq!(
+        extracted,
+        vec![
+            ObjectSubtypes::from(author()),
+            ObjectSubtypes::from(normalized_note)
+        ]
+    );
+}
+
+#[test]
+fn keep_anonymous() {
+    let mut anonymous = note();
+    anonymous.id = None;
+    let create = Create::new(url("https://example.com/users/alice"), anonymous.clone());
+    let (normalized, extracted) = normalize(create.clone());
+    assert_eq!(extracted, vec![ObjectSubtypes::from(author())]);
+    let Some(Or::Snd(Remotable::Inline(ObjectSubtypes::Note(inline)))) =
+        normalized.object.0.first()
+    else {
+        panic!("the anonymous note is not inline");
+    };
+    assert_eq!(inline.attributed_to, normalize(anonymous).0.attributed_to);
+}
+
+#[test]
+fn nothing_to_extract() {
+    let like = Like::new(
+        url("https://example.com/users/alice"),
+        url("https://example.com/notes/1"),
+    );
+    assert_eq!(normalize(like.clone()), (like, Vec::new()));
+}
+
+#[test]
+fn extract_from_closed() {
+    let question: Question = serde_json::from_value(serde_json::json!({
+        "type": "Question",
+        "closed": { "type": "Note", "id": "https://example.com/notes/2" }
+    }))
+    .unwrap();
+    let (question, extracted) = normalize(question);
+    assert_eq!(
+        serde_json::to_value(&question).unwrap(),
+        serde_json::json!({
+            "type": "Question",
+            "closed": "https://example.com/notes/2"
+        })
+    );
+    assert_eq!(extracted.len(), 1);
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "arbitrary-precision")]
+use activity_vocabulary::*;
+use activity_vocabulary_core::xsd;
+use serde_json::Value;
+
+/// Parse `json` as `T` and serialize it back, with the numbers compared as they are written.
+fn round_trip<T: serde::de::DeserializeOwned + serde::Serialize>(json: &str) {
+    let parsed: T = serde_json::from_str(json).unwrap();
+    assert_eq!(
+        serde_json::to_value(&parsed).unwrap(),
+        serde_json::from_str::<Value>(json).unwrap()
+    );
+}
+
+#[test]
+fn lexical_forms() {
+    let json =
+        r#"{"type":"Place","latitude":35.6580000,"longitude":139.74543210987654321,"radius":1e3}"#;
+    round_trip::<Place>(json);
+    round_trip::<ObjectSubtypes>(json);
+    assert!(
+        serde_json::to_string(&serde_json::from_str::<Place>(json).unwrap())
+            .unwrap()
+            .contains(r#""latitude":35.6580000"#)
+    );
+
+    let place: Place = serde_json::from_str(json).unwrap();
+    assert_eq!(
+        place.latitude.as_ref().map(xsd::Float::to_f64),
+        Some(35.658)
+    );
+    assert_eq!(place.radius_in(&Unit::Km), Some(1.0));
+}
+
+#[test]
+fn integers() {
+    round_trip::<LinkSubtypes>(
+        r#"{"type":"Link","href":"https://example.com/a.png","height":600,"width":800}"#,
+    );
+    round_trip::<OrderedCollection>(
+        r#"{"type":"OrderedCollection","totalItems":2,"orderedItems":[{"type":"Place","altitude":-12},{"type":"Place","altitude":12.50}]}"#,
+    );
+}
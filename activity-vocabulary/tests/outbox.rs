@@ -0,0 +1,81 @@
+use activity_vocabulary::*;
+
+#[test]
+fn wrap_in_create() {
+    let note: Note = serde_json::from_value(serde_json::json!({
+        "type": "Note",
+        "id": "https://example.com/notes/1",
+        "attributedTo": "https://example.com/users/alice",
+        "published": "2024-01-01T00:00:00Z",
+        "to": "https://www.w3.org/ns/activitystreams#Public",
+        "cc": "https://example.com/users/alice/followers",
+        "content": "hello"
+    }))
+    .unwrap();
+    let create = Create::wrap(note);
+    assert_eq!(
+        serde_json::to_value(&create).unwrap(),
+        serde_json::json!({
+            "type": "Create",
+            "id": "https://example.com/notes/1/activity",
+            "actor": "https://example.com/users/alice",
+            "published": "2024-01-01T00:00:00Z",
+            "to": "https://www.w3.org/ns/activitystreams#Public",
+            "cc": "https://example.com/users/alice/followers",
+            "object": {
+                "type": "Note",
+                "id": "https://example.com/notes/1",
+                "attributedTo": "https://example.com/users/alice",
+                "published": "2024-01-01T00:00:00Z",
+                "to": "https://www.w3.org/ns/activitystreams#Public",
+                "cc": "https://example.com/users/alice/followers",
+                "content": "hello"
+            }
+        })
+    );
+}
+
+#[test]
+fn wrap_object_merges_addressing() {
+    let mut create: Create = serde_json::from_value(serde_json::json!({
+        "type": "Create",
+        "to": "https://remote.example/users/bob"
+    }))
+    .unwrap();
+    let note: Note = serde_json::from_value(serde_json::json!({
+        "type": "Note",
+        "to": "https://remote.example/users/carol"
+    }))
+    .unwrap();
+    create.wrap_object(note);
+    let create = serde_json::to_value(&create).unwrap();
+    assert_eq!(
+        create["to"],
+        serde_json::json!([
+            "https://remote.example/users/bob",
+            "https://remote.example/users/carol"
+        ])
+    );
+    assert_eq!(
+        create["object"]["to"],
+        serde_json::json!([
+            "https://remote.example/users/carol",
+            "https://remote.example/users/bob"
+        ])
+    );
+}
+
+#[cfg(feature = "pleroma")]
+#[test]
+fn wrap_object_of_extension() {
+    let mut react = EmojiReact {
+        object_type: activity_vocabulary_core::Property(vec!["EmojiReact".to_owned()]),
+        to: serde_json::from_value(serde_json::json!("https://remote.example/users/bob")).unwrap(),
+        ..Default::default()
+    };
+    react.wrap_object(Note::new("hello"));
+    assert_eq!(
+        serde_json::to_value(&react).unwrap()["object"]["to"],
+        "https://remote.example/users/bob"
+    );
+}
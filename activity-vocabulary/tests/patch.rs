@@ -0,0 +1,101 @@
+#![cfg(feature = "patch")]
+use activity_vocabulary::{patch::*, *};
+
+fn stored() -> serde_json::Value {
+    serde_json::json!({
+        "type": "Note",
+        "id": "https://example.com/notes/1",
+        "content": "hello",
+        "summary": "greeting",
+        "to": "https://www.w3.org/ns/activitystreams#Public",
+        "_example_score": 1
+    })
+}
+
+fn note() -> Note {
+    serde_json::from_value(stored()).unwrap()
+}
+
+#[test]
+fn partial_update() {
+    let mut stored = stored();
+    let update = serde_json::value::to_raw_value(&serde_json::json!({
+        "type": "Article",
+        "id": "https://example.com/notes/1",
+        "content": "hello, world",
+        "summary": null,
+        "_example_rank": 2
+    }))
+    .unwrap();
+    let note: Note = apply_update(&mut stored, &update).unwrap();
+    assert_eq!(
+        serde_json::to_value(&note).unwrap(),
+        serde_json::json!({
+            "type": "Note",
+            "id": "https://example.com/notes/1",
+            "content": "hello, world",
+            "to": "https://www.w3.org/ns/activitystreams#Public"
+        })
+    );
+    assert_eq!(
+        stored,
+        serde_json::json!({
+            "type": "Note",
+            "id": "https://example.com/notes/1",
+            "content": "hello, world",
+            "to": "https://www.w3.org/ns/activitystreams#Public",
+            "_example_score": 1,
+            "_example_rank": 2
+        })
+    );
+}
+
+#[test]
+fn other_object() {
+    let mut stored = stored();
+    let update = serde_json::value::to_raw_value(&serde_json::json!({
+        "id": "https://example.com/notes/2",
+        "content": "hijacked"
+    }))
+    .unwrap();
+    assert!(matches!(
+        apply_update::<Note>(&mut stored, &update),
+        Err(PatchError::IdMismatch)
+    ));
+    assert_eq!(stored, self::stored());
+}
+
+#[test]
+fn diff_and_apply() {
+    let old = note();
+    let new: Note = serde_json::from_value(serde_json::json!({
+        "type": "Note",
+        "id": "https://example.com/notes/1",
+        "content": "hello, world",
+        "to": "https://www.w3.org/ns/activitystreams#Public"
+    }))
+    .unwrap();
+    let patch = diff(&old, &new).unwrap();
+    assert_eq!(
+        serde_json::Value::Object(patch.body.clone()),
+        serde_json::json!({
+            "type": "Note",
+            "id": "https://example.com/notes/1",
+            "content": "hello, world",
+            "summary": null
+        })
+    );
+    assert!(!patch.is_empty());
+    assert!(diff(&new, &new).unwrap().is_empty());
+
+    let mut stored = serde_json::to_value(&old).unwrap();
+    let updated: Note = apply_update(&mut stored, &patch.to_raw_value().unwrap()).unwrap();
+    assert_eq!(updated, new);
+
+    let update = patch
+        .into_update("https://example.com/users/alice".parse().unwrap(), &new)
+        .unwrap();
+    assert_eq!(update["type"], "Update");
+    assert_eq!(update["to"], "https://www.w3.org/ns/activitystreams#Public");
+    assert_eq!(update["object"]["summary"], serde_json::Value::Null);
+}
@@ -0,0 +1,75 @@
+use activity_vocabulary::{place::PlaceError, *};
+use activity_vocabulary_core::xsd;
+
+#[test]
+fn validate() {
+    let place: Place = serde_json::from_value(serde_json::json!({
+        "type": "Place",
+        "name": "Fresno Area",
+        "latitude": 36.75,
+        "longitude": 119.7667,
+        "radius": 15,
+        "units": "miles"
+    }))
+    .unwrap();
+    assert_eq!(place.validate(), Ok(()));
+
+    let place = Place {
+        latitude: xsd::Float::from_f64(91.0),
+        ..Default::default()
+    };
+    assert_eq!(place.validate(), Err(PlaceError::Latitude(91.0)));
+    let place = Place {
+        longitude: xsd::Float::from_f64(-180.5),
+        ..Default::default()
+    };
+    assert_eq!(place.validate(), Err(PlaceError::Longitude(-180.5)));
+}
+
+#[test]
+fn radius_in() {
+    let place = Place {
+        radius: xsd::Float::from_f64(15.0),
+        altitude: xsd::Float::from_f64(100.0),
+        units: Some(Unit::Miles),
+        ..Default::default()
+    };
+    assert!((place.radius_in(&Unit::Km).unwrap() - 24.14016).abs() < 1e-9);
+    assert!((place.altitude_in(&Unit::Feet).unwrap() - 528_000.0).abs() < 1e-6);
+    assert_eq!(
+        place.radius_in(&Unit::Uri("https://example.com/units/li".parse().unwrap())),
+        None
+    );
+
+    let place = Place {
+        radius: xsd::Float::from_f64(250.0),
+        ..Default::default()
+    };
+    assert_eq!(place.radius_in(&Unit::Cm), Some(25000.0));
+}
+
+#[test]
+fn units() {
+    assert_eq!(Unit::Km.to_meters(1.5), Some(1500.0));
+    assert!((Unit::convert(12.0, &Unit::Inches, &Unit::Feet).unwrap() - 1.0).abs() < 1e-12);
+    assert_eq!(Unit::convert(1.0, &Unit::M, &Unit::Cm), Some(100.0));
+    let li = Unit::Uri("https://example.com/units/li".parse().unwrap());
+    assert_eq!(li.to_meters(1.0), None);
+    assert_eq!(Unit::convert(1.0, &li, &li), Some(1.0));
+
+    let mut place = Place {
+        radius: xsd::Float::from_f64(2.0),
+        altitude: xsd::Float::from_f64(500.0),
+        units: Some(Unit::Km),
+        ..Default::default()
+    };
+    place.normalize_units();
+    assert_eq!(
+        place,
+        Place {
+            radius: xsd::Float::from_f64(2000.0),
+            altitude: xsd::Float::from_f64(500_000.0),
+            ..Default::default()
+        }
+    );
+}
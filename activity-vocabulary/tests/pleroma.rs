@@ -0,0 +1,70 @@
+#![cfg(feature = "pleroma")]
+use activity_vocabulary::{reaction::Reaction, *};
+use activity_vocabulary_core::Or;
+
+fn blobcat() -> Emoji {
+    serde_json::from_value(serde_json::json!({
+        "type": "Emoji",
+        "id": "https://example.com/emojis/blobcat",
+        "name": ":blobcat:",
+        "icon": {
+            "type": "Image",
+            "url": "https://example.com/emojis/blobcat.png"
+        }
+    }))
+    .unwrap()
+}
+
+#[test]
+fn custom_emoji_react() {
+    let input = serde_json::json!({
+        "type": "EmojiReact",
+        "actor": "https://example.com/users/alice",
+        "object": "https://remote.example/notes/1",
+        "content": ":blobcat:",
+        "tag": {
+            "type": "Emoji",
+            "id": "https://example.com/emojis/blobcat",
+            "name": ":blobcat:",
+            "icon": {
+                "type": "Image",
+                "url": "https://example.com/emojis/blobcat.png"
+            }
+        }
+    });
+    let react: ActivitySubtypes = serde_json::from_value(input.clone()).unwrap();
+    let ActivitySubtypes::EmojiReact(react) = react else {
+        panic!("must be EmojiReact");
+    };
+    assert_eq!(
+        react.reaction(),
+        Some(Reaction::Custom {
+            shortcode: ":blobcat:",
+            emoji: Some(&blobcat())
+        })
+    );
+    assert_eq!(serde_json::to_value(&react).unwrap(), input);
+}
+
+#[test]
+fn like_with_content() {
+    let mut like: Like = serde_json::from_value(serde_json::json!({
+        "type": "Like",
+        "object": "https://remote.example/notes/1",
+        "content": "👍"
+    }))
+    .unwrap();
+    assert_eq!(like.reaction(), Some(Reaction::Unicode("👍")));
+
+    like.set_reaction(Or::Snd(blobcat()));
+    assert_eq!(
+        like.reaction(),
+        Some(Reaction::Custom {
+            shortcode: ":blobcat:",
+            emoji: Some(&blobcat())
+        })
+    );
+    let output = serde_json::to_value(&like).unwrap();
+    assert_eq!(output["content"], ":blobcat:");
+    assert_eq!(output["tag"]["type"], "Emoji");
+}
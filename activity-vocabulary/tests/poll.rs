@@ -0,0 +1,60 @@
+use activity_vocabulary::*;
+
+#[test]
+fn single_choice() {
+    let question = Question::single_choice(["cats", "dogs"]);
+    assert_eq!(
+        serde_json::to_value(&question).unwrap(),
+        serde_json::json!({
+            "type": "Question",
+            "oneOf": [
+                {
+                    "type": "Note",
+                    "name": "cats",
+                    "replies": { "type": "Collection", "totalItems": 0 }
+                },
+                {
+                    "type": "Note",
+                    "name": "dogs",
+                    "replies": { "type": "Collection", "totalItems": 0 }
+                }
+            ]
+        })
+    );
+    assert_eq!(question.tally(), vec![("cats", 0), ("dogs", 0)]);
+    assert!(!question.is_closed());
+}
+
+#[test]
+fn tally_and_closed() {
+    let question: Question = serde_json::from_value(serde_json::json!({
+        "type": "Question",
+        "endTime": "2099-01-01T00:00:00Z",
+        "closed": "2024-01-01T00:00:00Z",
+        "anyOf": [
+            {
+                "type": "Note",
+                "name": "cats",
+                "replies": { "type": "Collection", "totalItems": 3 }
+            },
+            {
+                "type": "Note",
+                "name": "dogs",
+                "replies": { "type": "Collection", "totalItems": 5 }
+            }
+        ]
+    }))
+    .unwrap();
+    assert_eq!(question.tally(), vec![("cats", 3), ("dogs", 5)]);
+    assert!(question.is_closed());
+
+    let question: Question = serde_json::from_value(serde_json::json!({
+        "type": "Question",
+        "closed": false,
+        "endTime": "2000-01-01T00:00:00Z"
+    }))
+    .unwrap();
+    assert!(!question.is_closed());
+    let question = Question::multi_choice(["a"]);
+    assert_eq!(question.any_of.0.len(), 1);
+}
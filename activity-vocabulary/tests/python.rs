@@ -0,0 +1,72 @@
+#![cfg(feature = "python")]
+use activity_vocabulary::python::activity_vocabulary;
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyModule};
+
+const NOTE: &str = r#"{
+    "@context": "https://www.w3.org/ns/activitystreams",
+    "type": "Note",
+    "id": "https://example.com/notes/1",
+    "to": ["https://www.w3.org/ns/activitystreams#Public"],
+    "cc": "https://example.com/users/alice/followers",
+    "content": "hello"
+}"#;
+
+fn with_module<T>(f: impl FnOnce(&Bound<'_, PyModule>) -> PyResult<T>) -> T {
+    Python::initialize();
+    Python::attach(|py| {
+        let m = PyModule::new(py, "activity_vocabulary")?;
+        activity_vocabulary(&m)?;
+        f(&m)
+    })
+    .unwrap()
+}
+
+#[test]
+fn parse() {
+    with_module(|m| {
+        let document = m.getattr("parse")?.call1((NOTE,))?;
+        assert_eq!(document.getattr("type")?.extract::<String>()?, "Note");
+        assert_eq!(
+            document.getattr("id")?.extract::<String>()?,
+            "https://example.com/notes/1"
+        );
+        assert_eq!(
+            document.getattr("to")?.extract::<Vec<String>>()?,
+            vec!["https://www.w3.org/ns/activitystreams#Public"]
+        );
+        assert_eq!(
+            document.getattr("cc")?.extract::<Vec<String>>()?,
+            vec!["https://example.com/users/alice/followers"]
+        );
+        assert!(document
+            .getattr("bcc")?
+            .extract::<Vec<String>>()?
+            .is_empty());
+        Ok(())
+    })
+}
+
+#[test]
+fn canonicalize() {
+    with_module(|m| {
+        assert_eq!(
+            m.getattr("canonicalize")?
+                .call1((NOTE,))?
+                .extract::<String>()?,
+            r#"{"@context":"https://www.w3.org/ns/activitystreams","cc":"https://example.com/users/alice/followers","content":"hello","id":"https://example.com/notes/1","to":"https://www.w3.org/ns/activitystreams#Public","type":"Note"}"#
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn invalid() {
+    with_module(|m| {
+        let err = m
+            .getattr("validate")?
+            .call1((r#"{"type": "Note", "id": "not a url"}"#,))
+            .unwrap_err();
+        assert!(err.is_instance_of::<PyValueError>(m.py()));
+        Ok(())
+    })
+}
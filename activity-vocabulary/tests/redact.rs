@@ -0,0 +1,70 @@
+use activity_vocabulary::{redact::*, *};
+
+fn create() -> Create {
+    serde_json::from_value(serde_json::json!({
+        "type": "Create",
+        "id": "https://example.com/activities/1",
+        "actor": "https://example.com/users/alice",
+        "to": ["https://example.org/users/bob"],
+        "bcc": ["https://example.org/users/carol"],
+        "signature": {
+            "type": "RsaSignature2017",
+            "creator": "https://example.com/users/alice#main-key",
+            "created": "2024-01-01T00:00:00Z",
+            "signatureValue": "c2lnbmF0dXJl"
+        },
+        "object": {
+            "type": "Note",
+            "id": "https://example.com/notes/1",
+            "content": "secret",
+            "contentMap": { "en": "secret" },
+            "summary": "cw",
+            "bto": ["https://example.org/users/dave"],
+            "tag": [{ "type": "Mention", "href": "https://example.org/users/bob", "name": "@bob" }]
+        }
+    }))
+    .unwrap()
+}
+
+#[test]
+fn default_policy() {
+    let mut create = create();
+    create.redact(&RedactionPolicy::default());
+    let json = serde_json::to_value(&create).unwrap();
+    assert!(json.get("bcc").is_none());
+    assert!(json.get("signature").is_none());
+    assert!(json["object"].get("bto").is_none());
+    assert_eq!(json["to"], "https://example.org/users/bob");
+    assert_eq!(json["object"]["content"], "secret");
+}
+
+#[test]
+fn remove_and_mask() {
+    let mut create = create();
+    create.redact(
+        &RedactionPolicy::default()
+            .removing("summary")
+            .removing("href")
+            .masking("content")
+            .masking("name"),
+    );
+    let json = serde_json::to_value(&create).unwrap();
+    assert!(json["object"].get("summary").is_none());
+    assert_eq!(json["object"]["content"], "[redacted]");
+    assert_eq!(json["object"]["contentMap"]["en"], "[redacted]");
+    // required properties are kept
+    assert_eq!(
+        json["object"]["tag"]["href"],
+        "https://example.org/users/bob"
+    );
+    assert_eq!(json["object"]["tag"]["name"], "[redacted]");
+}
+
+#[test]
+fn through_subtypes() {
+    let mut object = ObjectSubtypes::from(create());
+    object.redact(&RedactionPolicy::default());
+    let json = serde_json::to_value(&object).unwrap();
+    assert!(json.get("bcc").is_none());
+    assert!(json["object"].get("bto").is_none());
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "redis")]
+use std::time::{Duration, SystemTime};
+
+use activity_vocabulary::{
+    cache::{CacheStore, CachedDocument},
+    redis::RedisStore,
+};
+use activity_vocabulary_core::CacheHeaders;
+
+/// Runs against the server at `REDIS_URL`, and is skipped without it.
+#[tokio::test]
+async fn put_and_get() {
+    let Ok(redis_url) = std::env::var("REDIS_URL") else {
+        return;
+    };
+    let connection = redis::Client::open(redis_url)
+        .unwrap()
+        .get_multiplexed_async_connection()
+        .await
+        .unwrap();
+    let store = RedisStore::new(connection).prefix("activity-vocabulary-test:");
+    let url: url::Url = "https://example.com/users/alice".parse().unwrap();
+    let document = CachedDocument {
+        document: serde_json::json!({ "id": url }),
+        headers: CacheHeaders {
+            etag: Some(r#""1""#.to_owned()),
+            ..Default::default()
+        },
+        fresh_until: SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+    };
+    store
+        .put(&url, document.clone(), Duration::from_secs(60))
+        .await
+        .unwrap();
+    assert_eq!(store.get(&url).await.unwrap(), Some(document));
+    assert_eq!(
+        store
+            .get(&"https://example.com/users/bob".parse().unwrap())
+            .await
+            .unwrap(),
+        None
+    );
+}
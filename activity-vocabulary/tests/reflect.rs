@@ -0,0 +1,48 @@
+use activity_vocabulary::*;
+use serde_json::json;
+
+#[test]
+fn names_and_uris() {
+    assert!(NoteProperty::ALL
+        .windows(2)
+        .all(|pair| pair[0].name() < pair[1].name()));
+    assert_eq!(NoteProperty::from_name("id"), Some(NoteProperty::Id));
+    assert_eq!(NoteProperty::Id.uri(), "@id");
+    let content = NoteProperty::from_name("contentMap").unwrap();
+    assert_eq!(content, NoteProperty::ContentMap);
+    assert_eq!(
+        content.uri(),
+        "https://www.w3.org/ns/activitystreams#content"
+    );
+    assert_eq!(
+        NoteProperty::from_name("attributedTo").unwrap().uri(),
+        "https://www.w3.org/ns/activitystreams#attributedTo"
+    );
+    assert_eq!(NoteProperty::from_name("unknown"), None);
+}
+
+#[test]
+fn get_and_set() {
+    let mut note = Note::new("hello");
+    assert_eq!(NoteProperty::Id.get(&note), serde_json::Value::Null);
+    assert_eq!(NoteProperty::Content.get(&note), json!("hello"));
+
+    NoteProperty::Id
+        .set(&mut note, json!("https://example.com/notes/1"))
+        .unwrap();
+    NoteProperty::ContentMap
+        .set(&mut note, json!({ "en": "hello", "ja": "こんにちは" }))
+        .unwrap();
+    assert_eq!(
+        note.id.as_ref().unwrap().as_str(),
+        "https://example.com/notes/1"
+    );
+    assert_eq!(
+        NoteProperty::ContentMap.get(&note),
+        json!({ "en": "hello", "ja": "こんにちは" })
+    );
+
+    let error = NoteProperty::Id.set(&mut note, json!(42)).unwrap_err();
+    assert_eq!(error.type_name, "Note");
+    assert_eq!(error.property, "id");
+}
@@ -0,0 +1,71 @@
+use activity_vocabulary::{report::ReportTarget, *};
+use activity_vocabulary_core::{Or, Property, Remotable};
+
+fn url(s: &str) -> url::Url {
+    s.parse().unwrap()
+}
+
+#[test]
+fn report() {
+    let flag = Flag::report(
+        url("https://example.com/actor"),
+        url("https://example.org/users/mallory"),
+        [url("https://example.org/notes/1")],
+        "spam",
+    );
+    assert_eq!(
+        serde_json::to_value(&flag).unwrap(),
+        serde_json::json!({
+            "type": "Flag",
+            "actor": "https://example.com/actor",
+            "object": ["https://example.org/users/mallory", "https://example.org/notes/1"],
+            "content": "spam"
+        })
+    );
+    assert_eq!(
+        flag.reported_accounts(),
+        [url("https://example.org/users/mallory")]
+    );
+    assert_eq!(
+        flag.reported_objects(),
+        [url("https://example.org/notes/1")]
+    );
+    assert_eq!(flag.report_comment(), Some("spam"));
+    assert_eq!(
+        flag.report_targets(),
+        [
+            ReportTarget::Account(url("https://example.org/users/mallory")),
+            ReportTarget::Object(url("https://example.org/notes/1")),
+        ]
+    );
+
+    let silent = Flag::report(
+        url("https://example.com/actor"),
+        url("https://example.org/users/mallory"),
+        [],
+        "",
+    );
+    assert_eq!(silent.report_comment(), None);
+}
+
+#[test]
+fn inlined_targets() {
+    let person = Person {
+        id: Some(url("https://example.org/users/mallory").into()),
+        ..Default::default()
+    };
+    let flag = Flag {
+        object: Property(vec![
+            Or::Snd(Remotable::Remote(url("https://example.org/notes/1"))),
+            Or::Snd(Remotable::Inline(person.into())),
+        ]),
+        ..Default::default()
+    };
+    assert_eq!(
+        flag.report_targets(),
+        [
+            ReportTarget::Object(url("https://example.org/notes/1")),
+            ReportTarget::Account(url("https://example.org/users/mallory")),
+        ]
+    );
+}
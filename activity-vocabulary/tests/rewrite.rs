@@ -0,0 +1,72 @@
+use activity_vocabulary::{rewrite::*, *};
+
+fn document(origin: &str) -> serde_json::Value {
+    let json = r#"{
+        "type": "Create",
+        "id": "ORIGIN/activities/1",
+        "actor": "ORIGIN/users/alice",
+        "to": ["https://www.w3.org/ns/activitystreams#Public"],
+        "cc": ["https://other.example/users/bob"],
+        "object": {
+            "type": "Note",
+            "id": "ORIGIN/notes/1",
+            "url": { "type": "Link", "href": "ORIGIN/@alice/1" },
+            "content": "hello",
+            "inReplyTo": "https://other.example/notes/2",
+            "tag": [{ "type": "Mention", "href": "https://other.example/users/bob" }],
+            "attributedTo": {
+                "type": "Person",
+                "id": "ORIGIN/users/alice",
+                "inbox": "ORIGIN/users/alice/inbox",
+                "outbox": "ORIGIN/users/alice/outbox",
+                "endpoints": { "sharedInbox": "ORIGIN/inbox" },
+                "publicKey": {
+                    "id": "ORIGIN/users/alice#main-key",
+                    "owner": "ORIGIN/users/alice",
+                    "publicKeyPem": "-----BEGIN PUBLIC KEY-----"
+                }
+            }
+        }
+    }"#;
+    serde_json::from_str(&json.replace("ORIGIN", origin)).unwrap()
+}
+
+#[test]
+fn rebase_origin() {
+    let mut create: Create = serde_json::from_value(document("https://old.example")).unwrap();
+    create.rewrite_urls(rebase(
+        "https://old.example".parse().unwrap(),
+        "https://new.example".parse().unwrap(),
+    ));
+    let expected: Create = serde_json::from_value(document("https://new.example")).unwrap();
+    assert_eq!(create, expected);
+}
+
+#[test]
+fn through_subtypes() {
+    let mut object: ObjectSubtypes =
+        serde_json::from_value(document("https://old.example")).unwrap();
+    let mut rewritten = Vec::new();
+    object.rewrite_urls(|url| {
+        rewritten.push(url.to_string());
+        None
+    });
+    assert_eq!(
+        object,
+        serde_json::from_value(document("https://old.example")).unwrap()
+    );
+    assert_eq!(rewritten.len(), 14);
+}
+
+#[test]
+fn rebase_prefix() {
+    let mut map = rebase(
+        "https://example.com/old/".parse().unwrap(),
+        "https://example.com/new/".parse().unwrap(),
+    );
+    assert_eq!(
+        map(&"https://example.com/old/notes/1".parse().unwrap()),
+        Some("https://example.com/new/notes/1".parse().unwrap())
+    );
+    assert_eq!(map(&"https://example.com/older".parse().unwrap()), None);
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "sanitize")]
+use activity_vocabulary::{sanitize::*, *};
+
+fn create() -> serde_json::Value {
+    serde_json::json!({
+        "type": "Create",
+        "actor": "https://example.com/users/alice",
+        "object": {
+            "type": "Note",
+            "content": "<p onclick=\"steal()\">hello<script>steal()</script></p>",
+            "contentMap": { "en": "<p>hello<img src=x onerror=steal()></p>" },
+            "summary": "<b>cw</b><iframe src=\"https://example.com\"></iframe>",
+            "name": "<script>kept</script>"
+        }
+    })
+}
+
+fn note(create: &Create) -> serde_json::Value {
+    serde_json::to_value(create).unwrap()["object"].clone()
+}
+
+#[test]
+fn sanitize_nested() {
+    let mut create: Create = serde_json::from_value(create()).unwrap();
+    create.sanitize(&Sanitizer::default());
+    let note = note(&create);
+    assert_eq!(note["content"], "<p>hello</p>");
+    assert_eq!(note["contentMap"]["en"], "<p>hello<img src=\"x\"></p>");
+    assert_eq!(note["summary"], "<b>cw</b>");
+    assert_eq!(note["name"], "<script>kept</script>");
+}
+
+#[test]
+fn allowlist() {
+    let mut sanitizer = Sanitizer::default();
+    sanitizer.html.tags(["p"].into());
+    sanitizer.properties.insert("name".to_owned());
+    let mut create: Create = serde_json::from_value(create()).unwrap();
+    create.sanitize(&sanitizer);
+    let note = note(&create);
+    assert_eq!(note["summary"], "cw");
+    assert_eq!(note["name"], "");
+}
+
+#[test]
+fn on_deserialize() {
+    let Sanitized(create) = serde_json::from_value::<Sanitized<Create>>(create()).unwrap();
+    assert_eq!(note(&create)["content"], "<p>hello</p>");
+}
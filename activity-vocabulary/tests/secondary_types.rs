@@ -0,0 +1,40 @@
+use activity_vocabulary::*;
+use serde_json::json;
+
+#[test]
+fn dispatch() {
+    let featured = json!({
+        "type": ["toot:Featured", "Person"],
+        "id": "https://example.com/users/alice",
+    });
+    let object: ObjectSubtypes = serde_json::from_value(featured.clone()).unwrap();
+    assert_eq!(object.type_name(), "Person");
+    assert_eq!(object.secondary_types(), ["toot:Featured"]);
+    assert_eq!(serde_json::to_value(&object).unwrap(), featured);
+}
+
+#[test]
+fn variant_leads() {
+    let featured = json!({ "type": ["toot:Featured"], "id": "https://example.com/users/alice" });
+    let object = ObjectSubtypes::Person(serde_json::from_value(featured).unwrap());
+    assert_eq!(object.secondary_types(), ["toot:Featured"]);
+    assert_eq!(
+        serde_json::to_value(&object).unwrap(),
+        json!({ "type": ["Person", "toot:Featured"], "id": "https://example.com/users/alice" })
+    );
+}
+
+#[test]
+fn unknown() {
+    let emoji = json!({ "type": ["toot:Emoji", "schema:Thing"], "name": ":blobcat:" });
+    let object: ObjectSubtypes = serde_json::from_value(emoji.clone()).unwrap();
+    assert_eq!(object.type_name(), "Object");
+    assert_eq!(object.secondary_types(), ["toot:Emoji", "schema:Thing"]);
+    assert_eq!(serde_json::to_value(&object).unwrap(), emoji);
+
+    let error = serde_json::from_value::<LinkSubtypes>(json!({ "type": ["toot:Emoji", "Foo"] }))
+        .unwrap_err();
+    assert!(error
+        .to_string()
+        .starts_with("unknown type 'toot:Emoji', expected "));
+}
@@ -0,0 +1,65 @@
+use std::collections::{BTreeMap, HashMap};
+
+use activity_vocabulary_core::{
+    impl_skip_serialization, LangContainer, Property, SkipSerialization,
+};
+use serde::Serialize;
+
+struct Emojis(Vec<String>);
+
+impl Emojis {
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[derive(Serialize)]
+struct Rating(u8);
+
+impl_skip_serialization!(Emojis);
+impl_skip_serialization!(Rating => |this| this.0 == 0);
+
+#[derive(Serialize)]
+struct Review {
+    #[serde(skip_serializing_if = "SkipSerialization::should_skip")]
+    rating: Rating,
+    #[serde(skip_serializing_if = "SkipSerialization::should_skip")]
+    tags: Vec<String>,
+}
+
+#[test]
+fn containers() {
+    assert!(Vec::<u8>::new().should_skip());
+    assert!(!vec![1].should_skip());
+    assert!(BTreeMap::<u8, u8>::new().should_skip());
+    assert!(!BTreeMap::from([(1, 1)]).should_skip());
+
+    let mut summary = LangContainer::<Property<String>>::default();
+    assert!(summary.should_skip());
+    summary.per_lang = HashMap::from([("ja".to_owned(), Property(vec!["要約".to_owned()]))]);
+    assert!(!summary.should_skip());
+    summary.per_lang.clear();
+    summary.default = Some(Property::default());
+    assert!(!summary.should_skip());
+}
+
+#[test]
+fn custom() {
+    assert!(Emojis(vec![]).should_skip());
+    assert!(!Emojis(vec![":blobcat:".to_owned()]).should_skip());
+    assert!(Rating(0).should_skip());
+
+    let review = Review {
+        rating: Rating(0),
+        tags: vec![],
+    };
+    assert_eq!(serde_json::to_value(review).unwrap(), serde_json::json!({}));
+    let review = Review {
+        rating: Rating(4),
+        tags: vec![],
+    };
+    assert_eq!(
+        serde_json::to_value(review).unwrap(),
+        serde_json::json!({ "rating": 4 })
+    );
+}
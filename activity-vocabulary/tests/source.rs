@@ -0,0 +1,21 @@
+use activity_vocabulary::*;
+
+#[test]
+fn source_round_trip() {
+    let input = serde_json::json!({
+        "type": "Note",
+        "content": "<p>I <em>really</em> like strawberries!</p>",
+        "source": {
+            "content": "I *really* like strawberries!",
+            "mediaType": "text/markdown"
+        }
+    });
+    let note: Note = serde_json::from_value(input.clone()).unwrap();
+    let source = note.source.as_ref().unwrap();
+    assert_eq!(
+        source.content.as_deref(),
+        Some("I *really* like strawberries!")
+    );
+    assert_eq!(source.media_type.as_deref(), Some("text/markdown"));
+    assert_eq!(serde_json::to_value(&note).unwrap(), input);
+}
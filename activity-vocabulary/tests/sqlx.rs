@@ -0,0 +1,39 @@
+#![cfg(feature = "sqlx")]
+use activity_vocabulary::{Note, ObjectSubtypes};
+use activity_vocabulary_core::{Context, WithContext};
+
+fn bindable<T>()
+where
+    T: for<'q> sqlx::Encode<'q, sqlx::Postgres> + sqlx::Type<sqlx::Postgres>,
+    T: for<'r> sqlx::Decode<'r, sqlx::Postgres>,
+{
+}
+
+#[test]
+fn jsonb_types() {
+    bindable::<WithContext<Note>>();
+    bindable::<ObjectSubtypes>();
+    bindable::<WithContext<ObjectSubtypes>>();
+}
+
+/// Runs against the database at `DATABASE_URL`, and is skipped without it.
+#[tokio::test]
+async fn bind_and_fetch() {
+    let Ok(database_url) = std::env::var("DATABASE_URL") else {
+        return;
+    };
+    let pool = sqlx::PgPool::connect(&database_url).await.unwrap();
+    let note = WithContext {
+        context: Some(Context::activitystreams()),
+        body: ObjectSubtypes::Note(Note {
+            id: Some("https://example.com/notes/1".parse().unwrap()),
+            ..Default::default()
+        }),
+    };
+    let (fetched,): (WithContext<ObjectSubtypes>,) = sqlx::query_as("SELECT $1::jsonb")
+        .bind(&note)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(fetched, note);
+}
@@ -0,0 +1,75 @@
+use activity_vocabulary::{tag::Tagged, *};
+
+fn note() -> Note {
+    serde_json::from_value(serde_json::json!({
+        "type": "Note",
+        "tag": [
+            {
+                "type": "Mention",
+                "href": "https://remote.example/users/bob",
+                "name": "@bob@remote.example"
+            },
+            {
+                "type": "Hashtag",
+                "href": "https://example.com/tags/rust",
+                "name": "#rust"
+            },
+            {
+                "type": "Emoji",
+                "id": "https://example.com/emojis/1",
+                "name": ":blobcat:"
+            },
+            "https://example.com/notes/2"
+        ]
+    }))
+    .unwrap()
+}
+
+#[test]
+fn mentions() {
+    let note = note();
+    let mentions = note.mentions();
+    assert_eq!(mentions.len(), 1);
+    assert_eq!(
+        mentions[0].href,
+        "https://remote.example/users/bob".parse().unwrap()
+    );
+}
+
+#[cfg(feature = "mastodon")]
+#[test]
+fn hashtags_and_emojis() {
+    let note = note();
+    let hashtags = note.hashtags();
+    assert_eq!(hashtags.len(), 1);
+    assert_eq!(
+        hashtags[0].href,
+        "https://example.com/tags/rust".parse().unwrap()
+    );
+    let emojis = note.custom_emojis();
+    assert_eq!(emojis.len(), 1);
+    assert_eq!(
+        emojis[0].id,
+        Some("https://example.com/emojis/1".parse().unwrap())
+    );
+}
+
+#[test]
+fn tags_of_any_object() {
+    let tags = serde_json::json!([{
+        "type": "Mention",
+        "href": "https://remote.example/users/bob"
+    }]);
+    let person: Person = serde_json::from_value(serde_json::json!({
+        "type": "Person",
+        "tag": tags
+    }))
+    .unwrap();
+    assert_eq!(person.mentions().len(), 1);
+    let image: ObjectSubtypes = serde_json::from_value(serde_json::json!({
+        "type": "Image",
+        "tag": tags
+    }))
+    .unwrap();
+    assert_eq!(image.mentions().len(), 1);
+}
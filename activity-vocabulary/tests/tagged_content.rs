@@ -0,0 +1,86 @@
+use std::collections::BTreeMap;
+
+use activity_vocabulary::*;
+use activity_vocabulary_core::{Property, TaggedContentVisitor};
+use serde::{Deserialize, Deserializer};
+use serde_value::Value;
+
+fn visit(content: Value) -> (Property<String>, Value) {
+    content
+        .deserialize_any(TaggedContentVisitor::<Property<String>>::new(
+            "thing", "type",
+        ))
+        .unwrap()
+}
+
+fn string(value: &str) -> Value {
+    Value::String(value.to_owned())
+}
+
+#[test]
+fn tag() {
+    let (tag, content) = serde_json::Deserializer::from_str(r#"{"@type":["Note"],"content":"hi"}"#)
+        .deserialize_any(TaggedContentVisitor::<Property<String>>::new(
+            "thing", "type",
+        ))
+        .unwrap();
+    assert_eq!(tag.0, ["Note"]);
+    assert_eq!(
+        content,
+        Value::Map(BTreeMap::from([
+            (string("@type"), Value::Seq(vec![string("Note")])),
+            (string("content"), string("hi")),
+        ]))
+    );
+
+    let (tag, _) = visit(Value::Map(BTreeMap::from([(
+        string("content"),
+        string("hi"),
+    )])));
+    assert!(tag.0.is_empty());
+}
+
+#[test]
+fn unusual_keys() {
+    let (tag, content) = visit(Value::Map(BTreeMap::from([
+        (Value::Bytes(b"type".to_vec()), string("Note")),
+        (Value::U64(1), Value::Bool(true)),
+        (Value::Char('x'), Value::Unit),
+        (Value::Seq(vec![]), string("skipped")),
+        (Value::Bool(false), string("skipped")),
+    ])));
+    assert_eq!(tag.0, ["Note"]);
+    assert_eq!(
+        content,
+        Value::Map(BTreeMap::from([
+            (string("type"), string("Note")),
+            (string("1"), Value::Bool(true)),
+            (string("x"), Value::Unit),
+        ]))
+    );
+
+    let note = serde_value::to_value(BTreeMap::from([
+        (Value::Bytes(b"type".to_vec()), string("Note")),
+        (Value::Bytes(b"content".to_vec()), string("hello")),
+    ]))
+    .unwrap();
+    let object = ObjectSubtypes::deserialize(note).unwrap();
+    assert_eq!(object.type_name(), "Note");
+}
+
+#[test]
+fn keyword() {
+    let visitor = || TaggedContentVisitor::<Property<String>>::new("thing", "kind");
+    let (tag, _) = Value::Map(BTreeMap::from([(string("@type"), string("Note"))]))
+        .deserialize_any(visitor().with_keyword("@type"))
+        .unwrap();
+    assert_eq!(tag.0, ["Note"]);
+    let (tag, _) = Value::Map(BTreeMap::from([(string("kind"), string("Note"))]))
+        .deserialize_any(visitor().with_keyword("@type"))
+        .unwrap();
+    assert_eq!(tag.0, ["Note"]);
+    let (tag, _) = Value::Map(BTreeMap::from([(string("@type"), string("Note"))]))
+        .deserialize_any(visitor())
+        .unwrap();
+    assert!(tag.0.is_empty());
+}
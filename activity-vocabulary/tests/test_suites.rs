@@ -1,32 +1,6 @@
 use activity_vocabulary::*;
 use activity_vocabulary_core::WithContext;
-use anyhow::bail;
-use std::{fs, path::Path};
-
-use serde::{Deserialize, Serialize};
-
-pub fn check<T, P: AsRef<Path>>(input: P, output: P) -> anyhow::Result<()>
-where
-    T: for<'de> Deserialize<'de> + Serialize,
-{
-    let input: serde_json::Value = serde_json::from_str(&fs::read_to_string(input)?)?;
-    let output: serde_json::Value = serde_json::from_str(&fs::read_to_string(output)?)?;
-    let deserialized: T = serde_json::from_value(input.clone())?;
-    let re_serialized = serde_json::to_value(deserialized)?;
-    if re_serialized != output {
-        let json = serde_json::to_string_pretty(&output)?;
-        let re_serialized = serde_json::to_string_pretty(&re_serialized)?;
-        for diff in diff::lines(&json, &re_serialized) {
-            match diff {
-                diff::Result::Left(l) => println!("-{}", l),
-                diff::Result::Both(l, _) => println!(" {}", l),
-                diff::Result::Right(r) => println!("+{}", r),
-            }
-        }
-        bail!("differ");
-    }
-    Ok(())
-}
+use activity_vocabulary_test_utils::check;
 
 #[test]
 fn core_ex1() {
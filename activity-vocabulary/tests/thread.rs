@@ -0,0 +1,49 @@
+use activity_vocabulary::{thread::Thread, *};
+
+fn note(id: &str, in_reply_to: Option<&str>, published: &str) -> ObjectSubtypes {
+    let mut note = serde_json::json!({
+        "type": "Note",
+        "id": format!("https://example.com/notes/{id}"),
+        "published": published
+    });
+    if let Some(parent) = in_reply_to {
+        note["inReplyTo"] = parent.into();
+    }
+    serde_json::from_value(note).unwrap()
+}
+
+fn id(id: &str) -> url::Url {
+    format!("https://example.com/notes/{id}").parse().unwrap()
+}
+
+#[test]
+fn conversation() {
+    let remote: url::Url = "https://remote.example/notes/0".parse().unwrap();
+    let thread = Thread::new([
+        note(
+            "3",
+            Some("https://example.com/notes/1"),
+            "2024-01-01T00:03:00Z",
+        ),
+        note("1", Some(remote.as_str()), "2024-01-01T00:01:00Z"),
+        note(
+            "2",
+            Some("https://example.com/notes/1"),
+            "2024-01-01T00:02:00Z",
+        ),
+        note(
+            "4",
+            Some("https://example.com/notes/2"),
+            "2024-01-01T00:04:00Z",
+        ),
+    ]);
+    assert_eq!(thread.roots(), [&remote]);
+    assert!(thread.get(&remote).is_none());
+    assert_eq!(thread.children(&id("1")), [id("2"), id("3")]);
+    assert_eq!(thread.parent(&id("4")), Some(&id("2")));
+    assert_eq!(thread.ancestors(&id("4")), [&id("2"), &id("1"), &remote]);
+    assert_eq!(
+        thread.descendants(&remote),
+        [&id("1"), &id("2"), &id("4"), &id("3")]
+    );
+}
@@ -0,0 +1,41 @@
+use activity_vocabulary::*;
+
+fn note() -> ObjectSubtypes {
+    serde_json::from_value(serde_json::json!({
+        "type": "Note",
+        "id": "https://example.com/notes/1",
+        "attributedTo": "https://example.com/users/alice",
+        "to": "https://www.w3.org/ns/activitystreams#Public",
+        "content": "hello"
+    }))
+    .unwrap()
+}
+
+#[test]
+fn tombstone() {
+    let deleted = "2024-01-01T00:00:00Z".parse().unwrap();
+    let tombstone = Tombstone::of(&note(), deleted);
+    assert_eq!(
+        serde_json::to_value(&tombstone).unwrap(),
+        serde_json::json!({
+            "type": "Tombstone",
+            "id": "https://example.com/notes/1",
+            "formerType": "Note",
+            "deleted": "2024-01-01T00:00:00Z"
+        })
+    );
+    // converting is the downcast, which a Note is not
+    assert!(TombstoneSubtypes::try_from(note()).is_err());
+}
+
+#[test]
+fn delete_for_object() {
+    let delete = serde_json::to_value(Delete::for_object(&note())).unwrap();
+    assert_eq!(delete["type"], "Delete");
+    assert_eq!(delete["id"], "https://example.com/notes/1#delete");
+    assert_eq!(delete["actor"], "https://example.com/users/alice");
+    assert_eq!(delete["to"], "https://www.w3.org/ns/activitystreams#Public");
+    assert_eq!(delete["object"]["type"], "Tombstone");
+    assert_eq!(delete["object"]["formerType"], "Note");
+    assert!(delete["object"]["deleted"].is_string());
+}
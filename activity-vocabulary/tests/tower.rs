@@ -0,0 +1,98 @@
+#![cfg(feature = "tower")]
+use std::{
+    convert::Infallible,
+    future::{ready, Ready},
+    task::{Context, Poll},
+};
+
+use activity_vocabulary::{accepts_activitystreams, tower::*};
+use http::{header, Request, Response};
+use tower_layer::Layer;
+use tower_service::Service;
+
+#[derive(Clone)]
+struct Fixed(&'static str, Option<&'static str>);
+
+impl Service<Request<()>> for Fixed {
+    type Response = Response<&'static str>;
+    type Error = Infallible;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _req: Request<()>) -> Self::Future {
+        let mut response = Response::builder();
+        if let Some(content_type) = self.1 {
+            response = response.header(header::CONTENT_TYPE, content_type);
+        }
+        ready(Ok(response.body(self.0).unwrap()))
+    }
+}
+
+async fn get(
+    service: &mut impl Service<Request<()>, Response = Response<&'static str>, Error = Infallible>,
+    accept: &str,
+) -> Response<&'static str> {
+    let request = Request::get("https://example.com/users/alice")
+        .header(header::ACCEPT, accept)
+        .body(())
+        .unwrap();
+    service.call(request).await.unwrap()
+}
+
+#[test]
+fn accept_preference() {
+    assert!(accepts_activitystreams("application/activity+json"));
+    assert!(accepts_activitystreams(
+        r#"application/ld+json; profile="https://www.w3.org/ns/activitystreams""#
+    ));
+    assert!(accepts_activitystreams(
+        "text/html;q=0.5, application/activity+json"
+    ));
+    assert!(!accepts_activitystreams(
+        "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8"
+    ));
+    assert!(!accepts_activitystreams(
+        "text/html, application/activity+json;q=0.9"
+    ));
+    assert!(!accepts_activitystreams("application/activity+json;q=0"));
+    assert!(!accepts_activitystreams("application/ld+json"));
+}
+
+#[tokio::test]
+async fn negotiate() {
+    let mut service = NegotiateLayer::new(Fixed("activity", Some("application/json")))
+        .layer(Fixed("html", Some("text/html")));
+
+    let response = get(&mut service, "application/activity+json").await;
+    assert_eq!(*response.body(), "activity");
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "application/activity+json"
+    );
+    assert_eq!(response.headers()[header::VARY], "accept");
+
+    let response = get(&mut service, "text/html").await;
+    assert_eq!(*response.body(), "html");
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "text/html");
+    assert_eq!(response.headers()[header::VARY], "accept");
+}
+
+#[tokio::test]
+async fn keep_explicit_content_type() {
+    let mut service = NegotiateLayer::new(Fixed("error", Some("text/plain")))
+        .layer(Fixed("html", Some("text/html")));
+    let response = get(&mut service, "application/activity+json").await;
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "text/plain");
+
+    let mut service = NegotiateLayer::new(Fixed("activity", None)).layer(Fixed("html", None));
+    let response = get(&mut service, "application/activity+json").await;
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "application/activity+json"
+    );
+    let response = get(&mut service, "text/html").await;
+    assert!(response.headers().get(header::CONTENT_TYPE).is_none());
+}
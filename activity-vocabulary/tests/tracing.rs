@@ -0,0 +1,136 @@
+#![cfg(feature = "tracing")]
+use std::{
+    collections::HashMap,
+    future::{ready, Future},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use activity_vocabulary::*;
+use activity_vocabulary_core::{Dereferencer, Or, Remotable};
+use tracing::{
+    field::{Field, Visit},
+    instrument::WithSubscriber,
+    span, Event, Metadata, Subscriber,
+};
+
+/// Spans and events recorded as their names or messages followed by their fields.
+#[derive(Default, Clone)]
+struct Recorder {
+    records: Arc<Mutex<Vec<String>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.0.insert_str(0, &format!("{value:?}"));
+        } else {
+            self.0.push_str(&format!(" {}={value:?}", field.name()));
+        }
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+        let mut fields = Fields(span.metadata().name().to_owned());
+        span.record(&mut fields);
+        self.records.lock().unwrap().push(fields.0);
+        span::Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(String::new());
+        event.record(&mut fields);
+        self.records.lock().unwrap().push(fields.0);
+    }
+
+    fn enter(&self, _: &span::Id) {}
+
+    fn exit(&self, _: &span::Id) {}
+}
+
+fn recorded(f: impl FnOnce()) -> Vec<String> {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), f);
+    let records = recorder.records.lock().unwrap().clone();
+    records
+}
+
+struct Documents(HashMap<url::Url, serde_json::Value>);
+
+impl Dereferencer for Documents {
+    type Error = String;
+
+    fn dereference(
+        &self,
+        url: &url::Url,
+    ) -> impl Future<Output = Result<serde_json::Value, Self::Error>> + Send {
+        ready(self.0.get(url).cloned().ok_or_else(|| url.to_string()))
+    }
+}
+
+#[test]
+fn malformed_property() {
+    let records = recorded(|| {
+        let document = serde_json::json!({
+            "type": "Note",
+            "id": "https://example.com/notes/1",
+            "published": "yesterday",
+        });
+        assert!(serde_json::from_value::<ObjectSubtypes>(document).is_err());
+    });
+    assert_eq!(
+        records,
+        [
+            "deserialize subtypes=\"Object\" type=\"Note\" id=\"https://example.com/notes/1\"",
+            "malformed property type=\"Note\" property=\"published\" \
+             error=input contains invalid characters",
+        ]
+    );
+}
+
+#[test]
+fn unknown_type() {
+    let records = recorded(|| {
+        let document = serde_json::json!({ "type": "Mentoin" });
+        assert!(serde_json::from_value::<LinkSubtypes>(document).is_err());
+    });
+    assert_eq!(
+        records.last().unwrap(),
+        "unknown type error=unknown type 'Mentoin', did you mean 'Mention'?"
+    );
+}
+
+#[tokio::test]
+async fn dereference() {
+    let id: url::Url = "https://example.com/notes/1".parse().unwrap();
+    let documents = Documents(
+        [(
+            id.clone(),
+            serde_json::to_value(Note::new("hello")).unwrap(),
+        )]
+        .into(),
+    );
+    let recorder = Recorder::default();
+    let note = Or::<LinkSubtypes, _>::Snd(Remotable::<ObjectSubtypes>::Remote(id));
+    assert!(note
+        .resolve(&documents)
+        .with_subscriber(recorder.clone())
+        .await
+        .is_ok());
+    let records = recorder.records.lock().unwrap().clone();
+    assert_eq!(records[0], "dereference url=https://example.com/notes/1");
+}
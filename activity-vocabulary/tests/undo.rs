@@ -0,0 +1,75 @@
+use std::{
+    collections::HashMap,
+    future::{ready, Future},
+};
+
+use activity_vocabulary::{undo::*, *};
+use activity_vocabulary_core::Dereferencer;
+
+struct Documents(HashMap<url::Url, serde_json::Value>);
+
+impl Dereferencer for Documents {
+    type Error = String;
+
+    fn dereference(
+        &self,
+        url: &url::Url,
+    ) -> impl Future<Output = Result<serde_json::Value, Self::Error>> + Send {
+        ready(self.0.get(url).cloned().ok_or_else(|| url.to_string()))
+    }
+}
+
+fn undo(object: serde_json::Value) -> Undo {
+    serde_json::from_value(serde_json::json!({
+        "type": "Undo",
+        "actor": "https://example.com/users/alice",
+        "object": object
+    }))
+    .unwrap()
+}
+
+fn follow() -> serde_json::Value {
+    serde_json::json!({
+        "type": "Follow",
+        "id": "https://example.com/follows/1",
+        "actor": "https://example.com/users/alice",
+        "object": "https://remote.example/users/bob"
+    })
+}
+
+#[tokio::test]
+async fn inline_target() {
+    let undo = undo(follow());
+    assert_eq!(undo.target_kind(), Some(UndoKind::Follow));
+    let (kind, _) = undo
+        .resolve_target(&Documents(HashMap::new()))
+        .await
+        .unwrap();
+    assert_eq!(kind, UndoKind::Follow);
+}
+
+#[tokio::test]
+async fn remote_target() {
+    let documents = Documents(HashMap::from([
+        ("https://example.com/follows/1".parse().unwrap(), follow()),
+        (
+            "https://example.com/likes/1".parse().unwrap(),
+            serde_json::json!({
+                "type": "Like",
+                "actor": "https://remote.example/users/mallory",
+                "object": "https://remote.example/notes/1"
+            }),
+        ),
+    ]));
+    let undo_follow = undo(serde_json::json!("https://example.com/follows/1"));
+    assert_eq!(undo_follow.target_kind(), None);
+    let (kind, target) = undo_follow.resolve_target(&documents).await.unwrap();
+    assert_eq!(kind, UndoKind::Follow);
+    assert!(matches!(target, ObjectSubtypes::Follow(_)));
+
+    let undo_like = undo(serde_json::json!("https://example.com/likes/1"));
+    assert!(matches!(
+        undo_like.resolve_target(&documents).await,
+        Err(UndoError::ActorMismatch)
+    ));
+}
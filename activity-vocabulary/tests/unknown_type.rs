@@ -0,0 +1,40 @@
+use activity_vocabulary::*;
+use activity_vocabulary_core::UnknownType;
+
+#[test]
+fn did_you_mean() {
+    let error = serde_json::from_value::<LinkSubtypes>(serde_json::json!({
+        "type": "Mention ",
+        "id": "https://example.com/mentions/1",
+    }))
+    .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "unknown type 'Mention ' of https://example.com/mentions/1, did you mean 'Mention'?"
+    );
+}
+
+#[test]
+fn without_suggestion() {
+    let error = serde_json::from_value::<LinkSubtypes>(serde_json::json!({
+        "type": "Emoji",
+    }))
+    .unwrap_err();
+    assert!(error
+        .to_string()
+        .starts_with("unknown type 'Emoji', expected "));
+}
+
+#[test]
+fn suggestion() {
+    let unknown = |received: &str| UnknownType {
+        received: received.to_owned(),
+        id: None,
+        expected: &["Accept", "Announce", "Create", "Note"],
+    };
+    assert_eq!(unknown("note").suggestion(), Some("Note"));
+    assert_eq!(unknown("Creat").suggestion(), Some("Create"));
+    assert_eq!(unknown("Anounce").suggestion(), Some("Announce"));
+    assert_eq!(unknown("Person").suggestion(), None);
+    assert_eq!(unknown("").suggestion(), None);
+}
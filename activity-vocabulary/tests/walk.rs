@@ -0,0 +1,102 @@
+use activity_vocabulary::{walk::Walk, *};
+
+fn create() -> Create {
+    serde_json::from_value(serde_json::json!({
+        "type": "Create",
+        "id": "https://example.com/activities/1",
+        "actor": "https://example.com/users/alice",
+        "object": {
+            "type": "Note",
+            "id": "https://example.com/notes/1",
+            "content": "hello",
+            "inReplyTo": "https://example.org/notes/2",
+            "tag": [{ "type": "Mention", "href": "https://example.org/users/bob" }],
+            "attachment": [
+                { "type": "Image", "url": "https://example.com/files/1.png" },
+                { "type": "Document", "content": "attached" }
+            ]
+        }
+    }))
+    .unwrap()
+}
+
+#[test]
+fn visit_parents_first() {
+    let create = create();
+    let mut visited = Vec::new();
+    create.walk(&mut |node| visited.push(node.object_id().map(url::Url::as_str)));
+    assert_eq!(
+        visited.first(),
+        Some(&Some("https://example.com/activities/1"))
+    );
+    let ids = visited.into_iter().flatten().collect::<Vec<_>>();
+    assert!(ids.contains(&"https://example.com/users/alice"));
+    assert!(ids.contains(&"https://example.org/notes/2"));
+    let note = ids
+        .iter()
+        .position(|id| *id == "https://example.com/notes/1")
+        .unwrap();
+    let in_reply_to = ids
+        .iter()
+        .position(|id| *id == "https://example.org/notes/2")
+        .unwrap();
+    assert!(note < in_reply_to);
+}
+
+#[test]
+fn count_nodes() {
+    let object = ObjectSubtypes::from(create());
+    let mut attachments = 0;
+    let mut mentions = 0;
+    object.walk(&mut |node| match node {
+        Node::Image(_) | Node::Document(_) => attachments += 1,
+        Node::Mention(mention) => {
+            assert_eq!(mention.href.as_str(), "https://example.org/users/bob");
+            mentions += 1
+        }
+        _ => (),
+    });
+    assert_eq!((attachments, mentions), (2, 1));
+}
+
+#[test]
+fn modify() {
+    let mut create = create();
+    create.walk_mut(&mut |node| match node {
+        NodeMut::Note(note) => note.tag = Default::default(),
+        NodeMut::Document(document) => document.content = Default::default(),
+        _ => (),
+    });
+    let mut mentions = 0;
+    let mut contents = 0;
+    create.walk(&mut |node| match node {
+        Node::Mention(_) => mentions += 1,
+        Node::Document(document) if document.content.default.is_some() => contents += 1,
+        _ => (),
+    });
+    assert_eq!((mentions, contents), (0, 0));
+}
+
+#[test]
+fn remote_refs() {
+    let mut create = create();
+    create.to = serde_json::from_value(serde_json::json!([
+        "https://www.w3.org/ns/activitystreams#Public",
+        "https://example.org/users/bob"
+    ]))
+    .unwrap();
+    let mut refs = create
+        .remote_refs()
+        .into_iter()
+        .map(url::Url::as_str)
+        .collect::<Vec<_>>();
+    refs.sort();
+    assert_eq!(
+        refs,
+        [
+            "https://example.com/users/alice",
+            "https://example.org/notes/2",
+            "https://example.org/users/bob",
+        ]
+    );
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "wasm")]
+// the bindings are called natively, on the paths which do not touch JavaScript
+use activity_vocabulary::{wasm::ActivityObject, ObjectSubtypes};
+
+const NOTE: &str = r#"{
+    "@context": "https://www.w3.org/ns/activitystreams",
+    "type": "Note",
+    "id": "https://example.com/notes/1",
+    "attributedTo": "https://example.com/users/alice",
+    "to": "https://www.w3.org/ns/activitystreams#Public",
+    "cc": ["https://example.com/users/alice/followers"],
+    "inReplyTo": "https://example.com/notes/0",
+    "content": "hello",
+    "published": "2024-01-01T00:00:00Z"
+}"#;
+
+#[test]
+fn getters() {
+    let note = ActivityObject::parse(NOTE).unwrap();
+    assert_eq!(note.type_name(), "Note");
+    assert_eq!(note.id().as_deref(), Some("https://example.com/notes/1"));
+    assert_eq!(note.content().as_deref(), Some("hello"));
+    assert_eq!(note.name(), None);
+    assert_eq!(
+        note.attributed_to(),
+        vec!["https://example.com/users/alice"]
+    );
+    assert_eq!(note.in_reply_to(), vec!["https://example.com/notes/0"]);
+    assert_eq!(
+        note.to(),
+        vec!["https://www.w3.org/ns/activitystreams#Public"]
+    );
+    assert_eq!(note.cc(), vec!["https://example.com/users/alice/followers"]);
+    assert!(note.published().unwrap().starts_with("2024-01-01T00:00:00"));
+}
+
+#[test]
+fn build() {
+    let mut note = ActivityObject::new("Note").unwrap();
+    note.set_id(Some("https://example.com/notes/1".to_owned()))
+        .unwrap();
+    note.set_content(Some("hello".to_owned())).unwrap();
+    note.set_to(vec![
+        "https://www.w3.org/ns/activitystreams#Public".to_owned()
+    ])
+    .unwrap();
+    assert_eq!(note.type_name(), "Note");
+    assert_eq!(note.content().as_deref(), Some("hello"));
+
+    let json: serde_json::Value = serde_json::from_str(&note.stringify().unwrap()).unwrap();
+    assert_eq!(json["@context"][0], "https://www.w3.org/ns/activitystreams");
+    assert_eq!(json["id"], "https://example.com/notes/1");
+    assert_eq!(json["to"], "https://www.w3.org/ns/activitystreams#Public");
+
+    note.set_content(None).unwrap();
+    assert_eq!(note.content(), None);
+    assert!(matches!(note.into_inner().body, ObjectSubtypes::Note(_)));
+}
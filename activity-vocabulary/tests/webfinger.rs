@@ -0,0 +1,77 @@
+#![cfg(feature = "webfinger")]
+use std::future::{ready, Future};
+
+use activity_vocabulary::webfinger::*;
+use activity_vocabulary_core::Dereferencer;
+
+struct Descriptor(serde_json::Value);
+
+impl Dereferencer for Descriptor {
+    type Error = String;
+
+    fn dereference(
+        &self,
+        url: &url::Url,
+    ) -> impl Future<Output = Result<serde_json::Value, Self::Error>> + Send {
+        let found = url.as_str()
+            == "https://example.com/.well-known/webfinger?resource=acct%3Aalice%40example.com";
+        ready(if found {
+            Ok(self.0.clone())
+        } else {
+            Err(url.to_string())
+        })
+    }
+}
+
+fn mastodon_jrd() -> serde_json::Value {
+    serde_json::json!({
+        "subject": "acct:alice@example.com",
+        "aliases": [
+            "https://example.com/@alice",
+            "https://example.com/users/alice"
+        ],
+        "links": [
+            {
+                "rel": "http://webfinger.net/rel/profile-page",
+                "type": "text/html",
+                "href": "https://example.com/@alice"
+            },
+            {
+                "rel": "self",
+                "type": "application/activity+json",
+                "href": "https://example.com/users/alice"
+            },
+            {
+                "rel": "http://ostatus.org/schema/1.0/subscribe",
+                "template": "https://example.com/authorize_interaction?uri={uri}"
+            }
+        ]
+    })
+}
+
+#[test]
+fn resource_descriptor() {
+    let descriptor: ResourceDescriptor = serde_json::from_value(mastodon_jrd()).unwrap();
+    assert_eq!(
+        descriptor.actor().map(url::Url::as_str),
+        Some("https://example.com/users/alice")
+    );
+    assert_eq!(serde_json::to_value(&descriptor).unwrap(), mastodon_jrd());
+}
+
+#[tokio::test]
+async fn resolve() {
+    let dereferencer = Descriptor(mastodon_jrd());
+    for acct in [
+        "acct:alice@example.com",
+        "alice@example.com",
+        "@alice@example.com",
+    ] {
+        let actor = resolve_acct(acct, &dereferencer).await.unwrap();
+        assert_eq!(actor.as_str(), "https://example.com/users/alice");
+    }
+    assert!(matches!(
+        resolve_acct("alice", &dereferencer).await,
+        Err(WebFingerError::InvalidAcct(_))
+    ));
+}
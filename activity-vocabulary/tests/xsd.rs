@@ -0,0 +1,125 @@
+use activity_vocabulary_core::xsd;
+
+#[test]
+fn duration() {
+    let duration: xsd::Duration = "P1DT2H3M4S".parse().unwrap();
+    assert_eq!(duration.days, 1);
+    assert_eq!(duration.duration.num_seconds(), 2 * 3600 + 3 * 60 + 4);
+}
+
+#[test]
+fn duration_out_of_range() {
+    assert!("PT-0055000000055555553S".parse::<xsd::Duration>().is_err());
+    assert!(format!("PT{}H", i64::MAX).parse::<xsd::Duration>().is_err());
+}
+
+#[test]
+fn datetime_order() {
+    let parse = |s: &str| s.parse::<xsd::DateTime>().unwrap();
+    let mut times = vec![
+        parse("2023-11-15T07:13:20+09:00"),
+        parse("2023-11-14T22:13:20.5"),
+        parse("2023-11-14T22:13:20Z"),
+        parse("2023-11-14T22:13:20"),
+        parse("2023-11-14T22:00:00+00:00"),
+    ];
+    times.sort();
+    assert_eq!(
+        times,
+        [
+            parse("2023-11-14T22:00:00+00:00"),
+            parse("2023-11-14T22:13:20"),
+            parse("2023-11-15T07:13:20+09:00"),
+            parse("2023-11-14T22:13:20Z"),
+            parse("2023-11-14T22:13:20.5"),
+        ]
+    );
+    assert_eq!(
+        parse("2023-11-15T07:13:20+09:00").cmp(&parse("2023-11-14T22:13:20Z")),
+        std::cmp::Ordering::Equal
+    );
+    assert_eq!(
+        parse("2023-11-15T07:13:20+09:00"),
+        parse("2023-11-14T22:13:20Z")
+    );
+    assert!(parse("2023-11-14T22:13:20") < parse("2023-11-14T22:13:20Z"));
+    assert_eq!(parse("2023-11-15T07:13:20+09:00").timestamp(), 1700000000);
+    assert_eq!(
+        parse("2023-11-14T22:13:20.5").timestamp_millis(),
+        1700000000500
+    );
+}
+
+#[test]
+fn datetime_conversions() {
+    let utc = chrono::DateTime::<chrono::Utc>::from_timestamp(1700000000, 0).unwrap();
+    let datetime = xsd::DateTime::from(utc);
+    assert_eq!(datetime.to_string(), "2023-11-14T22:13:20Z");
+    let with_offset: chrono::DateTime<chrono::FixedOffset> = datetime.try_into().unwrap();
+    assert_eq!(with_offset, utc);
+    let naive: xsd::DateTime = "2023-11-14T22:13:20".parse().unwrap();
+    assert!(chrono::DateTime::<chrono::FixedOffset>::try_from(naive).is_err());
+}
+
+#[test]
+fn datetime_constructors() {
+    let datetime = xsd::DateTime::from_unix(1700000000).unwrap();
+    assert_eq!(datetime.to_string(), "2023-11-14T22:13:20Z");
+    assert_eq!(xsd::DateTime::from_unix(i64::MAX), None);
+    let jst = chrono::FixedOffset::east_opt(9 * 3600).unwrap();
+    assert_eq!(
+        datetime.with_offset(jst).to_string(),
+        "2023-11-15T07:13:20+09:00"
+    );
+    let naive: xsd::DateTime = "2023-11-14T22:13:20".parse().unwrap();
+    assert_eq!(naive.with_offset(jst), datetime.with_offset(jst));
+    let before = xsd::DateTime::from(chrono::DateTime::<chrono::Utc>::from(
+        std::time::SystemTime::now(),
+    ));
+    assert!(xsd::DateTime::now() >= before);
+}
+
+#[test]
+fn duration_lexical() {
+    for lexical in [
+        "P1Y2M3DT4H5M6.5S",
+        "-P1D",
+        "PT0S",
+        "P0D",
+        "PT36H",
+        "PT0.000000001S",
+        "P2W",
+        "PT1.50S",
+    ] {
+        let duration: xsd::Duration = lexical.parse().unwrap();
+        assert_eq!(duration.to_string(), lexical);
+        let json = serde_json::to_value(&duration).unwrap();
+        assert_eq!(json, lexical);
+    }
+    for invalid in ["P", "PT", "P1DT", "1D", "P1.5D", "PT1.S", "P1W2D", "P-"] {
+        assert!(invalid.parse::<xsd::Duration>().is_err(), "{invalid}");
+    }
+}
+
+#[test]
+fn duration_canonical() {
+    let mut duration: xsd::Duration = "PT1.50S".parse().unwrap();
+    assert_eq!(duration.duration.num_milliseconds(), 1500);
+    assert_eq!(duration, "PT1.5S".parse().unwrap());
+    duration.days = 1;
+    assert_eq!(duration.to_string(), "P1DT1.5S");
+
+    assert_eq!("P2W".parse::<xsd::Duration>().unwrap().days, 14);
+    assert_eq!(
+        "P-1D".parse::<xsd::Duration>().unwrap(),
+        "-P1D".parse().unwrap()
+    );
+    assert_eq!(
+        "-PT0S".parse::<xsd::Duration>().unwrap(),
+        "PT0S".parse().unwrap()
+    );
+    let duration = xsd::Duration::new(true, 0, 13, 0, chrono::Duration::seconds(3661));
+    assert_eq!(duration.to_string(), "-P13MT1H1M1S");
+    let zero = xsd::Duration::new(false, 0, 0, 0, chrono::Duration::zero());
+    assert_eq!(zero.to_string(), "PT0S");
+}
@@ -0,0 +1,12 @@
+//! Arbitrary JSON into an object or a link of any type, which must serialize back.
+#![no_main]
+
+use activity_vocabulary::link::LinkOrObject;
+use activity_vocabulary_core::WithContext;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(document) = serde_json::from_slice::<WithContext<LinkOrObject>>(data) {
+        serde_json::to_string(&document).unwrap();
+    }
+});
@@ -0,0 +1,19 @@
+//! Arbitrary JSON into the generic types of the core, which the generated properties are made of.
+#![no_main]
+
+use std::collections::HashMap;
+
+use activity_vocabulary_core::{Context, LangContainer, Or, Property, Remotable, WithContext};
+use libfuzzer_sys::fuzz_target;
+
+type Properties = WithContext<HashMap<String, Property<Or<Remotable<serde_json::Value>, String>>>>;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(context) = serde_json::from_slice::<Context>(data) {
+        serde_json::to_string(&context).unwrap();
+    }
+    if let Ok(properties) = serde_json::from_slice::<Properties>(data) {
+        serde_json::to_string(&properties).unwrap();
+    }
+    let _ = serde_json::from_slice::<LangContainer<Property<String>>>(data);
+});
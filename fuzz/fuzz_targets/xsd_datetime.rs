@@ -0,0 +1,12 @@
+//! Arbitrary strings into `xsd:dateTime`, which must be written back.
+#![no_main]
+
+use activity_vocabulary_core::xsd::DateTime;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|src: &str| {
+    if let Ok(datetime) = src.parse::<DateTime>() {
+        let _ = datetime.to_string();
+        let _ = datetime.to_utc();
+    }
+});
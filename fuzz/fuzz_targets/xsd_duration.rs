@@ -0,0 +1,11 @@
+//! Arbitrary strings into `xsd:duration`, which must be written back.
+#![no_main]
+
+use activity_vocabulary_core::xsd::Duration;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|src: &str| {
+    if let Ok(duration) = src.parse::<Duration>() {
+        let _ = duration.to_string();
+    }
+});